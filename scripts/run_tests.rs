@@ -0,0 +1,87 @@
+//! ```cargo
+//! [package]
+//! edition = "2021"
+//!
+//! [dependencies]
+//! wolfram-app-discovery = { git = "https://github.com/WolframResearch/wolfram-app-discovery-rs" }
+//! ```
+
+//! Run the RustLink MUnit tests using the Wolfram Kernel located by
+//! `wolfram-app-discovery`.
+//!
+//! The kernel used can be overridden by setting either of these environment variables:
+//!
+//! * `WOLFRAM_KERNEL` — path to a `WolframKernel` executable
+//! * `WOLFRAM_APP_DIRECTORY` — path to a Wolfram application installation directory
+
+use std::{
+    path::PathBuf,
+    process::{self, Command},
+};
+
+use wolfram_app_discovery::WolframApp;
+
+const TEST_SCRIPT: &str = "scripts/RunTests.wls";
+
+fn main() {
+    let kernel = kernel_executable();
+
+    println!("Running {} using {}", TEST_SCRIPT, kernel.display());
+
+    let status = Command::new(&kernel)
+        .args(["-script", TEST_SCRIPT])
+        .status()
+        .unwrap_or_else(|err| {
+            error(&format!("unable to launch {}: {}", kernel.display(), err))
+        });
+
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+fn kernel_executable() -> PathBuf {
+    if let Some(kernel) = std::env::var_os("WOLFRAM_KERNEL") {
+        let kernel = PathBuf::from(kernel);
+
+        if !kernel.is_file() {
+            error(&format!(
+                "WOLFRAM_KERNEL is set, but does not refer to a file: {}",
+                kernel.display()
+            ));
+        }
+
+        return kernel;
+    }
+
+    let app = WolframApp::try_default().unwrap_or_else(|err| {
+        error(&format!("unable to locate a Wolfram application: {}", err))
+    });
+
+    app.kernel_executable_path().unwrap_or_else(|err| {
+        error(&format!(
+            "unable to locate the WolframKernel executable of {}: {}",
+            app.installation_directory().display(),
+            err
+        ))
+    })
+}
+
+fn error(message: &str) -> ! {
+    eprintln!(
+        "
+    ==== ERROR: RustLink tests =====
+
+    {}
+
+    Set the WOLFRAM_KERNEL environment variable to the path of a WolframKernel
+    executable, or set WOLFRAM_APP_DIRECTORY to the installation directory of a
+    Wolfram application, and try again.
+
+    ================================
+    ",
+        message
+    );
+
+    process::exit(1)
+}
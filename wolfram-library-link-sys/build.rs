@@ -20,7 +20,32 @@ fn main() {
     }
 
 
-    let app = WolframApp::try_default().expect("unable to locate WolframApp");
+    // `wolfram-app-discovery` checks this variable before searching the standard
+    // installation locations.
+    println!("cargo:rerun-if-env-changed=WOLFRAM_APP_DIRECTORY");
+
+    let app = match WolframApp::try_default() {
+        Ok(app) => app,
+        Err(err) => {
+            println!(
+                "
+    ==== ERROR: wolfram-library-link-sys =====
+
+    Unable to locate a local installation of the Wolfram Language:
+
+        {}
+
+    If the Wolfram Language is installed in a non-standard location, set the
+    WOLFRAM_APP_DIRECTORY environment variable to the installation directory
+    and try again.
+
+    =========================================
+            ",
+                err
+            );
+            panic!("<See printed error>");
+        },
+    };
 
     //---------------------------------------------------------------
     // Choose the pre-generated bindings to use for the target system
@@ -70,6 +95,21 @@ fn main() {
         "cargo:rustc-env=CRATE_WOLFRAM_LIBRARYLINK_SYS_BINDINGS={}",
         bindings_path.display()
     );
+
+    //-----------------------------------------------------
+    // Link against the standalone Wolfram Runtime Library
+    //-----------------------------------------------------
+
+    if std::env::var("CARGO_FEATURE_STANDALONE_RTL").is_ok() {
+        let libraries_dir = app
+            .installation_directory()
+            .join("SystemFiles")
+            .join("Libraries")
+            .join(system_id);
+
+        println!("cargo:rustc-link-search=native={}", libraries_dir.display());
+        println!("cargo:rustc-link-lib=dylib=WolframRTL");
+    }
 }
 
 /// Path (relative to the crate root directory) to the bindings file.
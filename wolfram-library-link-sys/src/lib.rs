@@ -8,3 +8,57 @@ include!(concat!(
     "../",
     env!("CRATE_WOLFRAM_LIBRARYLINK_SYS_BINDINGS"),
 ));
+
+//======================================
+// mcomplex
+//======================================
+
+impl mcomplex {
+    /// Construct a complex number from its real and imaginary parts.
+    pub const fn new(re: mreal, im: mreal) -> mcomplex {
+        mcomplex { ri: [re, im] }
+    }
+
+    /// The real part of this complex number.
+    pub const fn re(&self) -> mreal {
+        self.ri[0]
+    }
+
+    /// The imaginary part of this complex number.
+    pub const fn im(&self) -> mreal {
+        self.ri[1]
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl From<mcomplex> for num_complex::Complex64 {
+    fn from(value: mcomplex) -> Self {
+        num_complex::Complex64::new(value.re(), value.im())
+    }
+}
+
+#[cfg(feature = "num-complex")]
+impl From<num_complex::Complex64> for mcomplex {
+    fn from(value: num_complex::Complex64) -> Self {
+        mcomplex::new(value.re, value.im)
+    }
+}
+
+//======================================
+// Standalone Wolfram Runtime Library
+//======================================
+
+#[cfg(feature = "standalone-rtl")]
+extern "C" {
+    /// Initialize the standalone Wolfram Runtime Library.
+    ///
+    /// Returns 0 on success.
+    pub fn WolframRTL_initialize(version: mint) -> ::std::os::raw::c_int;
+
+    /// Construct a new [`WolframLibraryData`] instance whose callback functions are
+    /// provided by the standalone Wolfram Runtime Library.
+    pub fn WolframLibraryData_new(version: mint) -> WolframLibraryData;
+
+    /// Free a [`WolframLibraryData`] instance created by [`WolframLibraryData_new()`].
+    pub fn WolframLibraryData_free(data: WolframLibraryData);
+}
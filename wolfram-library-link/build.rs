@@ -0,0 +1,30 @@
+fn main() {
+    // Compile the signal handlers used by the `crash_guard` module.
+    #[cfg(feature = "crash-guard")]
+    if std::env::var_os("CARGO_CFG_UNIX").is_some() {
+        println!("cargo:rerun-if-changed=src/crash_guard.c");
+
+        cc::Build::new()
+            .file("src/crash_guard.c")
+            .compile("wll_crash_guard");
+    }
+
+    // Set the build ID used in the exported symbol names of this crate and its
+    // examples. See "Hot reloading" in the `generate_loader!` documentation.
+    if std::env::var_os("CARGO_FEATURE_HOT_RELOAD").is_some() {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or(0);
+
+        println!("cargo:rustc-env=WOLFRAM_LIBRARY_LINK_BUILD_ID={:x}", nanos);
+
+        // Generate a new ID each time the library or examples change.
+        println!("cargo:rerun-if-changed=src");
+        println!("cargo:rerun-if-changed=examples");
+    }
+
+    println!("cargo:rerun-if-changed=build.rs");
+}
@@ -11,10 +11,7 @@ wll::export![start_file_watcher(_, _)];
 /// Start an asynchronous task that will watch for modifications to a file.
 ///
 /// See `RustLink/Tests/AsyncExamples.wlt` for example usage of this function.
-fn start_file_watcher(pause_interval_ms: mint, path: String) -> mint {
-    let pause_interval_ms =
-        u64::try_from(pause_interval_ms).expect("mint interval overflows u64");
-
+fn start_file_watcher(pause_interval_ms: u64, path: String) -> mint {
     let path = PathBuf::from(path);
 
     // Spawn a new thread, which will run in the background and check for file
@@ -1,5 +1,4 @@
 use std::{
-    ffi::CStr,
     fs,
     os::raw::{c_uint, c_void},
     path::PathBuf,
@@ -9,6 +8,7 @@ use std::{
 use wolfram_library_link::{
     self as wll, rtl,
     sys::{self, mint, MArgument, LIBRARY_FUNCTION_ERROR, LIBRARY_NO_ERROR},
+    KernelString,
 };
 
 struct FileWatcherArgs {
@@ -41,8 +41,9 @@ pub extern "C" fn start_file_watcher(
             pause_interval_ms: u64::try_from(*args[0].integer)
                 .expect("i64 interval overflows u64"),
             path: {
-                let cstr = CStr::from_ptr(*args[1].utf8string);
-                match cstr.to_str() {
+                // Disowns the Kernel's copy of the string when dropped.
+                let string = KernelString::from_raw(*args[1].utf8string);
+                match string.to_str() {
                     Ok(s) => PathBuf::from(s),
                     Err(_) => return LIBRARY_FUNCTION_ERROR,
                 }
@@ -1,7 +1,7 @@
 //! This example demonstrates how LibraryLink native data types can be used in Rust
 //! functions called via LibraryLink.
 
-use wolfram_library_link::{self as wll, NumericArray, UninitNumericArray};
+use wolfram_library_link::{self as wll, Assoc, NumericArray, UninitNumericArray};
 
 wll::generate_loader!(load_basic_types_functions);
 
@@ -80,7 +80,24 @@ fn total_i64(list: &NumericArray<i64>) -> i64 {
     list.as_slice().into_iter().sum()
 }
 
-wll::export![total_i64(_)];
+//------------
+// total_f64()
+//------------
+
+fn total_f64(list: &NumericArray<f64>) -> f64 {
+    list.as_slice().into_iter().sum()
+}
+
+// Export `total_i64` and `total_f64`, and load them as a single `total` function that
+// calls whichever variant matches the type of its NumericArray argument:
+//
+// ```wolfram
+// total = $functions["total"];
+//
+// total[NumericArray[Range[100], "Integer64"]]
+// total[NumericArray[{1.5, 2.5}, "Real64"]]
+// ```
+wll::export_overloads![total: total_i64, total_f64];
 
 //---------------
 // positive_i64()
@@ -104,6 +121,81 @@ fn positive_i64(list: &NumericArray<i64>) -> NumericArray<u8> {
 
 wll::export![positive_i64(_)];
 
+//======================================
+// Associations
+//======================================
+
+//-------------
+// word_stats()
+//-------------
+
+// Returned as an `Association` when loaded using the loader function:
+//
+// ```wolfram
+// wordStats = $functions["word_stats"];
+//
+// wordStats["hello world"] (* <| "Words" -> 2, "Characters" -> 11 |> *)
+// ```
+fn word_stats(text: String) -> Assoc {
+    let mut stats = Assoc::new();
+
+    stats.insert("Words", text.split_whitespace().count() as i64);
+    stats.insert("Characters", text.chars().count() as i64);
+
+    stats
+}
+
+wll::export![word_stats(_)];
+
+//======================================
+// Tuples
+//======================================
+
+//-----------
+// min_max()
+//-----------
+
+// Returned as a `List` when loaded using the loader function:
+//
+// ```wolfram
+// minMax = $functions["min_max"];
+//
+// minMax[NumericArray[{3.0, 1.0, 2.0}, "Real64"]] (* {1., 3.} *)
+// ```
+fn min_max(list: &NumericArray<f64>) -> (f64, f64) {
+    list.as_slice()
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &elem| {
+            (min.min(elem), max.max(elem))
+        })
+}
+
+wll::export![min_max(_)];
+
+//======================================
+// 128-bit integers
+//======================================
+
+//-------------
+// factorial()
+//-------------
+
+// `u128` values are passed as strings, and returned as an `Integer` when loaded using the
+// loader function:
+//
+// ```wolfram
+// factorial = $functions["factorial"];
+//
+// factorial["30"] (* 265252859812191058636308480000000 *)
+// ```
+fn factorial(n: u128) -> u128 {
+    (1..=n)
+        .try_fold(1u128, |acc, i| acc.checked_mul(i))
+        .expect("factorial does not fit in a u128")
+}
+
+wll::export![factorial(_)];
+
 //======================================
 // get_random_number()
 //======================================
@@ -0,0 +1,61 @@
+//! Return large results over a WSTP link as compressed WXF data.
+//!
+//! This example requires the `"compression"` feature of `wolfram-library-link`.
+
+use wolfram_library_link::{
+    self as wll, compression,
+    expr::{Expr, Symbol},
+    wstp::Link,
+    LinkArgs,
+};
+
+wll::export_wstp![symbolic_range(&mut Link); random_walk(&mut Link)];
+
+/// Return the list `{x[1], x[2], ..., x[n]}`, compressing it if it is large.
+///
+/// ```wolfram
+/// symbolicRange = LibraryFunctionLoad["libcompression", "symbolic_range", LinkObject, LinkObject];
+///
+/// symbolicRange[100000] // Length    (* Returns 100000 *)
+/// ```
+fn symbolic_range(link: &mut Link) {
+    let n: i64 = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(1).unwrap();
+        args.next().unwrap()
+    };
+
+    let elements = (1..=n)
+        .map(|i| Expr::normal(Symbol::new("Global`x"), vec![Expr::from(i)]))
+        .collect();
+
+    let list = Expr::normal(Symbol::new("System`List"), elements);
+
+    compression::put_expr(link, &list, compression::DEFAULT_THRESHOLD).unwrap();
+}
+
+/// Return a packed array of the positions of a one-dimensional random walk of `n` steps.
+fn random_walk(link: &mut Link) {
+    let n: i64 = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(1).unwrap();
+        args.next().unwrap()
+    };
+
+    // Simple linear congruential generator, to avoid depending on a random number crate.
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut position: i64 = 0;
+
+    let positions: Vec<i64> = (0..n)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+
+            position += if state >> 63 == 0 { -1 } else { 1 };
+            position
+        })
+        .collect();
+
+    compression::put_compressed_i64_array(link, &positions, &[positions.len()]).unwrap();
+}
@@ -0,0 +1,33 @@
+//! Exchange dates with the Wolfram Language using the `chrono` crate.
+//!
+//! This example requires the `"chrono"` feature of `wolfram-library-link`.
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::Expr,
+};
+
+wll::export![add_days(_, _); days_between(_, _)];
+wll::export_wstp![dates_to_utc(_)];
+
+/// Add `days` days to `date`, which is passed as an `AbsoluteTime[TimeZone -> 0]` value.
+fn add_days(date: DateTime<Utc>, days: i64) -> DateTime<Utc> {
+    date + Duration::days(days)
+}
+
+fn days_between(start: NaiveDate, end: NaiveDate) -> i64 {
+    end.signed_duration_since(start).num_days()
+}
+
+/// Convert each `DateObject` argument to a `DateObject` in UTC.
+fn dates_to_utc(args: Vec<Expr>) -> Expr {
+    let dates: Vec<DateTime<Utc>> = args
+        .iter()
+        .map(|arg| DateTime::<Utc>::from_expr(arg).expect("expected DateObject"))
+        .collect();
+
+    dates.to_expr()
+}
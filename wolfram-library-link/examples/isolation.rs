@@ -0,0 +1,56 @@
+//! Run crash-prone code in a helper process, so that a crash does not terminate the
+//! Wolfram Kernel.
+//!
+//! This example requires the `"isolation"` feature of `wolfram-library-link`.
+
+use wolfram_library_link::{
+    self as wll,
+    expr::{Expr, Symbol},
+    isolation,
+    wstp::Link,
+    LinkArgs,
+};
+
+wll::export_wstp![isolated_square(&mut Link); isolated_crash(&mut Link)];
+
+/// Square an integer in a helper process.
+///
+/// ```wolfram
+/// isolatedSquare = LibraryFunctionLoad["libisolation", "isolated_square", LinkObject, LinkObject];
+///
+/// isolatedSquare[12]    (* Returns 144 *)
+/// ```
+fn isolated_square(link: &mut Link) {
+    let n: i64 = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(1).unwrap();
+        args.next().unwrap()
+    };
+
+    // Safety: The closure does not take any locks, print, or panic.
+    unsafe { isolation::put_isolated(link, || Expr::from(n.wrapping_mul(n))).unwrap() };
+}
+
+/// Dereference a null pointer in a helper process.
+///
+/// ```wolfram
+/// isolatedCrash = LibraryFunctionLoad["libisolation", "isolated_crash", LinkObject, LinkObject];
+///
+/// isolatedCrash[]    (* Returns Failure["RustIsolatedProcessFailed", <|...|>] *)
+/// ```
+fn isolated_crash(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    // Safety: The closure does not take any locks, print, or panic before it crashes.
+    unsafe {
+        isolation::put_isolated(link, || {
+            let null: *const i64 = std::ptr::null();
+
+            // Not safe at all; this is intended to crash the helper process.
+            let value = std::ptr::read_volatile(null);
+
+            Expr::normal(Symbol::new("System`List"), vec![Expr::from(value)])
+        })
+        .unwrap();
+    }
+}
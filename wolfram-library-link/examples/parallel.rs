@@ -0,0 +1,36 @@
+//! Process a NumericArray in parallel using Rayon, while remaining responsive to Wolfram
+//! Language aborts.
+//!
+//! This example requires the `"rayon"` feature of `wolfram-library-link`.
+
+use wolfram_library_link::{self as wll, parallel, NumericArray};
+
+wll::export![parallel_sum_of_squares(_); parallel_scale(_, _)];
+
+fn parallel_sum_of_squares(array: &NumericArray<f64>) -> f64 {
+    let partial_sums: Vec<f64> =
+        parallel::par_chunks_abortable(array.as_slice(), 4096, |chunk| {
+            chunk.iter().map(|x| x * x).sum()
+        })
+        .expect("parallel_sum_of_squares: computation was aborted");
+
+    partial_sums.into_iter().sum()
+}
+
+/// Multiply every element of `array` by `factor`.
+fn parallel_scale(array: &NumericArray<f64>, factor: f64) -> NumericArray<f64> {
+    let mut result: NumericArray<f64> = array.clone();
+
+    let data: &mut [f64] = result
+        .as_slice_mut()
+        .expect("cloned array should not be shared");
+
+    parallel::par_chunks_mut_abortable(data, 4096, |chunk| {
+        for elem in chunk {
+            *elem *= factor;
+        }
+    })
+    .expect("parallel_scale: computation was aborted");
+
+    result
+}
@@ -0,0 +1,25 @@
+//! Use `NumericArray` and `DataStore` from a standalone program, without a Wolfram
+//! Kernel.
+//!
+//! This example requires the `"standalone-rtl"` feature of `wolfram-library-link`, and
+//! is built as an executable rather than as a library:
+//!
+//! ```shell
+//! $ cargo run --example standalone --features standalone-rtl
+//! ```
+
+use wolfram_library_link::{self as wll, DataStore, NumericArray};
+
+fn main() {
+    wll::initialize_standalone().expect("failed to initialize the Wolfram RTL");
+
+    let array: NumericArray<f64> = NumericArray::from_slice(&[1.0, 2.0, 3.0, 4.0]);
+
+    let sum: f64 = array.as_slice().iter().sum();
+
+    let mut data = DataStore::new();
+    data.add_named_f64("sum", sum);
+    data.add_named_numeric_array("array", array.into_generic());
+
+    println!("DataStore with {} elements; sum = {}", data.len(), sum);
+}
@@ -2,7 +2,71 @@ mod test_native_args;
 mod test_share_counts;
 mod test_threading;
 
+mod test_bool_array;
+mod test_callback;
 mod test_data_store;
+mod test_dynamic;
 mod test_images;
+mod test_int128;
+mod test_io;
+mod test_managed;
+mod test_memory;
 mod test_numeric_array_conversions;
+mod test_registry;
+mod test_sparse_arrays;
+mod test_wide_string;
 mod test_wstp;
+
+// Tests for the conversions and modules enabled by optional features.
+#[cfg(feature = "arrow")]
+mod test_arrow;
+#[cfg(feature = "num-bigint")]
+mod test_bigint;
+#[cfg(feature = "chrono")]
+mod test_chrono;
+#[cfg(feature = "num-complex")]
+mod test_complex;
+#[cfg(feature = "compression")]
+mod test_compression;
+#[cfg(all(feature = "crash-guard", unix))]
+mod test_crash_guard;
+#[cfg(feature = "serde")]
+mod test_dataset;
+#[cfg(feature = "image")]
+mod test_image_buffers;
+#[cfg(all(feature = "isolation", unix))]
+mod test_isolation;
+#[cfg(feature = "serde_json")]
+mod test_json;
+#[cfg(feature = "rayon")]
+mod test_parallel;
+#[cfg(feature = "petgraph")]
+mod test_petgraph;
+#[cfg(feature = "polars")]
+mod test_polars;
+#[cfg(feature = "num-rational")]
+mod test_rational;
+#[cfg(feature = "sprs")]
+mod test_sprs;
+#[cfg(feature = "uom")]
+mod test_uom;
+#[cfg(feature = "uuid")]
+mod test_uuid;
+
+// Used by Loader.wlt to test loading the functions in this library into a context,
+// grouped by the module each function is exported from, timing calls to functions
+// exported using `#[timing]`, and unloading and reloading the library.
+wolfram_library_link::generate_loader![
+    load_library_tests,
+    context = "RustLinkTests`",
+    group_by_module,
+    reload,
+    timing
+];
+
+// Used by Loader.wlt to test loading the functions in this library lazily.
+wolfram_library_link::generate_loader![
+    load_library_tests_lazily,
+    context = "RustLinkTestsLazy`",
+    lazy
+];
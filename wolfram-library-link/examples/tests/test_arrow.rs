@@ -0,0 +1,167 @@
+use std::sync::Arc;
+
+use arrow_array::{
+    cast::AsArray,
+    types::{Float64Type, Int32Type},
+    ArrayRef, Float64Array, Int32Array, Int64Array, RecordBatch, StringArray,
+};
+
+use wolfram_library_link::{
+    self as wll,
+    arrow::{
+        arrow_from_numeric_array, data_store_from_record_batch, numeric_array_from_arrow,
+        record_batch_from_data_store,
+    },
+    DataStore, NumericArray,
+};
+
+wll::export![
+    test_arrow_array_conversions();
+    test_arrow_record_batch_conversions();
+    test_arrow_scale_columns(_, _);
+];
+
+fn test_arrow_array_conversions() {
+    let array = Int32Array::from(vec![1, 2, 3, 4]);
+
+    let numeric_array = numeric_array_from_arrow(&array).unwrap();
+
+    assert_eq!(numeric_array.as_slice(), [1, 2, 3, 4]);
+    assert_eq!(arrow_from_numeric_array::<Int32Type>(&numeric_array), array);
+
+    // Only the elements in a sliced array are converted.
+    assert_eq!(
+        numeric_array_from_arrow(&array.slice(1, 2))
+            .unwrap()
+            .as_slice(),
+        [2, 3]
+    );
+
+    assert!(
+        numeric_array_from_arrow(&Int32Array::from(Vec::<i32>::new()))
+            .unwrap()
+            .is_empty()
+    );
+
+    let error =
+        numeric_array_from_arrow(&Int32Array::from(vec![Some(1), None])).unwrap_err();
+
+    assert_eq!(error, "Arrow array of type Int32 contains 1 null values");
+
+    // Arrays of rank greater than 1 are flattened.
+    let matrix = NumericArray::from_array(&[2, 2], &[1.0, 2.0, 3.0, 4.0]);
+
+    assert_eq!(
+        arrow_from_numeric_array::<Float64Type>(&matrix),
+        Float64Array::from(vec![1.0, 2.0, 3.0, 4.0])
+    );
+}
+
+fn test_arrow_record_batch_conversions() {
+    let batch = RecordBatch::try_from_iter([
+        (
+            "x",
+            Arc::new(Float64Array::from(vec![0.5, f64::NAN])) as ArrayRef,
+        ),
+        (
+            "n",
+            Arc::new(Int64Array::from(vec![i64::MIN, i64::MAX])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+
+    let store = data_store_from_record_batch(&batch).unwrap();
+
+    assert_eq!(store.len(), 2);
+
+    let round_trip = record_batch_from_data_store(&store).unwrap();
+
+    assert_eq!(round_trip.schema().field(0).name(), "x");
+    assert_eq!(round_trip.schema().field(1).name(), "n");
+
+    let x = round_trip.column(0).as_primitive::<Float64Type>();
+
+    assert_eq!(x.value(0), 0.5);
+    assert!(x.value(1).is_nan());
+    assert_eq!(round_trip.column(1), batch.column(1));
+
+    //
+    // Errors
+    //
+
+    let strings = RecordBatch::try_from_iter([(
+        "s",
+        Arc::new(StringArray::from(vec!["a"])) as ArrayRef,
+    )])
+    .unwrap();
+
+    assert_eq!(
+        data_store_from_record_batch(&strings).unwrap_err(),
+        "column \"s\": unsupported Arrow data type: Utf8"
+    );
+
+    let nulls = RecordBatch::try_from_iter([(
+        "n",
+        Arc::new(Int64Array::from(vec![None, Some(1)])) as ArrayRef,
+    )])
+    .unwrap();
+
+    assert!(data_store_from_record_batch(&nulls)
+        .unwrap_err()
+        .starts_with("column \"n\": "));
+
+    let mut unnamed = DataStore::new();
+    unnamed.add_numeric_array(NumericArray::from_slice(&[1i64]).into_generic());
+
+    assert_eq!(
+        record_batch_from_data_store(&unnamed).unwrap_err(),
+        "DataStore node 1 has no name"
+    );
+
+    let mut scalar = DataStore::new();
+    scalar.add_named_i64("n", 1);
+
+    assert_eq!(
+        record_batch_from_data_store(&scalar).unwrap_err(),
+        "DataStore node \"n\" is not a NumericArray"
+    );
+
+    let mut matrix = DataStore::new();
+    matrix.add_named_numeric_array(
+        "m",
+        NumericArray::from_array(&[1, 1], &[1i64]).into_generic(),
+    );
+
+    assert_eq!(
+        record_batch_from_data_store(&matrix).unwrap_err(),
+        "DataStore node \"m\" has rank 2, expected rank 1"
+    );
+
+    let mut lengths = DataStore::new();
+    lengths
+        .add_named_numeric_array("a", NumericArray::from_slice(&[1i64]).into_generic());
+    lengths.add_named_numeric_array(
+        "b",
+        NumericArray::from_slice(&[1i64, 2]).into_generic(),
+    );
+
+    assert!(record_batch_from_data_store(&lengths).is_err());
+}
+
+/// Multiply each `"Real64"` column of `table` by `factor`.
+fn test_arrow_scale_columns(table: DataStore, factor: f64) -> DataStore {
+    let batch = record_batch_from_data_store(&table).unwrap();
+
+    let columns = batch
+        .columns()
+        .iter()
+        .map(|column| match column.as_primitive_opt::<Float64Type>() {
+            Some(column) => Arc::new(column.unary::<_, Float64Type>(|x| x * factor)) as _,
+            None => column.clone(),
+        })
+        .collect();
+
+    let batch = RecordBatch::try_new(batch.schema(), columns).unwrap();
+
+    data_store_from_record_batch(&batch).unwrap()
+}
@@ -0,0 +1,56 @@
+use num_bigint::BigInt;
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{self, FromExpr},
+    expr::Expr,
+    wstp::Link,
+};
+
+wll::export![
+    test_bigint_conversions();
+    test_bigint_double(_);
+];
+
+wll::export_wstp![test_bigint_link_double(&mut Link)];
+
+fn test_bigint_conversions() {
+    let large: BigInt = "123456789012345678901234567890".parse().unwrap();
+
+    assert_eq!(
+        BigInt::from_expr(&Expr::from(i64::MAX)),
+        Ok(BigInt::from(i64::MAX))
+    );
+    assert_eq!(
+        BigInt::from_expr(&Expr::from(i64::MIN)),
+        Ok(BigInt::from(i64::MIN))
+    );
+    assert_eq!(
+        BigInt::from_expr(&Expr::string("123456789012345678901234567890")),
+        Ok(large.clone())
+    );
+    assert_eq!(
+        BigInt::from_expr(&Expr::string("-123456789012345678901234567890")),
+        Ok(-large)
+    );
+
+    let error = BigInt::from_expr(&Expr::string("12a")).unwrap_err();
+
+    assert!(error.starts_with("invalid integer \"12a\""), "{}", error);
+
+    assert!(BigInt::from_expr(&Expr::string("")).is_err());
+    assert!(BigInt::from_expr(&Expr::string("1.5")).is_err());
+    assert!(BigInt::from_expr(&Expr::real(1.0)).is_err());
+}
+
+fn test_bigint_double(value: BigInt) -> BigInt {
+    value * 2
+}
+
+fn test_bigint_link_double(link: &mut Link) {
+    assert_eq!(link.test_head("System`List").unwrap(), 1);
+
+    let value = convert::get_big_integer(link).unwrap();
+
+    convert::put_big_integer(link, &(value * 2)).unwrap();
+}
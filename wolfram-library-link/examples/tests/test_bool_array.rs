@@ -0,0 +1,71 @@
+use wolfram_library_link::{self as wll, BoolArray, NumericArray};
+
+wll::export![
+    test_bool_array_conversions();
+    test_bool_array_not(_);
+    test_bool_array_count(_);
+    test_bool_array_mismatched_dimensions();
+];
+
+fn test_bool_array_conversions() {
+    let mask = BoolArray::from_bools(&[true, false, true]);
+
+    assert_eq!(mask.dimensions(), &[3]);
+    assert_eq!(mask.flattened_length(), 3);
+    assert_eq!(mask.to_vec(), [true, false, true]);
+    assert_eq!(mask.count_true(), 2);
+    assert_eq!(mask.get(0), Some(true));
+    assert_eq!(mask.get(1), Some(false));
+    assert_eq!(mask.get(3), None);
+
+    // Elements are stored as 0 or 1.
+    assert_eq!(mask.as_numeric_array().as_slice(), [1, 0, 1]);
+    assert_eq!(mask.clone().into_numeric_array().as_slice(), [1, 0, 1]);
+
+    assert_eq!(
+        format!("{:?}", mask),
+        "BoolArray { dimensions: [3], data: [true, false, true] }"
+    );
+
+    //
+    // Multidimensional arrays
+    //
+
+    let matrix = BoolArray::from_array(&[2, 2], &[true, false, false, true]);
+
+    assert_eq!(matrix.dimensions(), &[2, 2]);
+    assert_eq!(Vec::from(&matrix), [true, false, false, true]);
+
+    //
+    // Any non-zero element is true
+    //
+
+    let bytes = BoolArray::from(NumericArray::<u8>::from_slice(&[0, 1, 2, u8::MAX]));
+
+    assert_eq!(bytes.to_vec(), [false, true, true, true]);
+    assert_eq!(bytes.count_true(), 3);
+
+    //
+    // Empty arrays
+    //
+
+    let empty = BoolArray::from(Vec::new());
+
+    assert_eq!(empty.dimensions(), &[0]);
+    assert_eq!(empty.get(0), None);
+    assert_eq!(empty.count_true(), 0);
+}
+
+fn test_bool_array_not(mask: &BoolArray) -> BoolArray {
+    let data: Vec<bool> = mask.iter().map(|bool| !bool).collect();
+
+    BoolArray::from_array(mask.dimensions(), &data)
+}
+
+fn test_bool_array_count(mask: BoolArray) -> i64 {
+    mask.count_true() as i64
+}
+
+fn test_bool_array_mismatched_dimensions() -> BoolArray {
+    BoolArray::from_array(&[2, 2], &[true, false, true])
+}
@@ -0,0 +1,64 @@
+use std::sync::{Mutex, Once};
+
+use wolfram_library_link::{self as wll, callback::WolframCallback, sys};
+
+wll::export![
+    test_callback_register();
+    test_callback_argument_count();
+    test_callback_call(_, _);
+    test_callback_type_mismatch();
+    test_callback_disconnect();
+];
+
+/// The function most recently connected to the `"RustLinkTestsCallback"` manager.
+static CALLBACK: Mutex<Option<WolframCallback>> = Mutex::new(None);
+
+fn test_callback_register() {
+    static REGISTER: Once = Once::new();
+
+    REGISTER.call_once(|| {
+        wll::callback::register_library_callback_manager("RustLinkTestsCallback", connect)
+    });
+}
+
+/// Accept functions of two arguments, replacing the previously connected function.
+fn connect(callback: WolframCallback) -> bool {
+    if callback.argument_count() != 2 {
+        return false;
+    }
+
+    *CALLBACK.lock().unwrap() = Some(callback);
+
+    true
+}
+
+fn with_callback<T>(func: impl FnOnce(&WolframCallback) -> T) -> T {
+    let callback = CALLBACK.lock().unwrap();
+
+    func(callback.as_ref().expect("no function has been connected"))
+}
+
+fn test_callback_argument_count() -> i64 {
+    with_callback(|callback| callback.argument_count() as i64)
+}
+
+/// Call the connected function, which takes a Real and an Integer and returns a Real.
+fn test_callback_call(x: f64, n: i64) -> f64 {
+    with_callback(|callback| callback.args().arg(x).arg(n).call::<f64>())
+        .unwrap_or_else(|err| panic!("callback failed with error code {}", err))
+}
+
+/// Arguments whose types do not match the connected function are not passed to it.
+fn test_callback_type_mismatch() -> bool {
+    let result =
+        with_callback(|callback| callback.args().arg(1i64).arg(2i64).call::<f64>());
+
+    result == Err(sys::LIBRARY_TYPE_ERROR as sys::errcode_t)
+}
+
+/// Release the connected function.
+fn test_callback_disconnect() {
+    let callback = CALLBACK.lock().unwrap().take();
+
+    assert!(callback.is_some());
+}
@@ -0,0 +1,106 @@
+use chrono::{DateTime, FixedOffset, NaiveDate, TimeZone, Utc};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+    sys, TryFromArg,
+};
+
+wll::export![
+    test_chrono_conversions();
+    test_chrono_absolute_time_arg();
+    test_chrono_date_time(_);
+    test_chrono_naive_date(_);
+];
+
+/// AbsoluteTime[{2000, 1, 1}, TimeZone -> 0]
+const ABSOLUTE_TIME_2000: f64 = 3_155_673_600.0;
+
+fn test_chrono_conversions() {
+    let date = Utc.with_ymd_and_hms(2000, 1, 1, 12, 30, 15).unwrap();
+
+    //
+    // DateObject round trip
+    //
+
+    let expr = date.to_expr();
+
+    assert_eq!(DateTime::<Utc>::from_expr(&expr), Ok(date));
+    assert_eq!(NaiveDate::from_expr(&expr), Ok(date.date_naive()));
+
+    //
+    // Time zone offsets
+    //
+
+    let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+    let local = offset.with_ymd_and_hms(2000, 1, 1, 14, 30, 15).unwrap();
+
+    assert_eq!(
+        DateTime::<FixedOffset>::from_expr(&local.to_expr()),
+        Ok(local)
+    );
+    assert_eq!(DateTime::<Utc>::from_expr(&local.to_expr()), Ok(date));
+
+    //
+    // AbsoluteTime numbers
+    //
+
+    assert_eq!(
+        DateTime::<Utc>::from_expr(&Expr::real(ABSOLUTE_TIME_2000)),
+        Ok(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap())
+    );
+
+    // Fractional seconds that round up to a whole second carry into the seconds.
+    // (Values this close to a whole second can only be represented near the epoch.)
+    assert_eq!(
+        DateTime::<Utc>::from_expr(&Expr::real(0.9999999999)),
+        Ok(Utc.with_ymd_and_hms(1900, 1, 1, 0, 0, 1).unwrap())
+    );
+
+    assert!(DateTime::<Utc>::from_expr(&Expr::real(f64::NAN)).is_err());
+    assert!(DateTime::<Utc>::from_expr(&Expr::real(f64::INFINITY)).is_err());
+    assert!(DateTime::<Utc>::from_expr(&Expr::real(1e300)).is_err());
+
+    //
+    // Invalid DateObject expressions
+    //
+
+    let invalid = Expr::normal(Symbol::new("System`DateObject"), vec![Expr::normal(
+        Symbol::new("System`List"),
+        vec![Expr::from(2000i64), Expr::from(13i64), Expr::from(1i64)],
+    )]);
+
+    assert!(DateTime::<Utc>::from_expr(&invalid).is_err());
+    assert!(NaiveDate::from_expr(&Expr::string("2000-01-01")).is_err());
+}
+
+fn test_chrono_absolute_time_arg() {
+    unsafe fn try_from_real(mut value: f64) -> Result<DateTime<Utc>, String> {
+        let arg = sys::MArgument { real: &mut value };
+
+        DateTime::<Utc>::try_from_arg(&arg)
+    }
+
+    unsafe {
+        assert_eq!(
+            try_from_real(ABSOLUTE_TIME_2000),
+            Ok(Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap())
+        );
+
+        let error = try_from_real(f64::NAN).unwrap_err();
+
+        assert!(error.contains("invalid AbsoluteTime value"), "{}", error);
+
+        assert!(try_from_real(f64::NEG_INFINITY).is_err());
+        assert!(try_from_real(f64::MAX).is_err());
+    }
+}
+
+fn test_chrono_date_time(date: DateTime<Utc>) -> DateTime<Utc> {
+    date
+}
+
+fn test_chrono_naive_date(date: NaiveDate) -> NaiveDate {
+    date
+}
@@ -0,0 +1,96 @@
+use num_complex::Complex64;
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+wll::export![
+    test_complex_conversions();
+    test_complex_conjugate(_);
+];
+
+wll::export_wstp![test_complex_reciprocal(_)];
+
+fn complex(re: Expr, im: Expr) -> Expr {
+    Expr::normal(Symbol::new("System`Complex"), vec![re, im])
+}
+
+fn test_complex_conversions() {
+    let indeterminate = Expr::symbol(Symbol::new("System`Indeterminate"));
+
+    //
+    // ToExpr
+    //
+
+    assert_eq!(
+        Complex64::new(1.5, -2.0).to_expr(),
+        complex(Expr::real(1.5), Expr::real(-2.0))
+    );
+    assert_eq!(
+        Complex64::new(f64::MAX, f64::MIN_POSITIVE).to_expr(),
+        complex(Expr::real(f64::MAX), Expr::real(f64::MIN_POSITIVE))
+    );
+
+    // NaN parts are converted to Indeterminate.
+    assert_eq!(
+        Complex64::new(f64::NAN, 1.0).to_expr(),
+        complex(indeterminate.clone(), Expr::real(1.0))
+    );
+
+    //
+    // FromExpr
+    //
+
+    assert_eq!(
+        Complex64::from_expr(&complex(Expr::real(1.5), Expr::real(-2.0))),
+        Ok(Complex64::new(1.5, -2.0))
+    );
+    assert_eq!(
+        Complex64::from_expr(&complex(Expr::from(3i64), Expr::from(4i64))),
+        Ok(Complex64::new(3.0, 4.0))
+    );
+    assert_eq!(
+        Complex64::from_expr(&Expr::from(-7i64)),
+        Ok(Complex64::new(-7.0, 0.0))
+    );
+    assert_eq!(
+        Complex64::from_expr(&Expr::real(0.25)),
+        Ok(Complex64::new(0.25, 0.0))
+    );
+
+    let value = Complex64::from_expr(&complex(indeterminate, Expr::real(1.0))).unwrap();
+
+    assert!(value.re.is_nan());
+    assert_eq!(value.im, 1.0);
+
+    // Invalid expressions
+    assert!(Complex64::from_expr(&Expr::string("1 + 2 I")).is_err());
+    assert!(Complex64::from_expr(&complex(Expr::real(1.0), Expr::string("x"))).is_err());
+    assert!(
+        Complex64::from_expr(&Expr::normal(Symbol::new("System`Complex"), vec![
+            Expr::real(1.0)
+        ]))
+        .is_err()
+    );
+}
+
+fn test_complex_conjugate(value: Complex64) -> Complex64 {
+    value.conj()
+}
+
+/// Returns the reciprocal of a complex or real number, or a `Failure[..]`.
+fn test_complex_reciprocal(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    Complex64::from_expr(&args[0])
+        .and_then(|value| {
+            if value == Complex64::new(0.0, 0.0) {
+                return Err("division by zero".to_owned());
+            }
+
+            Ok(value.inv())
+        })
+        .to_expr()
+}
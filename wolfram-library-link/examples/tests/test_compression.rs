@@ -0,0 +1,116 @@
+use wolfram_library_link::{
+    self as wll, compression,
+    expr::{Expr, Symbol},
+    wstp::Link,
+    LinkArgs,
+};
+
+wll::export![test_compression_wxf()];
+
+wll::export_wstp![
+    test_compression_put_expr(&mut Link);
+    test_compression_round_trip(&mut Link);
+    test_compression_f64_array(&mut Link);
+    test_compression_i64_array(&mut Link);
+    test_compression_mismatched_dimensions(&mut Link);
+];
+
+fn test_compression_wxf() {
+    //
+    // Integers use the smallest token that can represent them
+    //
+
+    assert_eq!(compression::to_wxf(&Expr::from(-128i64)), b"8:C\x80");
+    assert_eq!(compression::to_wxf(&Expr::from(128i64)), b"8:j\x80\x00");
+    assert_eq!(
+        compression::to_wxf(&Expr::from(32768i64)),
+        b"8:i\x00\x80\x00\x00"
+    );
+    assert_eq!(
+        compression::to_wxf(&Expr::from(i64::MIN)),
+        b"8:L\x00\x00\x00\x00\x00\x00\x00\x80"
+    );
+
+    //
+    // Reals, strings, symbols and normal expressions
+    //
+
+    let mut real = b"8:r".to_vec();
+    real.extend_from_slice(&1.5f64.to_le_bytes());
+
+    assert_eq!(compression::to_wxf(&Expr::real(1.5)), real);
+
+    // Lengths are written as variable-length integers.
+    let long_string = "x".repeat(200);
+    let mut string = b"8:S\xC8\x01".to_vec();
+    string.extend_from_slice(long_string.as_bytes());
+
+    assert_eq!(compression::to_wxf(&Expr::string(long_string)), string);
+
+    // String lengths are in bytes, not characters.
+    assert_eq!(compression::to_wxf(&Expr::string("é")), b"8:S\x02\xC3\xA9");
+
+    let call = Expr::normal(Symbol::new("Global`f"), vec![Expr::from(1i64)]);
+
+    assert_eq!(compression::to_wxf(&call), b"8:f\x01s\x08Global`fC\x01");
+
+    //
+    // Compressed WXF
+    //
+
+    let compressed = compression::to_compressed_wxf(&Expr::string("x".repeat(10_000)));
+
+    assert!(compressed.starts_with(b"8C:"));
+    assert!(compressed.len() < 1000);
+}
+
+/// Returns `{x[1], ..., x[n]}`, compressed if its serialized size is at least
+/// `threshold` bytes.
+fn test_compression_put_expr(link: &mut Link) {
+    let (n, threshold): (i64, i64) = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(2).unwrap();
+        (args.next().unwrap(), args.next().unwrap())
+    };
+
+    let elements = (1..=n)
+        .map(|i| Expr::normal(Symbol::new("Global`x"), vec![Expr::from(i)]))
+        .collect();
+
+    let list = Expr::normal(Symbol::new("System`List"), elements);
+
+    compression::put_expr(link, &list, threshold as usize).unwrap();
+}
+
+/// Returns its argument, after a round trip through compressed WXF.
+fn test_compression_round_trip(link: &mut Link) {
+    let expr: Expr = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(1).unwrap();
+        args.next().unwrap()
+    };
+
+    compression::put_compressed_expr(link, &expr).unwrap();
+}
+
+fn test_compression_f64_array(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    let data = [1.5, -2.0, 0.0, f64::MAX, f64::MIN_POSITIVE, -1e-300];
+
+    compression::put_compressed_f64_array(link, &data, &[2, 3]).unwrap();
+}
+
+fn test_compression_i64_array(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    let data = [i64::MIN, -1, 0, 1, i64::MAX];
+
+    compression::put_compressed_i64_array(link, &data, &[5]).unwrap();
+}
+
+fn test_compression_mismatched_dimensions(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    compression::put_compressed_i64_array(link, &[1, 2, 3], &[2, 2]).unwrap();
+}
@@ -0,0 +1,86 @@
+use std::os::raw::{c_int, c_void};
+
+use wolfram_library_link::{self as wll, crash_guard};
+
+wll::export![test_crash_guard_installed(); test_crash_guard_calls()];
+
+// Note: The callees below stand in for the C functions that `call_guarded()` is meant
+//       for. They are `extern "C"` functions with no values to drop, so skipping
+//       their stack frames when a signal is caught does not skip any cleanup code.
+
+/// Add one to the `c_int` that `data` points to.
+unsafe extern "C" fn increment(data: *mut c_void) {
+    *data.cast::<c_int>() += 1;
+}
+
+/// Raise the signal whose number `data` points to.
+unsafe extern "C" fn raise_signal(data: *mut c_void) {
+    libc::raise(*data.cast::<c_int>());
+}
+
+/// Read from an invalid address, causing a segmentation fault.
+unsafe extern "C" fn read_invalid_address(_: *mut c_void) {
+    std::ptr::read_volatile(0x10 as *const u8);
+}
+
+fn test_crash_guard_installed() -> bool {
+    crash_guard::is_installed()
+}
+
+fn test_crash_guard_calls() {
+    assert!(crash_guard::is_installed());
+
+    let mut counter: c_int = 0;
+
+    unsafe {
+        assert_eq!(
+            crash_guard::call_guarded(increment, (&mut counter as *mut c_int).cast()),
+            Ok(())
+        );
+    }
+
+    assert_eq!(counter, 1);
+
+    //
+    // Crashes are returned as errors
+    //
+
+    let crash = unsafe {
+        crash_guard::call_guarded(read_invalid_address, std::ptr::null_mut()).unwrap_err()
+    };
+
+    assert_eq!(crash.signal(), libc::SIGSEGV);
+    assert_eq!(crash.address(), 0x10);
+    assert_eq!(
+        crash.to_string(),
+        "crashed with signal SIGSEGV (segmentation fault) at address 0x10. The library \
+         may be in an inconsistent state, and the Kernel should be restarted."
+    );
+
+    for signal in [libc::SIGFPE, libc::SIGBUS] {
+        let mut signal_number = signal;
+
+        let crash = unsafe {
+            crash_guard::call_guarded(
+                raise_signal,
+                (&mut signal_number as *mut c_int).cast(),
+            )
+            .unwrap_err()
+        };
+
+        assert_eq!(crash.signal(), signal);
+    }
+
+    //
+    // Guarded calls can be made again after a crash
+    //
+
+    unsafe {
+        assert_eq!(
+            crash_guard::call_guarded(increment, (&mut counter as *mut c_int).cast()),
+            Ok(())
+        );
+    }
+
+    assert_eq!(counter, 2);
+}
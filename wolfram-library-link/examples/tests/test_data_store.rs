@@ -1,8 +1,12 @@
-use std::os::raw::c_int;
+use std::{
+    collections::{BTreeMap, HashMap},
+    os::raw::c_int,
+};
 use wolfram_library_link::{
     self as wll,
     sys::{self, WolframLibraryData},
-    DataStore, NumericArray,
+    ColorSpace, DataStore, DataStoreValue, Event, EventField, NumericArray, SparseArray,
+    UninitImage,
 };
 
 
@@ -26,6 +30,14 @@ wll::export![
     test_iterated_nested_data_store();
     test_data_store_arg(_);
     test_data_store_nodes();
+    test_data_store_from_iter();
+    test_data_store_expr_round_trip(_);
+    test_data_store_get_path(_);
+    test_data_store_typed_nodes();
+    test_data_store_array_nodes(_);
+    test_data_store_len_and_clear();
+    test_data_store_named_map(_);
+    test_event_data_store();
 ];
 
 fn test_empty_data_store() -> DataStore {
@@ -163,16 +175,242 @@ fn test_data_store_nodes() {
 
         assert_eq!(
             format!("{:?}", nodes.next().unwrap()),
-            r#"DataStoreNode { name: None, value: 5 }"#
+            r#"DataStoreNode { name: None, type: "Integer", value: 5 }"#
         );
         assert_eq!(
             format!("{:?}", nodes.next().unwrap()),
-            r#"DataStoreNode { name: Some("condition"), value: true }"#
+            r#"DataStoreNode { name: Some("condition"), type: "Boolean", value: true }"#
         );
         assert_eq!(
             format!("{:?}", nodes.next().unwrap()),
-            r#"DataStoreNode { name: None, value: "Hello, World!" }"#
+            r#"DataStoreNode { name: None, type: "String", value: "Hello, World!" }"#
         );
         assert!(nodes.next().is_none());
     }
+
+    // Test Debug formatting of DataStore.
+    {
+        let mut inner = DataStore::new();
+        inner.add_f64(1.5);
+
+        let mut store = DataStore::new();
+
+        store.add_i64(5);
+        store.add_named_data_store("inner", inner);
+
+        assert_eq!(
+            format!("{:?}", store),
+            concat!(
+                r#"DataStore[DataStoreNode { name: None, type: "Integer", value: 5 }, "#,
+                r#"DataStoreNode { name: Some("inner"), type: "DataStore", "#,
+                r#"value: DataStore[DataStoreNode { name: None, type: "Real", value: 1.5 }] }]"#
+            )
+        );
+        assert_eq!(format!("{:?}", DataStore::new()), "DataStore[]");
+    }
+}
+
+fn test_data_store_from_iter() -> DataStore {
+    let mut store: DataStore = vec![
+        (None, DataStoreValue::from(1)),
+        (Some("name".to_owned()), DataStoreValue::from("hello")),
+    ]
+    .into_iter()
+    .collect();
+
+    store.extend([
+        (
+            None,
+            DataStoreValue::from(NumericArray::from_slice(&[1i64, 2, 3])),
+        ),
+        (
+            Some("inner".to_owned()),
+            DataStoreValue::from(DataStore::new()),
+        ),
+    ]);
+
+    store
+}
+
+fn test_data_store_expr_round_trip(store: DataStore) -> DataStore {
+    let expr = store
+        .to_expr()
+        .expect("DataStore to Expr conversion failed");
+
+    DataStore::try_from_expr(&expr).expect("Expr to DataStore conversion failed")
+}
+
+fn test_data_store_get_path(store: DataStore) -> i64 {
+    assert!(store.get_path(&[]).is_none());
+    assert!(store.get_path(&["missing"]).is_none());
+    assert!(store.get_path(&["outer", "missing"]).is_none());
+    // "flag" is not a DataStore, so it cannot be a path parent.
+    assert!(store.get_path(&["flag", "value"]).is_none());
+
+    assert_eq!(
+        store.get_path(&["outer"]).map(|node| node.data_type_raw()),
+        Some(sys::MType_DataStore as i32)
+    );
+
+    let node = store
+        .get_path(&["outer", "inner", "value"])
+        .expect("missing outer/inner/value node");
+
+    node.as_i64().unwrap()
+}
+
+fn test_data_store_typed_nodes() {
+    let mut inner = DataStore::new();
+    inner.add_i64(1);
+
+    let mut store = DataStore::new();
+
+    store.add_named_bool("bool", true);
+    store.add_named_i64("int", 5);
+    store.add_named_f64("real", 1.5);
+    store.add_named_complex_f64("complex", sys::mcomplex::new(1.0, -1.0));
+    store.add_named_str("str", "hello");
+    store.add_named_numeric_array(
+        "array",
+        NumericArray::from_slice(&[1.0f64, 2.0]).into_generic(),
+    );
+    store.add_data_store(inner);
+
+    let node = |name: &str| store.get(name).unwrap();
+
+    assert_eq!(node("bool").as_bool(), Ok(true));
+    assert_eq!(node("int").as_i64(), Ok(5));
+    assert_eq!(node("real").as_f64(), Ok(1.5));
+    assert_eq!(
+        node("complex").as_complex_f64().map(|c| c.ri),
+        Ok([1.0, -1.0])
+    );
+    assert_eq!(node("str").as_str(), Ok("hello"));
+    assert_eq!(
+        node("array")
+            .as_numeric_array::<f64>()
+            .map(|array| array.as_slice()),
+        Ok(&[1.0, 2.0][..])
+    );
+
+    let last = store.nodes().last().unwrap();
+    assert_eq!(last.as_data_store().map(|inner| inner.len()), Ok(1));
+
+    // Test type mismatch errors.
+    assert_eq!(
+        node("int").as_f64(),
+        Err(r#"DataStore node "int": expected Real, got Integer"#.to_owned())
+    );
+    assert_eq!(
+        node("array").as_numeric_array::<i64>().map(|_| ()),
+        Err(concat!(
+            r#"DataStore node "array": expected NumericArray of type Integer64, "#,
+            "got NumericArray of type Real64"
+        )
+        .to_owned())
+    );
+    assert_eq!(
+        last.as_str(),
+        Err("DataStore node: expected String, got DataStore".to_owned())
+    );
+}
+
+fn test_data_store_array_nodes(sparse: &SparseArray) -> DataStore {
+    let mut image = UninitImage::<u8>::new_2d(2, 1, 1, ColorSpace::Gray, false);
+    image.zero();
+    let image = unsafe { image.assume_init() };
+
+    let mut store = DataStore::new();
+
+    store.add_named_image("image", image.into_generic());
+    store.add_named_sparse_array("sparse", sparse.clone());
+
+    let sparse_node = store.get("sparse").unwrap();
+
+    assert_eq!(sparse_node.as_sparse_array().unwrap().dimensions(), &[3, 3]);
+    assert_eq!(
+        sparse_node.as_image().map(|_| ()),
+        Err(r#"DataStore node "sparse": expected Image, got SparseArray"#.to_owned())
+    );
+    assert!(store.get("image").unwrap().as_image().is_ok());
+
+    store
+}
+
+fn test_data_store_len_and_clear() {
+    let mut store = DataStore::new();
+
+    assert!(store.is_empty());
+
+    store.add_i64(1);
+    store.add_named_str("name", "value");
+
+    assert_eq!(store.len(), 2);
+    assert!(!store.is_empty());
+
+    // Clones are deep copies, and are not affected by changes to the original.
+    let clone = store.clone();
+
+    store.clear();
+
+    assert_eq!(store.len(), 0);
+    assert!(store.is_empty());
+    assert!(store.first_node().is_none());
+
+    assert_eq!(clone.len(), 2);
+    assert_eq!(clone.get("name").unwrap().as_str(), Ok("value"));
+
+    // A cleared DataStore can be reused.
+    store.add_bool(true);
+
+    assert_eq!(store.len(), 1);
+}
+
+/// Returns the sum of the "a" and "b" values in `store`.
+fn test_data_store_named_map(store: DataStore) -> i64 {
+    // Unnamed nodes are not allowed.
+    {
+        let mut unnamed = store.clone();
+        unnamed.add_i64(5);
+
+        assert_eq!(
+            unnamed
+                .into_named_map::<HashMap<String, DataStoreValue>>()
+                .map(|_| ()),
+            Err(format!(
+                "DataStore node at position {} has no name",
+                store.len() + 1
+            ))
+        );
+    }
+
+    let map: BTreeMap<String, DataStoreValue> = store.into_named_map().unwrap();
+
+    let int = |name: &str| match map.get(name) {
+        Some(DataStoreValue::Integer(value)) => *value,
+        other => panic!("unexpected value for {:?}: {:?}", name, other),
+    };
+
+    int("a") + int("b")
+}
+
+fn test_event_data_store() -> DataStore {
+    let event = Event::named("progress")
+        .field("done", 10)
+        .field("total", 100)
+        .field("message", "working");
+
+    let field = |name: &str, type_name: &'static str| EventField {
+        name: name.to_owned(),
+        type_name,
+    };
+
+    assert_eq!(event.name(), "progress");
+    assert_eq!(event.shape().fields, vec![
+        field("done", "Integer"),
+        field("total", "Integer"),
+        field("message", "String"),
+    ]);
+
+    event.into_data_store()
 }
@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use wolfram_library_link::{
+    self as wll, dataset,
+    expr::{Expr, Symbol},
+};
+
+wll::export![test_dataset_conversions()];
+
+wll::export_wstp![test_dataset_planets(_)];
+
+#[derive(Serialize)]
+struct Planet {
+    name: &'static str,
+    moons: u32,
+    radius_km: f64,
+    rings: Option<Rings>,
+}
+
+#[derive(Serialize)]
+enum Rings {
+    Faint,
+    Count(u8),
+    Span { inner_km: u32, outer_km: u32 },
+}
+
+#[derive(Serialize)]
+struct Unit;
+
+fn rule(key: Expr, value: Expr) -> Expr {
+    Expr::normal(Symbol::new("System`Rule"), vec![key, value])
+}
+
+fn association(rules: Vec<(&str, Expr)>) -> Expr {
+    let rules = rules
+        .into_iter()
+        .map(|(key, value)| rule(Expr::string(key), value))
+        .collect();
+
+    Expr::normal(Symbol::new("System`Association"), rules)
+}
+
+fn list(elements: Vec<Expr>) -> Expr {
+    Expr::normal(Symbol::new("System`List"), elements)
+}
+
+fn test_dataset_conversions() {
+    //
+    // Scalars
+    //
+
+    assert_eq!(dataset::to_expr(&true), Ok(Expr::from(true)));
+    assert_eq!(dataset::to_expr(&i64::MIN), Ok(Expr::from(i64::MIN)));
+    assert_eq!(dataset::to_expr(&1.5f32), Ok(Expr::real(1.5)));
+    assert_eq!(dataset::to_expr(&'x'), Ok(Expr::string("x")));
+    assert_eq!(
+        dataset::to_expr(&f64::NAN),
+        Ok(Expr::symbol(Symbol::new("System`Indeterminate")))
+    );
+    assert_eq!(
+        dataset::to_expr(&u64::MAX),
+        Err(format!("integer value overflows i64: {}", u64::MAX))
+    );
+
+    //
+    // Unit values, options, sequences and maps
+    //
+
+    let null = Expr::symbol(Symbol::new("System`Null"));
+
+    assert_eq!(dataset::to_expr(&()), Ok(null.clone()));
+    assert_eq!(dataset::to_expr(&Unit), Ok(null));
+    assert_eq!(
+        dataset::to_expr(&None::<i64>),
+        Ok(Expr::normal(Symbol::new("System`Missing"), vec![]))
+    );
+    assert_eq!(dataset::to_expr(&Some(1i64)), Ok(Expr::from(1i64)));
+    assert_eq!(
+        dataset::to_expr(&(1i64, "a")),
+        Ok(list(vec![Expr::from(1i64), Expr::string("a")]))
+    );
+
+    let map: BTreeMap<i64, &str> = [(2, "b"), (1, "a")].into_iter().collect();
+
+    assert_eq!(
+        dataset::to_expr(&map),
+        Ok(Expr::normal(Symbol::new("System`Association"), vec![
+            rule(Expr::from(1i64), Expr::string("a")),
+            rule(Expr::from(2i64), Expr::string("b")),
+        ]))
+    );
+
+    //
+    // Enum variants
+    //
+
+    assert_eq!(dataset::to_expr(&Rings::Faint), Ok(Expr::string("Faint")));
+    assert_eq!(
+        dataset::to_expr(&Rings::Count(7)),
+        Ok(association(vec![("Count", Expr::from(7i64))]))
+    );
+    assert_eq!(
+        dataset::to_expr(&Rings::Span {
+            inner_km: 1,
+            outer_km: 2
+        }),
+        Ok(association(vec![(
+            "Span",
+            association(vec![
+                ("inner_km", Expr::from(1i64)),
+                ("outer_km", Expr::from(2i64)),
+            ])
+        )]))
+    );
+
+    //
+    // Invalid rows
+    //
+
+    let error = dataset::from_iter([1i64, 2]).unwrap_err();
+
+    assert!(error.starts_with("row 1 did not serialize to an Association"));
+
+    let rows = vec![
+        BTreeMap::from([("a", 1i64)]),
+        BTreeMap::from([("a", 1i64), ("b", 2)]),
+    ];
+
+    let error = dataset::from_iter(rows).unwrap_err();
+
+    assert!(error.starts_with("row 2 has keys"), "{}", error);
+
+    // An empty iterator produces an empty Dataset.
+    assert_eq!(
+        dataset::from_iter(Vec::<Planet>::new()),
+        Ok(Expr::normal(Symbol::new("System`Dataset"), vec![list(
+            vec![]
+        )]))
+    );
+}
+
+fn test_dataset_planets(args: Vec<Expr>) -> Expr {
+    assert!(args.is_empty());
+
+    let planets = vec![
+        Planet {
+            name: "Earth",
+            moons: 1,
+            radius_km: 6371.0,
+            rings: None,
+        },
+        Planet {
+            name: "Saturn",
+            moons: 146,
+            radius_km: 58232.0,
+            rings: Some(Rings::Span {
+                inner_km: 66900,
+                outer_km: 140180,
+            }),
+        },
+        Planet {
+            name: "Neptune",
+            moons: 16,
+            radius_km: 24622.0,
+            rings: Some(Rings::Count(5)),
+        },
+        // A hypothetical planet, whose radius is unknown.
+        Planet {
+            name: "Planet Nine",
+            moons: 0,
+            radius_km: f64::NAN,
+            rings: Some(Rings::Faint),
+        },
+    ];
+
+    dataset::from_iter(planets).unwrap()
+}
@@ -0,0 +1,13 @@
+use wolfram_library_link as wll;
+
+wll::export![
+    test_register_native();
+];
+
+fn test_register_native() {
+    wll::register_native("test_dynamic_square", square as fn(i64) -> i64);
+}
+
+fn square(x: i64) -> i64 {
+    x * x
+}
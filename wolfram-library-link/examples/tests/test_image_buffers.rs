@@ -0,0 +1,116 @@
+use std::convert::TryFrom;
+
+use image::{DynamicImage, ImageBuffer, Luma, Rgb, Rgba};
+
+use wolfram_library_link::{
+    self as wll, ColorSpace, Image, ImageType, Pixel, UninitImage,
+};
+
+wll::export![
+    test_image_buffer_conversions();
+    test_dynamic_image_conversions();
+    test_dynamic_image_round_trip(_);
+];
+
+fn test_image_buffer_conversions() {
+    let buffer: ImageBuffer<Rgb<u8>, Vec<u8>> =
+        ImageBuffer::from_raw(2, 1, vec![1, 2, 3, 4, 5, 6]).unwrap();
+
+    let image = Image::from(&buffer);
+
+    assert_eq!(image.rank(), 2);
+    assert_eq!((image.column_count(), image.row_count()), (2, 1));
+    assert_eq!(image.channels(), 3);
+    assert_eq!(image.color_space(), ColorSpace::RGB);
+    assert!(image.is_interleaved());
+    assert_eq!(image.as_slice(), [1, 2, 3, 4, 5, 6]);
+
+    assert_eq!(ImageBuffer::<Rgb<u8>, _>::try_from(&image), Ok(buffer));
+
+    let error = ImageBuffer::<Rgba<u8>, _>::try_from(&image).unwrap_err();
+
+    assert_eq!(
+        error,
+        "image has 3 channels, but RGBA pixels have 4 channels"
+    );
+}
+
+fn test_dynamic_image_conversions() {
+    //
+    // Non-interleaved images are reordered
+    //
+
+    let mut planar = UninitImage::<u8>::new_2d(2, 1, 3, ColorSpace::RGB, false);
+
+    for (index, value) in (1..=6).enumerate() {
+        planar.set(Pixel::D2([1, index / 3 + 1]), index % 3 + 1, value);
+    }
+
+    let planar = unsafe { planar.assume_init() }.into_generic();
+
+    let dynamic = DynamicImage::try_from(&planar).unwrap();
+
+    assert_eq!(dynamic.as_rgb8().unwrap().as_raw(), &[1, 2, 3, 4, 5, 6]);
+
+    //
+    // Bit images are converted to u8
+    //
+
+    let mut bits = UninitImage::<bool>::new_2d(2, 1, 1, ColorSpace::Automatic, true);
+    bits.set(Pixel::D2([1, 1]), 1, true);
+    bits.set(Pixel::D2([1, 2]), 1, false);
+
+    let bits = unsafe { bits.assume_init() }.into_generic();
+
+    let dynamic = DynamicImage::try_from(&bits).unwrap();
+
+    assert_eq!(dynamic.as_luma8().unwrap().as_raw(), &[255, 0]);
+
+    //
+    // Grayscale Real64 images are converted to RGB f32
+    //
+
+    let mut reals = UninitImage::<f64>::new_2d(1, 1, 1, ColorSpace::Gray, true);
+    reals.set(Pixel::D2([1, 1]), 1, 0.25);
+
+    let reals = unsafe { reals.assume_init() }.into_generic();
+
+    let dynamic = DynamicImage::try_from(&reals).unwrap();
+
+    assert_eq!(dynamic.as_rgb32f().unwrap().as_raw(), &[0.25, 0.25, 0.25]);
+
+    //
+    // Bit depth is preserved when converting to Image
+    //
+
+    let gray16 = ImageBuffer::<Luma<u16>, _>::from_raw(1, 1, vec![u16::MAX]).unwrap();
+
+    let image = Image::from(&DynamicImage::ImageLuma16(gray16));
+
+    assert!(matches!(image.data_type(), ImageType::Bit16));
+    assert_eq!(image.color_space(), ColorSpace::Gray);
+
+    //
+    // Unsupported color spaces
+    //
+
+    let mut cmyk = UninitImage::<u8>::new_2d(1, 1, 4, ColorSpace::CMYK, true);
+    cmyk.zero();
+
+    let cmyk = unsafe { cmyk.assume_init() }.into_generic();
+
+    let error = DynamicImage::try_from(&cmyk).unwrap_err();
+
+    assert!(
+        error.starts_with("unsupported image color space"),
+        "{}",
+        error
+    );
+}
+
+fn test_dynamic_image_round_trip(image: &Image) -> Image {
+    let dynamic = DynamicImage::try_from(image)
+        .unwrap_or_else(|err| panic!("could not convert image: {}", err));
+
+    Image::from(&dynamic)
+}
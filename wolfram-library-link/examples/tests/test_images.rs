@@ -8,6 +8,14 @@ wll::export![
     test_create_bitmap_image();
     test_create_color_rgb_u8_image();
     test_create_color_rgb_f32_image();
+    // Test conversions between Image and NumericArray
+    test_image_to_numeric_array(_);
+    test_image_from_numeric_array(_);
+    // Test channel manipulation
+    test_image_channel(_);
+    test_image_swap_channels(_);
+    test_image_remove_alpha(_);
+    test_image_to_gray(_);
 ];
 
 fn test_image_arg(image: &Image<bool>) -> NumericArray<i8> {
@@ -117,3 +125,33 @@ fn test_create_color_rgb_f32_image() -> Image<f32> {
 
     unsafe { image.assume_init() }
 }
+
+fn test_image_to_numeric_array(image: &Image<u8>) -> NumericArray<u8> {
+    image.to_numeric_array()
+}
+
+fn test_image_from_numeric_array(data: &NumericArray<u8>) -> Image<u8> {
+    Image::from_numeric_array(data, ColorSpace::RGB, false).unwrap()
+}
+
+fn test_image_channel(image: &Image<u8>) -> Image<u8> {
+    image.channel(2).unwrap()
+}
+
+fn test_image_swap_channels(image: &Image<u8>) -> Image<u8> {
+    let r = image.channel(1).unwrap();
+    let g = image.channel(2).unwrap();
+    let b = image.channel(3).unwrap();
+
+    Image::from_channels(&[&b, &g, &r], ColorSpace::RGB, false).unwrap()
+}
+
+fn test_image_remove_alpha(image: &Image<u8>) -> Image<u8> {
+    assert!(image.has_alpha_channel());
+
+    image.remove_alpha_channel().unwrap()
+}
+
+fn test_image_to_gray(image: &Image<f64>) -> Image<f64> {
+    image.convert_color_space(ColorSpace::Gray).unwrap()
+}
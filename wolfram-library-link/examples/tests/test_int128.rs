@@ -0,0 +1,88 @@
+use wolfram_library_link::{
+    self as wll,
+    convert::{self, FromExpr},
+    expr::Expr,
+    wstp::Link,
+};
+
+wll::export![
+    test_int128_conversions();
+    test_i128_negate(_);
+    test_u128_double(_);
+];
+
+wll::export_wstp![test_i128_link_negate(&mut Link); test_u128_link_double(&mut Link)];
+
+fn test_int128_conversions() {
+    //
+    // i128
+    //
+
+    assert_eq!(
+        i128::from_expr(&Expr::from(i64::MIN)),
+        Ok(i128::from(i64::MIN))
+    );
+    assert_eq!(
+        i128::from_expr(&Expr::string(i128::MAX.to_string())),
+        Ok(i128::MAX)
+    );
+    assert_eq!(
+        i128::from_expr(&Expr::string(i128::MIN.to_string())),
+        Ok(i128::MIN)
+    );
+
+    // One more than i128::MAX.
+    let error = i128::from_expr(&Expr::string("170141183460469231731687303715884105728"))
+        .unwrap_err();
+
+    assert!(error.starts_with("invalid i128 integer"), "{}", error);
+
+    assert!(i128::from_expr(&Expr::string("12a")).is_err());
+    assert!(i128::from_expr(&Expr::string("")).is_err());
+    assert!(i128::from_expr(&Expr::real(1.0)).is_err());
+
+    //
+    // u128
+    //
+
+    assert_eq!(u128::from_expr(&Expr::from(i64::MAX)), Ok(i64::MAX as u128));
+    assert_eq!(
+        u128::from_expr(&Expr::string(u128::MAX.to_string())),
+        Ok(u128::MAX)
+    );
+
+    // Negative integers are never wrapped around.
+    assert!(u128::from_expr(&Expr::from(-1i64)).is_err());
+    assert!(u128::from_expr(&Expr::string("-1")).is_err());
+
+    // One more than u128::MAX.
+    let error = u128::from_expr(&Expr::string("340282366920938463463374607431768211456"))
+        .unwrap_err();
+
+    assert!(error.starts_with("invalid u128 integer"), "{}", error);
+}
+
+fn test_i128_negate(value: i128) -> i128 {
+    -value
+}
+
+fn test_u128_double(value: u128) -> u128 {
+    value * 2
+}
+
+fn test_i128_link_negate(link: &mut Link) {
+    assert_eq!(link.test_head("System`List").unwrap(), 1);
+
+    let value = convert::get_i128(link).unwrap();
+
+    convert::put_i128(link, -value).unwrap();
+}
+
+fn test_u128_link_double(link: &mut Link) {
+    assert_eq!(link.test_head("System`List").unwrap(), 1);
+
+    match convert::get_u128(link) {
+        Ok(value) => convert::put_u128(link, value * 2).unwrap(),
+        Err(message) => link.put_str(&message).unwrap(),
+    }
+}
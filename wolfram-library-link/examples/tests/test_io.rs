@@ -0,0 +1,61 @@
+use std::io::Write;
+
+use wolfram_library_link::{self as wll, io};
+
+wll::export![
+    test_io_redirect();
+    test_io_flush();
+    test_io_restore();
+    test_io_print_writer();
+];
+
+fn test_io_redirect() {
+    // Flushing before the streams are redirected does nothing.
+    io::flush_std_streams().unwrap();
+
+    io::redirect_std_streams().unwrap();
+    // Redirecting again has no effect.
+    io::redirect_std_streams().unwrap();
+
+    assert!(io::is_redirected());
+
+    // Forwarded to the Kernel when this function returns.
+    println!("to stdout");
+    eprintln!("to stderr");
+}
+
+fn test_io_flush() {
+    println!("first");
+
+    io::flush_std_streams().unwrap();
+
+    println!("second");
+}
+
+fn test_io_restore() {
+    // Written to the original stdout when the streams are restored.
+    println!("before restore");
+
+    io::restore_std_streams().unwrap();
+
+    assert!(!io::is_redirected());
+
+    // Restoring again does nothing.
+    io::restore_std_streams().unwrap();
+
+    println!("after restore");
+}
+
+fn test_io_print_writer() {
+    let mut writer = io::print_writer();
+
+    write!(writer, "one, ").unwrap();
+    writeln!(writer, "two").unwrap();
+    writer.flush().unwrap();
+
+    // Flushing an empty writer does not print anything.
+    writer.flush().unwrap();
+
+    // Printed when the writer is dropped.
+    write!(writer, "three").unwrap();
+}
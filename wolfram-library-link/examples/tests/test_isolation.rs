@@ -0,0 +1,162 @@
+use std::panic;
+
+use wolfram_library_link::{
+    self as wll,
+    expr::{Expr, Symbol},
+    isolation::{self, IsolationError},
+    wstp::Link,
+    LinkArgs,
+};
+
+wll::export![test_isolation_run_isolated(); test_isolation_errors()];
+
+wll::export_wstp![
+    test_isolation_put_expr(&mut Link);
+    test_isolation_put_crash(&mut Link);
+    test_isolation_infinite_loop(&mut Link);
+];
+
+// Note: The isolated functions below panic using `resume_unwind()`, which does not
+//       run the panic hook. The default hook locks `stderr()`, which is not safe to do
+//       in the helper process.
+
+fn test_isolation_run_isolated() {
+    let data: Vec<u8> = (0..=255).collect();
+
+    // Large results are read from the pipe in several chunks.
+    let large: Vec<u8> = data.iter().copied().cycle().take(100_000).collect();
+
+    unsafe {
+        assert_eq!(isolation::run_isolated(|| data.clone()), Ok(data.clone()));
+        assert_eq!(isolation::run_isolated(Vec::new), Ok(Vec::new()));
+        assert_eq!(isolation::run_isolated(|| large.clone()), Ok(large.clone()));
+    }
+
+    //
+    // Changes made by the isolated function are not visible to the parent process
+    //
+
+    let mut counter = 0;
+
+    unsafe {
+        isolation::run_isolated(|| {
+            counter += 1;
+            vec![counter]
+        })
+        .unwrap();
+    }
+
+    assert_eq!(counter, 0);
+}
+
+fn test_isolation_errors() {
+    unsafe {
+        assert_eq!(
+            isolation::run_isolated(|| panic::resume_unwind(Box::new("boom"))),
+            Err(IsolationError::Panicked("boom".to_owned()))
+        );
+        assert_eq!(
+            isolation::run_isolated(|| panic::resume_unwind(Box::new(format!("{}", 1)))),
+            Err(IsolationError::Panicked("1".to_owned()))
+        );
+        assert_eq!(
+            isolation::run_isolated(|| panic::resume_unwind(Box::new(0))),
+            Err(IsolationError::Panicked(
+                "Rust panic (no message)".to_owned()
+            ))
+        );
+
+        assert_eq!(
+            isolation::run_isolated(|| std::process::exit(3)),
+            Err(IsolationError::Exited { status: 3 })
+        );
+
+        // SIGKILL is used because, unlike SIGSEGV or SIGABRT, it cannot be handled by
+        // signal handlers installed by the Kernel or the Rust runtime.
+        assert_eq!(
+            isolation::run_isolated(|| {
+                libc::kill(libc::getpid(), libc::SIGKILL);
+                Vec::new()
+            }),
+            Err(IsolationError::Crashed {
+                signal: libc::SIGKILL
+            })
+        );
+    }
+
+    //
+    // Error messages
+    //
+
+    assert_eq!(
+        IsolationError::Exited { status: 3 }.to_string(),
+        "helper process exited with status 3 before sending a result"
+    );
+    assert_eq!(
+        IsolationError::Panicked("boom".to_owned()).to_string(),
+        "isolated function panicked: boom"
+    );
+    assert_eq!(
+        IsolationError::Aborted.to_string(),
+        "helper process was aborted"
+    );
+
+    let failure = IsolationError::Crashed { signal: 11 }
+        .to_failure()
+        .to_string();
+
+    assert!(
+        failure.contains("\"RustIsolatedProcessFailed\""),
+        "{}",
+        failure
+    );
+    assert!(
+        failure.contains("\"helper process was terminated by signal 11\""),
+        "{}",
+        failure
+    );
+}
+
+/// Returns `{x[1], ..., x[n]}`, computed in a helper process.
+fn test_isolation_put_expr(link: &mut Link) {
+    let n: i64 = {
+        let mut args = LinkArgs::new(link).unwrap();
+        args.expect_count(1).unwrap();
+        args.next().unwrap()
+    };
+
+    unsafe {
+        isolation::put_isolated(link, || {
+            let elements = (1..=n)
+                .map(|i| Expr::normal(Symbol::new("Global`x"), vec![Expr::from(i)]))
+                .collect();
+
+            Expr::normal(Symbol::new("System`List"), elements)
+        })
+        .unwrap();
+    }
+}
+
+fn test_isolation_put_crash(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    unsafe {
+        isolation::put_isolated(link, || {
+            libc::kill(libc::getpid(), libc::SIGKILL);
+            Expr::from(0i64)
+        })
+        .unwrap();
+    }
+}
+
+/// Never returns unless the evaluation is aborted.
+fn test_isolation_infinite_loop(link: &mut Link) {
+    LinkArgs::new(link).unwrap().expect_count(0).unwrap();
+
+    unsafe {
+        isolation::put_isolated(link, || loop {
+            std::hint::spin_loop();
+        })
+        .unwrap();
+    }
+}
@@ -0,0 +1,79 @@
+use serde_json::{json, Value};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+wll::export![test_json_conversions()];
+
+wll::export_wstp![test_json_parse(_); test_json_round_trip(_)];
+
+fn test_json_conversions() {
+    let value = json!({
+        "array": [1, -2.5, "three", null, true, false],
+        "nested": { "empty": {}, "list": [] },
+        "string": "text",
+    });
+
+    assert_eq!(Value::from_expr(&value.to_expr()), Ok(value));
+
+    //
+    // Numbers
+    //
+
+    assert_eq!(json!(i64::MIN).to_expr(), Expr::from(i64::MIN));
+    // Integers larger than i64::MAX are converted to Real.
+    assert_eq!(json!(u64::MAX).to_expr(), Expr::real(u64::MAX as f64));
+    assert_eq!(json!(0.5).to_expr(), Expr::real(0.5));
+
+    assert!(Value::from_expr(&Expr::real(f64::INFINITY)).is_err());
+
+    //
+    // Invalid expressions
+    //
+
+    let rule = |key: Expr| {
+        Expr::normal(Symbol::new("System`Association"), vec![Expr::normal(
+            Symbol::new("System`Rule"),
+            vec![key, Expr::from(1i64)],
+        )])
+    };
+
+    assert_eq!(
+        Value::from_expr(&rule(Expr::string("key"))),
+        Ok(json!({ "key": 1 }))
+    );
+    assert!(Value::from_expr(&rule(Expr::from(1i64))).is_err());
+    assert!(Value::from_expr(&Expr::symbol(Symbol::new("Global`x"))).is_err());
+    assert!(Value::from_expr(&Expr::normal(Symbol::new("Global`f"), vec![])).is_err());
+
+    // Errors in nested values include the key of the value.
+    let error = Value::from_expr(&Expr::normal(Symbol::new("System`Association"), vec![
+        Expr::normal(Symbol::new("System`Rule"), vec![
+            Expr::string("key"),
+            Expr::real(f64::NEG_INFINITY),
+        ]),
+    ]))
+    .unwrap_err();
+
+    assert!(error.starts_with("at key \"key\""), "{}", error);
+}
+
+/// Parse a JSON string, returning the same expression as `ImportString[json, "RawJSON"]`.
+fn test_json_parse(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    String::from_expr(&args[0])
+        .and_then(|json| {
+            serde_json::from_str::<Value>(&json).map_err(|err| err.to_string())
+        })
+        .to_expr()
+}
+
+fn test_json_round_trip(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    Value::from_expr(&args[0]).to_expr()
+}
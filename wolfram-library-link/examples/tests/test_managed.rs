@@ -0,0 +1,23 @@
+use wolfram_library_link::{
+    self as wll,
+    managed::{Handle, ManagedExpression},
+};
+
+wll::export![
+    test_managed_accumulate(_, _);
+    test_managed_total(_);
+];
+
+#[derive(Default, ManagedExpression)]
+#[managed_expression(name = "TestAccumulator")]
+struct Accumulator {
+    total: i64,
+}
+
+fn test_managed_accumulate(acc: Handle<Accumulator>, value: i64) {
+    acc.lock().total += value;
+}
+
+fn test_managed_total(acc: Handle<Accumulator>) -> i64 {
+    acc.lock().total
+}
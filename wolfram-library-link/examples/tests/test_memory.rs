@@ -0,0 +1,24 @@
+use wolfram_library_link::{self as wll, DataStore, NumericArray};
+
+wll::export![
+    test_memory_usage();
+];
+
+fn test_memory_usage() -> bool {
+    wll::start_memory_tracking();
+
+    let before = wll::memory_usage();
+
+    let array = NumericArray::<f64>::from_slice(&[1.0, 2.0, 3.0]);
+    let store = DataStore::new();
+
+    let during = wll::memory_usage();
+
+    drop(array);
+    drop(store);
+
+    during.numeric_arrays == before.numeric_arrays + 1
+        && during.numeric_array_bytes == before.numeric_array_bytes + 24
+        && during.data_stores == before.data_stores + 1
+        && wll::memory_usage() == before
+}
@@ -1,9 +1,12 @@
-use std::ffi::CString;
+use std::{
+    ffi::{CStr, CString},
+    path::PathBuf,
+};
 
 use wolfram_library_link::{
     self as wll,
     sys::{mint, mreal},
-    NumericArray, UninitNumericArray,
+    ErrorCode, KernelString, NumericArray, RawArgs, RawReturn, UninitNumericArray,
 };
 
 //======================================
@@ -11,19 +14,26 @@ use wolfram_library_link::{
 //======================================
 
 wll::export![
-    test_no_args();
+    test_no_args;
     test_ret_void();
     test_mint(_);
     test_raw_mint(_, _);
-    test_mint_mint(_, _);
+    test_raw_args(_, _);
+    test_mint_mint;
     test_mreal(_);
     test_i64(_);
-    test_i64_i64(_, _);
+    test_i64_i64;
+    #[timing] test_timed_i64_i64(_, _);
     test_f64(_);
-    // test_str(_);
+    test_u8(_);
+    test_str;
     test_string(_);
     test_c_string(_);
+    test_c_str;
+    test_kernel_string(_);
+    test_path_buf(_);
     test_panic();
+    test_set_function_error(_);
 ];
 
 fn test_no_args() -> i64 {
@@ -55,6 +65,19 @@ fn test_raw_mint(args: &[wll::sys::MArgument], ret: wll::sys::MArgument) {
     }
 }
 
+fn test_raw_args(args: RawArgs, ret: RawReturn) {
+    // Safety: the argument and return types match the LibraryFunctionLoad signature
+    //         used in NativeArgs.wlt.
+    unsafe {
+        let string: &str = args.utf8_str(0).unwrap();
+        let count: mint = args.integer(1).unwrap();
+
+        assert!(args.integer(2).is_err());
+
+        ret.set(string.repeat(count as usize));
+    }
+}
+
 fn test_mint_mint(x: mint, y: mint) -> mint {
     x + y
 }
@@ -75,17 +98,31 @@ fn test_i64_i64(x: i64, y: i64) -> i64 {
     x + y
 }
 
+fn test_timed_i64_i64(x: i64, y: i64) -> i64 {
+    x + y
+}
+
 fn test_f64(x: f64) -> f64 {
     x * x
 }
 
+//----------------------------
+// Checked integer conversions
+//----------------------------
+
+fn test_u8(x: u8) -> u16 {
+    u16::from(x) * 2
+}
+
 //--------
 // Strings
 //--------
 
-// fn test_str(string: &str) -> String {
-//     string.chars().rev().collect()
-// }
+fn test_str(string: KernelString) -> String {
+    let string: &str = string.to_str().unwrap();
+
+    string.chars().rev().collect()
+}
 
 fn test_string(string: String) -> String {
     string.chars().rev().collect()
@@ -95,6 +132,20 @@ fn test_c_string(string: CString) -> i64 {
     i64::try_from(string.as_bytes().len()).expect("string len usize overflows i64")
 }
 
+fn test_c_str(string: KernelString) -> i64 {
+    let string: &CStr = string.as_c_str();
+
+    i64::try_from(string.to_bytes().len()).expect("string len usize overflows i64")
+}
+
+fn test_kernel_string(string: KernelString) -> String {
+    string.to_str().unwrap().to_uppercase()
+}
+
+fn test_path_buf(path: PathBuf) -> String {
+    path.to_str().unwrap().to_owned()
+}
+
 //-------
 // Panics
 //-------
@@ -103,6 +154,23 @@ fn test_panic() {
     panic!("this function panicked");
 }
 
+fn test_set_function_error(x: i64) -> i64 {
+    if x < 0 {
+        wll::set_function_error(ErrorCode::User(7));
+    }
+
+    x
+}
+
+//----------
+// Constants
+//----------
+
+wll::export_constant![
+    test_constant_str: &str = "constant value";
+    test_constant_i64: i64 = 42;
+];
+
 //======================================
 // NumericArray's
 //======================================
@@ -110,6 +178,9 @@ fn test_panic() {
 wll::export![
     total_i64(_);
     positive_i64(_);
+    test_raw_packed_array(_, _);
+    test_raw_packed_array_type(_, _);
+    test_raw_packed_array_unsupported(_, _);
 ];
 
 fn total_i64(list: &NumericArray<i64>) -> i64 {
@@ -131,3 +202,40 @@ fn positive_i64(list: &NumericArray<i64>) -> NumericArray<u8> {
 
     unsafe { bools.assume_init() }
 }
+
+/// Returns its packed array argument, after a round trip through a `NumericArray`.
+fn test_raw_packed_array(args: RawArgs, ret: RawReturn) {
+    // Safety: the function takes one argument, and the argument and return types are
+    //         packed arrays of the same type in the LibraryFunctionLoad signatures used
+    //         in NativeArgs.wlt.
+    unsafe {
+        let array = args.packed_array(0).unwrap();
+
+        assert!(args.packed_array(1).is_err());
+
+        ret.set_packed_array(&array).unwrap();
+    }
+}
+
+/// Returns the type of the `NumericArray` that its packed array argument is converted
+/// to.
+fn test_raw_packed_array_type(args: RawArgs, ret: RawReturn) {
+    // Safety: the argument type is a packed array, and the return type is String.
+    unsafe {
+        let array = args.packed_array(0).unwrap();
+
+        ret.set(array.data_type().name().to_owned());
+    }
+}
+
+fn test_raw_packed_array_unsupported(_: RawArgs, ret: RawReturn) {
+    let array = NumericArray::from_slice(&[1u8, 2, 3]).into_generic();
+
+    // Safety: the return type is "Void", and nothing is written to `ret` on error.
+    let error = unsafe { ret.set_packed_array(&array) }.unwrap_err();
+
+    assert_eq!(
+        error,
+        "NumericArray of type UnsignedInteger8 cannot be converted to a tensor"
+    );
+}
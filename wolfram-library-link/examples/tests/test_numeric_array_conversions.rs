@@ -1,5 +1,5 @@
 use wolfram_library_link::{
-    self as wll, NumericArray, NumericArrayConvertMethod as Method,
+    self as wll, NumericArray, NumericArrayConvertMethod as Method, NumericArrayDataType,
 };
 
 fn from_slice<T: wll::NumericArrayType>(slice: &[T]) -> NumericArray<T> {
@@ -8,6 +8,12 @@ fn from_slice<T: wll::NumericArrayType>(slice: &[T]) -> NumericArray<T> {
 
 wll::export![
     test_na_conversions();
+    test_na_dynamic_conversions();
+    test_na_formatting();
+    test_na_equality();
+    test_na_reshape();
+    test_na_views();
+    test_na_empty_and_scalar();
 ];
 
 fn test_na_conversions() {
@@ -39,3 +45,152 @@ fn test_na_conversions() {
         [i8::MAX]
     );
 }
+
+fn test_na_dynamic_conversions() {
+    let array: NumericArray = from_slice(&[1i64, 2, 3])
+        .convert_to_type(NumericArrayDataType::Real64, Method::Check, 0.0)
+        .unwrap();
+
+    assert_eq!(array.data_type(), NumericArrayDataType::Real64);
+    assert_eq!(array.dimensions(), [3]);
+
+    let clone = array.try_clone().unwrap();
+
+    assert!(!clone.ptr_eq(&array));
+    assert_eq!(clone.data_type(), NumericArrayDataType::Real64);
+}
+
+fn test_na_formatting() {
+    let array = NumericArray::from_array(&[2, 3], &[1i64, 2, 3, 4, 5, 6]);
+
+    assert_eq!(
+        format!("{:?}", array),
+        "NumericArray { data_type: Bit64, dimensions: [2, 3], elements: [1, 2, 3, 4, 5, 6] }"
+    );
+    assert_eq!(
+        array.summary().to_string(),
+        "NumericArray[Type: Integer64, Dimensions: {2, 3}]"
+    );
+
+    let array: NumericArray<u8> = from_slice(&[0; 12]);
+
+    assert_eq!(
+        format!("{:?}", array),
+        "NumericArray { data_type: UBit8, dimensions: [12], \
+         elements: [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, ... 2 more] }"
+    );
+}
+
+fn test_na_equality() {
+    let array = NumericArray::from_array(&[2, 2], &[1i64, 2, 3, 4]);
+
+    assert_eq!(array, array.clone());
+    assert_eq!(array, array.clone().into_generic());
+    assert_ne!(array, NumericArray::from_slice(&[1i64, 2, 3, 4]));
+    assert_ne!(array, NumericArray::from_array(&[2, 2], &[1i32, 2, 3, 4]));
+
+    let a = NumericArray::from_slice(&[0.1f64 + 0.2, 1.0]);
+    let b = NumericArray::from_slice(&[0.3f64, 1.0]);
+
+    assert_ne!(a, b);
+    assert!(a.approx_eq(&b, 1e-12));
+    assert!(!a.approx_eq(&NumericArray::from_slice(&[0.3, 1.1]), 1e-12));
+}
+
+fn test_na_reshape() {
+    let array = from_slice(&[1i64, 2, 3, 4, 5, 6]);
+
+    // Reshaping to the current dimensions does not create a new array.
+    let same = array.clone().reshape(&[6]).unwrap();
+    assert_eq!(same, array);
+
+    let matrix = array.reshape(&[2, 3]).unwrap();
+    assert_eq!(matrix.dimensions(), &[2, 3]);
+    assert_eq!(matrix.as_slice(), &[1, 2, 3, 4, 5, 6]);
+
+    assert!(matrix.clone().reshape(&[4, 2]).is_err());
+    assert!(matrix.clone().reshape(&[]).is_err());
+
+    let column = matrix.clone().insert_axis(2);
+    assert_eq!(column.dimensions(), &[2, 3, 1]);
+    assert_eq!(column.rank(), 3);
+
+    let row = matrix.clone().insert_axis(0);
+    assert_eq!(row.dimensions(), &[1, 2, 3]);
+
+    let flat = row.flatten();
+    assert_eq!(flat.dimensions(), &[6]);
+    assert_eq!(flat.as_slice(), matrix.as_slice());
+}
+
+fn test_na_views() {
+    // {{1, 2, 3, 4}, {5, 6, 7, 8}, {9, 10, 11, 12}}
+    let data: Vec<i64> = (1..=12).collect();
+    let matrix = NumericArray::from_array(&[3, 4], &data);
+
+    let view = matrix.as_view();
+    assert_eq!(view.dimensions(), &[3, 4]);
+    assert_eq!(view.strides(), &[4, 1]);
+    assert_eq!(view.as_slice(), Some(matrix.as_slice()));
+
+    // Rows are stored contiguously.
+    let rows = matrix.slice(0, 1..3);
+    assert_eq!(rows.dimensions(), &[2, 4]);
+    assert_eq!(rows.as_slice(), Some(&data[4..12]));
+
+    // Columns are not.
+    let columns = matrix.slice(1, 1..3);
+    assert_eq!(columns.dimensions(), &[3, 2]);
+    assert_eq!(columns.as_slice(), None);
+    assert_eq!(columns.to_vec(), vec![2, 3, 6, 7, 10, 11]);
+    assert_eq!(columns.get(&[2, 1]), Some(&11));
+    assert_eq!(columns.get(&[3, 0]), None);
+
+    let block = matrix.view(&[1..3, 2..4]);
+    assert_eq!(block.to_vec(), vec![7, 8, 11, 12]);
+    assert_eq!(block.view(&[1..2]).to_vec(), vec![11, 12]);
+    assert_eq!(
+        block.to_numeric_array(),
+        NumericArray::from_array(&[2, 2], &[7i64, 8, 11, 12])
+    );
+
+    let empty = matrix.slice(0, 2..2);
+    assert!(empty.is_empty());
+    assert_eq!(empty.iter().count(), 0);
+
+    let empty = empty.to_numeric_array();
+    assert!(empty.is_empty());
+    assert_eq!(empty.dimensions(), &[0, 4]);
+
+    // The offset of this view is past the end of the array.
+    let empty = matrix.view(&[3..3, 1..3]);
+    assert_eq!(empty.dimensions(), &[0, 2]);
+    assert_eq!(empty.as_slice(), Some(&[][..]));
+    assert_eq!(empty.to_vec(), Vec::<i64>::new());
+    assert_eq!(empty.to_numeric_array().dimensions(), &[0, 2]);
+}
+
+fn test_na_empty_and_scalar() {
+    let empty = NumericArray::<f64>::empty();
+    assert!(empty.is_empty());
+    assert_eq!(empty.dimensions(), &[0]);
+    assert_eq!(empty.as_slice(), &[] as &[f64]);
+    assert_eq!(empty.as_scalar(), None);
+
+    // Arrays with a dimension of length 0 contain no elements.
+    let no_rows = NumericArray::<i64>::from_array(&[0, 3], &[]);
+    assert!(no_rows.is_empty());
+    assert_eq!(no_rows.dimensions(), &[0, 3]);
+    assert_eq!(no_rows.clone().flatten().dimensions(), &[0]);
+
+    // LibraryLink cannot construct rank-0 arrays.
+    assert_eq!(
+        NumericArray::<i64>::try_from_array(&[], &[5]).unwrap_err(),
+        wll::sys::LIBRARY_RANK_ERROR as wll::sys::errcode_t
+    );
+
+    let single = NumericArray::from_array(&[1, 1], &[5i64]);
+    assert!(!single.is_empty());
+    assert_eq!(single.as_scalar(), Some(&5));
+    assert_eq!(from_slice(&[1i64, 2]).as_scalar(), None);
+}
@@ -0,0 +1,98 @@
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use wolfram_library_link::{self as wll, parallel};
+
+wll::export![
+    test_parallel_chunks();
+    test_parallel_panic();
+    test_parallel_zero_chunk_size();
+    test_parallel_abort();
+    test_parallel_abort_processed();
+];
+
+/// The number of chunks processed by the last call to `test_parallel_abort()`.
+static PROCESSED: AtomicUsize = AtomicUsize::new(0);
+
+const ABORT_CHUNK_COUNT: usize = 10_000;
+
+fn test_parallel_chunks() {
+    let data: Vec<i64> = (0..10).collect();
+
+    //
+    // par_chunks_abortable()
+    //
+
+    // Results are returned in chunk order, and the last chunk may be shorter.
+    assert_eq!(
+        parallel::par_chunks_abortable(&data, 3, |chunk| chunk.iter().sum::<i64>()),
+        Some(vec![3, 12, 21, 9])
+    );
+    assert_eq!(
+        parallel::par_chunks_abortable(&data, 100, |chunk| chunk.len()),
+        Some(vec![10])
+    );
+    assert_eq!(
+        parallel::par_chunks_abortable(&data, 1, |chunk| chunk[0]),
+        Some(data.clone())
+    );
+    assert_eq!(
+        parallel::par_chunks_abortable(&[] as &[i64], 4, |chunk| chunk.len()),
+        Some(vec![])
+    );
+
+    //
+    // par_chunks_mut_abortable()
+    //
+
+    let mut data = data;
+
+    let lengths = parallel::par_chunks_mut_abortable(&mut data, 4, |chunk| {
+        for elem in chunk.iter_mut() {
+            *elem *= 2;
+        }
+
+        chunk.len()
+    });
+
+    assert_eq!(lengths, Some(vec![4, 4, 2]));
+    assert_eq!(data, [0, 2, 4, 6, 8, 10, 12, 14, 16, 18]);
+}
+
+/// Panics in the processing function are propagated to the caller.
+fn test_parallel_panic() {
+    let data = [1, 2, 3, 4];
+
+    let _ = parallel::par_chunks_abortable(&data, 1, |chunk| {
+        if chunk[0] == 3 {
+            panic!("chunk processing failed");
+        }
+    });
+}
+
+fn test_parallel_zero_chunk_size() {
+    let _ = parallel::par_chunks_abortable(&[1, 2, 3], 0, |chunk| chunk.len());
+}
+
+/// Process chunks slowly, so that an abort is requested before all of them have been
+/// processed.
+fn test_parallel_abort() -> bool {
+    PROCESSED.store(0, Ordering::SeqCst);
+
+    let data = vec![0u8; ABORT_CHUNK_COUNT];
+
+    let result = parallel::par_chunks_abortable(&data, 1, |_| {
+        thread::sleep(Duration::from_millis(10));
+
+        PROCESSED.fetch_add(1, Ordering::SeqCst);
+    });
+
+    result.is_none()
+}
+
+fn test_parallel_abort_processed() -> i64 {
+    PROCESSED.load(Ordering::SeqCst) as i64
+}
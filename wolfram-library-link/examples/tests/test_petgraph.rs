@@ -0,0 +1,126 @@
+use petgraph::graph::{DiGraph, UnGraph};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{graph_to_expr, weighted_graph_to_expr},
+    expr::{Expr, Symbol},
+};
+
+wll::export![test_petgraph_conversions()];
+
+wll::export_wstp![test_petgraph_dependencies(_); test_petgraph_distances(_)];
+
+fn edge(head: &str, from: Expr, to: Expr) -> Expr {
+    Expr::normal(Symbol::new(head), vec![from, to])
+}
+
+fn list(elements: Vec<Expr>) -> Expr {
+    Expr::normal(Symbol::new("System`List"), elements)
+}
+
+fn test_petgraph_conversions() {
+    //
+    // Directed graphs
+    //
+
+    let mut graph = DiGraph::<&str, ()>::new();
+
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    graph.add_node("c");
+
+    // Self loops and parallel edges are preserved.
+    graph.extend_with_edges(&[(a, b), (b, a), (a, b), (a, a)]);
+
+    let directed = |from: &str, to: &str| {
+        edge("System`DirectedEdge", Expr::string(from), Expr::string(to))
+    };
+
+    assert_eq!(
+        graph_to_expr(&graph),
+        Expr::normal(Symbol::new("System`Graph"), vec![
+            list(vec![
+                Expr::string("a"),
+                Expr::string("b"),
+                Expr::string("c")
+            ]),
+            list(vec![
+                directed("a", "b"),
+                directed("b", "a"),
+                directed("a", "b"),
+                directed("a", "a"),
+            ]),
+        ])
+    );
+
+    //
+    // Undirected weighted graphs
+    //
+
+    let mut graph = UnGraph::<i64, f64>::new_undirected();
+
+    let one = graph.add_node(1);
+    let two = graph.add_node(2);
+
+    graph.add_edge(one, two, 0.5);
+    graph.add_edge(two, one, f64::NAN);
+
+    let undirected = edge("System`UndirectedEdge", Expr::from(1i64), Expr::from(2i64));
+
+    let reversed = edge("System`UndirectedEdge", Expr::from(2i64), Expr::from(1i64));
+
+    assert_eq!(
+        weighted_graph_to_expr(&graph),
+        Expr::normal(Symbol::new("System`Graph"), vec![
+            list(vec![Expr::from(1i64), Expr::from(2i64)]),
+            list(vec![undirected, reversed]),
+            Expr::normal(Symbol::new("System`Rule"), vec![
+                Expr::symbol(Symbol::new("System`EdgeWeight")),
+                // NaN weights are converted to Indeterminate.
+                list(vec![
+                    Expr::real(0.5),
+                    Expr::symbol(Symbol::new("System`Indeterminate")),
+                ]),
+            ]),
+        ])
+    );
+
+    //
+    // Empty graphs
+    //
+
+    assert_eq!(
+        graph_to_expr(&DiGraph::<i64, ()>::new()),
+        Expr::normal(Symbol::new("System`Graph"), vec![
+            list(vec![]),
+            list(vec![])
+        ])
+    );
+}
+
+fn test_petgraph_dependencies(args: Vec<Expr>) -> Expr {
+    assert!(args.is_empty());
+
+    let mut graph = DiGraph::<String, ()>::new();
+
+    let app = graph.add_node("app".to_owned());
+    let core = graph.add_node("core".to_owned());
+    let sys = graph.add_node("sys".to_owned());
+
+    graph.extend_with_edges(&[(app, core), (core, sys), (app, sys)]);
+
+    graph_to_expr(&graph)
+}
+
+fn test_petgraph_distances(args: Vec<Expr>) -> Expr {
+    assert!(args.is_empty());
+
+    let mut graph = UnGraph::<i64, f64>::new_undirected();
+
+    let nodes: Vec<_> = (1..=3).map(|node| graph.add_node(node)).collect();
+
+    graph.add_edge(nodes[0], nodes[1], 1.5);
+    graph.add_edge(nodes[1], nodes[2], 2.0);
+
+    weighted_graph_to_expr(&graph)
+}
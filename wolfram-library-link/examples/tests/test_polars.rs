@@ -0,0 +1,119 @@
+use std::convert::TryFrom;
+
+use polars_core::{df, frame::DataFrame, prelude::NamedFrom, series::Series};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::ToExpr,
+    expr::{Expr, Symbol},
+    DataStore,
+};
+
+wll::export![
+    test_polars_conversions();
+    test_polars_data_store();
+    test_polars_data_frame();
+];
+
+wll::export_wstp![test_polars_rows(_)];
+
+fn data_frame() -> DataFrame {
+    df! {
+        "id" => &[1i64, 2],
+        "score" => &[0.5, f64::NAN],
+        "name" => &["a", "b"],
+        "flag" => &[true, false],
+    }
+    .unwrap()
+}
+
+fn rule(key: &str, value: Expr) -> Expr {
+    Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(key), value])
+}
+
+fn test_polars_conversions() {
+    let row = |id: i64, score: Expr, name: &str, flag: bool| {
+        Expr::normal(Symbol::new("System`Association"), vec![
+            rule("id", Expr::from(id)),
+            rule("score", score),
+            rule("name", Expr::string(name)),
+            rule("flag", Expr::from(flag)),
+        ])
+    };
+
+    assert_eq!(
+        data_frame().to_expr(),
+        Expr::normal(Symbol::new("System`List"), vec![
+            row(1, Expr::real(0.5), "a", true),
+            // NaN values are converted to Indeterminate.
+            row(
+                2,
+                Expr::symbol(Symbol::new("System`Indeterminate")),
+                "b",
+                false
+            ),
+        ])
+    );
+
+    // Null values are converted to Missing[].
+    let nulls = df! {
+        "x" => &[Some(1i64), None],
+        "y" => &[u64::MAX, 0],
+    }
+    .unwrap();
+
+    assert_eq!(
+        nulls.to_expr(),
+        Expr::normal(Symbol::new("System`List"), vec![
+            Expr::normal(Symbol::new("System`Association"), vec![
+                rule("x", Expr::from(1i64)),
+                // Integers larger than i64::MAX are converted to Real.
+                rule("y", Expr::real(u64::MAX as f64)),
+            ]),
+            Expr::normal(Symbol::new("System`Association"), vec![
+                rule("x", Expr::normal(Symbol::new("System`Missing"), vec![])),
+                rule("y", Expr::from(0i64)),
+            ]),
+        ])
+    );
+
+    assert_eq!(
+        DataFrame::empty().to_expr(),
+        Expr::normal(Symbol::new("System`List"), vec![])
+    );
+}
+
+fn test_polars_data_store() {
+    let store = DataStore::try_from(&data_frame()).unwrap();
+
+    assert_eq!(store.len(), 4);
+
+    let nulls = df! { "x" => &[Some(1i64), None] }.unwrap();
+
+    assert_eq!(
+        DataStore::try_from(&nulls).unwrap_err(),
+        "column \"x\" contains null values"
+    );
+
+    let list = Series::new("".into(), &[1i64]);
+    let lists = DataFrame::new(vec![Series::new("l".into(), &[list]).into()]).unwrap();
+
+    assert!(DataStore::try_from(&lists)
+        .unwrap_err()
+        .starts_with("column \"l\" has unsupported data type"));
+}
+
+fn test_polars_data_frame() -> DataFrame {
+    df! {
+        "id" => &[1i64, 2, 3],
+        "score" => &[0.5, 1.5, 2.5],
+        "name" => &["a", "b", "c"],
+    }
+    .unwrap()
+}
+
+fn test_polars_rows(args: Vec<Expr>) -> Expr {
+    assert!(args.is_empty());
+
+    data_frame().to_expr()
+}
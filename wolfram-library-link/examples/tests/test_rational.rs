@@ -0,0 +1,94 @@
+use num_rational::Ratio;
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+wll::export![test_rational_conversions()];
+
+wll::export_wstp![test_rational_reciprocal(_)];
+
+fn rational(numer: i64, denom: i64) -> Expr {
+    Expr::normal(Symbol::new("System`Rational"), vec![
+        Expr::from(numer),
+        Expr::from(denom),
+    ])
+}
+
+fn test_rational_conversions() {
+    //
+    // ToExpr
+    //
+
+    assert_eq!(Ratio::new(2i64, 4).to_expr(), rational(1, 2));
+    assert_eq!(Ratio::new(-3i64, 6).to_expr(), rational(-1, 2));
+    assert_eq!(Ratio::new(4i64, 2).to_expr(), Expr::from(2i64));
+    assert_eq!(
+        Ratio::from_integer(i64::MIN).to_expr(),
+        Expr::from(i64::MIN)
+    );
+
+    //
+    // FromExpr
+    //
+
+    assert_eq!(Ratio::from_expr(&rational(1, 3)), Ok(Ratio::new(1, 3)));
+    assert_eq!(
+        Ratio::from_expr(&Expr::from(7i64)),
+        Ok(Ratio::from_integer(7))
+    );
+
+    // Values that are not in lowest terms are normalized.
+    assert_eq!(Ratio::from_expr(&rational(2, -4)), Ok(Ratio::new(-1, 2)));
+    assert_eq!(
+        Ratio::from_expr(&rational(i64::MIN, 2)),
+        Ok(Ratio::new(i64::MIN / 2, 1))
+    );
+    assert_eq!(
+        Ratio::from_expr(&rational(i64::MAX, i64::MAX)),
+        Ok(Ratio::from_integer(1))
+    );
+
+    let error = Ratio::<i64>::from_expr(&rational(1, 0)).unwrap_err();
+
+    assert!(
+        error.starts_with("rational number has zero denominator"),
+        "{}",
+        error
+    );
+
+    // Making the denominator positive overflows i64.
+    let error = Ratio::<i64>::from_expr(&rational(1, i64::MIN)).unwrap_err();
+
+    assert!(
+        error.starts_with("rational number out of range"),
+        "{}",
+        error
+    );
+    assert!(Ratio::<i64>::from_expr(&rational(i64::MIN, -1)).is_err());
+
+    // Inexact values are not accepted.
+    assert!(Ratio::<i64>::from_expr(&Expr::real(0.5)).is_err());
+    assert!(Ratio::<i64>::from_expr(&Expr::normal(
+        Symbol::new("System`Rational"),
+        vec![Expr::real(1.0), Expr::from(2i64)]
+    ))
+    .is_err());
+}
+
+/// Returns the reciprocal of a `Rational` or `Integer`, or a `Failure[..]`.
+fn test_rational_reciprocal(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    Ratio::<i64>::from_expr(&args[0])
+        .and_then(|value| {
+            if *value.numer() == 0 {
+                return Err("division by zero".to_owned());
+            }
+
+            Ok(value.recip())
+        })
+        .to_expr()
+}
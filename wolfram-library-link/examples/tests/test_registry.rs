@@ -0,0 +1,43 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use wolfram_library_link::{
+    self as wll,
+    registry::{Handle, Registered, Registry},
+};
+
+wll::export![
+    test_registry_new(_);
+    test_registry_add(_, _);
+    test_registry_get(_);
+    test_registry_delete(_);
+];
+
+struct Counter {
+    value: AtomicI64,
+}
+
+static COUNTERS: Registry<Counter> = Registry::new();
+
+impl Registered for Counter {
+    fn registry() -> &'static Registry<Counter> {
+        &COUNTERS
+    }
+}
+
+fn test_registry_new(initial: i64) -> i64 {
+    COUNTERS.insert(Counter {
+        value: AtomicI64::new(initial),
+    })
+}
+
+fn test_registry_add(counter: Handle<Counter>, amount: i64) {
+    counter.value.fetch_add(amount, Ordering::SeqCst);
+}
+
+fn test_registry_get(counter: Handle<Counter>) -> i64 {
+    counter.value.load(Ordering::SeqCst)
+}
+
+fn test_registry_delete(handle: i64) -> bool {
+    COUNTERS.remove(handle).is_some()
+}
@@ -1,4 +1,8 @@
-use wolfram_library_link::{self as wll, DataStore, NumericArray};
+use wolfram_library_link::{
+    self as wll, debug,
+    memory::{Constant, Manual, Shared},
+    DataStore, NumericArray,
+};
 
 wll::export![
     test_na_automatic_count(_);
@@ -12,6 +16,22 @@ wll::export![
     // Test cloning NumericArray's
     test_na_clone();
     test_na_shared_clone(_);
+    // Test runtime borrow tracking of "Shared" NumericArray's
+    test_na_shared_overlapping_borrow(_, _);
+    // Test detection of NumericArray's used after being released
+    test_na_use_after_release();
+    // Test the memory management mode wrapper types
+    test_na_constant_wrapper_count(_);
+    test_na_manual_wrapper_doubled(_);
+    test_na_shared_wrapper_count(_);
+    test_na_shared_wrapper_owned_copy(_);
+    // Test memory management mode annotations
+    test_na_annotated_modes(#[automatic] _, #[manual] _);
+    // Test leak checking of "Manual" NumericArray's
+    test_start_leak_check();
+    test_na_manual_leaked(_);
+    test_na_manual_not_leaked(_);
+    test_stop_leak_check();
 ];
 
 fn test_na_automatic_count(array: &NumericArray) -> i64 {
@@ -95,3 +115,123 @@ fn test_na_shared_clone(array: NumericArray<i64>) -> bool {
 
     true
 }
+
+fn test_na_shared_overlapping_borrow(
+    mut array1: NumericArray<i64>,
+    array2: NumericArray<i64>,
+) -> bool {
+    let mut data = unsafe { array1.borrow_shared_mut() };
+    data[0] += 1;
+
+    // `array2` refers to the same shared array as `array1`, so accessing it while
+    // `data` is alive should panic in debug builds.
+    let result =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| array2.as_slice()[0]));
+
+    result.is_err() == cfg!(debug_assertions)
+}
+
+fn test_na_use_after_release() -> bool {
+    // Without the checks enabled by `debug_assertions`, the accesses below are
+    // undefined behavior.
+    if !cfg!(debug_assertions) {
+        return true;
+    }
+
+    let array: NumericArray<i64> = NumericArray::from_slice(&[1, 2, 3]);
+
+    // Incorrectly construct two owners of the same array.
+    let raw = array.into_raw();
+    let first: NumericArray<i64> = unsafe { NumericArray::from_raw(raw) };
+    let second: NumericArray<i64> = unsafe { NumericArray::from_raw(raw) };
+
+    drop(first);
+
+    let used =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| second.as_slice()[0]));
+
+    // Dropping `second` should panic instead of freeing the array a second time.
+    let released =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| drop(second)));
+
+    used.is_err() && released.is_err()
+}
+
+//--------------------------------------------
+// Test memory management mode wrapper types
+//--------------------------------------------
+
+fn test_na_constant_wrapper_count(array: Constant<i64>) -> i64 {
+    array.share_count() as i64
+}
+
+fn test_na_manual_wrapper_doubled(mut array: Manual<i64>) -> NumericArray<i64> {
+    for elem in array.as_slice_mut() {
+        *elem *= 2;
+    }
+
+    array.into_inner()
+}
+
+fn test_na_shared_wrapper_count(array: Shared<i64>) -> i64 {
+    let count = array.share_count() as i64;
+    array.disown();
+    count
+}
+
+fn test_na_shared_wrapper_owned_copy(array: Shared<i64>) -> NumericArray<i64> {
+    let mut copy = array.make_owned_copy();
+
+    assert!(!array.ptr_eq(&copy));
+    assert!(array.share_count() == 1);
+    assert!(copy.share_count() == 0);
+
+    for elem in copy.as_slice_mut().unwrap() {
+        *elem += 1;
+    }
+
+    copy
+}
+
+//--------------------------------------------
+// Test memory management mode annotations
+//--------------------------------------------
+
+fn test_na_annotated_modes(
+    automatic: &NumericArray<i64>,
+    mut manual: NumericArray<i64>,
+) -> DataStore {
+    let mut data = DataStore::new();
+    data.add_i64(automatic.share_count() as i64);
+    data.add_i64(manual.share_count() as i64);
+    data.add_bool(manual.as_slice_mut().is_some());
+    data
+}
+
+//--------------------------------------------
+// Test leak checking of "Manual" NumericArray's
+//--------------------------------------------
+
+fn test_start_leak_check() {
+    debug::clear_leaks();
+    debug::start_leak_check();
+}
+
+fn test_na_manual_leaked(array: Manual<i64>) {
+    // Deliberately leak the array.
+    std::mem::forget(array);
+}
+
+fn test_na_manual_not_leaked(array: Manual<i64>) -> i64 {
+    array.as_slice().iter().sum()
+}
+
+/// Returns the number of leaks detected.
+fn test_stop_leak_check() -> i64 {
+    debug::stop_leak_check();
+
+    let leaks = debug::leaks();
+    debug::clear_leaks();
+
+    leaks.len() as i64
+}
@@ -0,0 +1,40 @@
+use wolfram_library_link::{self as wll, NumericArray, SparseArray};
+
+wll::export![
+    test_sparse_to_dense(_);
+    test_sparse_from_dense(_);
+    // Test structural accessors and pattern arrays
+    test_sparse_structure(_);
+    test_sparse_to_pattern(_);
+    test_sparse_from_pattern();
+];
+
+fn test_sparse_to_dense(sparse: &SparseArray) -> NumericArray {
+    sparse.to_dense_numeric_array().unwrap()
+}
+
+fn test_sparse_from_dense(dense: &NumericArray<f64>) -> SparseArray {
+    SparseArray::from_dense(dense, 1e-10).unwrap()
+}
+
+fn test_sparse_structure(sparse: &SparseArray) -> bool {
+    assert_eq!(sparse.row_pointers(), &[0, 1, 2]);
+    assert_eq!(sparse.column_indices(), &[1, 3]);
+    assert_eq!(sparse.explicit_count(), 2);
+
+    let values = sparse.explicit_values();
+    assert_eq!(values.try_kind::<i64>().unwrap().as_slice(), &[5, 7]);
+
+    let implicit = sparse.implicit_value();
+    assert_eq!(implicit.try_kind::<i64>().unwrap().as_scalar(), Some(&0));
+
+    true
+}
+
+fn test_sparse_to_pattern(sparse: &SparseArray) -> SparseArray {
+    sparse.to_pattern().unwrap()
+}
+
+fn test_sparse_from_pattern() -> SparseArray {
+    SparseArray::from_pattern(&[3, 3], &[&[1, 2], &[2, 3]]).unwrap()
+}
@@ -0,0 +1,83 @@
+use std::convert::TryFrom;
+
+use sprs::CsMat;
+
+use wolfram_library_link::{self as wll, SparseArray};
+
+wll::export![
+    test_sprs_conversions();
+    test_sprs_transpose(_);
+    test_sprs_integer_double(_);
+];
+
+fn test_sprs_conversions() {
+    //
+    // Real matrices
+    //
+
+    let matrix = CsMat::new((2, 3), vec![0, 1, 3], vec![2, 0, 1], vec![1.5, 2.0, -3.0]);
+
+    let sparse = SparseArray::from(&matrix);
+
+    assert_eq!(sparse.dimensions(), &[2, 3]);
+    assert_eq!(sparse.row_pointers(), &[0, 1, 3]);
+    // Column indices are 1-based.
+    assert_eq!(sparse.column_indices(), &[3, 1, 2]);
+
+    let values = sparse.explicit_values();
+    assert_eq!(values.try_kind::<f64>().unwrap().as_slice(), &[
+        1.5, 2.0, -3.0
+    ]);
+
+    assert_eq!(CsMat::<f64>::try_from(&sparse), Ok(matrix));
+
+    //
+    // Integer matrices
+    //
+
+    let matrix = CsMat::new((2, 2), vec![0, 1, 1], vec![1], vec![i64::MIN]);
+
+    let sparse = SparseArray::from(&matrix);
+
+    assert_eq!(CsMat::<i64>::try_from(&sparse), Ok(matrix));
+
+    // Integer values are converted to Real.
+    assert_eq!(
+        CsMat::<f64>::try_from(&sparse),
+        Ok(CsMat::new((2, 2), vec![0, 1, 1], vec![1], vec![
+            i64::MIN as f64
+        ]))
+    );
+
+    //
+    // Invalid conversions
+    //
+
+    let reals = SparseArray::from(&CsMat::new((1, 1), vec![0, 1], vec![0], vec![0.5]));
+
+    assert_eq!(
+        CsMat::<i64>::try_from(&reals),
+        Err("expected SparseArray of Integer values".to_owned())
+    );
+
+    let rank_3 = SparseArray::from_pattern(&[2, 2, 2], &[&[1, 2, 1]]).unwrap();
+
+    assert_eq!(
+        CsMat::<f64>::try_from(&rank_3),
+        Err("expected SparseArray of rank 2, got rank 3".to_owned())
+    );
+}
+
+fn test_sprs_transpose(sparse: &SparseArray) -> SparseArray {
+    let matrix = CsMat::<f64>::try_from(sparse)
+        .unwrap_or_else(|err| panic!("could not convert SparseArray: {}", err));
+
+    SparseArray::from(&matrix.transpose_into().to_csr())
+}
+
+fn test_sprs_integer_double(sparse: &SparseArray) -> SparseArray {
+    let matrix = CsMat::<i64>::try_from(sparse)
+        .unwrap_or_else(|err| panic!("could not convert SparseArray: {}", err));
+
+    SparseArray::from(&matrix.map(|value| 2 * value))
+}
@@ -1,22 +1,57 @@
-use std::panic;
+use std::{panic, time::Duration};
 
 use wolfram_library_link::{
-    self as wll,
-    expr::{Expr, Symbol},
+    self as wll, debug, exported_functions,
+    expr::{Expr, ExprKind, Symbol},
+    kernel, rtl,
+    sandbox::{self, Capability},
+    sys, watchdog, CancelReason, CancelToken, EvaluationError, MainThreadExecutor,
+    StringFormat,
 };
 
 wll::export![
     test_runtime_function_from_main_thread();
     test_runtime_function_from_non_main_thread();
+    test_evaluate_expression_from_main_thread();
+    test_evaluate_to_string_from_main_thread();
+    test_main_thread_executor(_);
+    test_main_thread_executor_dropped();
+    test_evaluate_later();
+    test_transcript();
+    test_try_evaluate_uncaught_throw();
+    test_evaluate_reentrant();
+    test_evaluate_reentrant_inner();
+    test_kernel_info();
+    test_on_kernel_thread();
+    test_set_parallel_thread_count();
+    test_sandbox_unrestricted();
+    test_watchdog();
+    test_cancel_token();
+    test_cancel_token_watchdog();
+    #[thread_safe] test_thread_safe_square(_);
+    test_thread_safe_from_worker_threads();
 ];
 
+wll::export_wstp![test_evaluate_output(_)];
+
 fn test_runtime_function_from_main_thread() -> bool {
     let expr = Expr::normal(Symbol::new("System`Plus"), vec![
         Expr::from(2),
         Expr::from(2),
     ]);
 
-    wll::evaluate(&expr) == Expr::from(4)
+    wll::evaluate(&expr).unwrap() == Expr::from(4)
+}
+
+fn test_evaluate_expression_from_main_thread() -> bool {
+    wll::evaluate_expression::<i64>("2 + 2") == Ok(4)
+        && wll::evaluate_expression::<f64>("N[1/4]") == Ok(0.25)
+}
+
+fn test_evaluate_to_string_from_main_thread() -> String {
+    let expr = Expr::normal(Symbol::new("System`Range"), vec![Expr::from(3)]);
+
+    wll::evaluate_to_string(&expr, StringFormat::InputForm).unwrap()
 }
 
 fn test_runtime_function_from_non_main_thread() -> String {
@@ -54,3 +89,318 @@ fn test_runtime_function_from_non_main_thread() -> String {
         },
     }
 }
+
+/// Evaluate `Plus[index, index]` for each worker thread index, using a
+/// `MainThreadExecutor` to perform the evaluations on the main thread.
+fn test_main_thread_executor(workers: i64) -> i64 {
+    let executor = MainThreadExecutor::new();
+
+    let handles: Vec<_> = (0..workers)
+        .map(|index| {
+            let executor = executor.clone();
+
+            std::thread::spawn(move || {
+                let result = executor
+                    .spawn(move || {
+                        wll::evaluate(&Expr::normal(Symbol::new("System`Plus"), vec![
+                            Expr::from(index),
+                            Expr::from(index),
+                        ]))
+                        .unwrap()
+                    })
+                    .wait();
+
+                assert_eq!(result, Expr::from(2 * index));
+
+                index
+            })
+        })
+        .collect();
+
+    while !handles.iter().all(|handle| handle.is_finished()) {
+        executor.poll();
+        std::thread::yield_now();
+    }
+
+    handles
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .sum()
+}
+
+/// Check that waiting on a task whose executor was dropped before the task was run
+/// panics instead of blocking forever.
+fn test_main_thread_executor_dropped() -> bool {
+    let executor = MainThreadExecutor::new();
+
+    let task = executor.spawn(|| 1 + 1);
+
+    drop(executor);
+
+    let waiter = std::thread::spawn(move || task.wait());
+
+    waiter.join().is_err()
+}
+
+/// Queue assignments to `testEvaluateLaterValue` using `evaluate_later()`.
+///
+/// The first assignment is evaluated before the `evaluate()` callback, and the second
+/// when this function returns.
+fn test_evaluate_later() -> i64 {
+    let set = |value: i64| {
+        Expr::normal(Symbol::new("System`Set"), vec![
+            Expr::symbol(Symbol::new("Global`testEvaluateLaterValue")),
+            Expr::from(value),
+        ])
+    };
+
+    wll::evaluate_later(set(1));
+
+    let value =
+        wll::evaluate(&Expr::symbol(Symbol::new("Global`testEvaluateLaterValue")))
+            .unwrap();
+
+    wll::evaluate_later(set(2));
+
+    match value.kind() {
+        ExprKind::Integer(value) => *value,
+        _ => panic!("unexpected value: {}", value),
+    }
+}
+
+/// Record the evaluation of `Plus[1, 2]` in the evaluation transcript.
+fn test_transcript() {
+    debug::clear_transcript();
+    debug::start_transcript();
+
+    let result = wll::evaluate(&Expr::normal(Symbol::new("System`Plus"), vec![
+        Expr::from(1),
+        Expr::from(2),
+    ]))
+    .unwrap();
+
+    debug::stop_transcript();
+
+    let transcript = debug::transcript();
+
+    assert_eq!(transcript.len(), 1);
+    assert_eq!(transcript[0].result, Ok(result));
+}
+
+/// Evaluate `Print["Hello"]; 1/0`, and return `{value, output, messages}`.
+fn test_evaluate_output(_args: Vec<Expr>) -> Expr {
+    let expr = Expr::normal(Symbol::new("System`CompoundExpression"), vec![
+        Expr::normal(Symbol::new("System`Print"), vec![Expr::string("Hello")]),
+        Expr::normal(Symbol::new("System`Power"), vec![
+            Expr::from(0),
+            Expr::from(-1),
+        ]),
+    ]);
+
+    let result = wll::evaluate(&expr);
+
+    let strings = |strings: Vec<String>| {
+        Expr::normal(
+            Symbol::new("System`List"),
+            strings.into_iter().map(Expr::string).collect(),
+        )
+    };
+
+    Expr::normal(Symbol::new("System`List"), vec![
+        result.value.unwrap(),
+        strings(result.output),
+        strings(result.messages),
+    ])
+}
+
+/// Check that evaluating `Throw[1]` and `Throw[1, "tag"]` returns
+/// `EvaluationError::UncaughtThrow`.
+fn test_try_evaluate_uncaught_throw() -> bool {
+    let throw = |args: Vec<Expr>| {
+        wll::try_evaluate(&Expr::normal(Symbol::new("System`Throw"), args))
+    };
+
+    let untagged = throw(vec![Expr::from(1)]);
+    let tagged = throw(vec![Expr::from(1), Expr::string("tag")]);
+
+    untagged
+        == Err(EvaluationError::UncaughtThrow {
+            value: Expr::from(1),
+            tag: None,
+        })
+        && tagged
+            == Err(EvaluationError::UncaughtThrow {
+                value: Expr::from(1),
+                tag: Some(Expr::string("tag")),
+            })
+}
+
+/// Call `test_evaluate_reentrant_inner()` from an evaluation started by `evaluate()`.
+fn test_evaluate_reentrant() -> bool {
+    let load = Expr::normal(Symbol::new("System`LibraryFunctionLoad"), vec![
+        Expr::string("liblibrary_tests"),
+        Expr::string("test_evaluate_reentrant_inner"),
+        Expr::normal(Symbol::new("System`List"), vec![]),
+        Expr::string("Boolean"),
+    ]);
+
+    let result = wll::evaluate(&Expr::normal(load, vec![])).unwrap();
+
+    result == Expr::symbol(Symbol::new("System`True"))
+}
+
+/// Check that calling back into the Kernel while an outer evaluation is in progress
+/// returns `EvaluationError::Reentrant`.
+fn test_evaluate_reentrant_inner() -> bool {
+    wll::try_evaluate(&Expr::from(1)) == Err(EvaluationError::Reentrant)
+}
+
+fn test_kernel_info() -> String {
+    let version = kernel::version_string().unwrap();
+    let system_id = kernel::system_id().unwrap();
+
+    // Cached values are returned by later calls.
+    assert_eq!(kernel::version_string().unwrap(), version);
+    assert!(kernel::process_id().unwrap() > 0);
+
+    format!("{} ({})", version, system_id)
+}
+
+fn test_on_kernel_thread() -> bool {
+    let on_child_thread = std::thread::spawn(wll::on_kernel_thread).join().unwrap();
+
+    wll::on_kernel_thread() && !on_child_thread
+}
+
+fn test_thread_safe_square(x: i64) -> i64 {
+    x * x
+}
+
+/// Call the `#[thread_safe]` wrapper of `test_thread_safe_square()` concurrently from
+/// several worker threads.
+fn test_thread_safe_from_worker_threads() -> bool {
+    let is_thread_safe = |name: &str| {
+        exported_functions()
+            .iter()
+            .any(|func| func.name() == name && func.is_thread_safe())
+    };
+
+    if !is_thread_safe("test_thread_safe_square")
+        || is_thread_safe("test_on_kernel_thread")
+    {
+        return false;
+    }
+
+    // Raw pointers are not `Send`, so pass the library data address to each thread.
+    let lib = wll::get_library_data().raw_library_data as usize;
+
+    let workers: Vec<_> = (0..4)
+        .map(|i: sys::mint| {
+            std::thread::spawn(move || {
+                let mut x: sys::mint = i;
+                let mut out: sys::mint = 0;
+
+                let mut args = [sys::MArgument { integer: &mut x }];
+                let res = sys::MArgument { integer: &mut out };
+
+                let code = unsafe {
+                    test_thread_safe_square::test_thread_safe_square(
+                        lib as sys::WolframLibraryData,
+                        1,
+                        args.as_mut_ptr(),
+                        res,
+                    )
+                };
+
+                code == sys::LIBRARY_NO_ERROR && out == i * i
+            })
+        })
+        .collect();
+
+    workers.into_iter().all(|worker| worker.join().unwrap())
+}
+
+fn test_set_parallel_thread_count() -> bool {
+    let previous = rtl::parallel_thread_count();
+
+    let changed = {
+        let _guard = rtl::set_parallel_thread_count(1);
+
+        rtl::parallel_thread_count() == 1
+    };
+
+    changed && rtl::parallel_thread_count() == previous
+}
+
+fn test_sandbox_unrestricted() -> bool {
+    !sandbox::is_restricted()
+        && sandbox::is_allowed(Capability::Network)
+        && sandbox::is_allowed(Capability::ReadFile(&std::env::temp_dir()))
+}
+
+fn test_watchdog() -> bool {
+    let within_budget = {
+        let watchdog = watchdog::guard("test_watchdog", Duration::from_secs(60));
+
+        !watchdog.is_expired() && watchdog.check().is_ok()
+    };
+
+    // The deadline of this watchdog overflows `Instant`, so it never expires.
+    let unbounded = {
+        let watchdog = watchdog::guard("test_watchdog", Duration::MAX);
+
+        !watchdog.is_expired()
+    };
+
+    let overruns_before = watchdog::overruns().len();
+
+    let expired = {
+        let watchdog = watchdog::guard("test_watchdog", Duration::from_millis(10));
+
+        std::thread::sleep(Duration::from_millis(200));
+
+        watchdog.is_expired() && watchdog.check().is_err()
+    };
+
+    let overruns = watchdog::overruns();
+
+    within_budget
+        && unbounded
+        && expired
+        && overruns.len() > overruns_before
+        && overruns.last().unwrap().name == "test_watchdog"
+}
+
+fn test_cancel_token() -> bool {
+    let token = CancelToken::new();
+
+    let waiting = {
+        let token = token.clone();
+        std::thread::spawn(move || token.wait())
+    };
+
+    let timed_out = token.wait_timeout(Duration::from_millis(10)).is_none();
+    let not_aborted = !token.poll_abort();
+
+    // Cancel by ID, as `wll_cancel` does.
+    let found = wll::cancel(token.id());
+    token.cancel_with(CancelReason::Aborted);
+
+    timed_out
+        && not_aborted
+        && found
+        && waiting.join().unwrap() == CancelReason::Requested
+        && token.reason() == Some(CancelReason::Requested)
+        && token.check().is_err()
+}
+
+fn test_cancel_token_watchdog() -> bool {
+    let token = CancelToken::new();
+
+    let _watchdog =
+        watchdog::guard("test_cancel_token_watchdog", Duration::from_millis(10))
+            .cancel_token(&token);
+
+    token.run_abortable(|token| token.wait_timeout(Duration::from_secs(10)))
+        == Some(CancelReason::Watchdog)
+}
@@ -0,0 +1,87 @@
+use uom::si::{
+    f64::{Length, ThermodynamicTemperature, Time, Velocity},
+    length::{foot, kilometer, meter},
+    thermodynamic_temperature::kelvin,
+    time::second,
+};
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+wll::export![test_uom_conversions()];
+
+wll::export_wstp![test_uom_speed(_)];
+
+fn quantity(magnitude: Expr, unit: &str) -> Expr {
+    Expr::normal(Symbol::new("System`Quantity"), vec![
+        magnitude,
+        Expr::string(unit),
+    ])
+}
+
+fn test_uom_conversions() {
+    //
+    // ToExpr uses the first unit listed for each quantity type.
+    //
+
+    assert_eq!(
+        Length::new::<kilometer>(1.5).to_expr(),
+        quantity(Expr::real(1500.0), "Meters")
+    );
+
+    //
+    // FromExpr converts any supported unit.
+    //
+
+    let length = Length::from_expr(&quantity(Expr::from(12i64), "Inches")).unwrap();
+
+    assert!((length.get::<foot>() - 1.0).abs() < 1e-12);
+
+    let temperature =
+        ThermodynamicTemperature::from_expr(&quantity(Expr::real(0.0), "DegreesCelsius"))
+            .unwrap();
+
+    assert!((temperature.get::<kelvin>() - 273.15).abs() < 1e-9);
+
+    // NaN magnitudes round trip through Indeterminate.
+    let nan = Length::new::<meter>(f64::NAN).to_expr();
+
+    assert_eq!(
+        nan,
+        quantity(Expr::symbol(Symbol::new("System`Indeterminate")), "Meters")
+    );
+    assert!(Length::from_expr(&nan).unwrap().get::<meter>().is_nan());
+
+    //
+    // Errors
+    //
+
+    let error = Length::from_expr(&quantity(Expr::real(1.0), "Seconds")).unwrap_err();
+
+    assert_eq!(error, "unsupported unit for Length quantity: \"Seconds\"");
+
+    assert!(Length::from_expr(&quantity(Expr::string("1"), "Meters")).is_err());
+    assert!(Length::from_expr(&Expr::real(1.0)).is_err());
+    assert!(
+        Length::from_expr(&Expr::normal(Symbol::new("System`Quantity"), vec![
+            Expr::real(1.0),
+            Expr::symbol(Symbol::new("Global`Meters")),
+        ]))
+        .is_err()
+    );
+}
+
+/// Returns the average speed over a distance and time, or a `Failure[..]`.
+fn test_uom_speed(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 2);
+
+    let distance = Length::from_expr(&args[0]);
+    let time = Time::from_expr(&args[1]);
+
+    distance
+        .and_then(|distance| time.map(|time| -> Velocity { distance / time }))
+        .to_expr()
+}
@@ -0,0 +1,63 @@
+use std::convert::TryFrom;
+
+use uuid::Uuid;
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::Expr,
+    NumericArray,
+};
+
+wll::export![
+    test_uuid_conversions();
+    test_uuid_numeric_array_conversions();
+    test_uuid_arg(_);
+];
+
+const UUID: &str = "67e55044-10b1-426f-9247-bb680e5fe0c8";
+
+fn test_uuid_conversions() {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+
+    assert_eq!(uuid.to_expr(), Expr::string(UUID));
+    assert_eq!(Uuid::from_expr(&Expr::string(UUID)), Ok(uuid));
+
+    // Other formats supported by `Uuid::parse_str()` are accepted.
+    assert_eq!(
+        Uuid::from_expr(&Expr::string("67e5504410b1426f9247bb680e5fe0c8")),
+        Ok(uuid)
+    );
+    assert_eq!(
+        Uuid::from_expr(&Expr::string(format!("{{{}}}", UUID.to_uppercase()))),
+        Ok(uuid)
+    );
+
+    let error = Uuid::from_expr(&Expr::string("67e55044-10b1-426f-9247")).unwrap_err();
+
+    assert!(error.starts_with("invalid UUID"), "{}", error);
+
+    assert!(Uuid::from_expr(&Expr::string("")).is_err());
+    assert!(
+        Uuid::from_expr(&Expr::string("zze55044-10b1-426f-9247-bb680e5fe0c8")).is_err()
+    );
+    assert!(Uuid::from_expr(&Expr::from(5i64)).is_err());
+}
+
+fn test_uuid_numeric_array_conversions() {
+    let uuid = Uuid::parse_str(UUID).unwrap();
+
+    let bytes = NumericArray::from(uuid);
+
+    assert_eq!(bytes.dimensions(), [16]);
+    assert_eq!(bytes.as_slice(), uuid.as_bytes());
+    assert_eq!(Uuid::try_from(&bytes), Ok(uuid));
+
+    assert!(Uuid::try_from(&NumericArray::from_slice(&[0u8; 15])).is_err());
+    assert!(Uuid::try_from(&NumericArray::<u8>::empty()).is_err());
+    assert!(Uuid::try_from(&NumericArray::from_array(&[2, 8], uuid.as_bytes())).is_err());
+}
+
+fn test_uuid_arg(uuid: Uuid) -> Uuid {
+    uuid
+}
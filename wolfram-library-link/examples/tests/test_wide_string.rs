@@ -0,0 +1,72 @@
+use std::convert::TryFrom;
+
+use wolfram_library_link::{self as wll, wstp::Link, NumericArray, WideString};
+
+wll::export![
+    test_wide_string_conversions();
+    test_wide_string_units(_);
+    test_wide_string_from_units(_);
+    test_wide_string_echo(_);
+];
+
+wll::export_wstp![test_wide_string_link_reverse(&mut Link)];
+
+fn test_wide_string_conversions() {
+    let string = WideString::from("a€😀");
+
+    // U+1F600 is encoded as a surrogate pair.
+    assert_eq!(string.as_utf16(), [0x61, 0x20AC, 0xD83D, 0xDE00]);
+    assert_eq!(string.len(), 4);
+    assert!(!string.has_unpaired_surrogates());
+    assert_eq!(String::try_from(&string), Ok("a€😀".to_owned()));
+    assert_eq!(string, WideString::from("a€😀".to_owned()));
+
+    //
+    // Unpaired surrogates
+    //
+
+    let unpaired = WideString::from_utf16(vec![0x61, 0xD800, 0x62]);
+
+    assert!(unpaired.has_unpaired_surrogates());
+    assert!(String::try_from(&unpaired).is_err());
+    assert_eq!(unpaired.to_string_lossy(), "a\u{FFFD}b");
+    assert_eq!(unpaired.to_string(), "a\u{FFFD}b");
+    assert_eq!(format!("{:?}", unpaired), "WideString(\"a\u{FFFD}b\")");
+    assert_eq!(unpaired.clone().into_utf16(), [0x61, 0xD800, 0x62]);
+
+    // A low surrogate that is not preceded by a high surrogate.
+    assert!(WideString::from_utf16(vec![0xDC00, 0xD800]).has_unpaired_surrogates());
+
+    //
+    // Empty strings
+    //
+
+    let empty = WideString::default();
+
+    assert!(empty.is_empty());
+    assert_eq!(empty, WideString::from(""));
+    assert_eq!(String::try_from(&empty), Ok(String::new()));
+}
+
+fn test_wide_string_units(string: WideString) -> NumericArray<u16> {
+    NumericArray::from_slice(string.as_utf16())
+}
+
+fn test_wide_string_from_units(units: &NumericArray<u16>) -> WideString {
+    WideString::from_utf16(units.as_slice().to_vec())
+}
+
+fn test_wide_string_echo(string: WideString) -> WideString {
+    string
+}
+
+/// Reverse the UTF-16 code units of a string, which may split surrogate pairs.
+fn test_wide_string_link_reverse(link: &mut Link) {
+    assert_eq!(link.test_head("System`List").unwrap(), 1);
+
+    let mut units = WideString::get(link).unwrap().into_utf16();
+
+    units.reverse();
+
+    WideString::from_utf16(units).put(link).unwrap();
+}
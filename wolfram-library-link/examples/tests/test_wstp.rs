@@ -1,6 +1,7 @@
 use wolfram_library_link::{
     self as wll,
-    expr::Expr,
+    convert::{FromExpr, ToExpr},
+    expr::{Expr, Symbol},
     wstp::{self, Link},
 };
 
@@ -11,8 +12,12 @@ wll::export_wstp![
     test_wstp_fn_panic_immediately_with_formatting(&mut Link);
     test_wstp_panic_with_empty_link(&mut Link);
     test_wstp_fn_poison_link_and_panic(&mut Link);
+    test_wstp_write_in_background(&mut Link);
     // Vec<Expr>
     test_wstp_expr_return_null(_);
+    test_wstp_expr_tuple_reverse(_);
+    // WstpEnvironment
+    test_wstp_environment_loopback_link(_);
 ];
 
 fn test_wstp_fn_empty(_link: &mut Link) {
@@ -62,6 +67,50 @@ fn test_wstp_fn_poison_link_and_panic(link: &mut Link) {
     panic!("successful panic")
 }
 
+/// Write `Range[n]` to the link on a background thread.
+fn test_wstp_write_in_background(link: &mut Link) {
+    assert_eq!(link.test_head("System`List").unwrap(), 1);
+
+    let n = link.get_i64().unwrap();
+
+    let data: Vec<i64> = (1..=n).collect();
+    let list = Expr::normal(
+        Symbol::new("System`List"),
+        data.iter().copied().map(Expr::from).collect(),
+    );
+
+    let total = wll::write_in_background(
+        link,
+        move |link| link.put_expr(&list).map_err(|err| err.to_string()),
+        move || data.into_iter().sum::<i64>(),
+    )
+    .unwrap();
+
+    assert_eq!(total, n * (n + 1) / 2);
+}
+
 fn test_wstp_expr_return_null(_args: Vec<Expr>) {
     // Do nothing.
 }
+
+/// Convert `{integer, real, string}` into `{string, real, integer}`.
+fn test_wstp_expr_tuple_reverse(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    <(i64, f64, String)>::from_expr(&args[0])
+        .map(|(integer, real, string)| (string, real, integer))
+        .to_expr()
+}
+
+/// Round-trip `args` through a new loopback link opened using the Kernel's WSTP
+/// environment.
+fn test_wstp_environment_loopback_link(args: Vec<Expr>) -> Expr {
+    let mut link = wll::wstp_environment()
+        .open_link(&["-linkmode", "loopback"])
+        .unwrap();
+
+    link.put_expr(&Expr::normal(Symbol::new("System`List"), args))
+        .unwrap();
+
+    link.get_expr().unwrap()
+}
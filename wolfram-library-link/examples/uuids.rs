@@ -0,0 +1,37 @@
+//! Exchange UUIDs with the Wolfram Language using the `uuid` crate.
+//!
+//! This example requires the `"uuid"` feature of `wolfram-library-link`.
+
+use std::convert::TryFrom;
+
+use uuid::Uuid;
+
+use wolfram_library_link::{
+    self as wll,
+    convert::{FromExpr, ToExpr},
+    expr::Expr,
+    NumericArray,
+};
+
+wll::export![uuid_version(_); uuid_to_bytes(_); uuid_from_bytes(_)];
+wll::export_wstp![validate_uuid(_)];
+
+fn uuid_version(uuid: Uuid) -> i64 {
+    uuid.get_version_num() as i64
+}
+
+fn uuid_to_bytes(uuid: Uuid) -> NumericArray<u8> {
+    NumericArray::from(uuid)
+}
+
+fn uuid_from_bytes(bytes: &NumericArray<u8>) -> Uuid {
+    Uuid::try_from(bytes).unwrap()
+}
+
+/// Returns the UUID in canonical form, or a `Failure[..]` if the argument is not a valid
+/// UUID string.
+fn validate_uuid(args: Vec<Expr>) -> Expr {
+    assert!(args.len() == 1);
+
+    Uuid::from_expr(&args[0]).to_expr()
+}
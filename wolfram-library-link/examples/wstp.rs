@@ -3,8 +3,10 @@
 
 use wolfram_library_link::{
     self as wll,
+    convert::ToExpr,
     expr::{Expr, ExprKind, Number, Symbol},
     wstp::Link,
+    LinkArgs,
 };
 
 // Generates a special "loader" function, which returns an Association containing the
@@ -196,6 +198,43 @@ fn expr_string_join(link: &mut Link) {
     link.put_str(buffer.as_str()).unwrap()
 }
 
+//======================================
+// Using `LinkArgs`
+//======================================
+
+//------------------
+// string_repeat()
+//------------------
+
+wll::export_wstp![string_repeat(&mut Link)];
+
+/// Define a function that repeats a string a number of times.
+///
+/// This example uses [`LinkArgs`] to read typed arguments from the link. Errors are
+/// returned as a `Failure[..]` object.
+///
+/// ```wolfram
+/// stringRepeat = $functions["string_repeat"];
+///
+/// stringRepeat["ab", 3]    (* Returns "ababab" *)
+/// stringRepeat["ab"]       (* Returns Failure["RustError", ..] *)
+/// ```
+fn string_repeat(link: &mut Link) {
+    let result: Result<String, String> = LinkArgs::new(link).and_then(|mut args| {
+        args.expect_count(2)?;
+
+        let string: String = args.next()?;
+        let count: i64 = args.next()?;
+
+        let count = usize::try_from(count)
+            .map_err(|_| format!("expected non-negative count, got {}", count))?;
+
+        Ok(string.repeat(count))
+    });
+
+    link.put_expr(&result.to_expr()).unwrap();
+}
+
 //======================================
 // Using `Vec<Expr>` argument list
 //======================================
@@ -4,6 +4,7 @@
 use std::{
     cell::RefCell,
     ffi::{CStr, CString},
+    marker::PhantomData,
     os::raw::c_char,
 };
 
@@ -11,10 +12,12 @@ use ref_cast::RefCast;
 
 use crate::{
     expr::{Expr, ExprKind, Symbol},
+    released::{self, Release},
     rtl,
     sys::{self, mint, mreal, MArgument},
     wstp::Link,
-    DataStore, Image, NumericArray,
+    Assoc, BoolArray, DataStore, DataStoreValue, Image, NumericArray, SparseArray,
+    WideString,
 };
 
 /// Trait implemented for types that can be passed via an [`MArgument`].
@@ -37,6 +40,56 @@ pub trait FromArg<'a> {
     ///
     /// See also [`IntoArg::return_type()`] and [`NativeFunction::signature()`].
     fn parameter_type() -> Expr;
+
+    /// Convert `arg`, or return a description of why it cannot be represented by this
+    /// type.
+    ///
+    /// The default implementation calls [`FromArg::from_arg()`], and never fails. For
+    /// types that implement [`TryFromArg`], this returns the error from
+    /// [`TryFromArg::try_from_arg()`] instead of panicking.
+    ///
+    /// Functions exported using [`export!`][crate::export] convert their arguments
+    /// using this method.
+    unsafe fn checked_from_arg(arg: &'a MArgument) -> Result<Self, String>
+    where
+        Self: Sized,
+    {
+        Ok(Self::from_arg(arg))
+    }
+}
+
+/// Trait implemented for types that can be passed via an [`MArgument`], but which
+/// cannot represent every value of the corresponding *LibraryLink* type.
+///
+/// Every type that implements `TryFromArg` also implements [`FromArg`]. If the
+/// conversion fails, the function exported using [`export!`][crate::export] is not
+/// called, and the error message is recorded as the [`last_error()`][crate::last_error].
+/// The error is returned by [`FromArg::checked_from_arg()`], without unwinding.
+///
+/// For example, `u8` implements `TryFromArg`, so a function with a `u8` parameter can
+/// be called with any `Integer` argument, but is only called if the argument is between
+/// 0 and 255:
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link as wll;
+///
+/// wll::export![brightness_percent(_)];
+///
+/// fn brightness_percent(level: u8) -> f64 {
+///     f64::from(level) / 255.0 * 100.0
+/// }
+/// # }
+/// ```
+pub trait TryFromArg<'a>: Sized {
+    /// Convert `arg`, or return a description of why it cannot be represented by this
+    /// type.
+    unsafe fn try_from_arg(arg: &'a MArgument) -> Result<Self, String>;
+
+    /// Return the *LibraryLink* parameter type as a Wolfram Language expression.
+    ///
+    /// See [`FromArg::parameter_type()`].
+    fn parameter_type() -> Expr;
 }
 
 /// Trait implemented for types that can be returned via an [`MArgument`].
@@ -66,6 +119,22 @@ pub trait IntoArg {
     ///
     /// See also [`FromArg::parameter_type()`] and [`NativeFunction::signature()`].
     fn return_type() -> Expr;
+
+    /// Wolfram Language function that is applied to the value returned by the
+    /// *LibraryLink* function, if any.
+    ///
+    /// The loader function generated by [`generate_loader!`][crate::generate_loader]
+    /// applies this function to the result of every call. This can be used to convert the
+    /// returned value into a form that cannot be returned via *LibraryLink* directly. For
+    /// example, the return wrapper of [`Assoc`][crate::Assoc] converts the returned
+    /// ``Developer`DataStore[...]`` into an [`Association`][ref/Association]<sub>WL</sub>.
+    ///
+    /// The default implementation returns `None`.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    fn return_wrapper() -> Option<Expr> {
+        None
+    }
 }
 
 /// Trait implemented for any function whose parameters and return type are native
@@ -82,6 +151,22 @@ pub trait NativeFunction<'a> {
     /// Call the function using the raw LibraryLink [`MArgument`] fields.
     unsafe fn call(&self, args: &'a [MArgument], ret: MArgument);
 
+    /// Call the function using the raw LibraryLink [`MArgument`] fields, returning an
+    /// error instead of panicking if an argument cannot be converted to the type of the
+    /// corresponding parameter.
+    ///
+    /// Functions exported by [`export!`] are called using this method. The default
+    /// implementation calls [`NativeFunction::call()`], and never fails.
+    unsafe fn try_call(
+        &self,
+        args: &'a [MArgument],
+        ret: MArgument,
+    ) -> Result<(), String> {
+        self.call(args, ret);
+
+        Ok(())
+    }
+
     /// Get the type signature of this function, suitable for use in
     /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<code>[_, _, <i>parameters</i>, <i>ret</i>]</code>.
     ///
@@ -93,6 +178,26 @@ pub trait NativeFunction<'a> {
     /// type signature for functions exported by [`export!`] and [`export_wstp!`].
     // Note: This method takes `self` so that it is object safe.
     fn signature(&self) -> Result<(Vec<Expr>, Expr), String>;
+
+    /// Get the number of parameters of this function, if it is known.
+    ///
+    /// Functions exported by [`export!`] check the number of arguments they are called
+    /// with against this value before calling [`NativeFunction::call()`].
+    ///
+    /// The default implementation returns the number of parameter types in
+    /// [`NativeFunction::signature()`].
+    fn parameter_count(&self) -> Option<usize> {
+        let (param_tys, _) = self.signature().ok()?;
+
+        Some(param_tys.len())
+    }
+
+    /// Get the [`IntoArg::return_wrapper()`] of the return type of this function.
+    ///
+    /// The default implementation returns `None`.
+    fn return_wrapper(&self) -> Option<Expr> {
+        None
+    }
 }
 
 /// Trait implemented for any function whose parameters and return type can be passed
@@ -155,6 +260,66 @@ impl FromArg<'_> for sys::mcomplex {
     }
 }
 
+//--------------------------------------------------
+// Checked conversions for narrow integer sizes.
+//--------------------------------------------------
+
+/// # Panics
+///
+/// [`FromArg::from_arg()`] will panic if [`TryFromArg::try_from_arg()`] fails. The
+/// wrapper function generated by [`export!`][crate::export] uses
+/// [`FromArg::checked_from_arg()`] instead, and reports the error as an argument
+/// conversion error.
+impl<'a, T: TryFromArg<'a>> FromArg<'a> for T {
+    unsafe fn from_arg(arg: &'a MArgument) -> T {
+        match T::try_from_arg(arg) {
+            Ok(value) => value,
+            Err(message) => panic!("{}", message),
+        }
+    }
+
+    fn parameter_type() -> Expr {
+        <T as TryFromArg>::parameter_type()
+    }
+
+    unsafe fn checked_from_arg(arg: &'a MArgument) -> Result<T, String> {
+        T::try_from_arg(arg)
+    }
+}
+
+macro_rules! impl_TryFromArg_for_integer {
+    ($($type:ty),*) => {
+        $(
+            impl TryFromArg<'_> for $type {
+                unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+                    let value: mint = *arg.integer;
+
+                    <$type>::try_from(value).map_err(|_| {
+                        format!(
+                            "integer argument {} does not fit in {} (valid range: {} to {})",
+                            value,
+                            stringify!($type),
+                            <$type>::MIN,
+                            <$type>::MAX
+                        )
+                    })
+                }
+
+                fn parameter_type() -> Expr {
+                    Expr::symbol(Symbol::new("System`Integer"))
+                }
+            }
+        )*
+    };
+}
+
+impl_TryFromArg_for_integer!(i8, i16, u8, u16, u32, u64, isize, usize);
+
+// If we're on a 32 bit platform, mint might be an alias for i32. Avoid providing this
+// conversion on those platforms.
+#[cfg(target_pointer_width = "64")]
+impl_TryFromArg_for_integer!(i32);
+
 //--------------------------------------
 // Strings
 //--------------------------------------
@@ -206,8 +371,31 @@ impl<'a> FromArg<'a> for String {
     }
 }
 
-// TODO: Supported borrowed &CStr and &str's using some kind of wrapper that ensures we
-//       disown the Kernel string.
+/// Unpaired surrogates, and other characters which are not valid in a [`String`], are
+/// preserved.
+impl<'a> FromArg<'a> for WideString {
+    unsafe fn from_arg(arg: &'a MArgument) -> WideString {
+        let owned = {
+            let cstr: &'a CStr = c_str_from_arg(arg);
+            WideString::from_generalized_utf8(cstr.to_bytes())
+        };
+
+        // Now that we own our own copy of the string, disown the Kernel's copy.
+        rtl::UTF8String_disown(*arg.utf8string);
+
+        owned
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
+
+// Note: `&CStr` and `&str` cannot be used as parameter types. The lifetime `'a` is
+//       chosen by the caller of `FromArg::from_arg()`, so a function with a
+//       `&'static str` parameter could keep the string after the Kernel's copy was
+//       disowned. Use `KernelString`, which disowns the string when it is dropped, to
+//       borrow a string argument without copying it.
 
 /// # Safety
 ///
@@ -216,7 +404,8 @@ impl<'a> FromArg<'a> for String {
 /// # Warning
 ///
 /// Using `&CStr` as the parameter type of a *LibraryLink* function will result in a
-/// memory leak. Use [`String`] or [`CString`] instead.
+/// memory leak. Use [`KernelString`][crate::KernelString], [`String`] or [`CString`]
+/// instead.
 impl<'a> FromArg<'a> for &'a CStr {
     unsafe fn from_arg(arg: &'a MArgument) -> &'a CStr {
         c_str_from_arg(arg)
@@ -225,7 +414,10 @@ impl<'a> FromArg<'a> for &'a CStr {
     fn parameter_type() -> Expr {
         // This type implements `FromArg` purely for usage in DataStoreNode::value()
         // (via `FromArg for &str`).
-        panic!("&CStr cannot be used as a LibraryLink function parameter type")
+        panic!(
+            "&CStr cannot be used as a LibraryLink function parameter type; use \
+            KernelString instead"
+        )
     }
 }
 
@@ -240,7 +432,8 @@ impl<'a> FromArg<'a> for &'a CStr {
 /// # Warning
 ///
 /// Using `&str` as the parameter type of a *LibraryLink* function will result in a
-/// memory leak. Use [`String`] or [`CString`] instead.
+/// memory leak. Use [`KernelString`][crate::KernelString], [`String`] or [`CString`]
+/// instead.
 impl<'a> FromArg<'a> for &'a str {
     unsafe fn from_arg(arg: &'a MArgument) -> &'a str {
         let cstr: &'a CStr = FromArg::<'a>::from_arg(arg);
@@ -250,7 +443,10 @@ impl<'a> FromArg<'a> for &'a str {
 
     fn parameter_type() -> Expr {
         // This type implements `FromArg` purely for usage in DataStoreNode::value().
-        panic!("&str cannot be used as a LibraryLink function parameter type")
+        panic!(
+            "&str cannot be used as a LibraryLink function parameter type; use \
+            KernelString instead"
+        )
     }
 }
 
@@ -284,6 +480,8 @@ impl<'a> FromArg<'a> for &'a str {
 /// which is not valid because `bool` is not a valid numeric array type.
 impl<'a, T: crate::NumericArrayType> FromArg<'a> for &'a NumericArray<T> {
     unsafe fn from_arg(arg: &'a MArgument) -> &'a NumericArray<T> {
+        released::acquire(*arg.numeric);
+
         NumericArray::ref_cast(&*arg.numeric)
     }
 
@@ -312,7 +510,13 @@ impl<'a, T: crate::NumericArrayType> FromArg<'a> for &'a NumericArray<T> {
 
 impl<'a, T: crate::NumericArrayType> FromArg<'a> for NumericArray<T> {
     unsafe fn from_arg(arg: &'a MArgument) -> NumericArray<T> {
-        NumericArray::from_raw(*arg.numeric)
+        let array = NumericArray::from_raw(*arg.numeric);
+
+        // Detect a "Shared" array being passed back into this library while it is
+        // being mutated.
+        crate::shared_borrows::assert_not_mut_borrowed(array.data_ptr(), "receive");
+
+        array
     }
 
     fn parameter_type() -> Expr {
@@ -329,6 +533,8 @@ impl<'a, T: crate::NumericArrayType> FromArg<'a> for NumericArray<T> {
 
 impl<'a> FromArg<'a> for &'a NumericArray<()> {
     unsafe fn from_arg(arg: &'a MArgument) -> &'a NumericArray<()> {
+        released::acquire(*arg.numeric);
+
         NumericArray::ref_cast(&*arg.numeric)
     }
 
@@ -343,7 +549,12 @@ impl<'a> FromArg<'a> for &'a NumericArray<()> {
 
 impl<'a> FromArg<'a> for NumericArray<()> {
     unsafe fn from_arg(arg: &'a MArgument) -> NumericArray<()> {
-        NumericArray::from_raw(*arg.numeric)
+        let array = NumericArray::from_raw(*arg.numeric);
+
+        // See `impl FromArg for NumericArray<T>`.
+        crate::shared_borrows::assert_not_mut_borrowed(array.data_ptr(), "receive");
+
+        array
     }
 
     fn parameter_type() -> Expr {
@@ -355,6 +566,66 @@ impl<'a> FromArg<'a> for NumericArray<()> {
     }
 }
 
+//--------------------------------------
+// BoolArray
+//--------------------------------------
+
+impl<'a> FromArg<'a> for &'a BoolArray {
+    unsafe fn from_arg(arg: &'a MArgument) -> &'a BoolArray {
+        BoolArray::ref_cast(<&NumericArray<u8>>::from_arg(arg))
+    }
+
+    fn parameter_type() -> Expr {
+        <&NumericArray<u8>>::parameter_type()
+    }
+}
+
+impl<'a> FromArg<'a> for BoolArray {
+    unsafe fn from_arg(arg: &'a MArgument) -> BoolArray {
+        BoolArray::from(NumericArray::<u8>::from_arg(arg))
+    }
+
+    fn parameter_type() -> Expr {
+        NumericArray::<u8>::parameter_type()
+    }
+}
+
+//--------------------------------------
+// SparseArray
+//--------------------------------------
+
+impl<'a> FromArg<'a> for &'a SparseArray {
+    unsafe fn from_arg(arg: &'a MArgument) -> &'a SparseArray {
+        SparseArray::ref_cast(&*arg.sparse)
+    }
+
+    fn parameter_type() -> Expr {
+        // {LibraryDataType[SparseArray], "Constant"}
+        Expr::normal(Symbol::new("System`List"), vec![
+            Expr::normal(Symbol::new("System`LibraryDataType"), vec![Expr::from(
+                Symbol::new("System`SparseArray"),
+            )]),
+            Expr::string("Constant"),
+        ])
+    }
+}
+
+impl<'a> FromArg<'a> for SparseArray {
+    unsafe fn from_arg(arg: &'a MArgument) -> SparseArray {
+        SparseArray::from_raw(*arg.sparse)
+    }
+
+    fn parameter_type() -> Expr {
+        // {LibraryDataType[SparseArray], "Shared"}
+        Expr::normal(Symbol::new("System`List"), vec![
+            Expr::normal(Symbol::new("System`LibraryDataType"), vec![Expr::from(
+                Symbol::new("System`SparseArray"),
+            )]),
+            Expr::string("Shared"),
+        ])
+    }
+}
+
 //--------------------------------------
 // Image
 //--------------------------------------
@@ -449,7 +720,11 @@ impl FromArg<'_> for DataStore {
 
 impl<'a> FromArg<'a> for &'a DataStore {
     unsafe fn from_arg(arg: &MArgument) -> &'a DataStore {
-        DataStore::ref_cast(&*(arg.tensor as *mut sys::DataStore))
+        let raw = arg.tensor as *mut sys::DataStore;
+
+        released::acquire(*raw);
+
+        DataStore::ref_cast(&*raw)
     }
 
     fn parameter_type() -> Expr {
@@ -646,13 +921,46 @@ impl IntoArg for String {
     }
 }
 
+impl IntoArg for &str {
+    /// # Panics
+    ///
+    /// This function will panic if `self` cannot be converted into a [`CString`].
+    unsafe fn into_arg(self, arg: MArgument) {
+        <String as IntoArg>::into_arg(self.to_owned(), arg)
+    }
+
+    fn return_type() -> Expr {
+        <String as IntoArg>::return_type()
+    }
+}
+
+impl IntoArg for WideString {
+    /// # Panics
+    ///
+    /// This function will panic if `self` contains a NUL character.
+    unsafe fn into_arg(self, arg: MArgument) {
+        let cstring = CString::new(self.to_generalized_utf8())
+            .expect("IntoArg for WideString: could not convert WideString to CString");
+
+        <CString as IntoArg>::into_arg(cstring, arg)
+    }
+
+    fn return_type() -> Expr {
+        Expr::from(Symbol::new("System`String"))
+    }
+}
+
 //---------------------------------------
-// NumericArray, Image, DataStore
+// NumericArray, Image, SparseArray, DataStore
 //---------------------------------------
 
 impl<T: crate::NumericArrayType> IntoArg for NumericArray<T> {
     unsafe fn into_arg(self, arg: MArgument) {
-        *arg.numeric = self.into_raw();
+        let raw = self.into_raw();
+
+        released::release(raw, "NumericArray", Release::ReturnedToKernel);
+
+        *arg.numeric = raw;
     }
 
     fn return_type() -> Expr {
@@ -666,7 +974,11 @@ impl<T: crate::NumericArrayType> IntoArg for NumericArray<T> {
 
 impl IntoArg for NumericArray<()> {
     unsafe fn into_arg(self, arg: MArgument) {
-        *arg.numeric = self.into_raw();
+        let raw = self.into_raw();
+
+        released::release(raw, "NumericArray", Release::ReturnedToKernel);
+
+        *arg.numeric = raw;
     }
 
     fn return_type() -> Expr {
@@ -695,9 +1007,47 @@ impl<T: crate::ImageData> IntoArg for Image<T> {
     }
 }
 
+impl IntoArg for Image<()> {
+    unsafe fn into_arg(self, arg: MArgument) {
+        *arg.image = self.into_raw();
+    }
+
+    fn return_type() -> Expr {
+        // Image
+        Expr::from(Symbol::new("System`Image"))
+    }
+}
+
+impl IntoArg for BoolArray {
+    unsafe fn into_arg(self, arg: MArgument) {
+        self.into_numeric_array().into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        NumericArray::<u8>::return_type()
+    }
+}
+
+impl IntoArg for SparseArray {
+    unsafe fn into_arg(self, arg: MArgument) {
+        *arg.sparse = self.into_raw();
+    }
+
+    fn return_type() -> Expr {
+        // LibraryDataType[SparseArray]
+        Expr::normal(Symbol::new("System`LibraryDataType"), vec![Expr::from(
+            Symbol::new("System`SparseArray"),
+        )])
+    }
+}
+
 impl IntoArg for DataStore {
     unsafe fn into_arg(self, arg: MArgument) {
-        *arg.tensor = self.into_raw() as *mut _;
+        let raw = self.into_raw();
+
+        released::release(raw, "DataStore", Release::ReturnedToKernel);
+
+        *arg.tensor = raw as *mut _;
     }
 
     fn return_type() -> Expr {
@@ -705,6 +1055,94 @@ impl IntoArg for DataStore {
     }
 }
 
+impl IntoArg for Assoc {
+    unsafe fn into_arg(self, arg: MArgument) {
+        self.into_data_store().into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        DataStore::return_type()
+    }
+
+    fn return_wrapper() -> Option<Expr> {
+        Some(apply_to_data_store("System`Association"))
+    }
+}
+
+//--------------------------------------
+// Tuples
+//--------------------------------------
+
+/// Implement [`IntoArg`] for tuples whose elements can be stored in a [`DataStore`].
+///
+/// Tuples are returned as a `DataStore` with one unnamed node per element, which the
+/// loader function generated by [`generate_loader!`][crate::generate_loader] converts
+/// into a [`List`][ref/List]<sub>WL</sub>.
+///
+/// [ref/List]: https://reference.wolfram.com/language/ref/List.html
+macro_rules! impl_IntoArg_for_tuple {
+    ($($type:ident),*) => {
+        impl<$($type),*> IntoArg for ($($type,)*)
+        where
+            $($type: Into<DataStoreValue>),*
+        {
+            unsafe fn into_arg(self, arg: MArgument) {
+                #[allow(non_snake_case)]
+                let ($($type,)*) = self;
+
+                let mut store = DataStore::new();
+
+                $(
+                    store.add_value($type.into());
+                )*
+
+                store.into_arg(arg)
+            }
+
+            fn return_type() -> Expr {
+                DataStore::return_type()
+            }
+
+            fn return_wrapper() -> Option<Expr> {
+                Some(apply_to_data_store("System`List"))
+            }
+        }
+    };
+}
+
+impl_IntoArg_for_tuple!(A1);
+impl_IntoArg_for_tuple!(A1, A2);
+impl_IntoArg_for_tuple!(A1, A2, A3);
+impl_IntoArg_for_tuple!(A1, A2, A3, A4);
+impl_IntoArg_for_tuple!(A1, A2, A3, A4, A5);
+impl_IntoArg_for_tuple!(A1, A2, A3, A4, A5, A6);
+impl_IntoArg_for_tuple!(A1, A2, A3, A4, A5, A6, A7);
+impl_IntoArg_for_tuple!(A1, A2, A3, A4, A5, A6, A7, A8);
+
+/// `Replace[store_Developer`DataStore :> head @@ store]`
+///
+/// Only a `DataStore` result is replaced, so that `LibraryFunctionError[..]` results are
+/// returned unchanged.
+fn apply_to_data_store(head: &str) -> Expr {
+    let store = Expr::from(Symbol::new("RustLink`Private`store"));
+
+    Expr::normal(Symbol::new("System`Replace"), vec![Expr::normal(
+        Symbol::new("System`RuleDelayed"),
+        vec![
+            Expr::normal(Symbol::new("System`Pattern"), vec![
+                store.clone(),
+                Expr::normal(Symbol::new("System`Blank"), vec![Expr::from(Symbol::new(
+                    "Developer`DataStore",
+                ))]),
+            ]),
+            Expr::normal(Symbol::new("System`Apply"), vec![
+                Expr::from(Symbol::new(head)),
+                store,
+            ]),
+        ],
+    )])
+}
+
 //======================================
 // impl NativeFunction
 //======================================
@@ -752,16 +1190,52 @@ impl<'a: 'b, 'b> NativeFunction<'a> for fn(&'b [MArgument], MArgument) {
 // impl NativeFunction
 //--------------------
 
+/// Adapter that implements [`NativeFunction`] for any function whose parameters
+/// implement [`FromArg`] and whose return type implements [`IntoArg`].
+///
+/// `Params` is the `fn(..) -> R` pointer type with the same signature as `F`. Unlike the
+/// `fn(..) -> R` pointer types themselves, which a function item must first be cast to,
+/// `NativeFunctionItem` can wrap a function item directly: `Params` is inferred from the
+/// [`Fn`] implementation of `F`. This is what allows [`export!`][crate::export] to
+/// export a function without being told its number of parameters.
+#[doc(hidden)]
+pub struct NativeFunctionItem<F, Params> {
+    func: F,
+    params: PhantomData<Params>,
+}
+
+impl<F, Params> NativeFunctionItem<F, Params> {
+    #[allow(missing_docs)]
+    pub fn new(func: F) -> Self {
+        NativeFunctionItem {
+            func,
+            params: PhantomData,
+        }
+    }
+}
+
 macro_rules! impl_NativeFunction {
     ($($type:ident),*) => {
-        impl<'a, $($type,)* R> NativeFunction<'a> for fn($($type),*) -> R
+        impl<'a, F, $($type,)* R> NativeFunction<'a>
+            for NativeFunctionItem<F, fn($($type),*) -> R>
         where
+            F: Fn($($type),*) -> R,
             R: IntoArg,
             $($type: FromArg<'a>),*
         {
             unsafe fn call(&self, args: &'a [MArgument], ret: MArgument) {
+                if let Err(message) = self.try_call(args, ret) {
+                    panic!("{}", message)
+                }
+            }
+
+            unsafe fn try_call(
+                &self,
+                args: &'a [MArgument],
+                ret: MArgument,
+            ) -> Result<(), String> {
                 // Re-use the $type name as the local variable names. E.g.
-                //     let A1 = A1::from_arg(..);
+                //     let A1 = A1::checked_from_arg(..)?;
                 // This works because types and variable names are different namespaces.
                 #[allow(non_snake_case)]
                 let [$($type,)*] = match args {
@@ -775,12 +1249,14 @@ macro_rules! impl_NativeFunction {
 
                 $(
                     #[allow(non_snake_case)]
-                    let $type: $type = $type::from_arg($type);
+                    let $type: $type = $type::checked_from_arg($type)?;
                 )*
 
-                let result: R = self($($type,)*);
+                let result: R = (self.func)($($type,)*);
 
                 result.into_arg(ret);
+
+                Ok(())
             }
 
             fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
@@ -792,13 +1268,52 @@ macro_rules! impl_NativeFunction {
 
                 Ok((param_tys, R::return_type()))
             }
+
+            fn parameter_count(&self) -> Option<usize> {
+                Some([$(stringify!($type)),*].len())
+            }
+
+            fn return_wrapper(&self) -> Option<Expr> {
+                R::return_wrapper()
+            }
+        }
+
+        impl<'a, $($type,)* R> NativeFunction<'a> for fn($($type),*) -> R
+        where
+            R: IntoArg,
+            $($type: FromArg<'a>),*
+        {
+            unsafe fn call(&self, args: &'a [MArgument], ret: MArgument) {
+                NativeFunctionItem::<Self, Self>::new(*self).call(args, ret)
+            }
+
+            unsafe fn try_call(
+                &self,
+                args: &'a [MArgument],
+                ret: MArgument,
+            ) -> Result<(), String> {
+                NativeFunctionItem::<Self, Self>::new(*self).try_call(args, ret)
+            }
+
+            fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
+                NativeFunctionItem::<Self, Self>::new(*self).signature()
+            }
+
+            fn parameter_count(&self) -> Option<usize> {
+                NativeFunctionItem::<Self, Self>::new(*self).parameter_count()
+            }
+
+            fn return_wrapper(&self) -> Option<Expr> {
+                R::return_wrapper()
+            }
         }
     }
 }
 
 // Handle the zero-arguments case specially.
-impl<'a, R> NativeFunction<'a> for fn() -> R
+impl<'a, F, R> NativeFunction<'a> for NativeFunctionItem<F, fn() -> R>
 where
+    F: Fn() -> R,
     R: IntoArg,
 {
     unsafe fn call(&self, args: &[MArgument], ret: MArgument) {
@@ -810,7 +1325,7 @@ where
             );
         }
 
-        let result = self();
+        let result = (self.func)();
 
         result.into_arg(ret);
     }
@@ -818,6 +1333,35 @@ where
     fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
         Ok((Vec::new(), R::return_type()))
     }
+
+    fn parameter_count(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    fn return_wrapper(&self) -> Option<Expr> {
+        R::return_wrapper()
+    }
+}
+
+impl<'a, R> NativeFunction<'a> for fn() -> R
+where
+    R: IntoArg,
+{
+    unsafe fn call(&self, args: &[MArgument], ret: MArgument) {
+        NativeFunctionItem::<Self, Self>::new(*self).call(args, ret)
+    }
+
+    fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
+        NativeFunctionItem::<Self, Self>::new(*self).signature()
+    }
+
+    fn parameter_count(&self) -> Option<usize> {
+        Some(0)
+    }
+
+    fn return_wrapper(&self) -> Option<Expr> {
+        R::return_wrapper()
+    }
 }
 
 impl_NativeFunction!(A1);
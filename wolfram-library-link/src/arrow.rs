@@ -0,0 +1,227 @@
+//! Conversions between [Apache Arrow](https://arrow.apache.org) arrays and
+//! [`NumericArray`]s.
+//!
+//! *This module is only available if the `"arrow"` feature of `wolfram-library-link`
+//! is enabled.*
+//!
+//! Arrow [`PrimitiveArray`]s of integer and floating-point types can be converted to and
+//! from rank 1 [`NumericArray`]s, and Arrow [`RecordBatch`]es can be converted to and
+//! from [`DataStore`]s containing one named [`NumericArray`] node for each column.
+//!
+//! `NumericArray`s are allocated by the Wolfram Runtime Library, so each conversion
+//! copies the array data once. Arrow arrays that contain null values cannot be
+//! converted.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use arrow_array::{cast::AsArray, types::Float64Type, Float64Array, RecordBatch};
+//! use wolfram_library_link::{self as wll, arrow, DataStore};
+//!
+//! wll::export![normalize_columns(_)];
+//!
+//! /// Normalize each `"Real64"` column of a table to have a maximum value of 1.
+//! fn normalize_columns(table: DataStore) -> DataStore {
+//!     let batch: RecordBatch = arrow::record_batch_from_data_store(&table).unwrap();
+//!
+//!     let columns = batch
+//!         .columns()
+//!         .iter()
+//!         .map(|column| match column.as_primitive_opt::<Float64Type>() {
+//!             Some(column) => {
+//!                 let max = column.values().iter().cloned().fold(f64::MIN, f64::max);
+//!                 std::sync::Arc::new(column.unary::<_, Float64Type>(|x| x / max)) as _
+//!             },
+//!             None => column.clone(),
+//!         })
+//!         .collect();
+//!
+//!     let batch = RecordBatch::try_new(batch.schema(), columns).unwrap();
+//!
+//!     arrow::data_store_from_record_batch(&batch).unwrap()
+//! }
+//! # }
+//! ```
+
+use std::sync::Arc;
+
+use arrow_array::{
+    cast::AsArray,
+    types::{
+        ArrowPrimitiveType, Float32Type, Float64Type, Int16Type, Int32Type, Int64Type,
+        Int8Type, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+    },
+    Array, ArrayRef, PrimitiveArray, RecordBatch,
+};
+use arrow_schema::{DataType, Field, Schema};
+
+use crate::{
+    DataStore, DataStoreNodeValue, NumericArray, NumericArrayKind, NumericArrayType,
+};
+
+/// Convert an Arrow [`PrimitiveArray`] into a rank 1 [`NumericArray`].
+///
+/// Returns an error if `array` contains null values.
+pub fn numeric_array_from_arrow<T>(
+    array: &PrimitiveArray<T>,
+) -> Result<NumericArray<T::Native>, String>
+where
+    T: ArrowPrimitiveType,
+    T::Native: NumericArrayType,
+{
+    if array.null_count() != 0 {
+        return Err(format!(
+            "Arrow array of type {} contains {} null values",
+            array.data_type(),
+            array.null_count()
+        ));
+    }
+
+    Ok(NumericArray::from_slice(array.values()))
+}
+
+/// Convert a [`NumericArray`] into an Arrow [`PrimitiveArray`].
+///
+/// Arrays of rank greater than 1 are flattened.
+pub fn arrow_from_numeric_array<T>(array: &NumericArray<T::Native>) -> PrimitiveArray<T>
+where
+    T: ArrowPrimitiveType,
+    T::Native: NumericArrayType,
+{
+    PrimitiveArray::new(array.as_slice().to_vec().into(), None)
+}
+
+/// Convert an Arrow [`RecordBatch`] into a [`DataStore`] containing one named
+/// [`NumericArray`] node for each column.
+///
+/// The node names are the names of the columns in the record batch schema.
+///
+/// Returns an error if a column does not have a primitive integer or floating-point
+/// data type, or if a column contains null values.
+pub fn data_store_from_record_batch(batch: &RecordBatch) -> Result<DataStore, String> {
+    let mut store = DataStore::new();
+
+    for (field, column) in batch.schema().fields().iter().zip(batch.columns()) {
+        let array = numeric_array_from_column(column)
+            .map_err(|err| format!("column {:?}: {}", field.name(), err))?;
+
+        store.add_named_numeric_array(field.name(), array);
+    }
+
+    Ok(store)
+}
+
+/// Convert a [`DataStore`] into an Arrow [`RecordBatch`].
+///
+/// Every node in `store` must be a named, rank 1 [`NumericArray`], and all of the arrays
+/// must have the same length. The node names are used as the column names.
+///
+/// Complex-valued `NumericArray`s are not supported.
+pub fn record_batch_from_data_store(store: &DataStore) -> Result<RecordBatch, String> {
+    let mut fields = Vec::new();
+    let mut columns = Vec::new();
+
+    for (index, node) in store.nodes().enumerate() {
+        let name = node
+            .name()
+            .ok_or_else(|| format!("DataStore node {} has no name", index + 1))?;
+
+        let array = match node.value() {
+            DataStoreNodeValue::NumericArray(array) => array,
+            _ => return Err(format!("DataStore node {:?} is not a NumericArray", name)),
+        };
+
+        if array.rank() != 1 {
+            return Err(format!(
+                "DataStore node {:?} has rank {}, expected rank 1",
+                name,
+                array.rank()
+            ));
+        }
+
+        let column = column_from_numeric_array(array)
+            .map_err(|err| format!("DataStore node {:?}: {}", name, err))?;
+
+        fields.push(Field::new(name, column.data_type().clone(), false));
+        columns.push(column);
+    }
+
+    RecordBatch::try_new(Arc::new(Schema::new(fields)), columns)
+        .map_err(|err| err.to_string())
+}
+
+//======================================
+// Helpers
+//======================================
+
+fn numeric_array_from_column(column: &ArrayRef) -> Result<NumericArray, String> {
+    macro_rules! convert {
+        ($($data_type:ident => $arrow_type:ty),*) => {
+            match column.data_type() {
+                $(
+                    DataType::$data_type => {
+                        numeric_array_from_arrow(column.as_primitive::<$arrow_type>())?
+                            .into_generic()
+                    },
+                )*
+                other => return Err(format!("unsupported Arrow data type: {}", other)),
+            }
+        };
+    }
+
+    let array = convert! {
+        Int8 => Int8Type,
+        Int16 => Int16Type,
+        Int32 => Int32Type,
+        Int64 => Int64Type,
+        UInt8 => UInt8Type,
+        UInt16 => UInt16Type,
+        UInt32 => UInt32Type,
+        UInt64 => UInt64Type,
+        Float32 => Float32Type,
+        Float64 => Float64Type
+    };
+
+    Ok(array)
+}
+
+fn column_from_numeric_array(array: &NumericArray) -> Result<ArrayRef, String> {
+    let column: ArrayRef = match array.kind() {
+        NumericArrayKind::Bit8(array) => {
+            Arc::new(arrow_from_numeric_array::<Int8Type>(array))
+        },
+        NumericArrayKind::Bit16(array) => {
+            Arc::new(arrow_from_numeric_array::<Int16Type>(array))
+        },
+        NumericArrayKind::Bit32(array) => {
+            Arc::new(arrow_from_numeric_array::<Int32Type>(array))
+        },
+        NumericArrayKind::Bit64(array) => {
+            Arc::new(arrow_from_numeric_array::<Int64Type>(array))
+        },
+        NumericArrayKind::UBit8(array) => {
+            Arc::new(arrow_from_numeric_array::<UInt8Type>(array))
+        },
+        NumericArrayKind::UBit16(array) => {
+            Arc::new(arrow_from_numeric_array::<UInt16Type>(array))
+        },
+        NumericArrayKind::UBit32(array) => {
+            Arc::new(arrow_from_numeric_array::<UInt32Type>(array))
+        },
+        NumericArrayKind::UBit64(array) => {
+            Arc::new(arrow_from_numeric_array::<UInt64Type>(array))
+        },
+        NumericArrayKind::Real32(array) => {
+            Arc::new(arrow_from_numeric_array::<Float32Type>(array))
+        },
+        NumericArrayKind::Real64(array) => {
+            Arc::new(arrow_from_numeric_array::<Float64Type>(array))
+        },
+        NumericArrayKind::ComplexReal64(_) => {
+            return Err("complex NumericArray cannot be converted to Arrow".into())
+        },
+    };
+
+    Ok(column)
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+
+use crate::{DataStore, DataStoreValue};
+
+/// Key-value result that is returned to the Wolfram Language as an
+/// [`Association`][ref/Association]<sub>WL</sub>.
+///
+/// *LibraryLink* functions cannot return an `Association` directly. An `Assoc` is
+/// returned as a [`DataStore`] in which every node is named, and the loader function
+/// generated by [`generate_loader!`][crate::generate_loader] converts the returned
+/// ``Developer`DataStore[...]`` into an `Association` (see
+/// [`IntoArg::return_wrapper()`][crate::IntoArg::return_wrapper]).
+///
+/// If the same key is inserted more than once, the last value is used, matching the
+/// behavior of `Association`.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, Assoc};
+///
+/// wll::export![word_stats(_)];
+///
+/// fn word_stats(text: String) -> Assoc {
+///     let mut stats = Assoc::new();
+///
+///     stats.insert("Words", text.split_whitespace().count() as i64);
+///     stats.insert("Characters", text.chars().count() as i64);
+///     stats.insert("Text", text);
+///
+///     stats
+/// }
+/// # }
+/// ```
+///
+/// When loaded using the loader function, `word_stats` returns:
+///
+/// ```wolfram
+/// <| "Words" -> 2, "Characters" -> 11, "Text" -> "hello world" |>
+/// ```
+///
+/// A function loaded using [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub>
+/// returns the underlying `DataStore`. Use `Association @@ result` to convert it.
+///
+/// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+/// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+#[derive(Clone)]
+pub struct Assoc(DataStore);
+
+impl Assoc {
+    /// Create an empty `Assoc`.
+    pub fn new() -> Assoc {
+        Assoc(DataStore::new())
+    }
+
+    /// Insert a value with the key `key`.
+    pub fn insert<V: Into<DataStoreValue>>(&mut self, key: &str, value: V) {
+        let Assoc(store) = self;
+
+        store.add_named_value(key, value.into());
+    }
+
+    /// Returns the number of values inserted into this `Assoc`, including values whose
+    /// key was inserted more than once.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no values have been inserted into this `Assoc`.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Get the underlying [`DataStore`], in which every node is named.
+    pub fn as_data_store(&self) -> &DataStore {
+        &self.0
+    }
+
+    /// Convert this `Assoc` into the underlying [`DataStore`], in which every node is
+    /// named.
+    pub fn into_data_store(self) -> DataStore {
+        self.0
+    }
+}
+
+impl Default for Assoc {
+    fn default() -> Assoc {
+        Assoc::new()
+    }
+}
+
+impl<K: AsRef<str>, V: Into<DataStoreValue>> FromIterator<(K, V)> for Assoc {
+    fn from_iter<I>(iter: I) -> Assoc
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut assoc = Assoc::new();
+        assoc.extend(iter);
+        assoc
+    }
+}
+
+impl<K: AsRef<str>, V: Into<DataStoreValue>> Extend<(K, V)> for Assoc {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            self.insert(key.as_ref(), value);
+        }
+    }
+}
+
+impl fmt::Debug for Assoc {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Assoc(store) = self;
+
+        f.debug_tuple("Assoc").field(store).finish()
+    }
+}
@@ -6,13 +6,23 @@
 //! laid out by [this StackOverflow answer](https://mathematica.stackexchange.com/a/138433).
 
 use std::{
+    collections::BTreeMap,
     ffi::{c_void, CString},
     panic,
+    sync::Mutex,
 };
 
+use once_cell::sync::Lazy;
 use static_assertions::assert_not_impl_any;
 
-use crate::{rtl, sys, DataStore};
+use crate::{
+    expr::{Expr, Symbol},
+    rtl, sys, DataStore, DataStoreValue,
+};
+
+/// The fields of the most recent [`Event`] raised with each name.
+static EVENT_SHAPES: Lazy<Mutex<BTreeMap<String, Vec<EventField>>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
 
 
 /// Handle to a Wolfram Language [`AsynchronousTaskObject`][ref/AsynchronousTaskObject]<sub>WL</sub>
@@ -28,6 +38,93 @@ pub struct AsyncTaskObject(sys::mint);
 // TODO: Determine if it would be safe for this type to implement Copy/Clone.
 assert_not_impl_any!(AsyncTaskObject: Copy, Clone);
 
+/// Named asynchronous event whose data is a [`DataStore`] of named fields.
+///
+/// Construct an event using [`Event::named()`], add fields to it using
+/// [`field()`][Event::field], and raise it using [`raise()`][Event::raise]:
+///
+/// ```no_run
+/// use wolfram_library_link::{AsyncTaskObject, Event};
+///
+/// let task: AsyncTaskObject = todo!();
+///
+/// Event::named("progress")
+///     .field("done", 10)
+///     .field("total", 100)
+///     .raise(&task);
+/// ```
+///
+/// The Wolfram Language event handler is called with the fields in a
+/// ``Developer`DataStore``:
+///
+/// ```wolfram
+/// handler[task_, "progress", Developer`DataStore["done" -> done_, "total" -> total_]] :=
+///     Print[done, "/", total]
+/// ```
+///
+/// # Event shapes
+///
+/// The names and types of the fields of the most recent event raised with each name
+/// are recorded, and can be retrieved using [`event_shapes()`], to document the events
+/// that a library raises.
+pub struct Event {
+    name: String,
+    data: DataStore,
+    fields: Vec<EventField>,
+}
+
+/// The names and field types of an [`Event`].
+///
+/// Use [`event_shapes()`] to get the shapes of the events raised by this library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventShape {
+    /// The name of the event.
+    pub name: String,
+    /// The fields of the event, in the order they were added.
+    pub fields: Vec<EventField>,
+}
+
+/// A field of an [`Event`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventField {
+    /// The name of the field.
+    pub name: String,
+    /// The *LibraryLink* name of the type of the field value, like `"Integer"`.
+    pub type_name: &'static str,
+}
+
+/// Get the shapes of the events raised by this library using [`Event::raise()`], sorted
+/// by event name.
+///
+/// If events with the same name were raised with different fields, the shape of the
+/// most recent event is returned.
+///
+/// This information is also available from the Wolfram Language by loading the
+/// `wll_event_shapes` WSTP function that is exported automatically by every library
+/// that uses `wolfram-library-link`:
+///
+/// ```wolfram
+/// LibraryFunctionLoad["library", "wll_event_shapes", LinkObject, LinkObject][]
+/// ```
+///
+/// which returns an association from each event name to an association of its field
+/// names and types:
+///
+/// ```wolfram
+/// <|"progress" -> <|"done" -> "Integer", "total" -> "Integer"|>|>
+/// ```
+pub fn event_shapes() -> Vec<EventShape> {
+    EVENT_SHAPES
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|(name, fields)| EventShape {
+            name: name.clone(),
+            fields: fields.clone(),
+        })
+        .collect()
+}
+
 
 //======================================
 // Impls
@@ -64,6 +161,11 @@ impl AsyncTaskObject {
         spawn_async_task_with_thread(f)
     }
 
+    /// Construct a handle to the async task with the numeric ID `id`.
+    pub(crate) fn from_id(id: sys::mint) -> Self {
+        AsyncTaskObject(id)
+    }
+
     /// Returns the numeric ID which identifies this async object.
     pub fn id(&self) -> sys::mint {
         let AsyncTaskObject(id) = *self;
@@ -119,6 +221,80 @@ impl AsyncTaskObject {
     }
 }
 
+impl Event {
+    /// Construct a new event called `name`, with no fields.
+    pub fn named(name: &str) -> Self {
+        Event {
+            name: name.to_owned(),
+            data: DataStore::new(),
+            fields: Vec::new(),
+        }
+    }
+
+    /// Add a field called `name` to this event.
+    pub fn field<V: Into<DataStoreValue>>(mut self, name: &str, value: V) -> Self {
+        let value: DataStoreValue = value.into();
+
+        self.fields.push(EventField {
+            name: name.to_owned(),
+            type_name: value.type_name(),
+        });
+        self.data.add_named_value(name, value);
+
+        self
+    }
+
+    /// The name of this event.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The shape of this event.
+    pub fn shape(&self) -> EventShape {
+        EventShape {
+            name: self.name.clone(),
+            fields: self.fields.clone(),
+        }
+    }
+
+    /// Get the [`DataStore`] containing the fields of this event.
+    pub fn into_data_store(self) -> DataStore {
+        self.data
+    }
+
+    /// Raise this event on `task`, and record its [shape][EventShape].
+    ///
+    /// See [`AsyncTaskObject::raise_async_event()`].
+    pub fn raise(self, task: &AsyncTaskObject) {
+        let Event { name, data, fields } = self;
+
+        EVENT_SHAPES.lock().unwrap().insert(name.clone(), fields);
+
+        task.raise_async_event(&name, data);
+    }
+}
+
+impl EventShape {
+    /// Construct an [`Association`][ref/Association]<sub>WL</sub> from the name of each
+    /// field of this event to its type.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub(crate) fn to_expr(&self) -> Expr {
+        let fields = self
+            .fields
+            .iter()
+            .map(|field| {
+                Expr::normal(Symbol::new("System`Rule"), vec![
+                    Expr::string(&field.name),
+                    Expr::string(field.type_name),
+                ])
+            })
+            .collect();
+
+        Expr::normal(Symbol::new("System`Association"), fields)
+    }
+}
+
 fn spawn_async_task_with_thread<F>(task: F) -> AsyncTaskObject
 where
     // Note: Ensure that the bound on async_task_thread_trampoline() is kept up-to-date
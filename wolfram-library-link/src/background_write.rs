@@ -0,0 +1,112 @@
+use std::{panic, thread};
+
+use crate::wstp::Link;
+
+/// Write the result of a WSTP function to `link` on a background thread, while running
+/// `teardown` on the current thread.
+///
+/// Serializing a large result can take a significant amount of time. This function
+/// lets a function exported using [`export_wstp!`][crate::export_wstp] overlap that
+/// work with any cleanup it needs to do before returning, like dropping large
+/// intermediate data structures or releasing other resources.
+///
+/// `write` is called on a new thread with exclusive access to `link`. `teardown` is
+/// called on the current thread at the same time, and always runs to completion.
+///
+/// The [`Link`] passed to a WSTP function is only valid until the function returns, so
+/// this function waits for `write` to finish before returning. The link is never used
+/// by more than one thread at a time.
+///
+/// Returns the value returned by `teardown`, or the error returned by `write`.
+///
+/// # Threads
+///
+/// `teardown` runs on the calling thread, so it can use any *LibraryLink* API that the
+/// caller can, including [`aborted()`][crate::aborted] and the
+/// [`managed`][crate::managed] and [`callback`][crate::callback] modules.
+///
+/// `write` runs on a different thread, so it must not call any *LibraryLink* function
+/// that is only safe to call from the main Kernel thread. Building an
+/// [`Expr`][crate::expr::Expr] and writing it to the link is always safe.
+///
+/// # Panics
+///
+/// If `write` or `teardown` panics, the panic is propagated to the caller after both
+/// have finished.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{
+///     self as wll,
+///     expr::{Expr, Symbol},
+///     wstp::Link,
+/// };
+///
+/// wll::export_wstp![squares(&mut Link)];
+///
+/// fn squares(link: &mut Link) {
+///     assert_eq!(link.test_head("System`List").unwrap(), 1);
+///
+///     let n = link.get_i64().unwrap();
+///
+///     // Some large intermediate data that is expensive to drop.
+///     let cache: Vec<Vec<i64>> = (0..n).map(|i| vec![i; 100]).collect();
+///
+///     let elements = cache.iter().map(|v| Expr::from(v[0] * v[0])).collect();
+///     let list = Expr::normal(Symbol::new("System`List"), elements);
+///
+///     wll::write_in_background(
+///         link,
+///         move |link| link.put_expr(&list).map_err(|err| err.to_string()),
+///         move || drop(cache),
+///     )
+///     .unwrap();
+/// }
+/// # }
+/// ```
+pub fn write_in_background<W, T, R>(
+    link: &mut Link,
+    write: W,
+    teardown: T,
+) -> Result<R, String>
+where
+    W: FnOnce(&mut Link) -> Result<(), String> + Send,
+    T: FnOnce() -> R,
+{
+    let link = SendLink(link);
+
+    let (written, result) = thread::scope(|scope| {
+        let writer = scope.spawn(move || write(link.into_inner()));
+
+        let result = teardown();
+
+        (writer.join(), result)
+    });
+
+    match written {
+        Ok(Ok(())) => Ok(result),
+        Ok(Err(err)) => Err(err),
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+/// Exclusive reference to a [`Link`] that can be moved to the background writer thread.
+struct SendLink<'l>(&'l mut Link);
+
+// Safety: WSTP links can be used from any thread, as long as they are not used by more
+//         than one thread at the same time. `write_in_background()` moves the only
+//         reference to the link to the writer thread, and the writer thread is joined
+//         before the link is accessible to the caller again.
+unsafe impl Send for SendLink<'_> {}
+
+impl<'l> SendLink<'l> {
+    // Note: Take `self` by value so that closures capture the whole `SendLink`, and not
+    //       just the (non-`Send`) reference it contains.
+    fn into_inner(self) -> &'l mut Link {
+        let SendLink(link) = self;
+
+        link
+    }
+}
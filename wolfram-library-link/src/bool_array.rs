@@ -0,0 +1,161 @@
+use std::fmt;
+use std::mem::MaybeUninit;
+
+use crate::{NumericArray, UninitNumericArray};
+
+/// Boolean mask stored as an `"UnsignedInteger8"` [`NumericArray`].
+///
+/// The Wolfram Language has no boolean [`NumericArray`][ref/NumericArray]<sub>WL</sub>
+/// element type, so boolean masks are passed as `"UnsignedInteger8"` numeric arrays
+/// whose elements are `0` (`false`) or `1` (`true`). When reading a `BoolArray`, any
+/// non-zero element is treated as `true`.
+///
+/// `BoolArray` can be used as an argument or return type of functions exported using
+/// [`export!`][crate::export], with the same parameter types as [`NumericArray<u8>`].
+///
+/// ```wolfram
+/// mask = NumericArray[Boole[{True, False, True}], "UnsignedInteger8"]
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, BoolArray, NumericArray};
+///
+/// wll::export![masked_sum(_, _)];
+///
+/// fn masked_sum(values: &NumericArray<f64>, mask: &BoolArray) -> f64 {
+///     values
+///         .as_slice()
+///         .iter()
+///         .zip(mask.iter())
+///         .filter(|(_, selected)| *selected)
+///         .map(|(value, _)| value)
+///         .sum()
+/// }
+/// # }
+/// ```
+///
+/// [ref/NumericArray]: https://reference.wolfram.com/language/ref/NumericArray.html
+#[repr(transparent)]
+#[derive(ref_cast::RefCast)]
+pub struct BoolArray(NumericArray<u8>);
+
+impl BoolArray {
+    /// Construct a new one-dimensional `BoolArray` from a slice of booleans.
+    pub fn from_bools(data: &[bool]) -> BoolArray {
+        BoolArray::from_array(&[data.len()], data)
+    }
+
+    /// Construct a new multidimensional `BoolArray` from a list of dimensions and the
+    /// flat slice of data.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `data.len()` is not equal to the product of
+    /// `dimensions`, or if [`UninitNumericArray::try_from_dimensions()`] returns an
+    /// error.
+    pub fn from_array(dimensions: &[usize], data: &[bool]) -> BoolArray {
+        let mut uninit = UninitNumericArray::<u8>::from_dimensions(dimensions);
+
+        let elements: &mut [MaybeUninit<u8>] = uninit.as_slice_mut();
+
+        assert_eq!(
+            elements.len(),
+            data.len(),
+            "BoolArray::from_array: data length does not match dimensions"
+        );
+
+        for (elem, bool) in elements.iter_mut().zip(data) {
+            elem.write(u8::from(*bool));
+        }
+
+        BoolArray(unsafe { uninit.assume_init() })
+    }
+
+    /// Returns the element at `index` in the flattened array, or `None` if `index` is
+    /// out of bounds.
+    pub fn get(&self, index: usize) -> Option<bool> {
+        self.0.as_slice().get(index).map(|byte| *byte != 0)
+    }
+
+    /// Iterate over the elements of this array in flattened order.
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        self.0.as_slice().iter().map(|byte| *byte != 0)
+    }
+
+    /// Returns the number of `true` elements in this array.
+    pub fn count_true(&self) -> usize {
+        self.iter().filter(|bool| *bool).count()
+    }
+
+    /// Copy the elements of this array into a [`Vec`], in flattened order.
+    pub fn to_vec(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+
+    /// Returns the number of elements in this array.
+    pub fn flattened_length(&self) -> usize {
+        self.0.flattened_length()
+    }
+
+    /// Returns the dimensions of this array.
+    pub fn dimensions(&self) -> &[usize] {
+        self.0.dimensions()
+    }
+
+    /// Access the underlying `"UnsignedInteger8"` numeric array.
+    pub fn as_numeric_array(&self) -> &NumericArray<u8> {
+        &self.0
+    }
+
+    /// Convert this `BoolArray` into the underlying `"UnsignedInteger8"` numeric array.
+    pub fn into_numeric_array(self) -> NumericArray<u8> {
+        self.0
+    }
+}
+
+//======================================
+// Trait Impls
+//======================================
+
+impl From<NumericArray<u8>> for BoolArray {
+    /// Any non-zero element of `array` is treated as `true`.
+    fn from(array: NumericArray<u8>) -> BoolArray {
+        BoolArray(array)
+    }
+}
+
+impl From<&[bool]> for BoolArray {
+    fn from(data: &[bool]) -> BoolArray {
+        BoolArray::from_bools(data)
+    }
+}
+
+impl From<Vec<bool>> for BoolArray {
+    fn from(data: Vec<bool>) -> BoolArray {
+        BoolArray::from_bools(&data)
+    }
+}
+
+impl From<&BoolArray> for Vec<bool> {
+    fn from(array: &BoolArray) -> Vec<bool> {
+        array.to_vec()
+    }
+}
+
+impl Clone for BoolArray {
+    fn clone(&self) -> BoolArray {
+        BoolArray(self.0.clone())
+    }
+}
+
+impl fmt::Debug for BoolArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BoolArray")
+            .field("dimensions", &self.dimensions())
+            .field("data", &self.to_vec())
+            .finish()
+    }
+}
@@ -0,0 +1,414 @@
+//! Assemble a Wolfram Language paclet from compiled libraries.
+//!
+//! This module is intended for use from a `build.rs` script, an `xtask`, or any other
+//! Rust program that runs after the library has been compiled. A [`Paclet`] describes
+//! the paclet metadata and the libraries it contains, and [`Paclet::build()`] writes
+//! the paclet layout to a directory:
+//!
+//! ```text
+//! MyPaclet/
+//! ├── PacletInfo.wl
+//! ├── Kernel/
+//! │   └── MyPaclet.wl
+//! └── LibraryResources/
+//!     └── MacOSX-ARM64/
+//!         └── libmy_paclet.dylib
+//! ```
+//!
+//! `Kernel/MyPaclet.wl` is only generated if [`Paclet::loader`] is set to the name of a
+//! loader function generated by [`generate_loader!`][crate::generate_loader]. It calls
+//! the loader function of each library when the paclet is loaded, and stores the
+//! combined Association of loaded functions in `` MyPaclet`$Functions ``:
+//!
+//! ```wolfram
+//! Needs["MyPaclet`"]
+//!
+//! MyPaclet`$Functions["add2"][1, 2]
+//! ```
+//!
+//! The built paclet directory can be loaded using
+//! [`PacletDirectoryLoad`][ref/PacletDirectoryLoad]<sub>WL</sub>, or packed into an
+//! installable `.paclet` archive using
+//! [`CreatePacletArchive`][ref/CreatePacletArchive]<sub>WL</sub>.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use wolfram_library_link::build::Paclet;
+//!
+//! let mut paclet = Paclet::new("MyPaclet", "1.0.0");
+//! paclet.libraries.push("target/release/libmy_paclet.dylib".into());
+//! paclet.loader = Some("load_my_paclet_functions".to_owned());
+//!
+//! let dir = paclet.build("target/paclet").expect("failed to build paclet");
+//! ```
+//!
+//! Use [`system_id_from_target()`] and [`library_file_name()`] to stage libraries
+//! cross-compiled for other platforms:
+//!
+//! ```no_run
+//! use wolfram_library_link::build::{library_file_name, system_id_from_target, Paclet};
+//!
+//! for target in ["x86_64-apple-darwin", "aarch64-apple-darwin"] {
+//!     let system_id = system_id_from_target(target).unwrap();
+//!     let file_name = library_file_name("my-paclet", system_id).unwrap();
+//!
+//!     let mut paclet = Paclet::new("MyPaclet", "1.0.0");
+//!     paclet.libraries.push(format!("target/{}/release/{}", target, file_name).into());
+//!     paclet.loader = Some("load_my_paclet_functions".to_owned());
+//!     paclet.system_id = Some(system_id.to_owned());
+//!
+//!     paclet.build("target/paclet").expect("failed to build paclet");
+//! }
+//! ```
+//!
+//! [ref/PacletDirectoryLoad]: https://reference.wolfram.com/language/ref/PacletDirectoryLoad.html
+//! [ref/CreatePacletArchive]: https://reference.wolfram.com/language/ref/CreatePacletArchive.html
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{expr::Expr, wl_source::to_source};
+
+/// Description of a paclet containing one or more compiled libraries.
+///
+/// See the [module documentation][self] for an example.
+#[derive(Debug, Clone)]
+pub struct Paclet {
+    /// The paclet name, for example `"MyPaclet"` or `"Publisher/MyPaclet"`.
+    pub name: String,
+    /// The paclet version, for example `"1.0.0"`.
+    pub version: String,
+    /// The minimum Wolfram Language version required by the paclet, for example
+    /// `"13.0+"`.
+    pub wolfram_version: Option<String>,
+    /// Paths to the compiled dynamic libraries to include in the paclet.
+    pub libraries: Vec<PathBuf>,
+    /// The name of the loader function, generated by
+    /// [`generate_loader!`][crate::generate_loader], that is exported by each of
+    /// [`Paclet::libraries`].
+    ///
+    /// If this is `None`, no Wolfram Language package file is generated.
+    pub loader: Option<String>,
+    /// The [`$SystemID`][ref/$SystemID]<sub>WL</sub> of the platform that
+    /// [`Paclet::libraries`] were compiled for.
+    ///
+    /// If this is `None`, the value returned by [`system_id()`] is used.
+    ///
+    /// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+    pub system_id: Option<String>,
+}
+
+impl Paclet {
+    /// Construct a new paclet description with no libraries.
+    pub fn new(name: &str, version: &str) -> Paclet {
+        Paclet {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            wolfram_version: None,
+            libraries: Vec::new(),
+            loader: None,
+            system_id: None,
+        }
+    }
+
+    /// The Wolfram Language context of the package file generated for this paclet.
+    ///
+    /// This is the paclet name, with any `/` replaced by `` ` ``, followed by a
+    /// `` ` ``.
+    pub fn context(&self) -> String {
+        format!("{}`", self.name.replace('/', "`"))
+    }
+
+    /// Write the paclet layout into a directory inside `dir`, named after the paclet,
+    /// and return the path to the paclet directory.
+    ///
+    /// Any libraries previously written to the `LibraryResources` subdirectory for the
+    /// same [`$SystemID`][ref/$SystemID]<sub>WL</sub> are removed first. Libraries for
+    /// other platforms are kept, so a paclet containing libraries for several platforms
+    /// can be assembled by calling this function once per platform.
+    ///
+    /// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+    pub fn build<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, String> {
+        if self.name.is_empty() || self.name.contains(['`', '"']) {
+            return Err(format!("invalid paclet name: {:?}", self.name));
+        }
+
+        let paclet_dir = dir.as_ref().join(self.name.replace('/', "_"));
+
+        let system_id = match self.system_id {
+            Some(ref system_id) => system_id.clone(),
+            None => system_id()?.to_owned(),
+        };
+
+        let resources_dir = paclet_dir.join("LibraryResources").join(system_id);
+
+        if resources_dir.exists() {
+            fs::remove_dir_all(&resources_dir).map_err(|err| {
+                format!("failed to remove {}: {}", resources_dir.display(), err)
+            })?;
+        }
+
+        create_dir(&resources_dir)?;
+
+        for library in &self.libraries {
+            let file_name = library
+                .file_name()
+                .ok_or_else(|| format!("invalid library path: {}", library.display()))?;
+
+            fs::copy(library, resources_dir.join(file_name)).map_err(|err| {
+                format!("failed to copy library {}: {}", library.display(), err)
+            })?;
+        }
+
+        if let Some(ref loader) = self.loader {
+            let kernel_dir = paclet_dir.join("Kernel");
+            create_dir(&kernel_dir)?;
+
+            let file_name = format!("{}.wl", self.package_name());
+
+            write_file(&kernel_dir.join(file_name), &self.package_file(loader)?)?;
+        }
+
+        write_file(&paclet_dir.join("PacletInfo.wl"), &self.paclet_info())?;
+
+        Ok(paclet_dir)
+    }
+
+    /// The last component of the paclet name.
+    fn package_name(&self) -> &str {
+        self.name.rsplit('/').next().unwrap_or(&self.name)
+    }
+
+    /// Generate the contents of `PacletInfo.wl`.
+    fn paclet_info(&self) -> String {
+        let string = |value: &str| to_source(&Expr::string(value));
+
+        let mut fields = vec![
+            format!("\"Name\" -> {}", string(&self.name)),
+            format!("\"Version\" -> {}", string(&self.version)),
+        ];
+
+        if let Some(ref version) = self.wolfram_version {
+            fields.push(format!("\"WolframVersion\" -> {}", string(version)));
+        }
+
+        let mut extensions = vec![];
+
+        if self.loader.is_some() {
+            extensions.push(format!(
+                "{{\"Kernel\", \"Root\" -> \"Kernel\", \"Context\" -> {{{}}}}}",
+                string(&self.context())
+            ));
+        }
+
+        extensions.push("{\"LibraryLink\"}".to_owned());
+
+        fields.push(format!(
+            "\"Extensions\" -> {{\n\t\t{}\n\t}}",
+            extensions.join(",\n\t\t")
+        ));
+
+        format!(
+            "(* Generated by wolfram-library-link. *)\n\nPacletObject[<|\n\t{}\n|>]\n",
+            fields.join(",\n\t")
+        )
+    }
+
+    /// Generate the contents of the package file, which loads the functions exported by
+    /// each library using `loader`.
+    fn package_file(&self, loader: &str) -> Result<String, String> {
+        let string = |value: &str| to_source(&Expr::string(value));
+
+        let libraries = self
+            .libraries
+            .iter()
+            .map(|library| {
+                library
+                    .file_stem()
+                    .and_then(|stem| stem.to_str())
+                    .map(string)
+                    .ok_or_else(|| format!("invalid library path: {}", library.display()))
+            })
+            .collect::<Result<Vec<String>, String>>()?;
+
+        let context = self.context();
+
+        Ok(format!(
+            r#"(* Generated by wolfram-library-link. *)
+
+BeginPackage[{context}]
+
+$Functions::usage = "$Functions is an Association containing the functions exported by the libraries in the {name} paclet."
+
+Begin["`Private`"]
+
+$Functions = Join @@ Map[
+	library |-> LibraryFunctionLoad[library, {loader}, LinkObject, LinkObject][library],
+	{{{libraries}}}
+]
+
+End[]
+
+EndPackage[]
+"#,
+            context = string(&context),
+            name = self.name,
+            loader = string(loader),
+            libraries = libraries.join(", "),
+        ))
+    }
+}
+
+//======================================
+// Platforms
+//======================================
+
+/// Get the Wolfram [`$SystemID`][ref/$SystemID]<sub>WL</sub> of the platform that
+/// libraries are being built for.
+///
+/// When called from a build script, this is the platform of the Cargo compilation
+/// target, as given by the `TARGET` environment variable. Otherwise, it is the platform
+/// the calling program was compiled for.
+///
+/// Returns an error if the platform is not supported by the Wolfram Language.
+///
+/// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+pub fn system_id() -> Result<&'static str, String> {
+    if let Ok(target) = std::env::var("TARGET") {
+        return system_id_from_target(&target)
+            .ok_or_else(|| format!("unsupported target: {}", target));
+    }
+
+    use std::env::consts::{ARCH, OS};
+
+    let system_id = match (OS, ARCH) {
+        ("macos", "x86_64") => "MacOSX-x86-64",
+        ("macos", "aarch64") => "MacOSX-ARM64",
+        ("linux", "x86_64") => "Linux-x86-64",
+        ("linux", "aarch64") => "Linux-ARM64",
+        ("linux", "arm") => "Linux-ARM",
+        ("linux", "x86") => "Linux",
+        ("windows", "x86_64") => "Windows-x86-64",
+        ("windows", "x86") => "Windows",
+        _ => return Err(format!("unsupported platform: {}-{}", ARCH, OS)),
+    };
+
+    Ok(system_id)
+}
+
+/// Get the Wolfram [`$SystemID`][ref/$SystemID]<sub>WL</sub> corresponding to a Rust
+/// target triple, like `"aarch64-apple-darwin"`.
+///
+/// Returns `None` if the target platform is not supported by the Wolfram Language.
+///
+/// ```
+/// use wolfram_library_link::build::system_id_from_target;
+///
+/// assert_eq!(system_id_from_target("aarch64-apple-darwin"), Some("MacOSX-ARM64"));
+/// assert_eq!(system_id_from_target("x86_64-pc-windows-msvc"), Some("Windows-x86-64"));
+/// assert_eq!(system_id_from_target("wasm32-unknown-unknown"), None);
+/// ```
+///
+/// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+pub fn system_id_from_target(target: &str) -> Option<&'static str> {
+    let arch = target.split('-').next()?;
+
+    let system_id = if target.contains("-apple-darwin") {
+        match arch {
+            "x86_64" => "MacOSX-x86-64",
+            "aarch64" => "MacOSX-ARM64",
+            _ => return None,
+        }
+    } else if target.contains("-linux-") {
+        match arch {
+            "x86_64" => "Linux-x86-64",
+            "aarch64" => "Linux-ARM64",
+            "arm" | "armv7" => "Linux-ARM",
+            "i686" => "Linux",
+            _ => return None,
+        }
+    } else if target.contains("-windows-") {
+        match arch {
+            "x86_64" => "Windows-x86-64",
+            "i686" => "Windows",
+            _ => return None,
+        }
+    } else {
+        return None;
+    };
+
+    Some(system_id)
+}
+
+/// Get the file name extension used for dynamic libraries on the platform with the
+/// Wolfram [`$SystemID`][ref/$SystemID]<sub>WL</sub> `system_id`.
+///
+/// Returns `None` if `system_id` is not recognized.
+///
+/// ```
+/// use wolfram_library_link::build::library_extension;
+///
+/// assert_eq!(library_extension("MacOSX-ARM64"), Some("dylib"));
+/// assert_eq!(library_extension("Linux-x86-64"), Some("so"));
+/// assert_eq!(library_extension("Windows-x86-64"), Some("dll"));
+/// ```
+///
+/// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+pub fn library_extension(system_id: &str) -> Option<&'static str> {
+    match system_id {
+        "MacOSX-x86-64" | "MacOSX-ARM64" => Some("dylib"),
+        "Linux" | "Linux-x86-64" | "Linux-ARM64" | "Linux-ARM" => Some("so"),
+        "Windows" | "Windows-x86-64" => Some("dll"),
+        _ => None,
+    }
+}
+
+/// Get the file name Cargo uses for the `cdylib` built from the crate `crate_name` on
+/// the platform with the Wolfram [`$SystemID`][ref/$SystemID]<sub>WL</sub>
+/// `system_id`.
+///
+/// Any `-` characters in `crate_name` are replaced with `_`, and the `lib` prefix is
+/// added on platforms other than Windows.
+///
+/// ```
+/// use wolfram_library_link::build::library_file_name;
+///
+/// assert_eq!(
+///     library_file_name("my-paclet", "MacOSX-ARM64").as_deref(),
+///     Some("libmy_paclet.dylib")
+/// );
+/// assert_eq!(
+///     library_file_name("my-paclet", "Windows-x86-64").as_deref(),
+///     Some("my_paclet.dll")
+/// );
+/// ```
+///
+/// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+pub fn library_file_name(crate_name: &str, system_id: &str) -> Option<String> {
+    let extension = library_extension(system_id)?;
+
+    let prefix = match extension {
+        "dll" => "",
+        _ => "lib",
+    };
+
+    Some(format!(
+        "{}{}.{}",
+        prefix,
+        crate_name.replace('-', "_"),
+        extension
+    ))
+}
+
+fn create_dir(dir: &Path) -> Result<(), String> {
+    fs::create_dir_all(dir)
+        .map_err(|err| format!("failed to create directory {}: {}", dir.display(), err))
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents)
+        .map_err(|err| format!("failed to write {}: {}", path.display(), err))
+}
@@ -0,0 +1,568 @@
+//! Calling Wolfram Language functions from Rust using library callback functions.
+//!
+//! A [`CompiledFunction`][ref/CompiledFunction]<sub>WL</sub> can be passed to a loaded
+//! library using
+//! [`ConnectLibraryCallbackFunction`][ref/ConnectLibraryCallbackFunction]<sub>WL</sub>.
+//! The library is notified of each connected function by the callback manager
+//! registered using [`register_library_callback_manager()`], which receives a
+//! [`WolframCallback`] handle that can be used to call the function from Rust.
+//!
+//! # Example
+//!
+//! Store a user-supplied scoring function, and use it later to score values:
+//!
+//! ```no_run
+//! # mod scope {
+//! use std::sync::Mutex;
+//!
+//! use once_cell::sync::Lazy;
+//! use wolfram_library_link::{self as wll, callback::WolframCallback};
+//!
+//! static SCORER: Lazy<Mutex<Option<WolframCallback>>> = Lazy::new(|| Mutex::new(None));
+//!
+//! #[wll::init]
+//! fn init() {
+//!     wll::callback::register_library_callback_manager("scorer", |callback| {
+//!         if callback.argument_count() != 1 {
+//!             return false;
+//!         }
+//!
+//!         *SCORER.lock().unwrap() = Some(callback);
+//!         true
+//!     });
+//! }
+//!
+//! wll::export![best_score(_)];
+//!
+//! fn best_score(count: i64) -> f64 {
+//!     let scorer = SCORER.lock().unwrap();
+//!     let scorer = scorer.as_ref().expect("no scoring function has been connected");
+//!
+//!     (0..count)
+//!         .map(|i| scorer.args().arg(i).call::<f64>().unwrap())
+//!         .fold(f64::NEG_INFINITY, f64::max)
+//! }
+//! # }
+//! ```
+//!
+//! ```wolfram
+//! ConnectLibraryCallbackFunction["scorer", Compile[{{x, _Integer}}, Sin[x]]]
+//! ```
+//!
+//! # Related links
+//!
+//! * [Library Callback Functions] section of the LibraryLink documentation.
+//!
+//! [Library Callback Functions]: https://reference.wolfram.com/language/LibraryLink/tutorial/InteractionWithWolframLanguage.html#1474570163
+//! [ref/CompiledFunction]: https://reference.wolfram.com/language/ref/CompiledFunction.html
+//! [ref/ConnectLibraryCallbackFunction]: https://reference.wolfram.com/language/ref/ConnectLibraryCallbackFunction.html
+
+use std::{
+    ffi::CString,
+    fmt,
+    os::raw::c_char,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    rtl,
+    sys::{self, mcomplex, mint, mreal},
+    FromArg,
+};
+
+/// Handle to a Wolfram Language function connected to this library using
+/// [`ConnectLibraryCallbackFunction`][ref/ConnectLibraryCallbackFunction]<sub>WL</sub>.
+///
+/// The connected function is released when the `WolframCallback` is dropped, if the
+/// callback manager accepted it by returning `true`.
+///
+/// [ref/ConnectLibraryCallbackFunction]: https://reference.wolfram.com/language/ref/ConnectLibraryCallbackFunction.html
+pub struct WolframCallback {
+    id: sys::mint,
+    /// The type of each argument of the connected function.
+    argument_types: Vec<ArgumentType>,
+    /// Set after the callback manager returns `true`. A function that was not accepted
+    /// is not connected, and must not be released.
+    accepted: Arc<AtomicBool>,
+}
+
+/// Arguments to a [`WolframCallback`] call.
+///
+/// Use [`WolframCallback::args()`] to construct a `CallbackArgs` instance.
+pub struct CallbackArgs<'c> {
+    callback: &'c WolframCallback,
+    /// The argument values. Strings are owned here until the call returns.
+    values: Vec<Value>,
+}
+
+/// Types that can be passed as an argument to a [`WolframCallback`], using
+/// [`CallbackArgs::arg()`].
+///
+/// Unlike [`IntoArg`][crate::IntoArg], which is used to return a value from an
+/// exported function to the Kernel, passing a value to a callback does not give
+/// ownership of any data to the Kernel. Data pointed to by an argument, like the
+/// characters of a string, is owned by the [`CallbackArgs`] until the call returns.
+pub trait CallbackArg {
+    #[doc(hidden)]
+    fn into_callback_value(self) -> CallbackValue;
+}
+
+/// Opaque argument value constructed by [`CallbackArg`].
+#[doc(hidden)]
+pub struct CallbackValue(Value);
+
+enum Value {
+    Boolean(bool),
+    Integer(mint),
+    Real(mreal),
+    Complex(mcomplex),
+    String(CString),
+}
+
+/// Type and rank of an argument of a connected function, as reported by the Kernel.
+#[derive(Debug, Copy, Clone)]
+struct ArgumentType {
+    type_: sys::mint,
+    rank: sys::mint,
+}
+
+/// Register a new callback manager for handling connected library callback functions.
+///
+/// `connect` is called each time
+/// [`ConnectLibraryCallbackFunction`][ref/ConnectLibraryCallbackFunction]<sub>WL</sub>`[name, func]`
+/// is evaluated. `connect` should return `true` if the function was accepted, in which
+/// case the [`WolframCallback`] should typically be stored for later use.
+///
+/// # Panics
+///
+/// This function will panic if a callback manager named `name` has already been
+/// registered, or if the maximum number of callback managers have already been
+/// registered by this library.
+///
+/// [ref/ConnectLibraryCallbackFunction]: https://reference.wolfram.com/language/ref/ConnectLibraryCallbackFunction.html
+pub fn register_library_callback_manager(
+    name: &str,
+    connect: fn(WolframCallback) -> bool,
+) {
+    register_using_next_slot(name, connect)
+}
+
+//======================================
+// Impls
+//======================================
+
+impl WolframCallback {
+    /// The number of arguments expected by the connected function.
+    pub fn argument_count(&self) -> usize {
+        self.argument_types.len()
+    }
+
+    /// Construct the argument list for a call to this function.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::callback::WolframCallback;
+    /// # fn f(callback: &WolframCallback) {
+    /// let result: f64 = callback.args().arg(1.5).arg(2i64).call().unwrap();
+    /// # }
+    /// ```
+    pub fn args(&self) -> CallbackArgs<'_> {
+        CallbackArgs {
+            callback: self,
+            values: Vec::with_capacity(self.argument_types.len()),
+        }
+    }
+
+    /// Get the id of the connected function.
+    pub fn id(&self) -> sys::mint {
+        self.id
+    }
+}
+
+impl<'c> CallbackArgs<'c> {
+    /// Append an argument value.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `value` is a string that contains a nul byte.
+    pub fn arg<T: CallbackArg>(mut self, value: T) -> Self {
+        let CallbackValue(value) = value.into_callback_value();
+
+        self.values.push(value);
+
+        self
+    }
+
+    /// Call the connected function with these arguments, and convert the returned
+    /// value to `R`.
+    ///
+    /// If the call failed, the error code returned by the Kernel is returned. If the type
+    /// of an argument value does not match the type of the corresponding argument of the
+    /// connected function, the function is not called, and
+    /// [`LIBRARY_TYPE_ERROR`][sys::LIBRARY_TYPE_ERROR] is returned.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the number of arguments does not match
+    /// [`WolframCallback::argument_count()`], or if called from a thread other than the
+    /// main Kernel thread.
+    ///
+    /// *LibraryLink C Function:* [`callLibraryCallbackFunction`][rtl::callLibraryCallbackFunction]
+    pub fn call<R>(self) -> Result<R, sys::errcode_t>
+    where
+        R: for<'a> FromArg<'a>,
+    {
+        crate::assert_main_thread();
+
+        let CallbackArgs { callback, values } = self;
+
+        assert_eq!(
+            values.len(),
+            callback.argument_types.len(),
+            "WolframCallback: wrong number of arguments"
+        );
+
+        let types_match =
+            values
+                .iter()
+                .zip(&callback.argument_types)
+                .all(|(value, expected)| {
+                    expected.rank == 0 && expected.type_ == value.type_()
+                });
+
+        if !types_match {
+            return Err(sys::LIBRARY_TYPE_ERROR as sys::errcode_t);
+        }
+
+        // Storage for the values pointed to by each MArgument. Each element is large
+        // enough to store any scalar or pointer argument type (`mcomplex` is the
+        // largest).
+        let mut storage: Vec<[sys::mint; 2]> = vec![[0; 2]; values.len()];
+
+        for (value, slot) in values.iter().zip(&mut storage) {
+            let ptr = slot.as_mut_ptr();
+
+            // Safety: `slot` is large enough and sufficiently aligned to store each of
+            //         these types. String data is owned by `values`, which outlives
+            //         the call below.
+            unsafe {
+                match value {
+                    Value::Boolean(value) => {
+                        let boole = if *value { sys::True } else { sys::False };
+                        *(ptr as *mut sys::mbool) = boole as sys::mbool;
+                    },
+                    Value::Integer(value) => *ptr = *value,
+                    Value::Real(value) => *(ptr as *mut mreal) = *value,
+                    Value::Complex(value) => *(ptr as *mut mcomplex) = *value,
+                    Value::String(value) => {
+                        *(ptr as *mut *mut c_char) = value.as_ptr() as *mut c_char
+                    },
+                }
+            }
+        }
+
+        let mut args: Vec<sys::MArgument> = storage
+            .iter_mut()
+            .map(|slot| sys::MArgument {
+                integer: slot.as_mut_ptr(),
+            })
+            .collect();
+
+        let mut result: [sys::mint; 2] = [0; 2];
+        let result_arg = sys::MArgument {
+            integer: result.as_mut_ptr(),
+        };
+
+        let argc =
+            sys::mint::try_from(args.len()).expect("argument count overflows mint");
+
+        let err_code = unsafe {
+            rtl::callLibraryCallbackFunction(
+                callback.id,
+                argc,
+                args.as_mut_ptr(),
+                result_arg,
+            )
+        };
+
+        // Keep the argument strings alive until the call has returned.
+        drop(values);
+
+        if err_code != 0 {
+            return Err(err_code);
+        }
+
+        Ok(unsafe { R::from_arg(&result_arg) })
+    }
+}
+
+impl CallbackArg for bool {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::Boolean(self))
+    }
+}
+
+impl CallbackArg for mint {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::Integer(self))
+    }
+}
+
+impl CallbackArg for i32 {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::Integer(mint::from(self)))
+    }
+}
+
+impl CallbackArg for mreal {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::Real(self))
+    }
+}
+
+impl CallbackArg for mcomplex {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::Complex(self))
+    }
+}
+
+impl CallbackArg for CString {
+    fn into_callback_value(self) -> CallbackValue {
+        CallbackValue(Value::String(self))
+    }
+}
+
+impl CallbackArg for &str {
+    fn into_callback_value(self) -> CallbackValue {
+        let string =
+            CString::new(self).expect("callback string argument contains nul byte");
+
+        CallbackValue(Value::String(string))
+    }
+}
+
+impl CallbackArg for String {
+    fn into_callback_value(self) -> CallbackValue {
+        self.as_str().into_callback_value()
+    }
+}
+
+impl Value {
+    /// The `MType_*` value of this argument.
+    fn type_(&self) -> sys::mint {
+        let type_ = match self {
+            Value::Boolean(_) => sys::MType_Boolean,
+            Value::Integer(_) => sys::MType_Integer,
+            Value::Real(_) => sys::MType_Real,
+            Value::Complex(_) => sys::MType_Complex,
+            Value::String(_) => sys::MType_UTF8String,
+        };
+
+        sys::mint::from(type_)
+    }
+}
+
+//======================================
+// Trait Impls
+//======================================
+
+impl Drop for WolframCallback {
+    fn drop(&mut self) {
+        if !self.accepted.load(Ordering::Acquire) {
+            return;
+        }
+
+        let _: std::os::raw::c_int =
+            unsafe { rtl::releaseLibraryCallbackFunction(self.id) };
+    }
+}
+
+impl fmt::Debug for WolframCallback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WolframCallback")
+            .field("id", &self.id)
+            .field("argument_types", &self.argument_types)
+            .finish()
+    }
+}
+
+//======================================
+// C wrapper functions
+//======================================
+
+/// `registerLibraryCallbackManager()` does not allow passing a custom argument to the
+/// registered callback function, so the same "slot" system used by
+/// [`register_library_expression_manager()`][crate::managed::register_library_expression_manager]
+/// is used here. See the implementation note in `managed.rs` for details.
+static SLOTS: Lazy<Mutex<[Option<fn(WolframCallback) -> bool>; 8]>> =
+    Lazy::new(|| Mutex::new([None; 8]));
+
+fn register_using_next_slot(name: &str, connect: fn(WolframCallback) -> bool) {
+    let name_cstr = CString::new(name).expect("failed to allocate C string");
+
+    let mut slots = SLOTS.lock().unwrap();
+
+    let available_slot: Option<(usize, &mut Option<_>)> = slots
+        .iter_mut()
+        .enumerate()
+        .find(|(_, slot)| slot.is_none());
+
+    let (index, result) = if let Some((index, slot)) = available_slot {
+        *slot = Some(connect);
+        (index, register_using_slot(name_cstr, index))
+    } else {
+        // Drop `slots` to avoid poisoning SLOTS when we panic.
+        drop(slots);
+        panic!("maxiumum number of library callback managers have been registered");
+    };
+
+    if result.is_err() {
+        // Free the slot, so that it can be used by a later registration.
+        slots[index] = None;
+    }
+
+    drop(slots);
+
+    if let Err(()) = result {
+        panic!(
+            "library callback manager with name '{}' has already been registered",
+            name
+        );
+    }
+}
+
+fn register_using_slot(name_cstr: CString, index: usize) -> Result<(), ()> {
+    let static_slot_fn: unsafe extern "C" fn(_, _, _) -> _ = match index {
+        0 => slot_0,
+        1 => slot_1,
+        2 => slot_2,
+        3 => slot_3,
+        4 => slot_4,
+        5 => slot_5,
+        6 => slot_6,
+        7 => slot_7,
+        _ => unreachable!(),
+    };
+
+    let err_code: i32 = unsafe {
+        rtl::registerLibraryCallbackManager(name_cstr.as_ptr(), Some(static_slot_fn))
+    };
+
+    if err_code != 0 {
+        Err(())
+    } else {
+        Ok(())
+    }
+}
+
+//--------------------------
+// Static slot_<X> functions
+//--------------------------
+
+fn call_callback_in_slot(slot: usize, id: sys::mint, arg_types: sys::MTensor) -> bool {
+    let slots = SLOTS.lock().unwrap();
+
+    let user_fn: fn(WolframCallback) -> bool = match slots[slot] {
+        Some(func) => func,
+        None => return false,
+    };
+
+    // Ensure we're not holding a lock on `slots`, to avoid poisoning SLOTS in the case
+    // `user_fn` panics.
+    drop(slots);
+
+    let argument_types = match unsafe { read_argument_types(arg_types) } {
+        Some(types) => types,
+        None => return false,
+    };
+
+    let accepted = Arc::new(AtomicBool::new(false));
+
+    let callback = WolframCallback {
+        id,
+        argument_types,
+        accepted: Arc::clone(&accepted),
+    };
+
+    // If `user_fn` returns `false`, the function is not connected, and the callback
+    // must not release it when dropped.
+    let is_accepted = user_fn(callback);
+
+    accepted.store(is_accepted, Ordering::Release);
+
+    is_accepted
+}
+
+/// Read the argument types of a connected function.
+///
+/// `arg_types` is an integer matrix with one row per argument of the connected function,
+/// followed by a row for its result. Each row starts with the type and rank of the
+/// argument.
+unsafe fn read_argument_types(arg_types: sys::MTensor) -> Option<Vec<ArgumentType>> {
+    if rtl::MTensor_getRank(arg_types) != 2
+        || rtl::MTensor_getType(arg_types) != sys::mint::from(sys::MType_Integer)
+    {
+        return None;
+    }
+
+    let dims = std::slice::from_raw_parts(rtl::MTensor_getDimensions(arg_types), 2);
+
+    let rows = usize::try_from(dims[0]).ok()?;
+    let columns = usize::try_from(dims[1]).ok()?;
+
+    if rows == 0 || columns < 2 {
+        return None;
+    }
+
+    let data = std::slice::from_raw_parts(
+        rtl::MTensor_getIntegerData(arg_types),
+        rows * columns,
+    );
+
+    // Skip the last row, which describes the result.
+    let types = data
+        .chunks_exact(columns)
+        .take(rows - 1)
+        .map(|row| ArgumentType {
+            type_: row[0],
+            rank: row[1],
+        })
+        .collect();
+
+    Some(types)
+}
+
+macro_rules! def_slot_fn {
+    ($name:ident, $index:literal) => {
+        unsafe extern "C" fn $name(
+            // Assume this library is already initialized.
+            _: sys::WolframLibraryData,
+            id: sys::mint,
+            arg_types: sys::MTensor,
+        ) -> sys::mbool {
+            let result = crate::catch_panic::call_and_catch_panic(|| {
+                call_callback_in_slot($index, id, arg_types)
+            });
+
+            match result {
+                Ok(true) => 1,
+                Ok(false) | Err(_) => 0,
+            }
+        }
+    };
+}
+
+def_slot_fn!(slot_0, 0);
+def_slot_fn!(slot_1, 1);
+def_slot_fn!(slot_2, 2);
+def_slot_fn!(slot_3, 3);
+def_slot_fn!(slot_4, 4);
+def_slot_fn!(slot_5, 5);
+def_slot_fn!(slot_6, 6);
+def_slot_fn!(slot_7, 7);
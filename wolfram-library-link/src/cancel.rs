@@ -0,0 +1,311 @@
+use std::{
+    collections::HashMap,
+    fmt, panic,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc, Arc, Condvar, Mutex, Weak,
+    },
+    thread,
+    time::Duration,
+};
+
+use once_cell::sync::Lazy;
+
+/// How often [`CancelToken::run_abortable()`] checks whether an abort has been
+/// requested.
+const ABORT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Tokens that can be cancelled by ID using [`cancel()`].
+static TOKENS: Lazy<Mutex<HashMap<u64, Weak<Inner>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Shared cancellation flag for library code.
+///
+/// A `CancelToken` is a single flag that is set when work should stop, regardless of
+/// the reason. It can be triggered by:
+///
+/// * a Wolfram Kernel abort, detected by [`CancelToken::poll_abort()`] or
+///   [`CancelToken::run_abortable()`],
+/// * a [`Watchdog`][crate::watchdog::Watchdog] that exceeded its time budget, using
+///   [`Watchdog::cancel_token()`][crate::watchdog::Watchdog::cancel_token],
+/// * the Wolfram Language, by calling the `wll_cancel` WSTP function that is exported
+///   automatically by every library that uses `wolfram-library-link`, with the
+///   [ID][CancelToken::id] of the token:
+///
+///   ```wolfram
+///   LibraryFunctionLoad["library", "wll_cancel", LinkObject, LinkObject][id]
+///   ```
+///
+///   which returns `True` if a token with that ID exists, or
+/// * library code, using [`CancelToken::cancel()`] or [`cancel()`].
+///
+/// `CancelToken` is cheap to clone, and clones share the same flag, so a token can be
+/// given to any number of worker threads. Workers can poll it using
+/// [`is_cancelled()`][CancelToken::is_cancelled] or [`check()`][CancelToken::check],
+/// or block until it is cancelled using [`wait()`][CancelToken::wait].
+///
+/// Note that the Wolfram Language can only call `wll_cancel` while the Kernel is not
+/// busy evaluating a library function, so it is useful for cancelling work done in the
+/// background, for example by an [`AsyncTaskObject`][crate::AsyncTaskObject].
+///
+/// # Example
+///
+/// Sum a large array on a worker thread, stopping early if the user aborts the
+/// evaluation:
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, CancelToken, NumericArray};
+///
+/// wll::export![checked_sum(_)];
+///
+/// fn checked_sum(array: &NumericArray<f64>) -> f64 {
+///     let token = CancelToken::new();
+///     let data = array.as_slice();
+///
+///     token.run_abortable(|token| {
+///         let mut sum = 0.0;
+///
+///         for chunk in data.chunks(4096) {
+///             token.check().expect("computation was cancelled");
+///             sum += chunk.iter().sum::<f64>();
+///         }
+///
+///         sum
+///     })
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CancelToken {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    id: u64,
+    cancelled: AtomicBool,
+    reason: Mutex<Option<CancelReason>>,
+    condvar: Condvar,
+}
+
+/// Why a [`CancelToken`] was cancelled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CancelReason {
+    /// The user aborted the Wolfram Kernel evaluation.
+    Aborted,
+    /// A [`Watchdog`][crate::watchdog::Watchdog] exceeded its time budget.
+    Watchdog,
+    /// The token was cancelled explicitly, from library code or by a call to
+    /// `wll_cancel`.
+    Requested,
+}
+
+/// Cancel the [`CancelToken`] with the specified [ID][CancelToken::id].
+///
+/// Returns `false` if there is no token with that ID, for example because every clone
+/// of the token has been dropped.
+///
+/// This is the function called by the automatically exported `wll_cancel` WSTP
+/// function.
+pub fn cancel(id: u64) -> bool {
+    let token = TOKENS.lock().unwrap().get(&id).and_then(Weak::upgrade);
+
+    match token {
+        Some(inner) => {
+            CancelToken { inner }.cancel_with(CancelReason::Requested);
+            true
+        },
+        None => false,
+    }
+}
+
+impl CancelToken {
+    /// Construct a new token that has not been cancelled.
+    pub fn new() -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+
+        let inner = Arc::new(Inner {
+            id,
+            cancelled: AtomicBool::new(false),
+            reason: Mutex::new(None),
+            condvar: Condvar::new(),
+        });
+
+        TOKENS.lock().unwrap().insert(id, Arc::downgrade(&inner));
+
+        CancelToken { inner }
+    }
+
+    /// Get the ID of this token, which can be passed to [`cancel()`] or `wll_cancel`.
+    ///
+    /// IDs are unique for the lifetime of the loaded library.
+    pub fn id(&self) -> u64 {
+        self.inner.id
+    }
+
+    /// Cancel this token, with [`CancelReason::Requested`].
+    pub fn cancel(&self) {
+        self.cancel_with(CancelReason::Requested)
+    }
+
+    /// Cancel this token, recording `reason`.
+    ///
+    /// If this token has already been cancelled, the original reason is kept.
+    pub fn cancel_with(&self, reason: CancelReason) {
+        let mut current = self.inner.reason.lock().unwrap();
+
+        if current.is_none() {
+            *current = Some(reason);
+            self.inner.cancelled.store(true, Ordering::Release);
+            self.inner.condvar.notify_all();
+        }
+    }
+
+    /// Returns `true` if this token has been cancelled.
+    ///
+    /// This is a cheap atomic load, and can be called frequently.
+    pub fn is_cancelled(&self) -> bool {
+        self.inner.cancelled.load(Ordering::Acquire)
+    }
+
+    /// Get the reason this token was cancelled, or `None` if it has not been cancelled.
+    pub fn reason(&self) -> Option<CancelReason> {
+        *self.inner.reason.lock().unwrap()
+    }
+
+    /// Returns an error if this token has been cancelled.
+    pub fn check(&self) -> Result<(), String> {
+        match self.reason() {
+            Some(reason) => Err(format!("operation was cancelled: {}", reason)),
+            None => Ok(()),
+        }
+    }
+
+    /// Block the current thread until this token is cancelled.
+    pub fn wait(&self) -> CancelReason {
+        let reason = self.inner.reason.lock().unwrap();
+
+        let reason = self
+            .inner
+            .condvar
+            .wait_while(reason, |reason| reason.is_none())
+            .unwrap();
+
+        reason.expect("cancel reason is set")
+    }
+
+    /// Block the current thread until this token is cancelled, or until `timeout` has
+    /// elapsed.
+    ///
+    /// Returns `None` if the timeout elapsed before this token was cancelled.
+    pub fn wait_timeout(&self, timeout: Duration) -> Option<CancelReason> {
+        let reason = self.inner.reason.lock().unwrap();
+
+        let (reason, _) = self
+            .inner
+            .condvar
+            .wait_timeout_while(reason, timeout, |reason| reason.is_none())
+            .unwrap();
+
+        *reason
+    }
+
+    /// Cancel this token, with [`CancelReason::Aborted`], if the user has requested that
+    /// the current evaluation be aborted.
+    ///
+    /// Returns `true` if this token has been cancelled, for any reason.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from a thread other than the main Kernel
+    /// thread.
+    pub fn poll_abort(&self) -> bool {
+        crate::assert_main_thread();
+
+        if !self.is_cancelled() && crate::aborted() {
+            self.cancel_with(CancelReason::Aborted);
+        }
+
+        self.is_cancelled()
+    }
+
+    /// Run `work` on a separate thread, while polling for Kernel aborts on the current
+    /// thread.
+    ///
+    /// If the user aborts the evaluation, this token is cancelled with
+    /// [`CancelReason::Aborted`], and `work` is expected to check the token and stop as
+    /// soon as possible. This function returns when `work` returns. If `work` panics,
+    /// the panic is propagated to the current thread.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from a thread other than the main Kernel
+    /// thread.
+    pub fn run_abortable<O, W>(&self, work: W) -> O
+    where
+        O: Send,
+        W: FnOnce(&CancelToken) -> O + Send,
+    {
+        crate::assert_main_thread();
+
+        let result = thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel::<()>();
+
+            let worker = scope.spawn(|| {
+                // Notify the current thread on completion, even if `work` panics.
+                let _sender = sender;
+
+                work(self)
+            });
+
+            // The channel is disconnected when the worker finishes.
+            while let Err(mpsc::RecvTimeoutError::Timeout) =
+                receiver.recv_timeout(ABORT_POLL_INTERVAL)
+            {
+                let _: bool = self.poll_abort();
+            }
+
+            worker.join()
+        });
+
+        match result {
+            Ok(value) => value,
+            Err(payload) => panic::resume_unwind(payload),
+        }
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        CancelToken::new()
+    }
+}
+
+impl fmt::Debug for CancelToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("CancelToken")
+            .field("id", &self.id())
+            .field("reason", &self.reason())
+            .finish()
+    }
+}
+
+impl Drop for Inner {
+    fn drop(&mut self) {
+        if let Ok(mut tokens) = TOKENS.lock() {
+            tokens.remove(&self.id);
+        }
+    }
+}
+
+impl fmt::Display for CancelReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CancelReason::Aborted => write!(f, "evaluation was aborted"),
+            CancelReason::Watchdog => write!(f, "time budget was exceeded"),
+            CancelReason::Requested => write!(f, "cancellation was requested"),
+        }
+    }
+}
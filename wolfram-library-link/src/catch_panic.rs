@@ -1,6 +1,7 @@
 //! Utilities for catching panics, capturing a backtrace, and extracting the panic
 //! message.
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::panic::{self, UnwindSafe};
 use std::process;
@@ -34,6 +35,10 @@ pub struct CaughtPanic {
 }
 
 impl CaughtPanic {
+    pub(crate) fn message(&self) -> &str {
+        self.message.as_deref().unwrap_or("Rust panic (no message)")
+    }
+
     pub(crate) fn to_pretty_expr(&self) -> Expr {
         let CaughtPanic {
             message,
@@ -222,6 +227,28 @@ where
     result
 }
 
+/// Call `func` and catch any unwinding panic which occurs during that call, without
+/// changing the panic hook.
+///
+/// Unlike [`call_and_catch_panic()`], which temporarily replaces the process-wide panic
+/// hook, this is safe to call concurrently from multiple threads. The panic message is
+/// read from the payload of the panic on the current thread, so the location and
+/// backtrace of the panic are not available.
+pub(crate) fn call_and_catch_panic_concurrent<T, F>(func: F) -> Result<T, CaughtPanic>
+where
+    F: FnOnce() -> T + UnwindSafe,
+{
+    panic::catch_unwind(func).map_err(|payload| {
+        let message: Option<String> = get_payload_message(&*payload);
+
+        CaughtPanic {
+            message,
+            location: None,
+            backtrace: None,
+        }
+    })
+}
+
 fn get_caught_panic() -> CaughtPanic {
     let id = thread::current().id();
     let mut map = acquire_lock();
@@ -302,24 +329,32 @@ fn custom_hook(info: &panic::PanicInfo) {
 }
 
 fn get_panic_message(info: &panic::PanicInfo) -> Option<String> {
+    if let Some(message) = get_payload_message(info.payload()) {
+        return Some(message);
+    }
+
+    #[cfg(feature = "nightly")]
+    if let Some(fmt_arguments) = info.message() {
+        return Some(format!("{}", fmt_arguments));
+    }
+
+    None
+}
+
+fn get_payload_message(payload: &(dyn Any + Send)) -> Option<String> {
     // Extract the message from `panic!("...")` statements.
     // In this case, the payload is always the static formatting string.
-    if let Some(string) = info.payload().downcast_ref::<&str>() {
+    if let Some(string) = payload.downcast_ref::<&str>() {
         return Some(string.to_string());
     }
 
     // Extract the message from `panic!("... {} ...", arg...)` statements.
     // In this case, the payload has to be a dynamically allocated String to contain
     // the arbitrary formatted arguments.
-    if let Some(string) = info.payload().downcast_ref::<String>() {
+    if let Some(string) = payload.downcast_ref::<String>() {
         return Some(string.to_owned());
     }
 
-    #[cfg(feature = "nightly")]
-    if let Some(fmt_arguments) = info.message() {
-        return Some(format!("{}", fmt_arguments));
-    }
-
     None
 }
 
@@ -0,0 +1,293 @@
+//! Compression of large results returned over a WSTP [`Link`].
+//!
+//! Functions exported using [`export_wstp!`][crate::export_wstp] that return very large
+//! expressions or arrays can spend a significant amount of time transferring the result
+//! over the link. The functions in this module instead serialize the result to the
+//! compressed [WXF][WXF] binary format, and write an expression of the form:
+//!
+//! ```wolfram
+//! BinaryDeserialize[ByteArray[{...}]]
+//! ```
+//!
+//! which the Wolfram Language evaluates to the original result when the function
+//! returns. No special handling is needed on the Wolfram Language side, so functions
+//! that use compression can be loaded using
+//! [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> or
+//! [`generate_loader!`][crate::generate_loader] as usual.
+//!
+//! Compression is only worthwhile for large results. Use [`put_expr()`] with a size
+//! threshold to compress only results whose serialized size is at least that large.
+//!
+//! This module requires the `"compression"` feature of `wolfram-library-link`.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     compression,
+//!     expr::{Expr, Symbol},
+//!     wstp::Link,
+//! };
+//!
+//! wll::export_wstp![symbolic_range(&mut Link)];
+//!
+//! /// Returns `{x[1], x[2], ..., x[n]}`.
+//! fn symbolic_range(link: &mut Link) {
+//!     assert_eq!(link.test_head("System`List").unwrap(), 1);
+//!
+//!     let n = link.get_i64().unwrap();
+//!
+//!     let elements = (1..=n)
+//!         .map(|i| Expr::normal(Symbol::new("Global`x"), vec![Expr::from(i)]))
+//!         .collect();
+//!
+//!     let list = Expr::normal(Symbol::new("System`List"), elements);
+//!
+//!     compression::put_expr(link, &list, compression::DEFAULT_THRESHOLD).unwrap();
+//! }
+//! # }
+//! ```
+//!
+//! [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+//! [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+
+use std::convert::TryFrom;
+
+use crate::{
+    expr::{Expr, ExprKind},
+    wstp::Link,
+};
+
+/// Default serialized size, in bytes, above which [`put_expr()`] compresses its
+/// result.
+pub const DEFAULT_THRESHOLD: usize = 64 * 1024;
+
+/// zlib compression level used for compressed WXF data.
+const COMPRESSION_LEVEL: u8 = 6;
+
+//======================================
+// Writing to a Link
+//======================================
+
+/// Write `expr` to `link`, compressing it if its serialized size is at least
+/// `threshold` bytes.
+///
+/// Expressions smaller than `threshold` are written using
+/// [`Link::put_expr()`][Link::put_expr].
+pub fn put_expr(link: &mut Link, expr: &Expr, threshold: usize) -> Result<(), String> {
+    let wxf = to_wxf(expr);
+
+    if wxf.len() < threshold {
+        return link.put_expr(expr).map_err(|err| err.to_string());
+    }
+
+    put_compressed_wxf(link, &wxf[WXF_HEADER.len()..])
+}
+
+/// Write `expr` to `link` as compressed WXF data, regardless of its size.
+pub fn put_compressed_expr(link: &mut Link, expr: &Expr) -> Result<(), String> {
+    let mut body = Vec::new();
+    write_expr(&mut body, expr);
+
+    put_compressed_wxf(link, &body)
+}
+
+/// Write a packed array of integers with the specified dimensions to `link` as
+/// compressed WXF data.
+///
+/// # Panics
+///
+/// This function will panic if the product of `dimensions` is not equal to
+/// `data.len()`.
+pub fn put_compressed_i64_array(
+    link: &mut Link,
+    data: &[i64],
+    dimensions: &[usize],
+) -> Result<(), String> {
+    let mut body = Vec::new();
+    write_packed_array_header(&mut body, PACKED_INTEGER64, data.len(), dimensions);
+
+    for elem in data {
+        body.extend_from_slice(&elem.to_le_bytes());
+    }
+
+    put_compressed_wxf(link, &body)
+}
+
+/// Write a packed array of reals with the specified dimensions to `link` as compressed
+/// WXF data.
+///
+/// # Panics
+///
+/// This function will panic if the product of `dimensions` is not equal to
+/// `data.len()`.
+pub fn put_compressed_f64_array(
+    link: &mut Link,
+    data: &[f64],
+    dimensions: &[usize],
+) -> Result<(), String> {
+    let mut body = Vec::new();
+    write_packed_array_header(&mut body, PACKED_REAL64, data.len(), dimensions);
+
+    for elem in data {
+        body.extend_from_slice(&elem.to_le_bytes());
+    }
+
+    put_compressed_wxf(link, &body)
+}
+
+/// Write `BinaryDeserialize[ByteArray[{...}]]` to `link`, where the byte array
+/// contains the compressed form of the WXF expression `body`.
+fn put_compressed_wxf(link: &mut Link, body: &[u8]) -> Result<(), String> {
+    let bytes = compress(body);
+
+    link.put_function("System`BinaryDeserialize", 1)
+        .and_then(|()| link.put_function("System`ByteArray", 1))
+        .and_then(|()| link.put_u8_array(&bytes, &[bytes.len()]))
+        .map_err(|err| format!("failed to write compressed expression: {}", err))
+}
+
+//======================================
+// WXF serialization
+//======================================
+
+const WXF_HEADER: &[u8] = b"8:";
+const COMPRESSED_WXF_HEADER: &[u8] = b"8C:";
+
+// WXF expression tokens.
+const FUNCTION: u8 = b'f';
+const SYMBOL: u8 = b's';
+const STRING: u8 = b'S';
+const INTEGER8: u8 = b'C';
+const INTEGER16: u8 = b'j';
+const INTEGER32: u8 = b'i';
+const INTEGER64: u8 = b'L';
+const REAL64: u8 = b'r';
+const PACKED_ARRAY: u8 = 0xC1;
+
+// WXF packed array element types.
+const PACKED_INTEGER64: u8 = 0x03;
+const PACKED_REAL64: u8 = 0x23;
+
+/// Serialize `expr` to the [WXF][WXF] binary format.
+///
+/// The result can be deserialized in the Wolfram Language using
+/// [`BinaryDeserialize`][ref/BinaryDeserialize]<sub>WL</sub>.
+///
+/// [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+/// [ref/BinaryDeserialize]: https://reference.wolfram.com/language/ref/BinaryDeserialize.html
+pub fn to_wxf(expr: &Expr) -> Vec<u8> {
+    let mut bytes = WXF_HEADER.to_vec();
+    write_expr(&mut bytes, expr);
+    bytes
+}
+
+/// Serialize `expr` to the compressed [WXF][WXF] binary format.
+///
+/// The result can be deserialized in the Wolfram Language using
+/// [`BinaryDeserialize`][ref/BinaryDeserialize]<sub>WL</sub>.
+///
+/// [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+/// [ref/BinaryDeserialize]: https://reference.wolfram.com/language/ref/BinaryDeserialize.html
+pub fn to_compressed_wxf(expr: &Expr) -> Vec<u8> {
+    let mut body = Vec::new();
+    write_expr(&mut body, expr);
+
+    compress(&body)
+}
+
+/// Compress the WXF expression `body`, and prepend the compressed WXF header.
+fn compress(body: &[u8]) -> Vec<u8> {
+    let mut bytes = COMPRESSED_WXF_HEADER.to_vec();
+    bytes.extend(miniz_oxide::deflate::compress_to_vec_zlib(
+        body,
+        COMPRESSION_LEVEL,
+    ));
+    bytes
+}
+
+fn write_expr(out: &mut Vec<u8>, expr: &Expr) {
+    match expr.kind() {
+        ExprKind::Integer(int) => write_integer(out, *int),
+        ExprKind::Real(real) => {
+            out.push(REAL64);
+            out.extend_from_slice(&f64::to_le_bytes(**real));
+        },
+        ExprKind::String(string) => {
+            out.push(STRING);
+            write_varint(out, string.len());
+            out.extend_from_slice(string.as_bytes());
+        },
+        ExprKind::Symbol(symbol) => {
+            out.push(SYMBOL);
+            write_varint(out, symbol.as_str().len());
+            out.extend_from_slice(symbol.as_str().as_bytes());
+        },
+        ExprKind::Normal(normal) => {
+            out.push(FUNCTION);
+            write_varint(out, normal.elements().len());
+            write_expr(out, normal.head());
+
+            for elem in normal.elements() {
+                write_expr(out, elem);
+            }
+        },
+    }
+}
+
+/// Write `int` using the smallest WXF integer token that can represent it.
+fn write_integer(out: &mut Vec<u8>, int: i64) {
+    if let Ok(int) = i8::try_from(int) {
+        out.push(INTEGER8);
+        out.extend_from_slice(&int.to_le_bytes());
+    } else if let Ok(int) = i16::try_from(int) {
+        out.push(INTEGER16);
+        out.extend_from_slice(&int.to_le_bytes());
+    } else if let Ok(int) = i32::try_from(int) {
+        out.push(INTEGER32);
+        out.extend_from_slice(&int.to_le_bytes());
+    } else {
+        out.push(INTEGER64);
+        out.extend_from_slice(&int.to_le_bytes());
+    }
+}
+
+fn write_packed_array_header(
+    out: &mut Vec<u8>,
+    element_type: u8,
+    len: usize,
+    dimensions: &[usize],
+) {
+    assert_eq!(
+        dimensions.iter().product::<usize>(),
+        len,
+        "packed array dimensions {:?} do not match data length {}",
+        dimensions,
+        len
+    );
+
+    out.push(PACKED_ARRAY);
+    out.push(element_type);
+    write_varint(out, dimensions.len());
+
+    for &dim in dimensions {
+        write_varint(out, dim);
+    }
+}
+
+/// Write `value` as a WXF variable-length unsigned integer.
+fn write_varint(out: &mut Vec<u8>, mut value: usize) {
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+
+        out.push(byte | 0x80);
+    }
+}
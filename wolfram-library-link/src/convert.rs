@@ -0,0 +1,353 @@
+//! Conversions between Rust values and Wolfram Language expressions.
+//!
+//! [`ToExpr`] and [`FromExpr`] are implemented for Rust types that have a natural
+//! Wolfram Language expression representation. They are useful when writing functions
+//! exported using [`export_wstp!`][crate::export_wstp], which receive their arguments
+//! and return their result as [`Expr`] values.
+//!
+//! # Optional integrations
+//!
+//! Implementations for types from other crates are available when the corresponding
+//! [feature][cargo-features] of `wolfram-library-link` is enabled:
+//!
+//! Feature          | Rust types                                  | Wolfram Language expression
+//! -----------------|---------------------------------------------|----------------------------
+//! `"chrono"`       | [`DateTime<Utc>`][::chrono::DateTime], [`DateTime<FixedOffset>`][::chrono::DateTime], [`NaiveDateTime`][::chrono::NaiveDateTime], [`NaiveDate`][::chrono::NaiveDate] | [`DateObject`][ref/DateObject]<sub>WL</sub>
+//! `"image"`        | [`DynamicImage`][::image::DynamicImage], [`ImageBuffer`][::image::ImageBuffer] | [`Image`][crate::Image] (using `From` and `TryFrom`)
+//! `"num-bigint"`   | [`BigInt`][::num_bigint::BigInt]            | [`Integer`][ref/Integer]<sub>WL</sub>
+//! `"num-complex"`  | [`Complex64`][::num_complex::Complex64]     | [`Complex`][ref/Complex]<sub>WL</sub>
+//! `"num-rational"` | [`Ratio<i64>`][::num_rational::Ratio]       | [`Rational`][ref/Rational]<sub>WL</sub>
+//! `"petgraph"`     | [`petgraph`][::petgraph] graphs (using [`graph_to_expr()`] and [`weighted_graph_to_expr()`]) | [`Graph`][ref/Graph]<sub>WL</sub>
+//! `"polars"`       | [`DataFrame`][::polars_core::frame::DataFrame] | [`Dataset`][ref/Dataset]<sub>WL</sub>-shaped list of associations
+//! `"serde_json"`   | [`serde_json::Value`][::serde_json::Value]  | `"RawJSON"` expressions
+//! `"sprs"`         | [`CsMat<f64>`][::sprs::CsMat], [`CsMat<i64>`][::sprs::CsMat] | [`SparseArray`][crate::SparseArray] (using `From` and `TryFrom`)
+//! `"uom"`          | [`uom::si::f64`][::uom::si::f64] quantities, e.g. [`Length`][::uom::si::f64::Length] | [`Quantity`][ref/Quantity]<sub>WL</sub>
+//! `"uuid"`         | [`Uuid`][::uuid::Uuid]                      | [`String`][ref/String]<sub>WL</sub>
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     convert::{FromExpr, ToExpr},
+//!     expr::Expr,
+//! };
+//!
+//! wll::export_wstp![reverse_strings(_)];
+//!
+//! fn reverse_strings(args: Vec<Expr>) -> Expr {
+//!     let strings: Vec<String> = args
+//!         .iter()
+//!         .map(|arg| String::from_expr(arg).unwrap())
+//!         .map(|string| string.chars().rev().collect())
+//!         .collect();
+//!
+//!     strings.to_expr()
+//! }
+//! # }
+//! ```
+//!
+//! [cargo-features]: https://doc.rust-lang.org/cargo/reference/features.html
+//! [ref/Complex]: https://reference.wolfram.com/language/ref/Complex.html
+//! [ref/DateObject]: https://reference.wolfram.com/language/ref/DateObject.html
+//! [ref/Dataset]: https://reference.wolfram.com/language/ref/Dataset.html
+//! [ref/Graph]: https://reference.wolfram.com/language/ref/Graph.html
+//! [ref/Integer]: https://reference.wolfram.com/language/ref/Integer.html
+//! [ref/Quantity]: https://reference.wolfram.com/language/ref/Quantity.html
+//! [ref/Rational]: https://reference.wolfram.com/language/ref/Rational.html
+//! [ref/String]: https://reference.wolfram.com/language/ref/String.html
+
+#[cfg(feature = "num-bigint")]
+mod bigint;
+#[cfg(feature = "chrono")]
+mod chrono;
+#[cfg(feature = "num-complex")]
+mod complex;
+#[cfg(feature = "image")]
+mod image;
+mod int128;
+#[cfg(feature = "serde_json")]
+mod json;
+#[cfg(feature = "petgraph")]
+mod petgraph;
+#[cfg(feature = "polars")]
+mod polars;
+#[cfg(feature = "num-rational")]
+mod rational;
+#[cfg(feature = "sprs")]
+mod sprs;
+#[cfg(feature = "uom")]
+mod uom;
+#[cfg(feature = "uuid")]
+mod uuid;
+
+pub use self::int128::{get_i128, get_u128, put_i128, put_u128};
+
+#[cfg(feature = "num-bigint")]
+pub use self::bigint::{get_big_integer, put_big_integer};
+#[cfg(feature = "petgraph")]
+pub use self::petgraph::{graph_to_expr, weighted_graph_to_expr};
+
+use crate::expr::{Expr, ExprKind, Number, Symbol};
+
+/// Trait implemented for types that can be converted into an [`Expr`].
+pub trait ToExpr {
+    /// Construct the Wolfram Language expression representation of `self`.
+    fn to_expr(&self) -> Expr;
+}
+
+/// Trait implemented for types that can be constructed from an [`Expr`].
+pub trait FromExpr: Sized {
+    /// Construct a value of this type from its Wolfram Language expression
+    /// representation.
+    ///
+    /// Returns an error describing the mismatch if `expr` does not have the expected
+    /// form.
+    fn from_expr(expr: &Expr) -> Result<Self, String>;
+}
+
+//======================================
+// Impls
+//======================================
+
+impl ToExpr for Expr {
+    fn to_expr(&self) -> Expr {
+        self.clone()
+    }
+}
+
+impl FromExpr for Expr {
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        Ok(expr.clone())
+    }
+}
+
+impl ToExpr for bool {
+    fn to_expr(&self) -> Expr {
+        Expr::from(*self)
+    }
+}
+
+impl FromExpr for bool {
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Symbol(sym) if sym.as_str() == "System`True" => Ok(true),
+            ExprKind::Symbol(sym) if sym.as_str() == "System`False" => Ok(false),
+            _ => Err(mismatch("True or False", expr)),
+        }
+    }
+}
+
+impl ToExpr for i64 {
+    fn to_expr(&self) -> Expr {
+        Expr::from(*self)
+    }
+}
+
+impl FromExpr for i64 {
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Integer(int) => Ok(*int),
+            _ => Err(mismatch("Integer", expr)),
+        }
+    }
+}
+
+impl ToExpr for f64 {
+    /// NaN, which cannot be represented as a `Real` expression, is converted to
+    /// [`Indeterminate`][ref/Indeterminate]<sub>WL</sub>.
+    ///
+    /// [ref/Indeterminate]: https://reference.wolfram.com/language/ref/Indeterminate.html
+    fn to_expr(&self) -> Expr {
+        if self.is_nan() {
+            return Expr::symbol(Symbol::new("System`Indeterminate"));
+        }
+
+        Expr::number(Number::real(*self))
+    }
+}
+
+impl FromExpr for f64 {
+    /// Integers are converted to the nearest `f64` value, and `Indeterminate` is
+    /// converted to NaN.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Real(real) => Ok(**real),
+            ExprKind::Integer(int) => Ok(*int as f64),
+            ExprKind::Symbol(sym) if sym.as_str() == "System`Indeterminate" => {
+                Ok(f64::NAN)
+            },
+            _ => Err(mismatch("Real", expr)),
+        }
+    }
+}
+
+impl ToExpr for str {
+    fn to_expr(&self) -> Expr {
+        Expr::string(self)
+    }
+}
+
+impl ToExpr for String {
+    fn to_expr(&self) -> Expr {
+        Expr::string(self.as_str())
+    }
+}
+
+impl FromExpr for String {
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::String(string) => Ok(string.clone()),
+            _ => Err(mismatch("String", expr)),
+        }
+    }
+}
+
+impl<T: ToExpr> ToExpr for [T] {
+    fn to_expr(&self) -> Expr {
+        list(self.iter().map(ToExpr::to_expr).collect())
+    }
+}
+
+impl<T: ToExpr> ToExpr for Vec<T> {
+    fn to_expr(&self) -> Expr {
+        self.as_slice().to_expr()
+    }
+}
+
+impl<T: FromExpr> FromExpr for Vec<T> {
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        list_elements(expr)?
+            .iter()
+            .enumerate()
+            .map(|(index, elem)| {
+                T::from_expr(elem)
+                    // Use 1-based indexing, to match Wolfram Language part numbers.
+                    .map_err(|err| format!("at list position {}: {}", index + 1, err))
+            })
+            .collect()
+    }
+}
+
+/// Implement [`ToExpr`] and [`FromExpr`] for tuples, which are represented as a
+/// [`List`][ref/List]<sub>WL</sub> with one element per tuple field.
+///
+/// [ref/List]: https://reference.wolfram.com/language/ref/List.html
+macro_rules! impl_tuple_conversions {
+    ($len:literal; $($type:ident),*) => {
+        impl<$($type: ToExpr),*> ToExpr for ($($type,)*) {
+            fn to_expr(&self) -> Expr {
+                #[allow(non_snake_case)]
+                let ($($type,)*) = self;
+
+                list(vec![$($type.to_expr()),*])
+            }
+        }
+
+        impl<$($type: FromExpr),*> FromExpr for ($($type,)*) {
+            fn from_expr(expr: &Expr) -> Result<Self, String> {
+                let elements = list_elements(expr)?;
+
+                if elements.len() != $len {
+                    return Err(mismatch(&format!("List of length {}", $len), expr));
+                }
+
+                let mut elements = elements.iter().enumerate();
+
+                Ok(($({
+                    let (index, elem) = elements.next().unwrap();
+
+                    $type::from_expr(elem)
+                        // Use 1-based indexing, to match Wolfram Language part numbers.
+                        .map_err(|err| format!("at list position {}: {}", index + 1, err))?
+                },)*))
+            }
+        }
+    };
+}
+
+impl_tuple_conversions!(1; A1);
+impl_tuple_conversions!(2; A1, A2);
+impl_tuple_conversions!(3; A1, A2, A3);
+impl_tuple_conversions!(4; A1, A2, A3, A4);
+impl_tuple_conversions!(5; A1, A2, A3, A4, A5);
+impl_tuple_conversions!(6; A1, A2, A3, A4, A5, A6);
+impl_tuple_conversions!(7; A1, A2, A3, A4, A5, A6, A7);
+impl_tuple_conversions!(8; A1, A2, A3, A4, A5, A6, A7, A8);
+
+impl<T: ToExpr + ?Sized> ToExpr for &T {
+    fn to_expr(&self) -> Expr {
+        (**self).to_expr()
+    }
+}
+
+impl<T: ToExpr> ToExpr for Result<T, String> {
+    /// `Ok` values are converted using [`ToExpr`]. `Err` values are converted to a
+    /// [`Failure`][ref/Failure]<sub>WL</sub> object containing the error message:
+    ///
+    /// ```wolfram
+    /// Failure["RustError", <|
+    ///     "MessageTemplate" -> "`message`",
+    ///     "MessageParameters" -> <| "message" -> "..." |>
+    /// |>]
+    /// ```
+    ///
+    /// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+    fn to_expr(&self) -> Expr {
+        match self {
+            Ok(value) => value.to_expr(),
+            Err(message) => failure(message),
+        }
+    }
+}
+
+//======================================
+// Utilities
+//======================================
+
+/// Construct `List[elements...]`.
+pub(crate) fn list(elements: Vec<Expr>) -> Expr {
+    Expr::normal(Symbol::new("System`List"), elements)
+}
+
+/// Get the elements of `expr`, which must have the form `List[...]`.
+pub(crate) fn list_elements(expr: &Expr) -> Result<&[Expr], String> {
+    normal_elements(expr, "System`List").ok_or_else(|| mismatch("List", expr))
+}
+
+/// Get the elements of `expr` if it has the form `head[...]`.
+pub(crate) fn normal_elements<'e>(expr: &'e Expr, head: &str) -> Option<&'e [Expr]> {
+    match expr.kind() {
+        ExprKind::Normal(normal) if normal.has_head(&Symbol::new(head)) => {
+            Some(normal.elements())
+        },
+        _ => None,
+    }
+}
+
+/// Construct a `Failure["RustError", ..]` object containing `message`.
+fn failure(message: &str) -> Expr {
+    let rule = |lhs: &str, rhs: Expr| {
+        Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(lhs), rhs])
+    };
+
+    Expr::normal(Symbol::new("System`Failure"), vec![
+        Expr::string("RustError"),
+        Expr::normal(Symbol::new("System`Association"), vec![
+            rule("MessageTemplate", Expr::string("`message`")),
+            rule(
+                "MessageParameters",
+                Expr::normal(Symbol::new("System`Association"), vec![rule(
+                    "message",
+                    Expr::string(message),
+                )]),
+            ),
+        ]),
+    ])
+}
+
+/// Construct an error message for an expression that did not have the expected form.
+pub(crate) fn mismatch(expected: &str, expr: &Expr) -> String {
+    format!("expected {}, got {}", expected, expr)
+}
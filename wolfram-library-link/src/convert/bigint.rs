@@ -0,0 +1,124 @@
+//! Conversions for [`BigInt`] values.
+//!
+//! [`Expr`] can only represent integers that fit in an [`i64`], so arbitrary precision
+//! integers are exchanged with the Wolfram Language in one of two ways:
+//!
+//! * In functions exported using [`export_wstp!`][crate::export_wstp] that operate
+//!   directly on the [`Link`], using [`get_big_integer()`] and [`put_big_integer()`],
+//!   which read and write Wolfram Language integers of any size.
+//! * In functions exported using [`export!`][crate::export], as a [`String`] containing
+//!   the decimal digits of the integer. Use
+//!   [`IntegerString`][ref/IntegerString]<sub>WL</sub> to convert an integer argument
+//!   to a string. Returned values are converted back to an
+//!   [`Integer`][ref/Integer]<sub>WL</sub> by the loader function generated by
+//!   [`generate_loader!`][crate::generate_loader].
+//!
+//! [ref/IntegerString]: https://reference.wolfram.com/language/ref/IntegerString.html
+//! [ref/Integer]: https://reference.wolfram.com/language/ref/Integer.html
+
+use num_bigint::BigInt;
+
+use crate::{
+    convert::{
+        int128::{get_integer_digits, integer_digits_wrapper, put_integer_digits},
+        mismatch, FromExpr,
+    },
+    expr::{Expr, ExprKind, Symbol},
+    sys::MArgument,
+    wstp::Link,
+    FromArg, IntoArg, TryFromArg,
+};
+
+/// Read an integer of any size from `link`.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use num_bigint::BigInt;
+/// use wolfram_library_link::{self as wll, convert, wstp::Link};
+///
+/// wll::export_wstp![factorial(&mut Link)];
+///
+/// fn factorial(link: &mut Link) {
+///     assert_eq!(link.test_head("System`List").unwrap(), 1);
+///
+///     let n = convert::get_big_integer(link).unwrap();
+///
+///     let mut result = BigInt::from(1);
+///     let mut i = BigInt::from(2);
+///     while i <= n {
+///         result *= &i;
+///         i += 1;
+///     }
+///
+///     convert::put_big_integer(link, &result).unwrap();
+/// }
+/// # }
+/// ```
+pub fn get_big_integer(link: &mut Link) -> Result<BigInt, String> {
+    get_integer_digits(link).and_then(|digits| parse(&digits))
+}
+
+/// Write an integer of any size to `link`.
+///
+/// See [`get_big_integer()`] for an example.
+pub fn put_big_integer(link: &mut Link, value: &BigInt) -> Result<(), String> {
+    put_integer_digits(link, &value.to_str_radix(10))
+}
+
+//======================================
+// FromExpr
+//======================================
+
+impl FromExpr for BigInt {
+    /// Accepts an `Integer`, or a `String` containing the decimal digits of an
+    /// integer.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Integer(int) => Ok(BigInt::from(*int)),
+            ExprKind::String(string) => parse(string),
+            _ => Err(mismatch("Integer", expr)),
+        }
+    }
+}
+
+//======================================
+// TryFromArg / IntoArg
+//======================================
+
+/// Conversion fails if the argument is not a string containing the decimal digits of an
+/// integer.
+impl TryFromArg<'_> for BigInt {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+        let string = String::from_arg(arg);
+
+        parse(&string)
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
+
+/// The integer is returned as a string containing its decimal digits, which the loader
+/// function converts back to an `Integer`.
+impl IntoArg for BigInt {
+    unsafe fn into_arg(self, arg: MArgument) {
+        self.to_str_radix(10).into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+
+    fn return_wrapper() -> Option<Expr> {
+        Some(integer_digits_wrapper())
+    }
+}
+
+fn parse(string: &str) -> Result<BigInt, String> {
+    string
+        .parse()
+        .map_err(|err| format!("invalid integer {:?}: {}", string, err))
+}
@@ -0,0 +1,293 @@
+//! Conversions for [`chrono`] date and time types.
+//!
+//! Dates are represented in the Wolfram Language as
+//! [`DateObject`][ref/DateObject]<sub>WL</sub> expressions when using [`ToExpr`] and
+//! [`FromExpr`], and as [`AbsoluteTime`][ref/AbsoluteTime]<sub>WL</sub> values (with a
+//! time zone of 0) when passed as native *LibraryLink* arguments.
+//!
+//! [ref/DateObject]: https://reference.wolfram.com/language/ref/DateObject.html
+//! [ref/AbsoluteTime]: https://reference.wolfram.com/language/ref/AbsoluteTime.html
+
+use chrono::{
+    DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc,
+};
+
+use crate::{
+    convert::{mismatch, normal_elements, FromExpr, ToExpr},
+    expr::{Expr, ExprKind, Number, Symbol},
+    sys::{mreal, MArgument},
+    IntoArg, TryFromArg,
+};
+
+/// Number of seconds between the Wolfram Language epoch (1900-01-01) and the Unix epoch
+/// (1970-01-01).
+const WOLFRAM_EPOCH_OFFSET: i64 = 2_208_988_800;
+
+//======================================
+// ToExpr
+//======================================
+
+impl ToExpr for DateTime<Utc> {
+    /// `DateObject[{y, m, d, h, min, s}, "Instant", "Gregorian", 0.]`
+    fn to_expr(&self) -> Expr {
+        date_object(&self.naive_utc(), Some(0))
+    }
+}
+
+impl ToExpr for DateTime<FixedOffset> {
+    /// `DateObject[{y, m, d, h, min, s}, "Instant", "Gregorian", tz]`
+    fn to_expr(&self) -> Expr {
+        date_object(&self.naive_local(), Some(self.offset().local_minus_utc()))
+    }
+}
+
+impl ToExpr for NaiveDateTime {
+    /// `DateObject[{y, m, d, h, min, s}]`
+    ///
+    /// The Wolfram Language interprets a date without a time zone as being in
+    /// [`$TimeZone`][ref/$TimeZone]<sub>WL</sub>.
+    ///
+    /// [ref/$TimeZone]: https://reference.wolfram.com/language/ref/$TimeZone.html
+    fn to_expr(&self) -> Expr {
+        date_object(self, None)
+    }
+}
+
+impl ToExpr for NaiveDate {
+    /// `DateObject[{y, m, d}, "Day"]`
+    fn to_expr(&self) -> Expr {
+        Expr::normal(Symbol::new("System`DateObject"), vec![
+            Expr::normal(Symbol::new("System`List"), vec![
+                Expr::from(i64::from(self.year())),
+                Expr::from(i64::from(self.month())),
+                Expr::from(i64::from(self.day())),
+            ]),
+            Expr::string("Day"),
+        ])
+    }
+}
+
+fn date_object(date: &NaiveDateTime, offset_seconds: Option<i32>) -> Expr {
+    let seconds = f64::from(date.second()) + f64::from(date.nanosecond()) / 1e9;
+
+    let mut elements = vec![Expr::normal(Symbol::new("System`List"), vec![
+        Expr::from(i64::from(date.year())),
+        Expr::from(i64::from(date.month())),
+        Expr::from(i64::from(date.day())),
+        Expr::from(i64::from(date.hour())),
+        Expr::from(i64::from(date.minute())),
+        Expr::number(Number::real(seconds)),
+    ])];
+
+    if let Some(offset_seconds) = offset_seconds {
+        elements.extend([
+            Expr::string("Instant"),
+            Expr::string("Gregorian"),
+            // The time zone is specified as an offset in hours.
+            Expr::number(Number::real(f64::from(offset_seconds) / 3600.0)),
+        ]);
+    }
+
+    Expr::normal(Symbol::new("System`DateObject"), elements)
+}
+
+//======================================
+// FromExpr
+//======================================
+
+impl FromExpr for DateTime<FixedOffset> {
+    /// Accepts a `DateObject[{y, m, d, ...}, ___]` expression, or an `AbsoluteTime`
+    /// number.
+    ///
+    /// A `DateObject` without an explicit time zone is interpreted as UTC.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        if let Some(absolute_time) = number(expr) {
+            return Ok(from_absolute_time(absolute_time)?.into());
+        }
+
+        let (date, offset) = parse_date_object(expr)?;
+
+        let offset = FixedOffset::east_opt(offset.unwrap_or(0))
+            .ok_or_else(|| format!("time zone offset out of range in {}", expr))?;
+
+        offset
+            .from_local_datetime(&date)
+            .single()
+            .ok_or_else(|| format!("invalid local date in {}", expr))
+    }
+}
+
+impl FromExpr for DateTime<Utc> {
+    /// Accepts a `DateObject[{y, m, d, ...}, ___]` expression, or an `AbsoluteTime`
+    /// number.
+    ///
+    /// A `DateObject` without an explicit time zone is interpreted as UTC.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        DateTime::<FixedOffset>::from_expr(expr).map(|date| date.with_timezone(&Utc))
+    }
+}
+
+impl FromExpr for NaiveDateTime {
+    /// Accepts a `DateObject[{y, m, d, ...}, ___]` expression. Any time zone is
+    /// ignored.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        let (date, _) = parse_date_object(expr)?;
+
+        Ok(date)
+    }
+}
+
+impl FromExpr for NaiveDate {
+    /// Accepts a `DateObject[{y, m, d, ...}, ___]` expression. Any time of day is
+    /// ignored.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        let (date, _) = parse_date_object(expr)?;
+
+        Ok(date.date())
+    }
+}
+
+/// Parse `DateObject[{y, m, d, h, min, s}, ___, tz?]`, returning the date and the time
+/// zone offset in seconds, if one was specified.
+fn parse_date_object(expr: &Expr) -> Result<(NaiveDateTime, Option<i32>), String> {
+    let elements = normal_elements(expr, "System`DateObject")
+        .ok_or_else(|| mismatch("DateObject", expr))?;
+
+    let components = match elements.first() {
+        Some(list) => normal_elements(list, "System`List"),
+        None => None,
+    }
+    .ok_or_else(|| mismatch("DateObject[{y, m, d, ...}, ...]", expr))?;
+
+    if components.is_empty() || components.len() > 6 {
+        return Err(mismatch("DateObject[{y, m, d, ...}, ...]", expr));
+    }
+
+    // Components that are not specified default to the start of the period.
+    let mut values: [f64; 6] = [0.0, 1.0, 1.0, 0.0, 0.0, 0.0];
+
+    for (value, component) in values.iter_mut().zip(components) {
+        *value = number(component)
+            .ok_or_else(|| mismatch("DateObject with numeric date components", expr))?;
+    }
+
+    let [year, month, day, hour, minute, second] = values;
+
+    let whole_seconds = second.trunc();
+    let nanos = ((second - whole_seconds) * 1e9).round() as u32;
+
+    let date = NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+        .and_then(|date| {
+            date.and_hms_nano_opt(
+                hour as u32,
+                minute as u32,
+                whole_seconds as u32,
+                nanos.min(999_999_999),
+            )
+        })
+        .ok_or_else(|| format!("invalid date: {}", expr))?;
+
+    // The time zone, if present, is the last element: DateObject[date, gran, cal, tz].
+    let offset = elements
+        .get(3)
+        .and_then(number)
+        .map(|hours| (hours * 3600.0).round() as i32);
+
+    Ok((date, offset))
+}
+
+fn number(expr: &Expr) -> Option<f64> {
+    match expr.kind() {
+        ExprKind::Integer(int) => Some(*int as f64),
+        ExprKind::Real(real) => Some(**real),
+        _ => None,
+    }
+}
+
+//======================================
+// AbsoluteTime
+//======================================
+
+/// Convert a Wolfram Language `AbsoluteTime` value (with a time zone of 0) to a
+/// `DateTime<Utc>`.
+fn from_absolute_time(absolute_time: f64) -> Result<DateTime<Utc>, String> {
+    if !absolute_time.is_finite() {
+        return Err(format!("invalid AbsoluteTime value: {}", absolute_time));
+    }
+
+    let mut seconds = absolute_time.floor();
+    let mut nanos = ((absolute_time - seconds) * 1e9).round();
+
+    // Rounding up to a whole second carries into the seconds.
+    if nanos >= 1e9 {
+        seconds += 1.0;
+        nanos -= 1e9;
+    }
+
+    // Note: `as i64` saturates, and a saturated value is out of range for `Utc`.
+    (seconds as i64)
+        .checked_sub(WOLFRAM_EPOCH_OFFSET)
+        .and_then(|timestamp| Utc.timestamp_opt(timestamp, nanos as u32).single())
+        .ok_or_else(|| format!("AbsoluteTime value out of range: {}", absolute_time))
+}
+
+/// Convert a `DateTime<Utc>` to a Wolfram Language `AbsoluteTime` value (with a time
+/// zone of 0).
+fn to_absolute_time(date: &DateTime<Utc>) -> f64 {
+    (date.timestamp() + WOLFRAM_EPOCH_OFFSET) as f64
+        + f64::from(date.timestamp_subsec_nanos()) / 1e9
+}
+
+//======================================
+// TryFromArg / IntoArg
+//======================================
+
+/// Conversion fails if the argument is not a finite `AbsoluteTime` value in the range
+/// supported by `chrono`.
+impl TryFromArg<'_> for DateTime<Utc> {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+        let absolute_time: mreal = *arg.real;
+
+        from_absolute_time(absolute_time)
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Real"))
+    }
+}
+
+impl IntoArg for DateTime<Utc> {
+    unsafe fn into_arg(self, arg: MArgument) {
+        *arg.real = to_absolute_time(&self);
+    }
+
+    fn return_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Real"))
+    }
+}
+
+/// Conversion fails if the argument is not a finite `AbsoluteTime` value in the range
+/// supported by `chrono`.
+impl TryFromArg<'_> for NaiveDate {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+        DateTime::<Utc>::try_from_arg(arg).map(|date| date.date_naive())
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Real"))
+    }
+}
+
+impl IntoArg for NaiveDate {
+    unsafe fn into_arg(self, arg: MArgument) {
+        let midnight = self
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is always a valid time");
+
+        Utc.from_utc_datetime(&midnight).into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Real"))
+    }
+}
@@ -0,0 +1,65 @@
+//! Conversions for [`Complex64`].
+//!
+//! Complex numbers are represented in the Wolfram Language as
+//! [`Complex`][ref/Complex]<sub>WL</sub>`[re, im]` expressions when using [`ToExpr`] and
+//! [`FromExpr`], and as native [`mcomplex`] values when passed as *LibraryLink*
+//! arguments.
+//!
+//! [ref/Complex]: https://reference.wolfram.com/language/ref/Complex.html
+
+use num_complex::Complex64;
+
+use crate::{
+    convert::{mismatch, normal_elements, FromExpr, ToExpr},
+    expr::{Expr, Symbol},
+    sys::{mcomplex, MArgument},
+    FromArg, IntoArg,
+};
+
+impl ToExpr for Complex64 {
+    /// `Complex[re, im]`, with NaN parts converted to `Indeterminate`.
+    fn to_expr(&self) -> Expr {
+        Expr::normal(Symbol::new("System`Complex"), vec![
+            self.re.to_expr(),
+            self.im.to_expr(),
+        ])
+    }
+}
+
+impl FromExpr for Complex64 {
+    /// Accepts a `Complex[re, im]` expression, or a real or integer number.
+    /// `Indeterminate` is converted to NaN.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        if let Ok(re) = f64::from_expr(expr) {
+            return Ok(Complex64::new(re, 0.0));
+        }
+
+        match normal_elements(expr, "System`Complex") {
+            Some([re, im]) => match (f64::from_expr(re), f64::from_expr(im)) {
+                (Ok(re), Ok(im)) => Ok(Complex64::new(re, im)),
+                _ => Err(mismatch("Complex[re, im] with numeric parts", expr)),
+            },
+            _ => Err(mismatch("Complex", expr)),
+        }
+    }
+}
+
+impl FromArg<'_> for Complex64 {
+    unsafe fn from_arg(arg: &MArgument) -> Self {
+        Complex64::from(mcomplex::from_arg(arg))
+    }
+
+    fn parameter_type() -> Expr {
+        mcomplex::parameter_type()
+    }
+}
+
+impl IntoArg for Complex64 {
+    unsafe fn into_arg(self, arg: MArgument) {
+        mcomplex::from(self).into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        mcomplex::return_type()
+    }
+}
@@ -0,0 +1,285 @@
+//! Conversions between [`Image`]s and [`image`](https://docs.rs/image) crate image
+//! buffers.
+//!
+//! Only 2D images in the `"Grayscale"` or `"RGB"` color spaces (with an optional alpha
+//! channel), or with an `Automatic` color space and 1 to 4 channels, can be converted.
+//! Channel values are always stored in interleaved order in the `image` crate; images
+//! with non-interleaved data are reordered during conversion.
+//!
+//! Converting an [`Image`] into a [`DynamicImage`] changes the bit depth of the image
+//! data when the `image` crate does not have an equivalent [`DynamicImage`] variant:
+//!
+//! `Image` type  | `DynamicImage` data type
+//! --------------|-------------------------
+//! `"Bit"`       | `u8`, with `True` pixels stored as 255
+//! `"Byte"`      | `u8`
+//! `"Bit16"`     | `u16`
+//! `"Real32"`    | `f32` (grayscale images are converted to RGB)
+//! `"Real64"`    | `f32` (grayscale images are converted to RGB)
+
+use std::convert::TryFrom;
+
+use image::{
+    buffer::ConvertBuffer, DynamicImage, ImageBuffer, Luma, LumaA, Pixel as ImagePixel,
+    Rgb, Rgba,
+};
+
+use crate::{ColorSpace, Image, ImageData, UninitImage};
+
+//======================================
+// ImageBuffer
+//======================================
+
+/// Construct an interleaved 2D [`Image`] containing the pixels of an [`ImageBuffer`].
+///
+/// The color space of the image is `"Grayscale"` for [`Luma`] and [`LumaA`] pixels,
+/// and `"RGB"` for [`Rgb`] and [`Rgba`] pixels.
+impl<P> From<&ImageBuffer<P, Vec<P::Subpixel>>> for Image<P::Subpixel>
+where
+    P: ImagePixel,
+    P::Subpixel: ImageData<STORAGE = P::Subpixel>,
+{
+    fn from(buffer: &ImageBuffer<P, Vec<P::Subpixel>>) -> Self {
+        let (width, height) = buffer.dimensions();
+
+        let space = match P::COLOR_MODEL {
+            "Y" | "YA" => ColorSpace::Gray,
+            "RGB" | "RGBA" => ColorSpace::RGB,
+            _ => ColorSpace::Automatic,
+        };
+
+        let mut image = UninitImage::<P::Subpixel>::new_2d(
+            width as usize,
+            height as usize,
+            usize::from(P::CHANNEL_COUNT),
+            space,
+            true,
+        );
+
+        for (elem, value) in image.as_slice_mut().iter_mut().zip(buffer.as_raw()) {
+            elem.write(*value);
+        }
+
+        // Safety: `buffer` contains a value for every pixel and channel of `image`.
+        unsafe { image.assume_init() }
+    }
+}
+
+/// Construct an [`ImageBuffer`] from a 2D [`Image`] whose channel count matches the
+/// pixel type `P`.
+impl<P> TryFrom<&Image<P::Subpixel>> for ImageBuffer<P, Vec<P::Subpixel>>
+where
+    P: ImagePixel,
+    P::Subpixel: ImageData<STORAGE = P::Subpixel>,
+{
+    type Error = String;
+
+    fn try_from(image: &Image<P::Subpixel>) -> Result<Self, String> {
+        let (width, height) = dimensions(image)?;
+
+        if image.channels() != usize::from(P::CHANNEL_COUNT) {
+            return Err(format!(
+                "image has {} channels, but {} pixels have {} channels",
+                image.channels(),
+                P::COLOR_MODEL,
+                P::CHANNEL_COUNT
+            ));
+        }
+
+        from_raw(width, height, interleaved_data(image))
+    }
+}
+
+//======================================
+// DynamicImage
+//======================================
+
+/// Construct an [`Image`] from a [`DynamicImage`], preserving its bit depth.
+impl From<&DynamicImage> for Image {
+    fn from(image: &DynamicImage) -> Self {
+        match image {
+            DynamicImage::ImageLuma8(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageLumaA8(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgb8(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgba8(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageLuma16(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageLumaA16(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgb16(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgba16(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgb32F(buffer) => Image::from(buffer).into_generic(),
+            DynamicImage::ImageRgba32F(buffer) => Image::from(buffer).into_generic(),
+            other => Image::from(&other.to_rgba32f()).into_generic(),
+        }
+    }
+}
+
+/// Construct a [`DynamicImage`] from a 2D [`Image`] of any data type.
+///
+/// See the table above for how the bit depth of the image data is converted.
+impl TryFrom<&Image> for DynamicImage {
+    type Error = String;
+
+    fn try_from(image: &Image) -> Result<Self, String> {
+        if let Some(image) = image.try_kind::<bool>() {
+            let (width, height) = dimensions(image)?;
+
+            let data = interleaved_data(image)
+                .into_iter()
+                .map(|bit| if bit != 0 { u8::MAX } else { 0 })
+                .collect();
+
+            dynamic_from_u8(width, height, image.channels(), data)
+        } else if let Some(image) = image.try_kind::<u8>() {
+            let (width, height) = dimensions(image)?;
+
+            dynamic_from_u8(width, height, image.channels(), interleaved_data(image))
+        } else if let Some(image) = image.try_kind::<u16>() {
+            let (width, height) = dimensions(image)?;
+
+            dynamic_from_u16(width, height, image.channels(), interleaved_data(image))
+        } else if let Some(image) = image.try_kind::<f32>() {
+            let (width, height) = dimensions(image)?;
+
+            dynamic_from_f32(width, height, image.channels(), interleaved_data(image))
+        } else if let Some(image) = image.try_kind::<f64>() {
+            let (width, height) = dimensions(image)?;
+
+            let data = interleaved_data(image)
+                .into_iter()
+                .map(|value| value as f32)
+                .collect();
+
+            dynamic_from_f32(width, height, image.channels(), data)
+        } else {
+            Err(format!(
+                "unsupported image data type: {}",
+                image.data_type().name()
+            ))
+        }
+    }
+}
+
+fn dynamic_from_u8(
+    width: u32,
+    height: u32,
+    channels: usize,
+    data: Vec<u8>,
+) -> Result<DynamicImage, String> {
+    let image = match channels {
+        1 => DynamicImage::ImageLuma8(from_raw(width, height, data)?),
+        2 => DynamicImage::ImageLumaA8(from_raw(width, height, data)?),
+        3 => DynamicImage::ImageRgb8(from_raw(width, height, data)?),
+        4 => DynamicImage::ImageRgba8(from_raw(width, height, data)?),
+        _ => return Err(unsupported_channels(channels)),
+    };
+
+    Ok(image)
+}
+
+fn dynamic_from_u16(
+    width: u32,
+    height: u32,
+    channels: usize,
+    data: Vec<u16>,
+) -> Result<DynamicImage, String> {
+    let image = match channels {
+        1 => DynamicImage::ImageLuma16(from_raw(width, height, data)?),
+        2 => DynamicImage::ImageLumaA16(from_raw(width, height, data)?),
+        3 => DynamicImage::ImageRgb16(from_raw(width, height, data)?),
+        4 => DynamicImage::ImageRgba16(from_raw(width, height, data)?),
+        _ => return Err(unsupported_channels(channels)),
+    };
+
+    Ok(image)
+}
+
+fn dynamic_from_f32(
+    width: u32,
+    height: u32,
+    channels: usize,
+    data: Vec<f32>,
+) -> Result<DynamicImage, String> {
+    // The `image` crate does not have grayscale floating-point DynamicImage variants.
+    let image = match channels {
+        1 => {
+            let gray: ImageBuffer<Luma<f32>, Vec<f32>> = from_raw(width, height, data)?;
+            DynamicImage::ImageRgb32F(gray.convert())
+        },
+        2 => {
+            let gray: ImageBuffer<LumaA<f32>, Vec<f32>> = from_raw(width, height, data)?;
+            DynamicImage::ImageRgba32F(gray.convert())
+        },
+        3 => DynamicImage::ImageRgb32F(from_raw::<Rgb<f32>>(width, height, data)?),
+        4 => DynamicImage::ImageRgba32F(from_raw::<Rgba<f32>>(width, height, data)?),
+        _ => return Err(unsupported_channels(channels)),
+    };
+
+    Ok(image)
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Get the width and height of a 2D image in a supported color space.
+fn dimensions<T>(image: &Image<T>) -> Result<(u32, u32), String> {
+    if image.rank() != 2 {
+        return Err(format!(
+            "expected 2D image, got image of rank {}",
+            image.rank()
+        ));
+    }
+
+    let supported = match image.color_space() {
+        ColorSpace::Automatic => (1..=4).contains(&image.channels()),
+        ColorSpace::Gray => (1..=2).contains(&image.channels()),
+        ColorSpace::RGB => (3..=4).contains(&image.channels()),
+        _ => false,
+    };
+
+    if !supported {
+        return Err(format!(
+            "unsupported image color space (ColorSpace raw value {}) with {} channels",
+            image.color_space_raw(),
+            image.channels()
+        ));
+    }
+
+    let width = u32::try_from(image.column_count())
+        .map_err(|_| "image width overflows u32".to_owned())?;
+    let height = u32::try_from(image.row_count())
+        .map_err(|_| "image height overflows u32".to_owned())?;
+
+    Ok((width, height))
+}
+
+/// Get the data of `image` with the channel values of each pixel stored contiguously.
+fn interleaved_data<T: ImageData>(image: &Image<T>) -> Vec<T::STORAGE> {
+    let data = image.as_slice();
+
+    if image.is_interleaved() {
+        return data.to_vec();
+    }
+
+    // Non-interleaved data stores each channel as a separate plane.
+    let channels = image.channels();
+    let pixel_count = data.len() / channels;
+
+    (0..pixel_count)
+        .flat_map(|pixel| (0..channels).map(move |channel| (pixel, channel)))
+        .map(|(pixel, channel)| data[channel * pixel_count + pixel])
+        .collect()
+}
+
+fn from_raw<P: ImagePixel>(
+    width: u32,
+    height: u32,
+    data: Vec<P::Subpixel>,
+) -> Result<ImageBuffer<P, Vec<P::Subpixel>>, String> {
+    ImageBuffer::from_raw(width, height, data)
+        .ok_or_else(|| "image data length does not match image dimensions".to_owned())
+}
+
+fn unsupported_channels(channels: usize) -> String {
+    format!("unsupported image channel count: {}", channels)
+}
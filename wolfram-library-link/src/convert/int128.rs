@@ -0,0 +1,248 @@
+//! Conversions for [`i128`] and [`u128`] values.
+//!
+//! *LibraryLink* integers are limited to the size of [`mint`][crate::sys::mint], and
+//! [`Expr`] can only represent integers that fit in an [`i64`], so 128-bit integers are
+//! exchanged with the Wolfram Language in one of two ways:
+//!
+//! * In functions exported using [`export!`][crate::export], as a [`String`] containing
+//!   the decimal digits of the integer. Use [`ToString`][ref/ToString]<sub>WL</sub> to
+//!   convert an integer argument to a string. Returned values are converted back to an
+//!   [`Integer`][ref/Integer]<sub>WL</sub> by the loader function generated by
+//!   [`generate_loader!`][crate::generate_loader].
+//! * In functions exported using [`export_wstp!`][crate::export_wstp] that operate
+//!   directly on the [`Link`], using [`get_i128()`], [`put_i128()`], [`get_u128()`],
+//!   and [`put_u128()`], which read and write Wolfram Language integers of any size.
+//!
+//! Conversions from the Wolfram Language are overflow-checked: an integer that does
+//! not fit in the target type is an error, and is never truncated.
+//!
+//! [ref/Integer]: https://reference.wolfram.com/language/ref/Integer.html
+//! [ref/ToString]: https://reference.wolfram.com/language/ref/ToString.html
+
+use std::{
+    convert::TryFrom,
+    ffi::CStr,
+    os::raw::{c_char, c_int},
+};
+
+use crate::{
+    convert::{mismatch, FromExpr},
+    expr::{Expr, ExprKind, Symbol},
+    sys::MArgument,
+    wstp::{self, Link},
+    FromArg, IntoArg, TryFromArg,
+};
+
+/// `WSTKINT`, the WSTP token type of integers.
+const WSTKINT: c_int = b'I' as c_int;
+
+/// Read an [`i128`] from `link`.
+///
+/// Returns an error if the integer on `link` does not fit in an `i128`.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, convert, wstp::Link};
+///
+/// wll::export_wstp![square_i128(&mut Link)];
+///
+/// fn square_i128(link: &mut Link) {
+///     assert_eq!(link.test_head("System`List").unwrap(), 1);
+///
+///     let result = convert::get_i128(link)
+///         .and_then(|x| x.checked_mul(x).ok_or_else(|| "overflow".to_owned()));
+///
+///     match result {
+///         Ok(value) => convert::put_i128(link, value).unwrap(),
+///         Err(message) => link.put_str(&message).unwrap(),
+///     }
+/// }
+/// # }
+/// ```
+pub fn get_i128(link: &mut Link) -> Result<i128, String> {
+    get_integer_digits(link).and_then(|digits| parse(&digits))
+}
+
+/// Write an [`i128`] to `link`.
+///
+/// See [`get_i128()`] for an example.
+pub fn put_i128(link: &mut Link, value: i128) -> Result<(), String> {
+    put_integer_digits(link, &value.to_string())
+}
+
+/// Read a [`u128`] from `link`.
+///
+/// Returns an error if the integer on `link` is negative or does not fit in a `u128`.
+pub fn get_u128(link: &mut Link) -> Result<u128, String> {
+    get_integer_digits(link).and_then(|digits| parse(&digits))
+}
+
+/// Write a [`u128`] to `link`.
+pub fn put_u128(link: &mut Link, value: u128) -> Result<(), String> {
+    put_integer_digits(link, &value.to_string())
+}
+
+//======================================
+// FromExpr
+//======================================
+
+impl FromExpr for i128 {
+    /// Accepts an `Integer`, or a `String` containing the decimal digits of an
+    /// integer.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Integer(int) => Ok(i128::from(*int)),
+            ExprKind::String(string) => parse(string),
+            _ => Err(mismatch("Integer", expr)),
+        }
+    }
+}
+
+impl FromExpr for u128 {
+    /// Accepts a non-negative `Integer`, or a `String` containing the decimal digits of
+    /// a non-negative integer.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::Integer(int) => {
+                u128::try_from(*int).map_err(|_| mismatch("non-negative Integer", expr))
+            },
+            ExprKind::String(string) => parse(string),
+            _ => Err(mismatch("non-negative Integer", expr)),
+        }
+    }
+}
+
+//======================================
+// TryFromArg / IntoArg
+//======================================
+
+macro_rules! impl_arg_traits_for_int128 {
+    ($type:ty) => {
+        /// Conversion fails if the argument is not a string containing the decimal
+        /// digits of an integer that fits in this type.
+        impl TryFromArg<'_> for $type {
+            unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+                let string = String::from_arg(arg);
+
+                parse(&string)
+            }
+
+            fn parameter_type() -> Expr {
+                Expr::symbol(Symbol::new("System`String"))
+            }
+        }
+
+        /// The integer is returned as a string containing its decimal digits, which
+        /// the loader function converts back to an `Integer`.
+        impl IntoArg for $type {
+            unsafe fn into_arg(self, arg: MArgument) {
+                self.to_string().into_arg(arg)
+            }
+
+            fn return_type() -> Expr {
+                Expr::symbol(Symbol::new("System`String"))
+            }
+
+            fn return_wrapper() -> Option<Expr> {
+                Some(integer_digits_wrapper())
+            }
+        }
+    };
+}
+
+impl_arg_traits_for_int128!(i128);
+impl_arg_traits_for_int128!(u128);
+
+//======================================
+// Utilities
+//======================================
+
+/// `Replace[digits_String :> ToExpression[digits]]`
+///
+/// Only a `String` result is converted, so that `LibraryFunctionError[..]` results are
+/// returned unchanged.
+pub(super) fn integer_digits_wrapper() -> Expr {
+    let digits = Expr::from(Symbol::new("RustLink`Private`digits"));
+
+    Expr::normal(Symbol::new("System`Replace"), vec![Expr::normal(
+        Symbol::new("System`RuleDelayed"),
+        vec![
+            Expr::normal(Symbol::new("System`Pattern"), vec![
+                digits.clone(),
+                Expr::normal(Symbol::new("System`Blank"), vec![Expr::from(Symbol::new(
+                    "System`String",
+                ))]),
+            ]),
+            Expr::normal(Symbol::new("System`ToExpression"), vec![digits]),
+        ],
+    )])
+}
+
+fn parse<T>(string: &str) -> Result<T, String>
+where
+    T: std::str::FromStr<Err = std::num::ParseIntError>,
+{
+    string.parse().map_err(|err| {
+        format!(
+            "invalid {} integer {:?}: {}",
+            std::any::type_name::<T>(),
+            string,
+            err
+        )
+    })
+}
+
+/// Read the decimal digits of an integer of any size from `link`.
+pub(super) fn get_integer_digits(link: &mut Link) -> Result<String, String> {
+    let raw_link = unsafe { link.raw_link() };
+
+    let mut digits: *const c_char = std::ptr::null();
+
+    if unsafe { wstp::sys::WSGetNumberAsString(raw_link, &mut digits) } == 0 {
+        return Err(link_error(link, "expected Integer"));
+    }
+
+    let string = unsafe { CStr::from_ptr(digits) }
+        .to_string_lossy()
+        .into_owned();
+
+    unsafe { wstp::sys::WSReleaseString(raw_link, digits) };
+
+    Ok(string)
+}
+
+/// Write an integer of any size, given as a string of decimal digits, to `link`.
+pub(super) fn put_integer_digits(link: &mut Link, digits: &str) -> Result<(), String> {
+    // Integers that fit in an i64 can be written directly.
+    if let Ok(value) = digits.parse::<i64>() {
+        return link.put_i64(value).map_err(|err| err.to_string());
+    }
+
+    // Larger integers are written as a WSTKINT token containing their decimal digits.
+    let length = c_int::try_from(digits.len())
+        .map_err(|_| format!("integer has too many digits: {}", digits.len()))?;
+
+    let raw_link = unsafe { link.raw_link() };
+
+    let ok = unsafe {
+        wstp::sys::WSPutNext(raw_link, WSTKINT) != 0
+            && wstp::sys::WSPutSize(raw_link, length) != 0
+            && wstp::sys::WSPutData(raw_link, digits.as_ptr() as *const c_char, length)
+                != 0
+    };
+
+    if !ok {
+        return Err(link_error(link, "failed to put Integer"));
+    }
+
+    Ok(())
+}
+
+fn link_error(link: &Link, context: &str) -> String {
+    match link.error_message() {
+        Some(message) => format!("{}: {}", context, message),
+        None => context.to_owned(),
+    }
+}
@@ -0,0 +1,108 @@
+//! Conversions for [`serde_json::Value`].
+//!
+//! JSON values are represented using the same expressions produced by
+//! [`ImportString`][ref/ImportString]<sub>WL</sub>`[json, "RawJSON"]`:
+//!
+//! JSON             | Wolfram Language expression
+//! -----------------|----------------------------------------
+//! object           | [`Association`][ref/Association]<sub>WL</sub>`[key -> value, ...]`
+//! array            | `List[...]`
+//! string           | `String`
+//! number           | `Integer` or `Real`
+//! `true`, `false`  | `True`, `False`
+//! `null`           | `Null`
+//!
+//! [ref/ImportString]: https://reference.wolfram.com/language/ref/ImportString.html
+//! [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+
+use serde_json::{Map, Number as JsonNumber, Value};
+
+use crate::{
+    convert::{list, mismatch, normal_elements, FromExpr, ToExpr},
+    expr::{Expr, ExprKind, Symbol},
+};
+
+impl ToExpr for Value {
+    fn to_expr(&self) -> Expr {
+        match self {
+            Value::Null => Expr::symbol(Symbol::new("System`Null")),
+            Value::Bool(bool) => Expr::from(*bool),
+            Value::Number(number) => match number.as_i64() {
+                Some(int) => Expr::from(int),
+                // Integers larger than i64::MAX, and all non-integer values.
+                None => number.as_f64().unwrap_or(f64::NAN).to_expr(),
+            },
+            Value::String(string) => Expr::string(string.as_str()),
+            Value::Array(array) => list(array.iter().map(ToExpr::to_expr).collect()),
+            Value::Object(object) => {
+                let rules = object
+                    .iter()
+                    .map(|(key, value)| {
+                        Expr::normal(Symbol::new("System`Rule"), vec![
+                            Expr::string(key.as_str()),
+                            value.to_expr(),
+                        ])
+                    })
+                    .collect();
+
+                Expr::normal(Symbol::new("System`Association"), rules)
+            },
+        }
+    }
+}
+
+impl FromExpr for Value {
+    /// Accepts the expressions listed in the table above. `Real` values must be finite,
+    /// and `Association` keys must be strings.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        let value = match expr.kind() {
+            ExprKind::Integer(int) => Value::from(*int),
+            ExprKind::Real(real) => JsonNumber::from_f64(**real)
+                .map(Value::Number)
+                .ok_or_else(|| format!("JSON numbers must be finite, got {}", expr))?,
+            ExprKind::String(string) => Value::String(string.clone()),
+            ExprKind::Symbol(symbol) => match symbol.as_str() {
+                "System`True" => Value::Bool(true),
+                "System`False" => Value::Bool(false),
+                "System`Null" => Value::Null,
+                _ => return Err(mismatch("JSON value", expr)),
+            },
+            ExprKind::Normal(_) => {
+                if let Some(rules) = normal_elements(expr, "System`Association") {
+                    Value::Object(object_from_rules(rules)?)
+                } else if normal_elements(expr, "System`List").is_some() {
+                    Value::Array(Vec::from_expr(expr)?)
+                } else {
+                    return Err(mismatch("JSON value", expr));
+                }
+            },
+        };
+
+        Ok(value)
+    }
+}
+
+fn object_from_rules(rules: &[Expr]) -> Result<Map<String, Value>, String> {
+    let mut object = Map::new();
+
+    for rule in rules {
+        let (key, value) = match normal_elements(rule, "System`Rule")
+            .or_else(|| normal_elements(rule, "System`RuleDelayed"))
+        {
+            Some([key, value]) => (key, value),
+            _ => return Err(mismatch("key -> value", rule)),
+        };
+
+        let key = match key.kind() {
+            ExprKind::String(key) => key.clone(),
+            _ => return Err(mismatch("JSON object key String", key)),
+        };
+
+        let value = Value::from_expr(value)
+            .map_err(|err| format!("at key {:?}: {}", key, err))?;
+
+        object.insert(key, value);
+    }
+
+    Ok(object)
+}
@@ -0,0 +1,141 @@
+//! Conversions from [`petgraph`] graphs to Wolfram Language
+//! [`Graph`][ref/Graph]<sub>WL</sub> expressions.
+//!
+//! [ref/Graph]: https://reference.wolfram.com/language/ref/Graph.html
+
+use petgraph::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeReferences, NodeIndexable, NodeRef,
+};
+
+use crate::{
+    convert::{list, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+/// Convert `graph` into a [`Graph`][ref/Graph]<sub>WL</sub>`[{v1, ...}, {e1, ...}]`
+/// expression.
+///
+/// Vertices are the node weights of `graph`, converted using [`ToExpr`]. Node weights
+/// should be unique, because the Wolfram Language identifies vertices by value.
+/// Edges are [`DirectedEdge`][ref/DirectedEdge]<sub>WL</sub> or
+/// [`UndirectedEdge`][ref/UndirectedEdge]<sub>WL</sub> expressions, depending on
+/// whether `graph` is directed. Edge weights are ignored; use [`weighted_graph_to_expr()`]
+/// to include them.
+///
+/// Any [`petgraph`] graph type can be converted, including
+/// [`Graph`][petgraph::graph::Graph], `StableGraph`, and `GraphMap`.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use petgraph::graph::DiGraph;
+/// use wolfram_library_link::{self as wll, convert::graph_to_expr, expr::Expr};
+///
+/// wll::export_wstp![dependency_graph(_)];
+///
+/// fn dependency_graph(_args: Vec<Expr>) -> Expr {
+///     let mut graph = DiGraph::<String, ()>::new();
+///
+///     let app = graph.add_node("app".to_owned());
+///     let core = graph.add_node("core".to_owned());
+///     let sys = graph.add_node("sys".to_owned());
+///
+///     graph.extend_with_edges(&[(app, core), (core, sys)]);
+///
+///     graph_to_expr(&graph)
+/// }
+/// # }
+/// ```
+///
+/// [ref/Graph]: https://reference.wolfram.com/language/ref/Graph.html
+/// [ref/DirectedEdge]: https://reference.wolfram.com/language/ref/DirectedEdge.html
+/// [ref/UndirectedEdge]: https://reference.wolfram.com/language/ref/UndirectedEdge.html
+pub fn graph_to_expr<G>(graph: G) -> Expr
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::NodeWeight: ToExpr,
+{
+    let (vertices, edges) = vertices_and_edges(graph);
+
+    Expr::normal(Symbol::new("System`Graph"), vec![vertices, edges])
+}
+
+/// Convert `graph` into a [`Graph`][ref/Graph]<sub>WL</sub> expression whose
+/// [`EdgeWeight`][ref/EdgeWeight]<sub>WL</sub> option is set from the edge weights of
+/// `graph`:
+///
+/// ```wolfram
+/// Graph[{v1, ...}, {e1, ...}, EdgeWeight -> {w1, ...}]
+/// ```
+///
+/// See [`graph_to_expr()`] for how vertices and edges are converted.
+///
+/// [ref/Graph]: https://reference.wolfram.com/language/ref/Graph.html
+/// [ref/EdgeWeight]: https://reference.wolfram.com/language/ref/EdgeWeight.html
+pub fn weighted_graph_to_expr<G>(graph: G) -> Expr
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::NodeWeight: ToExpr,
+    G::EdgeWeight: ToExpr,
+{
+    let (vertices, edges) = vertices_and_edges(graph);
+
+    let weights = list(
+        graph
+            .edge_references()
+            .map(|edge| edge.weight().to_expr())
+            .collect(),
+    );
+
+    Expr::normal(Symbol::new("System`Graph"), vec![
+        vertices,
+        edges,
+        Expr::normal(Symbol::new("System`Rule"), vec![
+            Expr::symbol(Symbol::new("System`EdgeWeight")),
+            weights,
+        ]),
+    ])
+}
+
+/// Returns the `{v1, ...}` vertex list and the `{e1, ...}` edge list of `graph`.
+fn vertices_and_edges<G>(graph: G) -> (Expr, Expr)
+where
+    G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+    G::NodeWeight: ToExpr,
+{
+    // Vertex expressions, indexed by `NodeIndexable::to_index()`.
+    let mut vertex_exprs: Vec<Option<Expr>> = vec![None; graph.node_bound()];
+
+    for node in graph.node_references() {
+        vertex_exprs[graph.to_index(node.id())] = Some(node.weight().to_expr());
+    }
+
+    let edge_head = match graph.is_directed() {
+        true => Symbol::new("System`DirectedEdge"),
+        false => Symbol::new("System`UndirectedEdge"),
+    };
+
+    let vertex = |id| {
+        vertex_exprs[graph.to_index(id)]
+            .clone()
+            .expect("petgraph edge endpoint is not a node of the graph")
+    };
+
+    let edges = graph
+        .edge_references()
+        .map(|edge| {
+            Expr::normal(edge_head.clone(), vec![
+                vertex(edge.source()),
+                vertex(edge.target()),
+            ])
+        })
+        .collect();
+
+    let vertices = graph
+        .node_references()
+        .map(|node| vertex(node.id()))
+        .collect();
+
+    (list(vertices), list(edges))
+}
@@ -0,0 +1,213 @@
+//! Conversions for [`polars`](https://pola.rs) [`DataFrame`]s.
+//!
+//! A [`DataFrame`] can be returned to the Wolfram Language in two forms:
+//!
+//! * Using [`ToExpr`], as a list of associations, one for each row, with the column
+//!   names as keys. This is the form expected by
+//!   [`Dataset`][ref/Dataset]<sub>WL</sub>. Null values are represented as
+//!   [`Missing[]`][ref/Missing]<sub>WL</sub>.
+//! * Using [`IntoArg`], as a [`DataStore`] with one named node for each column.
+//!   Numeric columns are stored as [`NumericArray`]s, and boolean and string columns
+//!   are stored as nested [`DataStore`]s containing one node per value.
+//!
+//! [ref/Dataset]: https://reference.wolfram.com/language/ref/Dataset.html
+//! [ref/Missing]: https://reference.wolfram.com/language/ref/Missing.html
+
+use std::convert::TryFrom;
+
+use polars_core::{
+    datatypes::{AnyValue, DataType},
+    frame::{column::Column, DataFrame},
+};
+
+use crate::{
+    convert::{list, ToExpr},
+    expr::{Expr, Symbol},
+    sys::MArgument,
+    DataStore, IntoArg, NumericArray,
+};
+
+//======================================
+// ToExpr
+//======================================
+
+impl ToExpr for DataFrame {
+    /// `{<| "column" -> value, ... |>, ...}`
+    fn to_expr(&self) -> Expr {
+        let columns = self.get_columns();
+
+        let rows = (0..self.height())
+            .map(|row| {
+                let rules = columns
+                    .iter()
+                    .map(|column| {
+                        let value = match column.get(row) {
+                            Ok(value) => any_value_to_expr(&value),
+                            Err(_) => missing(),
+                        };
+
+                        Expr::normal(Symbol::new("System`Rule"), vec![
+                            Expr::string(column.name().as_str()),
+                            value,
+                        ])
+                    })
+                    .collect();
+
+                Expr::normal(Symbol::new("System`Association"), rules)
+            })
+            .collect();
+
+        list(rows)
+    }
+}
+
+fn any_value_to_expr(value: &AnyValue) -> Expr {
+    match value {
+        AnyValue::Null => missing(),
+        AnyValue::Boolean(bool) => Expr::from(*bool),
+        AnyValue::String(string) => Expr::string(*string),
+        AnyValue::StringOwned(string) => Expr::string(string.as_str()),
+        AnyValue::Int8(int) => Expr::from(i64::from(*int)),
+        AnyValue::Int16(int) => Expr::from(i64::from(*int)),
+        AnyValue::Int32(int) => Expr::from(i64::from(*int)),
+        AnyValue::Int64(int) => Expr::from(*int),
+        AnyValue::UInt8(int) => Expr::from(i64::from(*int)),
+        AnyValue::UInt16(int) => Expr::from(i64::from(*int)),
+        AnyValue::UInt32(int) => Expr::from(i64::from(*int)),
+        AnyValue::UInt64(int) => match i64::try_from(*int) {
+            Ok(int) => Expr::from(int),
+            // Integers larger than i64::MAX cannot be represented by `Expr`.
+            Err(_) => (*int as f64).to_expr(),
+        },
+        AnyValue::Float32(real) => f64::from(*real).to_expr(),
+        AnyValue::Float64(real) => real.to_expr(),
+        AnyValue::List(series) => list(
+            series
+                .iter()
+                .map(|value| any_value_to_expr(&value))
+                .collect(),
+        ),
+        // Fall back to the polars display representation of other data types.
+        other => Expr::string(other.to_string()),
+    }
+}
+
+fn missing() -> Expr {
+    Expr::normal(Symbol::new("System`Missing"), vec![])
+}
+
+//======================================
+// DataStore
+//======================================
+
+/// Construct a [`DataStore`] containing one named node for each column of a
+/// [`DataFrame`].
+///
+/// Columns containing null values, and columns whose data type is not a boolean,
+/// string, integer, or floating-point type, are not supported.
+impl TryFrom<&DataFrame> for DataStore {
+    type Error = String;
+
+    fn try_from(frame: &DataFrame) -> Result<Self, String> {
+        let mut store = DataStore::new();
+
+        for column in frame.get_columns() {
+            let name = column.name().as_str();
+
+            if column.null_count() != 0 {
+                return Err(format!("column {:?} contains null values", name));
+            }
+
+            match column.dtype() {
+                DataType::Boolean | DataType::String => {
+                    store.add_named_data_store(name, values_data_store(column)?)
+                },
+                _ => store.add_named_numeric_array(name, numeric_array(column)?),
+            }
+        }
+
+        Ok(store)
+    }
+}
+
+/// The [`DataFrame`] is returned as a [`DataStore`].
+///
+/// # Panics
+///
+/// This conversion will panic if the `DataFrame` cannot be converted into a `DataStore`.
+/// See `impl TryFrom<&DataFrame> for DataStore`.
+impl IntoArg for DataFrame {
+    unsafe fn into_arg(self, arg: MArgument) {
+        let store = DataStore::try_from(&self).unwrap_or_else(|err| {
+            panic!(
+                "IntoArg for DataFrame: could not convert to DataStore: {}",
+                err
+            )
+        });
+
+        store.into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        DataStore::return_type()
+    }
+}
+
+fn numeric_array(column: &Column) -> Result<NumericArray, String> {
+    let series = column.as_materialized_series();
+
+    macro_rules! convert {
+        ($($data_type:ident => $method:ident),*) => {
+            match column.dtype() {
+                $(
+                    DataType::$data_type => {
+                        let values: Vec<_> = series
+                            .$method()
+                            .map_err(|err| err.to_string())?
+                            .into_no_null_iter()
+                            .collect();
+
+                        NumericArray::from_slice(&values).into_generic()
+                    },
+                )*
+                other => {
+                    return Err(format!(
+                        "column {:?} has unsupported data type: {}",
+                        column.name().as_str(),
+                        other
+                    ))
+                },
+            }
+        };
+    }
+
+    let array = convert! {
+        Int8 => i8,
+        Int16 => i16,
+        Int32 => i32,
+        Int64 => i64,
+        UInt8 => u8,
+        UInt16 => u16,
+        UInt32 => u32,
+        UInt64 => u64,
+        Float32 => f32,
+        Float64 => f64
+    };
+
+    Ok(array)
+}
+
+fn values_data_store(column: &Column) -> Result<DataStore, String> {
+    let mut store = DataStore::new();
+
+    for index in 0..column.len() {
+        match column.get(index).map_err(|err| err.to_string())? {
+            AnyValue::Boolean(bool) => store.add_bool(bool),
+            AnyValue::String(string) => store.add_str(string),
+            AnyValue::StringOwned(string) => store.add_str(string.as_str()),
+            other => return Err(format!("unexpected value in column: {}", other)),
+        }
+    }
+
+    Ok(store)
+}
@@ -0,0 +1,66 @@
+//! Conversions for [`Ratio<i64>`] values.
+//!
+//! Exact rational numbers are represented in the Wolfram Language as
+//! [`Rational`][ref/Rational]<sub>WL</sub>`[p, q]` expressions. Rational numbers whose
+//! denominator is 1 are represented as an [`Integer`][ref/Integer]<sub>WL</sub>.
+//!
+//! [ref/Rational]: https://reference.wolfram.com/language/ref/Rational.html
+//! [ref/Integer]: https://reference.wolfram.com/language/ref/Integer.html
+
+use num_rational::Ratio;
+
+use crate::{
+    convert::{mismatch, normal_elements, FromExpr, ToExpr},
+    expr::{Expr, ExprKind, Symbol},
+};
+
+impl ToExpr for Ratio<i64> {
+    /// `Rational[p, q]`, or `p` if the denominator is 1.
+    fn to_expr(&self) -> Expr {
+        if self.is_integer() {
+            return Expr::from(*self.numer());
+        }
+
+        Expr::normal(Symbol::new("System`Rational"), vec![
+            Expr::from(*self.numer()),
+            Expr::from(*self.denom()),
+        ])
+    }
+}
+
+impl FromExpr for Ratio<i64> {
+    /// Accepts a `Rational[p, q]` expression, or an `Integer`.
+    ///
+    /// `Real` values are not accepted, so that inexact values are not silently treated as
+    /// exact rational numbers.
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        if let ExprKind::Integer(int) = expr.kind() {
+            return Ok(Ratio::from_integer(*int));
+        }
+
+        let (numer, denom) = match normal_elements(expr, "System`Rational") {
+            Some([numer, denom]) => (integer(numer, expr)?, integer(denom, expr)?),
+            _ => return Err(mismatch("Rational[p, q] or Integer", expr)),
+        };
+
+        if denom == 0 {
+            return Err(format!("rational number has zero denominator: {}", expr));
+        }
+
+        // Normalize using `i128`, because making the denominator positive overflows if
+        // either part is `i64::MIN`.
+        let ratio = Ratio::new(i128::from(numer), i128::from(denom));
+
+        match (i64::try_from(*ratio.numer()), i64::try_from(*ratio.denom())) {
+            (Ok(numer), Ok(denom)) => Ok(Ratio::new_raw(numer, denom)),
+            _ => Err(format!("rational number out of range: {}", expr)),
+        }
+    }
+}
+
+fn integer(part: &Expr, expr: &Expr) -> Result<i64, String> {
+    match part.kind() {
+        ExprKind::Integer(int) => Ok(*int),
+        _ => Err(mismatch("Rational[p, q] with Integer p and q", expr)),
+    }
+}
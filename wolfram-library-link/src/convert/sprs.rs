@@ -0,0 +1,146 @@
+//! Conversions between [`SparseArray`] and [`sprs`] sparse matrices.
+//!
+//! A rank 2 [`SparseArray`] with an implicit value of `0` is converted to and from a
+//! compressed sparse row [`CsMat`] without materializing the dense matrix. Both
+//! [`Real`][ref/Real]<sub>WL</sub> (`f64`) and [`Integer`][ref/Integer]<sub>WL</sub>
+//! (`i64`) matrices are supported.
+//!
+//! [ref/Real]: https://reference.wolfram.com/language/ref/Real.html
+//! [ref/Integer]: https://reference.wolfram.com/language/ref/Integer.html
+
+use sprs::CsMat;
+
+use crate::{
+    sparse_array::{
+        new_integer_tensor, new_real_tensor, tensor_integer_data, tensor_real_data,
+        tensor_type,
+    },
+    sys::{self, mint},
+    SparseArray,
+};
+
+macro_rules! sparse_matrix_conversions {
+    ($elem:ty, $new_tensor:ident) => {
+        impl TryFrom<&SparseArray> for CsMat<$elem> {
+            type Error = String;
+
+            /// Fails if `sparse` is not a rank 2 matrix whose implicit value is `0`.
+            fn try_from(sparse: &SparseArray) -> Result<Self, String> {
+                let (rows, cols) = matrix_shape(sparse)?;
+
+                let values =
+                    unsafe { <$elem>::explicit_values(sparse.explicit_values_raw())? };
+
+                let indptr: Vec<usize> = sparse
+                    .row_pointers()
+                    .iter()
+                    .map(|&offset| offset as usize)
+                    .collect();
+
+                // Column indices are 1-based.
+                let indices: Vec<usize> = sparse
+                    .column_indices()
+                    .iter()
+                    .map(|&index| (index - 1) as usize)
+                    .collect();
+
+                CsMat::try_new((rows, cols), indptr, indices, values).map_err(
+                    |(_, _, _, err)| format!("invalid SparseArray structure: {}", err),
+                )
+            }
+        }
+
+        impl From<&CsMat<$elem>> for SparseArray {
+            fn from(matrix: &CsMat<$elem>) -> SparseArray {
+                let nnz = matrix.nnz();
+
+                let mut positions: Vec<mint> = Vec::with_capacity(2 * nnz);
+                let mut values: Vec<$elem> = Vec::with_capacity(nnz);
+
+                for (&value, (row, col)) in matrix.iter() {
+                    // Explicit positions are 1-based.
+                    positions.extend([row as mint + 1, col as mint + 1]);
+                    values.push(value);
+                }
+
+                let dims = [matrix.rows() as mint, matrix.cols() as mint];
+
+                unsafe {
+                    SparseArray::from_explicit_positions(
+                        new_integer_tensor(&[nnz, 2], &positions),
+                        $new_tensor(&[nnz], &values),
+                        new_integer_tensor(&[2], &dims),
+                        $new_tensor(&[], &[<$elem>::default()]),
+                    )
+                }
+                .unwrap_or_else(|err_code| {
+                    panic!(
+                        "MSparseArray_fromExplicitPositions failed with error code {}",
+                        err_code
+                    )
+                })
+            }
+        }
+    };
+}
+
+sparse_matrix_conversions!(f64, new_real_tensor);
+sparse_matrix_conversions!(i64, new_integer_tensor);
+
+/// Get the `(rows, cols)` shape of `sparse`, which must be a matrix whose implicit value
+/// is `0`.
+fn matrix_shape(sparse: &SparseArray) -> Result<(usize, usize), String> {
+    let (rows, cols) = match *sparse.dimensions() {
+        [rows, cols] => (rows, cols),
+        ref dims => {
+            return Err(format!(
+                "expected SparseArray of rank 2, got rank {}",
+                dims.len()
+            ))
+        },
+    };
+
+    let implicit = sparse.implicit_value_raw();
+
+    let is_zero = unsafe {
+        match tensor_type(implicit) {
+            sys::MType_Integer => tensor_integer_data(implicit) == [0],
+            sys::MType_Real => tensor_real_data(implicit) == [0.0],
+            _ => false,
+        }
+    };
+
+    if !is_zero {
+        return Err("expected SparseArray with an implicit value of 0".to_owned());
+    }
+
+    Ok((rows, cols))
+}
+
+/// Element types of the explicit values of a [`SparseArray`].
+trait ExplicitValue: Sized {
+    unsafe fn explicit_values(tensor: sys::MTensor) -> Result<Vec<Self>, String>;
+}
+
+impl ExplicitValue for f64 {
+    /// Integer values are converted to the nearest `f64` value.
+    unsafe fn explicit_values(tensor: sys::MTensor) -> Result<Vec<f64>, String> {
+        match tensor_type(tensor) {
+            sys::MType_Real => Ok(tensor_real_data(tensor).to_vec()),
+            sys::MType_Integer => Ok(tensor_integer_data(tensor)
+                .iter()
+                .map(|&int| int as f64)
+                .collect()),
+            _ => Err("expected SparseArray of Real or Integer values".to_owned()),
+        }
+    }
+}
+
+impl ExplicitValue for i64 {
+    unsafe fn explicit_values(tensor: sys::MTensor) -> Result<Vec<i64>, String> {
+        match tensor_type(tensor) {
+            sys::MType_Integer => Ok(tensor_integer_data(tensor).to_vec()),
+            _ => Err("expected SparseArray of Integer values".to_owned()),
+        }
+    }
+}
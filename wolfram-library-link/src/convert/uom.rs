@@ -0,0 +1,190 @@
+//! Conversions for [`uom`] quantities.
+//!
+//! Quantities are represented in the Wolfram Language as
+//! [`Quantity`][ref/Quantity]<sub>WL</sub>`[magnitude, "Unit"]` expressions.
+//!
+//! [`ToExpr`] always uses the first unit listed for a quantity type in the table below.
+//! [`FromExpr`] accepts any of the units listed for the quantity type, and converts the
+//! magnitude to the unit used by the `uom` quantity value. Quantities using other
+//! units can be converted to a supported unit in the Wolfram Language using
+//! [`UnitConvert`][ref/UnitConvert]<sub>WL</sub>.
+//!
+//! [ref/Quantity]: https://reference.wolfram.com/language/ref/Quantity.html
+//! [ref/UnitConvert]: https://reference.wolfram.com/language/ref/UnitConvert.html
+
+use uom::si::{
+    acceleration, area, electric_current, electric_potential, energy,
+    f64::{
+        Acceleration, Area, ElectricCurrent, ElectricPotential, Energy, Force, Frequency,
+        Length, Mass, Power, Pressure, ThermodynamicTemperature, Time, Velocity, Volume,
+    },
+    force, frequency, length, mass, power, pressure, thermodynamic_temperature, time,
+    velocity, volume,
+};
+
+use crate::{
+    convert::{mismatch, normal_elements, FromExpr, ToExpr},
+    expr::{Expr, ExprKind, Symbol},
+};
+
+/// Implement [`ToExpr`] and [`FromExpr`] for a `uom` quantity type, using a table
+/// mapping Wolfram Language unit names to `uom` units.
+///
+/// The first unit in the table is used by [`ToExpr`].
+macro_rules! quantities {
+    ($(
+        $quantity:ident {
+            $first_name:literal => $first_unit:path,
+            $($name:literal => $unit:path,)*
+        }
+    )*) => {
+        $(
+            impl ToExpr for $quantity {
+                #[doc = concat!("`Quantity[magnitude, \"", $first_name, "\"]`")]
+                fn to_expr(&self) -> Expr {
+                    quantity(self.get::<$first_unit>(), $first_name)
+                }
+            }
+
+            impl FromExpr for $quantity {
+                #[doc = concat!(
+                    "Accepts a `Quantity[magnitude, \"Unit\"]` expression, where `Unit` is ",
+                    "one of: `\"", $first_name, "\"`", $(", `\"", $name, "\"`",)* "."
+                )]
+                fn from_expr(expr: &Expr) -> Result<Self, String> {
+                    let (magnitude, unit) = parse_quantity(expr)?;
+
+                    let value = match unit {
+                        $first_name => $quantity::new::<$first_unit>(magnitude),
+                        $($name => $quantity::new::<$unit>(magnitude),)*
+                        _ => return Err(unsupported_unit(stringify!($quantity), unit)),
+                    };
+
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+quantities! {
+    Length {
+        "Meters" => length::meter,
+        "Kilometers" => length::kilometer,
+        "Centimeters" => length::centimeter,
+        "Millimeters" => length::millimeter,
+        "Micrometers" => length::micrometer,
+        "Nanometers" => length::nanometer,
+        "Inches" => length::inch,
+        "Feet" => length::foot,
+        "Yards" => length::yard,
+        "Miles" => length::mile,
+    }
+    Mass {
+        "Kilograms" => mass::kilogram,
+        "Grams" => mass::gram,
+        "Milligrams" => mass::milligram,
+        "MetricTons" => mass::ton,
+        "Ounces" => mass::ounce,
+        "Pounds" => mass::pound,
+    }
+    Time {
+        "Seconds" => time::second,
+        "Milliseconds" => time::millisecond,
+        "Microseconds" => time::microsecond,
+        "Nanoseconds" => time::nanosecond,
+        "Minutes" => time::minute,
+        "Hours" => time::hour,
+        "Days" => time::day,
+    }
+    Velocity {
+        "MetersPerSecond" => velocity::meter_per_second,
+        "KilometersPerHour" => velocity::kilometer_per_hour,
+        "MilesPerHour" => velocity::mile_per_hour,
+    }
+    Acceleration {
+        "MetersPerSecondSquared" => acceleration::meter_per_second_squared,
+    }
+    Force {
+        "Newtons" => force::newton,
+    }
+    Energy {
+        "Joules" => energy::joule,
+        "Kilojoules" => energy::kilojoule,
+        "KilowattHours" => energy::kilowatt_hour,
+        "Electronvolts" => energy::electronvolt,
+    }
+    Power {
+        "Watts" => power::watt,
+        "Kilowatts" => power::kilowatt,
+        "Megawatts" => power::megawatt,
+    }
+    Pressure {
+        "Pascals" => pressure::pascal,
+        "Kilopascals" => pressure::kilopascal,
+        "Bars" => pressure::bar,
+        "Atmospheres" => pressure::atmosphere,
+    }
+    ThermodynamicTemperature {
+        "Kelvins" => thermodynamic_temperature::kelvin,
+        "DegreesCelsius" => thermodynamic_temperature::degree_celsius,
+        "DegreesFahrenheit" => thermodynamic_temperature::degree_fahrenheit,
+    }
+    ElectricCurrent {
+        "Amperes" => electric_current::ampere,
+        "Milliamperes" => electric_current::milliampere,
+    }
+    ElectricPotential {
+        "Volts" => electric_potential::volt,
+        "Millivolts" => electric_potential::millivolt,
+        "Kilovolts" => electric_potential::kilovolt,
+    }
+    Frequency {
+        "Hertz" => frequency::hertz,
+        "Kilohertz" => frequency::kilohertz,
+        "Megahertz" => frequency::megahertz,
+        "Gigahertz" => frequency::gigahertz,
+    }
+    Area {
+        "SquareMeters" => area::square_meter,
+        "SquareKilometers" => area::square_kilometer,
+        "SquareCentimeters" => area::square_centimeter,
+        "Hectares" => area::hectare,
+    }
+    Volume {
+        "CubicMeters" => volume::cubic_meter,
+        "Liters" => volume::liter,
+        "Milliliters" => volume::milliliter,
+        "CubicCentimeters" => volume::cubic_centimeter,
+    }
+}
+
+/// Construct `Quantity[magnitude, "unit"]`.
+fn quantity(magnitude: f64, unit: &str) -> Expr {
+    Expr::normal(Symbol::new("System`Quantity"), vec![
+        magnitude.to_expr(),
+        Expr::string(unit),
+    ])
+}
+
+/// Parse `Quantity[magnitude, "unit"]`.
+fn parse_quantity(expr: &Expr) -> Result<(f64, &str), String> {
+    let (magnitude, unit) = match normal_elements(expr, "System`Quantity") {
+        Some([magnitude, unit]) => (magnitude, unit),
+        _ => return Err(mismatch("Quantity[magnitude, \"Unit\"]", expr)),
+    };
+
+    let magnitude = f64::from_expr(magnitude)
+        .map_err(|_| mismatch("Quantity with a numeric magnitude", expr))?;
+
+    let unit = match unit.kind() {
+        ExprKind::String(unit) => unit.as_str(),
+        _ => return Err(mismatch("Quantity with a unit name string", expr)),
+    };
+
+    Ok((magnitude, unit))
+}
+
+fn unsupported_unit(quantity: &str, unit: &str) -> String {
+    format!("unsupported unit for {} quantity: {:?}", quantity, unit)
+}
@@ -0,0 +1,94 @@
+//! Conversions for [`Uuid`] values.
+//!
+//! A [`Uuid`] is represented in the Wolfram Language as a string in the standard
+//! hyphenated form, e.g. `"67e55044-10b1-426f-9247-bb680e5fe0c8"`, matching the strings
+//! returned by [`CreateUUID`][ref/CreateUUID]<sub>WL</sub>. A [`Uuid`] can also be
+//! converted to and from a [`NumericArray<u8>`] containing its 16 bytes.
+//!
+//! [ref/CreateUUID]: https://reference.wolfram.com/language/ref/CreateUUID.html
+
+use std::convert::TryFrom;
+
+use uuid::Uuid;
+
+use crate::{
+    convert::{mismatch, FromExpr, ToExpr},
+    expr::{Expr, ExprKind, Symbol},
+    sys::MArgument,
+    FromArg, IntoArg, NumericArray, TryFromArg,
+};
+
+impl ToExpr for Uuid {
+    fn to_expr(&self) -> Expr {
+        Expr::string(self.hyphenated().to_string())
+    }
+}
+
+impl FromExpr for Uuid {
+    /// Accepts a string containing a UUID in any of the formats supported by
+    /// [`Uuid::parse_str()`].
+    fn from_expr(expr: &Expr) -> Result<Self, String> {
+        match expr.kind() {
+            ExprKind::String(string) => parse(string),
+            _ => Err(mismatch("UUID string", expr)),
+        }
+    }
+}
+
+/// Conversion fails if the argument is not a valid UUID string (see [`TryFromArg`]).
+/// Use [`FromExpr`] in a function exported using [`export_wstp!`][crate::export_wstp]
+/// to return the validation error as a `Failure[..]` instead.
+impl TryFromArg<'_> for Uuid {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Self, String> {
+        let string = String::from_arg(arg);
+
+        parse(&string)
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
+
+impl IntoArg for Uuid {
+    unsafe fn into_arg(self, arg: MArgument) {
+        self.hyphenated().to_string().into_arg(arg)
+    }
+
+    fn return_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
+
+/// Construct a rank 1 [`NumericArray`] containing the 16 bytes of a [`Uuid`].
+impl From<Uuid> for NumericArray<u8> {
+    fn from(uuid: Uuid) -> Self {
+        NumericArray::from_slice(uuid.as_bytes())
+    }
+}
+
+/// Construct a [`Uuid`] from a rank 1 [`NumericArray`] of length 16.
+impl TryFrom<&NumericArray<u8>> for Uuid {
+    type Error = String;
+
+    fn try_from(array: &NumericArray<u8>) -> Result<Self, String> {
+        if array.rank() != 1 {
+            return Err(format!(
+                "expected rank 1 NumericArray of 16 bytes, got array of rank {}",
+                array.rank()
+            ));
+        }
+
+        Uuid::from_slice(array.as_slice()).map_err(|err| {
+            format!(
+                "invalid UUID NumericArray of length {}: {}",
+                array.flattened_length(),
+                err
+            )
+        })
+    }
+}
+
+fn parse(string: &str) -> Result<Uuid, String> {
+    Uuid::parse_str(string).map_err(|err| format!("invalid UUID {:?}: {}", string, err))
+}
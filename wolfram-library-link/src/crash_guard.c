@@ -0,0 +1,102 @@
+/*
+ * Signal handlers used by the `crash-guard` feature. See `crash_guard.rs`.
+ *
+ * `sigsetjmp()` returns twice, which Rust code cannot do, so the guarded call and the
+ * signal handler that jumps back to it are implemented in C.
+ */
+
+#include <setjmp.h>
+#include <signal.h>
+#include <stddef.h>
+#include <string.h>
+
+#define SIGNAL_COUNT 3
+
+static const int GUARDED_SIGNALS[SIGNAL_COUNT] = {SIGSEGV, SIGFPE, SIGBUS};
+
+/* Handlers that were installed before `wll_crash_guard_install()` was called. */
+static struct sigaction previous_actions[SIGNAL_COUNT];
+
+/* Jump buffer of the innermost guarded call on the current thread, if any. */
+static __thread sigjmp_buf *current_jump = NULL;
+
+static __thread int caught_signal = 0;
+static __thread void *caught_address = NULL;
+
+/* Pass a signal that did not occur within a guarded call to the previous handler. */
+static void chain_signal(int sig, siginfo_t *info, void *context) {
+    for (int i = 0; i < SIGNAL_COUNT; i++) {
+        if (GUARDED_SIGNALS[i] != sig) {
+            continue;
+        }
+
+        struct sigaction *previous = &previous_actions[i];
+
+        if (previous->sa_flags & SA_SIGINFO) {
+            previous->sa_sigaction(sig, info, context);
+        } else if (previous->sa_handler == SIG_DFL || previous->sa_handler == SIG_IGN) {
+            /* Restore the default action, which terminates the process when the
+               signal is delivered again after this handler returns. */
+            signal(sig, SIG_DFL);
+            raise(sig);
+        } else {
+            previous->sa_handler(sig);
+        }
+
+        return;
+    }
+}
+
+static void handle_signal(int sig, siginfo_t *info, void *context) {
+    sigjmp_buf *jump = current_jump;
+
+    if (jump == NULL) {
+        chain_signal(sig, info, context);
+        return;
+    }
+
+    caught_signal = sig;
+    caught_address = info != NULL ? info->si_addr : NULL;
+
+    siglongjmp(*jump, 1);
+}
+
+int wll_crash_guard_install(void) {
+    struct sigaction action;
+
+    memset(&action, 0, sizeof(action));
+    action.sa_sigaction = handle_signal;
+    action.sa_flags = SA_SIGINFO | SA_ONSTACK;
+    sigemptyset(&action.sa_mask);
+
+    for (int i = 0; i < SIGNAL_COUNT; i++) {
+        if (sigaction(GUARDED_SIGNALS[i], &action, &previous_actions[i]) != 0) {
+            return -1;
+        }
+    }
+
+    return 0;
+}
+
+/*
+ * Call `func(data)`. Returns 0 if `func` returned normally, or the number of the
+ * signal that interrupted it, in which case `*address` is set to the faulting address.
+ */
+int wll_crash_guard_call(void (*func)(void *), void *data, void **address) {
+    sigjmp_buf jump;
+    sigjmp_buf *outer = current_jump;
+
+    /* Save the signal mask, so that the caught signal is unblocked again after
+       jumping out of the signal handler. */
+    if (sigsetjmp(jump, 1) != 0) {
+        current_jump = outer;
+        *address = caught_address;
+        return caught_signal;
+    }
+
+    current_jump = &jump;
+    func(data);
+    current_jump = outer;
+
+    return 0;
+}
@@ -0,0 +1,170 @@
+//! Catch crashes in C code called by a library.
+//!
+//! When the `"crash-guard"` feature is enabled, [`call_guarded()`] calls a C function
+//! with handlers installed for `SIGSEGV`, `SIGFPE` and `SIGBUS`. If one of those
+//! signals is raised while the function is running, control jumps back to
+//! `call_guarded()`, which returns a [`Crash`] describing the signal instead of
+//! letting the crash terminate the Kernel.
+//!
+//! The handlers are installed when the library is initialized by
+//! [`initialize()`][crate::initialize]. Signals raised outside of a guarded call are
+//! passed on to the handler that was installed before, so the Kernel's own crash
+//! handling is unaffected.
+//!
+//! # Requirements
+//!
+//! Jumping out of the signal handler skips the rest of the guarded function, and of
+//! every function it was called from. Skipping Rust stack frames is undefined
+//! behavior, and would leave any locks they hold locked forever, so only functions
+//! that do not call back into Rust code can be guarded, e.g. a function of a C library
+//! that the library wraps. See [`call_guarded()`] for details.
+//!
+//! This is a last resort, intended for libraries that wrap third-party native code they
+//! don't control. The C library may be left in an inconsistent state, so the Kernel
+//! should be restarted after a crash is reported. To run crash-prone code in a separate
+//! process instead, use the `isolation` module, enabled by the `"isolation"` feature.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use std::os::raw::c_void;
+//!
+//! use wolfram_library_link::{self as wll, crash_guard};
+//!
+//! extern "C" {
+//!     // Provided by a C library that this library links against.
+//!     fn parse_document(data: *mut c_void);
+//! }
+//!
+//! wll::export![parse(_)];
+//!
+//! fn parse(path: String) -> String {
+//!     let mut path = path.into_bytes();
+//!
+//!     // Safety: `parse_document()` is a C function that does not call back into Rust.
+//!     let result =
+//!         unsafe { crash_guard::call_guarded(parse_document, path.as_mut_ptr().cast()) };
+//!
+//!     if let Err(crash) = result {
+//!         panic!("parse_document() {}", crash);
+//!     }
+//!
+//!     "parsed".to_owned()
+//! }
+//! # }
+//! ```
+
+use std::{
+    error::Error,
+    fmt,
+    os::raw::{c_int, c_void},
+};
+
+use once_cell::sync::OnceCell;
+
+extern "C" {
+    fn wll_crash_guard_install() -> c_int;
+
+    fn wll_crash_guard_call(
+        func: unsafe extern "C" fn(*mut c_void),
+        data: *mut c_void,
+        address: *mut *mut c_void,
+    ) -> c_int;
+}
+
+static INSTALLED: OnceCell<bool> = OnceCell::new();
+
+/// A signal that interrupted a call to [`call_guarded()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Crash {
+    signal: c_int,
+    address: usize,
+}
+
+/// Install the signal handlers used by [`call_guarded()`], if they have not been
+/// installed already.
+pub(crate) fn install() {
+    let _: &bool = INSTALLED.get_or_init(|| unsafe { wll_crash_guard_install() } == 0);
+}
+
+/// Returns `true` if the signal handlers used by [`call_guarded()`] are installed.
+///
+/// The handlers are installed by [`initialize()`][crate::initialize].
+pub fn is_installed() -> bool {
+    INSTALLED.get() == Some(&true)
+}
+
+/// Call the C function `func` with `data`, returning an error if it is interrupted by
+/// `SIGSEGV`, `SIGFPE` or `SIGBUS`.
+///
+/// If the signal handlers are not [installed][is_installed], `func` is called without
+/// a guard.
+///
+/// # Safety
+///
+/// `func` must be safe to call with `data`. In addition, `func` must not call any Rust
+/// code, including by calling back into this library, the Wolfram Kernel, or a Rust
+/// callback passed to a C library. When a crash is caught, the stack frames of `func`
+/// and of every function it called are skipped without running any cleanup code, which
+/// is undefined behavior for Rust stack frames.
+///
+/// Any locks held and resources owned by the skipped C code when it crashes are never
+/// released.
+pub unsafe fn call_guarded(
+    func: unsafe extern "C" fn(*mut c_void),
+    data: *mut c_void,
+) -> Result<(), Crash> {
+    if !is_installed() {
+        func(data);
+        return Ok(());
+    }
+
+    let mut address: *mut c_void = std::ptr::null_mut();
+
+    let signal = wll_crash_guard_call(func, data, &mut address);
+
+    if signal != 0 {
+        return Err(Crash {
+            signal,
+            address: address as usize,
+        });
+    }
+
+    Ok(())
+}
+
+impl Crash {
+    /// The number of the signal that was raised.
+    pub fn signal(&self) -> c_int {
+        self.signal
+    }
+
+    /// The memory address that caused the fault, if reported by the operating system,
+    /// or 0 otherwise.
+    pub fn address(&self) -> usize {
+        self.address
+    }
+}
+
+impl fmt::Display for Crash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let Crash { signal, address } = *self;
+
+        let name = match signal {
+            libc::SIGSEGV => "SIGSEGV (segmentation fault)".to_owned(),
+            libc::SIGFPE => "SIGFPE (arithmetic error)".to_owned(),
+            libc::SIGBUS => "SIGBUS (bus error)".to_owned(),
+            _ => format!("{}", signal),
+        };
+
+        write!(
+            f,
+            "crashed with signal {} at address {:#x}. The library may be in an \
+            inconsistent state, and the Kernel should be restarted.",
+            name, address
+        )
+    }
+}
+
+impl Error for Crash {}
@@ -7,12 +7,18 @@ use std::{
 
 
 use once_cell::sync::OnceCell;
+use ref_cast::RefCast;
 use static_assertions::assert_not_impl_any;
 
 use crate::{
+    convert::{mismatch, normal_elements, FromExpr},
+    expr::{Expr, ExprKind, Number, Symbol},
+    memory,
+    numeric_array::{numeric_array_from_expr, numeric_array_to_expr},
+    released::{self, Release},
     rtl,
     sys::{self, mcomplex, mint, mreal},
-    FromArg, Image, NumericArray,
+    FromArg, Image, NumericArray, NumericArrayType, SparseArray,
 };
 
 
@@ -45,9 +51,37 @@ use crate::{
 /// data.add_str("hello");
 /// data.add_bool(false);
 /// ```
-// TODO: Provide better Debug formatting for this type.
-#[derive(Debug)]
-#[derive(ref_cast::RefCast)]
+///
+/// # Node types
+///
+/// A `DataStore` node can contain `True` or `False`, an integer, real, or complex number,
+/// a string, a [`NumericArray`], an [`Image`], a [`SparseArray`], or a nested
+/// `DataStore`. See [`DataStoreValue`] for the owned form of these values.
+///
+/// *LibraryLink* has no node type for `Null`, or for any symbol other than `True` and
+/// `False`.
+///
+/// # Debug formatting
+///
+/// The [`Debug`][fmt::Debug] representation of a `DataStore` lists the name, type, and
+/// value of each node. Use `{:#?}` to print each node on a separate line:
+///
+/// ```no_run
+/// # use wolfram_library_link::DataStore;
+/// let mut data = DataStore::new();
+///
+/// data.add_i64(1);
+/// data.add_named_str("greeting", "hello");
+///
+/// println!("{:?}", data);
+/// ```
+///
+/// prints:
+///
+/// ```text
+/// DataStore[DataStoreNode { name: None, type: "Integer", value: 1 }, DataStoreNode { name: Some("greeting"), type: "String", value: "hello" }]
+/// ```
+#[derive(RefCast)]
 #[repr(transparent)]
 pub struct DataStore(sys::DataStore);
 
@@ -106,9 +140,47 @@ pub enum DataStoreNodeValue<'node> {
     Str(&'node str),
     NumericArray(&'node NumericArray),
     Image(&'node Image),
+    SparseArray(&'node SparseArray),
     DataStore(&'node DataStore),
 }
 
+/// Owned value that can be added to a [`DataStore`].
+///
+/// A `DataStore` can be built from an iterator over `(Option<String>, DataStoreValue)`
+/// pairs, where the first element is the optional name of the node:
+///
+/// ```no_run
+/// use wolfram_library_link::{DataStore, DataStoreValue};
+///
+/// let store: DataStore = vec![
+///     (None, DataStoreValue::from(1)),
+///     (Some("name".to_owned()), DataStoreValue::from("hello")),
+///     (None, DataStoreValue::from(false)),
+/// ]
+/// .into_iter()
+/// .collect();
+/// ```
+///
+/// which has the Wolfram Language representation:
+///
+/// ```wolfram
+/// Developer`DataStore[1, "name" -> "hello", False]
+/// ```
+///
+/// Use [`DataStoreNodeValue`] to read the values stored in an existing `DataStore`.
+#[allow(missing_docs)]
+pub enum DataStoreValue {
+    Boolean(bool),
+    Integer(mint),
+    Real(mreal),
+    Complex(mcomplex),
+    Str(String),
+    NumericArray(NumericArray),
+    Image(Image),
+    SparseArray(SparseArray),
+    DataStore(DataStore),
+}
+
 /// Iterator over the [`DataStoreNode`]s stored in a [`DataStore`].
 ///
 /// Instances of this type are returned by [`DataStore::nodes()`].
@@ -131,9 +203,9 @@ pub enum DataStoreNodeValue<'node> {
 /// prints:
 ///
 /// ```text
-/// node: DataStoreNode { name: None, value: 5 }
-/// node: DataStoreNode { name: Some("condition"), value: true }
-/// node: DataStoreNode { name: None, value: "Hello, World!" }
+/// node: DataStoreNode { name: None, type: "Integer", value: 5 }
+/// node: DataStoreNode { name: Some("condition"), type: "Boolean", value: true }
+/// node: DataStoreNode { name: None, type: "String", value: "Hello, World!" }
 /// ```
 pub struct Nodes<'s> {
     node: Option<DataStoreNode<'s>>,
@@ -154,6 +226,9 @@ impl DataStore {
             panic!("sys::DataStore is NULL");
         }
 
+        memory::track(ds, memory::Container::DataStore);
+        released::acquire(ds);
+
         DataStore(ds)
     }
 
@@ -163,13 +238,36 @@ impl DataStore {
     pub fn len(&self) -> usize {
         let DataStore(ds) = *self;
 
+        released::assert_live(ds, "get the length of");
+
         let len: i64 = unsafe { rtl::DataStore_getLength(ds) };
 
         usize::try_from(len).expect("DataStore i64 length overflows usize")
     }
 
+    /// Returns `true` if this data store contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Remove all elements from this data store.
+    ///
+    /// *LibraryLink* provides no function for removing the nodes of a data store, so this
+    /// replaces the underlying [`sys::DataStore`] with a new, empty one.
+    pub fn clear(&mut self) {
+        *self = DataStore::new();
+    }
+
     /// Construct a `DataStore` from a raw [`wolfram_library_link_sys::DataStore`] pointer.
+    ///
+    /// The returned `DataStore` takes ownership of `raw`, and deletes it when dropped.
+    ///
+    /// In builds with `debug_assertions` enabled, dropping two `DataStore`s that own the
+    /// same pointer, or using a `DataStore` after it has been deleted or returned to the
+    /// Kernel, panics instead of silently causing undefined behavior.
     pub unsafe fn from_raw(raw: sys::DataStore) -> Self {
+        released::acquire(raw);
+
         DataStore(raw)
     }
 
@@ -180,6 +278,8 @@ impl DataStore {
         // Don't run Drop on `self`; ownership of this value is being given to the caller.
         std::mem::forget(self);
 
+        memory::untrack(ds);
+
         ds
     }
 
@@ -274,6 +374,8 @@ impl DataStore {
         // Use into_raw() to avoid running Drop on `ds`.
         let other_ds = ds.into_raw();
 
+        released::release(other_ds, "DataStore", Release::AddedToDataStore);
+
         unsafe { rtl::DataStore_addDataStore(this_ds, other_ds) }
     }
 
@@ -297,11 +399,52 @@ impl DataStore {
     /// See also: [`NumericArray::into_generic()`].
     pub fn add_numeric_array(&mut self, array: NumericArray) {
         let DataStore(ds) = *self;
-        let array = unsafe { array.into_raw() };
+        let array = array.into_raw();
+
+        released::release(array, "NumericArray", Release::AddedToDataStore);
 
         unsafe { rtl::DataStore_addMNumericArray(ds, array) }
     }
 
+    /// Add an [`Image`] value to this `DataStore`.
+    ///
+    /// *LibraryLink C Function:* [`DataStore_addMImage`][rtl::DataStore_addMImage].
+    ///
+    /// See also: [`Image::into_generic()`].
+    pub fn add_image(&mut self, image: Image) {
+        let DataStore(ds) = *self;
+        let image = unsafe { image.into_raw() };
+
+        unsafe { rtl::DataStore_addMImage(ds, image) }
+    }
+
+    /// Add a [`SparseArray`] value to this `DataStore`.
+    ///
+    /// *LibraryLink C Function:* [`DataStore_addMSparseArray`][rtl::DataStore_addMSparseArray].
+    pub fn add_sparse_array(&mut self, array: SparseArray) {
+        let DataStore(ds) = *self;
+        let array = unsafe { array.into_raw() };
+
+        unsafe { rtl::DataStore_addMSparseArray(ds, array) }
+    }
+
+    /// Add a [`DataStoreValue`] to this `DataStore`.
+    pub fn add_value(&mut self, value: DataStoreValue) {
+        use DataStoreValue as V;
+
+        match value {
+            V::Boolean(value) => self.add_bool(value),
+            V::Integer(value) => self.add_i64(value),
+            V::Real(value) => self.add_f64(value),
+            V::Complex(value) => self.add_complex_f64(value),
+            V::Str(value) => self.add_str(&value),
+            V::NumericArray(value) => self.add_numeric_array(value),
+            V::Image(value) => self.add_image(value),
+            V::SparseArray(value) => self.add_sparse_array(value),
+            V::DataStore(value) => self.add_data_store(value),
+        }
+    }
+
     //==================================
     // Named data
     //==================================
@@ -422,6 +565,8 @@ impl DataStore {
         // Use into_raw() to avoid running Drop on `ds`.
         let other_ds = ds.into_raw();
 
+        released::release(other_ds, "DataStore", Release::AddedToDataStore);
+
         let name = CString::new(name).expect("could not convert &str to CString");
 
         unsafe {
@@ -440,7 +585,9 @@ impl DataStore {
     /// *LibraryLink C Function:* [`DataStore_addNamedMNumericArray`][rtl::DataStore_addNamedMNumericArray].
     pub fn add_named_numeric_array(&mut self, name: &str, array: NumericArray) {
         let DataStore(ds) = *self;
-        let array = unsafe { array.into_raw() };
+        let array = array.into_raw();
+
+        released::release(array, "NumericArray", Release::AddedToDataStore);
 
         let name = CString::new(name).expect("could not convert &str to CString");
 
@@ -449,6 +596,161 @@ impl DataStore {
         }
     }
 
+    /// Add an [`Image`] value to this `DataStore`.
+    ///
+    /// See also [`DataStore::add_image()`].
+    ///
+    /// *LibraryLink C Function:* [`DataStore_addNamedMImage`][rtl::DataStore_addNamedMImage].
+    pub fn add_named_image(&mut self, name: &str, image: Image) {
+        let DataStore(ds) = *self;
+        let image = unsafe { image.into_raw() };
+
+        let name = CString::new(name).expect("could not convert &str to CString");
+
+        unsafe { rtl::DataStore_addNamedMImage(ds, name.as_ptr() as *mut c_char, image) }
+    }
+
+    /// Add a [`SparseArray`] value to this `DataStore`.
+    ///
+    /// See also [`DataStore::add_sparse_array()`].
+    ///
+    /// *LibraryLink C Function:* [`DataStore_addNamedMSparseArray`][rtl::DataStore_addNamedMSparseArray].
+    pub fn add_named_sparse_array(&mut self, name: &str, array: SparseArray) {
+        let DataStore(ds) = *self;
+        let array = unsafe { array.into_raw() };
+
+        let name = CString::new(name).expect("could not convert &str to CString");
+
+        unsafe {
+            rtl::DataStore_addNamedMSparseArray(ds, name.as_ptr() as *mut c_char, array)
+        }
+    }
+
+    /// Add a [`DataStoreValue`] with the associated name `name` to this `DataStore`.
+    pub fn add_named_value(&mut self, name: &str, value: DataStoreValue) {
+        use DataStoreValue as V;
+
+        match value {
+            V::Boolean(value) => self.add_named_bool(name, value),
+            V::Integer(value) => self.add_named_i64(name, value),
+            V::Real(value) => self.add_named_f64(name, value),
+            V::Complex(value) => self.add_named_complex_f64(name, value),
+            V::Str(value) => self.add_named_str(name, &value),
+            V::NumericArray(value) => self.add_named_numeric_array(name, value),
+            V::Image(value) => self.add_named_image(name, value),
+            V::SparseArray(value) => self.add_named_sparse_array(name, value),
+            V::DataStore(value) => self.add_named_data_store(name, value),
+        }
+    }
+
+    /// Convert this `DataStore` to its Wolfram Language ``Developer`DataStore[...]``
+    /// expression form.
+    ///
+    /// Named nodes are represented as `"name" -> value` rules:
+    ///
+    /// ```no_run
+    /// use wolfram_library_link::DataStore;
+    ///
+    /// let mut store = DataStore::new();
+    ///
+    /// store.add_i64(1);
+    /// store.add_named_str("greeting", "hello");
+    ///
+    /// // Developer`DataStore[1, "greeting" -> "hello"]
+    /// let expr = store.to_expr().unwrap();
+    /// ```
+    ///
+    /// [`NumericArray`] nodes are represented as
+    /// <code>NumericArray[<i>data</i>, &quot;<i>type</i>&quot;]</code> expressions, and
+    /// complex numbers as `Complex[re, im]` expressions.
+    ///
+    /// An error is returned if this `DataStore` contains a node whose value has no
+    /// expression representation, like an [`Image`] node.
+    ///
+    /// Use [`DataStore::try_from_expr()`] to perform the inverse conversion.
+    pub fn to_expr(&self) -> Result<Expr, String> {
+        let elements = self
+            .nodes()
+            .map(|node| {
+                let value = node_value_to_expr(&node)?;
+
+                Ok(match node.name() {
+                    Some(name) => Expr::normal(Symbol::new("System`Rule"), vec![
+                        Expr::string(name),
+                        value,
+                    ]),
+                    None => value,
+                })
+            })
+            .collect::<Result<Vec<Expr>, String>>()?;
+
+        Ok(Expr::normal(Symbol::new("Developer`DataStore"), elements))
+    }
+
+    /// Construct a `DataStore` from a Wolfram Language ``Developer`DataStore[...]``
+    /// expression.
+    ///
+    /// This is the inverse of [`DataStore::to_expr()`]. Each element of `expr` must be
+    /// one of:
+    ///
+    /// * `True` or `False`
+    /// * an `Integer`, `Real`, or `Complex[re, im]` number
+    /// * a `String`
+    /// * a <code>NumericArray[<i>data</i>, &quot;<i>type</i>&quot;]</code> expression
+    /// * a nested ``Developer`DataStore[...]`` expression
+    /// * a `"name" -> value` rule, where `value` is one of the above
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wolfram_library_link::{
+    ///     expr::{Expr, Symbol},
+    ///     DataStore,
+    /// };
+    ///
+    /// // Developer`DataStore[1, "greeting" -> "hello"]
+    /// let expr = Expr::normal(Symbol::new("Developer`DataStore"), vec![
+    ///     Expr::from(1i64),
+    ///     Expr::normal(Symbol::new("System`Rule"), vec![
+    ///         Expr::string("greeting"),
+    ///         Expr::string("hello"),
+    ///     ]),
+    /// ]);
+    ///
+    /// let store = DataStore::try_from_expr(&expr).unwrap();
+    ///
+    /// assert_eq!(store.len(), 2);
+    /// ```
+    pub fn try_from_expr(expr: &Expr) -> Result<DataStore, String> {
+        let elements = normal_elements(expr, "Developer`DataStore")
+            .ok_or_else(|| mismatch("Developer`DataStore", expr))?;
+
+        let mut store = DataStore::new();
+
+        for (index, elem) in elements.iter().enumerate() {
+            let named = match normal_elements(elem, "System`Rule") {
+                Some([name, value]) => match name.kind() {
+                    ExprKind::String(name) => Some((name.as_str(), value)),
+                    _ => None,
+                },
+                _ => None,
+            };
+
+            let result = match named {
+                Some((name, value)) => {
+                    expr_to_value(value).map(|value| store.add_named_value(name, value))
+                },
+                None => expr_to_value(elem).map(|value| store.add_value(value)),
+            };
+
+            // Use 1-based indexing, to match Wolfram Language part numbers.
+            result
+                .map_err(|err| format!("at DataStore position {}: {}", index + 1, err))?;
+        }
+
+        Ok(store)
+    }
+
     /// Returns an iterator over the [`DataStoreNode`]s of this `DataStore`.
     ///
     /// A [`DataStore`] is made up of a linked list of [`DataStoreNode`]s. The [`Nodes`]
@@ -460,10 +762,120 @@ impl DataStore {
         }
     }
 
+    /// Get the first node in this `DataStore` with the name `name`.
+    ///
+    /// Returns `None` if this `DataStore` contains no node named `name`.
+    pub fn get<'s>(&'s self, name: &str) -> Option<DataStoreNode<'s>> {
+        self.nodes()
+            .find(|node| node.name().as_deref() == Some(name))
+    }
+
+    /// Get the node at `path` in a tree of nested `DataStore`s.
+    ///
+    /// Each element of `path` except the last names a node containing a nested
+    /// `DataStore`, and the last element names the node to return.
+    ///
+    /// Returns `None` if `path` is empty, if any node along `path` does not exist, or if
+    /// any node except the last does not contain a `DataStore`.
+    ///
+    /// # Example
+    ///
+    /// Get the `"value"` node in the following `DataStore`:
+    ///
+    /// ```wolfram
+    /// Developer`DataStore[
+    ///     "outer" -> Developer`DataStore[
+    ///         "inner" -> Developer`DataStore["value" -> 5]
+    ///     ]
+    /// ]
+    /// ```
+    ///
+    /// ```no_run
+    /// use wolfram_library_link::{DataStore, DataStoreNodeValue};
+    ///
+    /// fn get_value(store: &DataStore) -> Option<i64> {
+    ///     let node = store.get_path(&["outer", "inner", "value"])?;
+    ///
+    ///     match node.value() {
+    ///         DataStoreNodeValue::Integer(value) => Some(value),
+    ///         _ => None,
+    ///     }
+    /// }
+    /// ```
+    pub fn get_path<'s>(&'s self, path: &[&str]) -> Option<DataStoreNode<'s>> {
+        let (last, parents) = path.split_last()?;
+
+        let mut store: &'s DataStore = self;
+
+        for name in parents {
+            store = store.get(name)?.as_data_store().ok()?;
+        }
+
+        store.get(last)
+    }
+
+    /// Collect the named nodes of this `DataStore` into a map from node names to
+    /// values.
+    ///
+    /// The map can be any type that implements
+    /// [`FromIterator<(String, DataStoreValue)>`][FromIterator], like
+    /// [`HashMap`][std::collections::HashMap] or [`BTreeMap`][std::collections::BTreeMap].
+    ///
+    /// This follows the semantics of the Wolfram Language
+    /// [`Association`][ref/Association]<sub>WL</sub> function:
+    ///
+    /// * If several nodes have the same name, the value of the last node is used.
+    /// * Unnamed nodes are not allowed, and result in an error.
+    ///
+    /// An error is also returned if a node value cannot be copied into a
+    /// [`DataStoreValue`]. See [`DataStoreNode::to_value()`].
+    ///
+    /// # Example
+    ///
+    /// Read the options passed as ``Developer`DataStore["limit" -> 10, "label" -> "x"]``:
+    ///
+    /// ```no_run
+    /// use std::collections::HashMap;
+    ///
+    /// use wolfram_library_link::{DataStore, DataStoreValue};
+    ///
+    /// fn limit(options: DataStore) -> Result<i64, String> {
+    ///     let options: HashMap<String, DataStoreValue> = options.into_named_map()?;
+    ///
+    ///     match options.get("limit") {
+    ///         Some(DataStoreValue::Integer(limit)) => Ok(*limit),
+    ///         Some(other) => Err(format!("invalid limit: {:?}", other)),
+    ///         None => Ok(100),
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub fn into_named_map<M>(self) -> Result<M, String>
+    where
+        M: FromIterator<(String, DataStoreValue)>,
+    {
+        self.nodes()
+            .enumerate()
+            .map(|(index, node)| {
+                // Use 1-based indexing, to match Wolfram Language part numbers.
+                let name = node.name().ok_or_else(|| {
+                    format!("DataStore node at position {} has no name", index + 1)
+                })?;
+
+                let value = node.to_value()?;
+
+                Ok((name, value))
+            })
+            .collect()
+    }
+
     /// Get the first [`DataStoreNode`] of this `DataStore`.
     pub fn first_node<'s>(&'s self) -> Option<DataStoreNode<'s>> {
         let DataStore(raw) = *self;
 
+        released::assert_live(raw, "read the nodes of");
+
         let node = unsafe { rtl::DataStore_getFirstNode(raw) };
 
         if node.is_null() {
@@ -530,7 +942,7 @@ impl<'store> DataStoreNode<'store> {
                     unimplemented!("unhandled DataStoreNode Tensor data type")
                 },
                 sys::MType_SparseArray => {
-                    unimplemented!("unhandled DataStoreNode SparseArray data type")
+                    V::SparseArray(<&SparseArray>::from_arg(data_raw))
                 },
                 sys::MType_NumericArray => {
                     V::NumericArray(<&NumericArray>::from_arg(data_raw))
@@ -544,6 +956,215 @@ impl<'store> DataStoreNode<'store> {
         }
     }
 
+    /// Copy the value stored in this node into an owned [`DataStoreValue`].
+    ///
+    /// [`NumericArray`], [`Image`], [`SparseArray`], and [`DataStore`] values are cloned.
+    ///
+    /// An error is returned if this node contains a value of a type that is not supported
+    /// by `DataStoreValue`, or if cloning the value fails.
+    pub fn to_value(&self) -> Result<DataStoreValue, String> {
+        use DataStoreNodeValue as V;
+
+        let clone_failed = |err_code| {
+            format!(
+                "failed to clone DataStore node value of type {} (error code: {})",
+                self.data_type_name().unwrap_or("unknown"),
+                err_code
+            )
+        };
+
+        let value = match self.try_value() {
+            Some(V::Boolean(value)) => DataStoreValue::Boolean(value),
+            Some(V::Integer(value)) => DataStoreValue::Integer(value),
+            Some(V::Real(value)) => DataStoreValue::Real(value),
+            Some(V::Complex(value)) => DataStoreValue::Complex(value),
+            Some(V::Str(value)) => DataStoreValue::Str(value.to_owned()),
+            Some(V::NumericArray(array)) => {
+                DataStoreValue::NumericArray(array.try_clone().map_err(clone_failed)?)
+            },
+            Some(V::Image(image)) => {
+                DataStoreValue::Image(image.try_clone().map_err(clone_failed)?)
+            },
+            Some(V::SparseArray(array)) => {
+                DataStoreValue::SparseArray(array.try_clone().map_err(clone_failed)?)
+            },
+            Some(V::DataStore(store)) => DataStoreValue::DataStore(store.clone()),
+            None => {
+                return Err(format!(
+                    "DataStore node of type {} cannot be converted to a DataStoreValue",
+                    self.data_type_name().unwrap_or("unknown")
+                ))
+            },
+        };
+
+        Ok(value)
+    }
+
+    /// Get the value stored in this `DataStoreNode`, or `None` if the value has a type
+    /// that [`DataStoreNode::value()`] does not support.
+    fn try_value(&self) -> Option<DataStoreNodeValue<'_>> {
+        // DataStoreNode::value() panics for nodes of any other type.
+        match self.data_type_raw() as u32 {
+            sys::MType_Boolean
+            | sys::MType_Integer
+            | sys::MType_Real
+            | sys::MType_Complex
+            | sys::MType_UTF8String
+            | sys::MType_NumericArray
+            | sys::MType_Image
+            | sys::MType_SparseArray
+            | sys::MType_DataStore => Some(self.value()),
+            _ => None,
+        }
+    }
+
+    /// Get the `bool` value stored in this node.
+    ///
+    /// An error is returned if this node does not contain a `Boolean` value.
+    pub fn as_bool(&self) -> Result<bool, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Boolean(value)) => Ok(value),
+            _ => Err(self.type_mismatch("Boolean")),
+        }
+    }
+
+    /// Get the `i64` value stored in this node.
+    ///
+    /// An error is returned if this node does not contain an `Integer` value.
+    pub fn as_i64(&self) -> Result<i64, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Integer(value)) => Ok(value),
+            _ => Err(self.type_mismatch("Integer")),
+        }
+    }
+
+    /// Get the `f64` value stored in this node.
+    ///
+    /// An error is returned if this node does not contain a `Real` value. `Integer`
+    /// values are not converted.
+    pub fn as_f64(&self) -> Result<f64, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Real(value)) => Ok(value),
+            _ => Err(self.type_mismatch("Real")),
+        }
+    }
+
+    /// Get the [`mcomplex`][sys::mcomplex] value stored in this node.
+    ///
+    /// An error is returned if this node does not contain a `Complex` value.
+    pub fn as_complex_f64(&self) -> Result<mcomplex, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Complex(value)) => Ok(value),
+            _ => Err(self.type_mismatch("Complex")),
+        }
+    }
+
+    /// Get the string value stored in this node.
+    ///
+    /// An error is returned if this node does not contain a `String` value.
+    pub fn as_str(&self) -> Result<&str, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Str(value)) => Ok(value),
+            _ => Err(self.type_mismatch("String")),
+        }
+    }
+
+    /// Get the [`NumericArray`] stored in this node, with elements of type `T`.
+    ///
+    /// An error is returned if this node does not contain a `NumericArray`, or if the
+    /// element type of the array is not `T`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use wolfram_library_link::DataStore;
+    ///
+    /// fn total(store: &DataStore) -> Result<f64, String> {
+    ///     let node = store.get("values").ok_or("missing node: values")?;
+    ///
+    ///     let array = node.as_numeric_array::<f64>()?;
+    ///
+    ///     Ok(array.as_slice().iter().sum())
+    /// }
+    /// ```
+    pub fn as_numeric_array<T: NumericArrayType>(
+        &self,
+    ) -> Result<&NumericArray<T>, String> {
+        let array = match self.try_value() {
+            Some(DataStoreNodeValue::NumericArray(array)) => array.try_kind::<T>().ok(),
+            _ => None,
+        };
+
+        array.ok_or_else(|| {
+            self.type_mismatch(&format!("NumericArray of type {}", T::TYPE.name()))
+        })
+    }
+
+    /// Get the [`Image`] stored in this node.
+    ///
+    /// An error is returned if this node does not contain an `Image`.
+    pub fn as_image(&self) -> Result<&Image, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::Image(image)) => Ok(image),
+            _ => Err(self.type_mismatch("Image")),
+        }
+    }
+
+    /// Get the [`SparseArray`] stored in this node.
+    ///
+    /// An error is returned if this node does not contain a `SparseArray`.
+    pub fn as_sparse_array(&self) -> Result<&SparseArray, String> {
+        match self.try_value() {
+            Some(DataStoreNodeValue::SparseArray(array)) => Ok(array),
+            _ => Err(self.type_mismatch("SparseArray")),
+        }
+    }
+
+    /// Get the [`DataStore`] stored in this node.
+    ///
+    /// Unlike the other `as_*()` methods, the returned `DataStore` is borrowed for the
+    /// lifetime of the `DataStore` this node was borrowed from, and not just for the
+    /// lifetime of this node. This makes it possible to return nodes of nested
+    /// `DataStore`s, as done by [`DataStore::get_path()`].
+    ///
+    /// An error is returned if this node does not contain a `DataStore`.
+    pub fn as_data_store(&self) -> Result<&'store DataStore, String> {
+        if self.data_type_raw() as u32 != sys::MType_DataStore {
+            return Err(self.type_mismatch("DataStore"));
+        }
+
+        // The `DataStore` pointed to by the node data is owned by the `DataStore` this
+        // node was borrowed from, so it is valid for the `'store` lifetime.
+        unsafe {
+            let data: &sys::MArgument = self.data_raw();
+
+            let store: *mut sys::DataStore = data.tensor as *mut sys::DataStore;
+
+            released::acquire(*store);
+
+            Ok(DataStore::ref_cast(&*store))
+        }
+    }
+
+    /// Construct an error message for an `as_*()` method called on a node that does not
+    /// contain a value of the `expected` type.
+    fn type_mismatch(&self, expected: &str) -> String {
+        let actual = match self.try_value() {
+            Some(DataStoreNodeValue::NumericArray(array)) => {
+                format!("NumericArray of type {}", array.data_type().name())
+            },
+            _ => self.data_type_name().unwrap_or("unknown type").to_owned(),
+        };
+
+        match self.name() {
+            Some(name) => format!(
+                "DataStore node {:?}: expected {}, got {}",
+                name, expected, actual
+            ),
+            None => format!("DataStore node: expected {}, got {}", expected, actual),
+        }
+    }
+
     /// Get the next node in this linked list of `DataStoreNode`'s.
     ///
     /// *LibraryLink C Function:* [`DataStoreNode_getNextNode`][rtl::DataStoreNode_getNextNode].
@@ -567,6 +1188,27 @@ impl<'store> DataStoreNode<'store> {
         unsafe { rtl::DataStoreNode_getDataType(self.raw) }
     }
 
+    /// The *LibraryLink* name of the type of the value stored in this node, or `None` if
+    /// the type is not recognized.
+    fn data_type_name(&self) -> Option<&'static str> {
+        let name = match self.data_type_raw() as u32 {
+            sys::MType_Undef => "Undef",
+            sys::MType_Boolean => "Boolean",
+            sys::MType_Integer => "Integer",
+            sys::MType_Real => "Real",
+            sys::MType_Complex => "Complex",
+            sys::MType_UTF8String => "String",
+            sys::MType_Tensor => "Tensor",
+            sys::MType_SparseArray => "SparseArray",
+            sys::MType_NumericArray => "NumericArray",
+            sys::MType_Image => "Image",
+            sys::MType_DataStore => "DataStore",
+            _ => return None,
+        };
+
+        Some(name)
+    }
+
     /// *LibraryLink C Function:* [`DataStoreNode_getData`][rtl::DataStoreNode_getData].
     pub unsafe fn data_raw(&self) -> &sys::MArgument {
         match self.try_data_raw() {
@@ -578,10 +1220,23 @@ impl<'store> DataStoreNode<'store> {
         }
     }
 
+    /// Get the raw value stored in this node, or the error code returned by
+    /// [`DataStoreNode_getData`][rtl::DataStoreNode_getData] if it could not be
+    /// accessed.
+    ///
+    /// This is a fallible version of [`DataStoreNode::data_raw()`].
+    ///
+    /// # Safety
+    ///
+    /// The returned `MArgument` is a union. Only the field that matches the
+    /// [`data_type_raw()`][DataStoreNode::data_type_raw] of this node may be read.
+    ///
+    /// Pointers stored in the returned value point to data owned by the `DataStore`
+    /// containing this node. They must not be freed, and must not be used after that
+    /// `DataStore` is modified or dropped.
+    ///
     /// *LibraryLink C Function:* [`DataStoreNode_getData`][rtl::DataStoreNode_getData].
-    pub unsafe fn try_data_raw<'node>(
-        &'node self,
-    ) -> Result<&'node sys::MArgument, sys::errcode_t> {
+    pub unsafe fn try_data_raw(&self) -> Result<&sys::MArgument, sys::errcode_t> {
         self.data
             .get_or_try_init(|| -> Result<sys::MArgument, sys::errcode_t> {
                 let mut arg: sys::MArgument = sys::MArgument {
@@ -600,6 +1255,78 @@ impl<'store> DataStoreNode<'store> {
     }
 }
 
+//---------------------
+// Expression utilities
+//---------------------
+
+/// Convert the value stored in `node` to an expression.
+fn node_value_to_expr(node: &DataStoreNode) -> Result<Expr, String> {
+    use DataStoreNodeValue as V;
+
+    let unsupported = || {
+        format!(
+            "DataStore node of type {} cannot be converted to Expr",
+            node.data_type_name().unwrap_or("unknown")
+        )
+    };
+
+    let expr = match node.try_value().ok_or_else(unsupported)? {
+        V::Boolean(value) => Expr::from(value),
+        V::Integer(value) => Expr::from(value),
+        V::Real(value) => real_to_expr(value)?,
+        V::Complex(mcomplex { ri: [re, im] }) => {
+            Expr::normal(Symbol::new("System`Complex"), vec![
+                real_to_expr(re)?,
+                real_to_expr(im)?,
+            ])
+        },
+        V::Str(value) => Expr::string(value),
+        V::NumericArray(array) => numeric_array_to_expr(array)?,
+        V::DataStore(store) => store.to_expr()?,
+        V::Image(_) | V::SparseArray(_) => return Err(unsupported()),
+    };
+
+    Ok(expr)
+}
+
+fn real_to_expr(value: mreal) -> Result<Expr, String> {
+    match value.is_nan() {
+        true => Err("NaN Real value cannot be converted to Expr".to_owned()),
+        false => Ok(Expr::number(Number::real(value))),
+    }
+}
+
+/// Convert an element of a ``Developer`DataStore[...]`` expression to a
+/// [`DataStoreValue`].
+fn expr_to_value(expr: &Expr) -> Result<DataStoreValue, String> {
+    let value = match expr.kind() {
+        ExprKind::Integer(value) => DataStoreValue::Integer(*value),
+        ExprKind::Real(value) => DataStoreValue::Real(**value),
+        ExprKind::String(value) => DataStoreValue::Str(value.clone()),
+        ExprKind::Symbol(symbol) => match symbol.as_str() {
+            "System`True" => DataStoreValue::Boolean(true),
+            "System`False" => DataStoreValue::Boolean(false),
+            _ => return Err(mismatch("DataStore value", expr)),
+        },
+        ExprKind::Normal(_) => {
+            if let Some([re, im]) = normal_elements(expr, "System`Complex") {
+                let re = f64::from_expr(re)?;
+                let im = f64::from_expr(im)?;
+
+                DataStoreValue::Complex(mcomplex::new(re, im))
+            } else if normal_elements(expr, "System`NumericArray").is_some() {
+                DataStoreValue::NumericArray(numeric_array_from_expr(expr)?)
+            } else if normal_elements(expr, "Developer`DataStore").is_some() {
+                DataStoreValue::DataStore(DataStore::try_from_expr(expr)?)
+            } else {
+                return Err(mismatch("DataStore value", expr));
+            }
+        },
+    };
+
+    Ok(value)
+}
+
 //---------------
 // Nodes iterator
 //---------------
@@ -618,16 +1345,136 @@ impl<'store> Iterator for Nodes<'store> {
     }
 }
 
+//======================================
+// Collection Impls
+//======================================
+
+impl FromIterator<(Option<String>, DataStoreValue)> for DataStore {
+    fn from_iter<I>(iter: I) -> DataStore
+    where
+        I: IntoIterator<Item = (Option<String>, DataStoreValue)>,
+    {
+        let mut store = DataStore::new();
+        store.extend(iter);
+        store
+    }
+}
+
+impl Extend<(Option<String>, DataStoreValue)> for DataStore {
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (Option<String>, DataStoreValue)>,
+    {
+        for (name, value) in iter {
+            match name {
+                Some(name) => self.add_named_value(&name, value),
+                None => self.add_value(value),
+            }
+        }
+    }
+}
+
+//======================================
+// DataStoreValue Conversion Impls
+//======================================
+
+impl DataStoreValue {
+    /// The *LibraryLink* name of the type of this value, like `"Integer"`.
+    pub(crate) fn type_name(&self) -> &'static str {
+        use DataStoreValue as V;
+
+        match self {
+            V::Boolean(_) => "Boolean",
+            V::Integer(_) => "Integer",
+            V::Real(_) => "Real",
+            V::Complex(_) => "Complex",
+            V::Str(_) => "String",
+            V::NumericArray(_) => "NumericArray",
+            V::Image(_) => "Image",
+            V::SparseArray(_) => "SparseArray",
+            V::DataStore(_) => "DataStore",
+        }
+    }
+}
+
+impl From<bool> for DataStoreValue {
+    fn from(value: bool) -> DataStoreValue {
+        DataStoreValue::Boolean(value)
+    }
+}
+
+impl From<i64> for DataStoreValue {
+    fn from(value: i64) -> DataStoreValue {
+        DataStoreValue::Integer(value)
+    }
+}
+
+impl From<f64> for DataStoreValue {
+    fn from(value: f64) -> DataStoreValue {
+        DataStoreValue::Real(value)
+    }
+}
+
+impl From<mcomplex> for DataStoreValue {
+    fn from(value: mcomplex) -> DataStoreValue {
+        DataStoreValue::Complex(value)
+    }
+}
+
+impl From<&str> for DataStoreValue {
+    fn from(value: &str) -> DataStoreValue {
+        DataStoreValue::Str(value.to_owned())
+    }
+}
+
+impl From<String> for DataStoreValue {
+    fn from(value: String) -> DataStoreValue {
+        DataStoreValue::Str(value)
+    }
+}
+
+impl<T> From<NumericArray<T>> for DataStoreValue {
+    fn from(value: NumericArray<T>) -> DataStoreValue {
+        DataStoreValue::NumericArray(value.into_generic())
+    }
+}
+
+impl<T> From<Image<T>> for DataStoreValue {
+    fn from(value: Image<T>) -> DataStoreValue {
+        DataStoreValue::Image(value.into_generic())
+    }
+}
+
+impl From<SparseArray> for DataStoreValue {
+    fn from(value: SparseArray) -> DataStoreValue {
+        DataStoreValue::SparseArray(value)
+    }
+}
+
+impl From<DataStore> for DataStoreValue {
+    fn from(value: DataStore) -> DataStoreValue {
+        DataStoreValue::DataStore(value)
+    }
+}
+
 //======================================
 // Clone and Drop Impls
 //======================================
 
 impl Clone for DataStore {
+    /// Create a deep copy of this `DataStore`.
+    ///
+    /// *LibraryLink C Function:* [`copyDataStore`][rtl::copyDataStore].
     fn clone(&self) -> DataStore {
         let DataStore(ds) = *self;
 
+        released::assert_live(ds, "clone");
+
         let duplicate = unsafe { rtl::copyDataStore(ds) };
 
+        memory::track(duplicate, memory::Container::DataStore);
+        released::acquire(duplicate);
+
         DataStore(duplicate)
     }
 }
@@ -637,6 +1484,11 @@ impl Drop for DataStore {
         let DataStore(ds) = *self;
         let ds: sys::DataStore = ds;
 
+        memory::untrack(ds);
+
+        // Panics if `ds` has already been deleted, instead of deleting it a second time.
+        released::release(ds, "DataStore", Release::Dropped);
+
         unsafe { rtl::deleteDataStore(ds) }
     }
 }
@@ -645,14 +1497,47 @@ impl Drop for DataStore {
 // Formatting Impls
 //======================================
 
+impl fmt::Debug for DataStore {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("DataStore")?;
+
+        f.debug_list().entries(self.nodes()).finish()
+    }
+}
+
 impl<'store> fmt::Debug for DataStoreNode<'store> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("DataStoreNode")
-            .field("name", &self.name())
-            .field("value", &self.value())
-            // TODO: Add an enum to wrap the raw data type and use that here instead.
-            // .field("data_type_raw", &self.data_type_raw())
-            .finish()
+        let mut debug = f.debug_struct("DataStoreNode");
+
+        debug.field("name", &self.name());
+
+        match self.data_type_name() {
+            Some(type_name) => debug.field("type", &type_name),
+            None => debug.field("data_type_raw", &self.data_type_raw()),
+        };
+
+        match self.try_value() {
+            Some(value) => debug.field("value", &value).finish(),
+            None => debug.finish_non_exhaustive(),
+        }
+    }
+}
+
+impl fmt::Debug for DataStoreValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use DataStoreValue as V;
+
+        match self {
+            V::Boolean(val) => val.fmt(f),
+            V::Integer(val) => val.fmt(f),
+            V::Real(val) => val.fmt(f),
+            V::Complex(val) => val.fmt(f),
+            V::Str(val) => val.fmt(f),
+            V::NumericArray(val) => val.fmt(f),
+            V::Image(val) => val.fmt(f),
+            V::SparseArray(val) => val.fmt(f),
+            V::DataStore(val) => val.fmt(f),
+        }
     }
 }
 
@@ -668,6 +1553,7 @@ impl<'node> fmt::Debug for DataStoreNodeValue<'node> {
             V::Str(val) => val.fmt(f),
             V::NumericArray(val) => val.fmt(f),
             V::Image(val) => val.fmt(f),
+            V::SparseArray(val) => val.fmt(f),
             V::DataStore(val) => val.fmt(f),
         }
     }
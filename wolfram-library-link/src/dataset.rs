@@ -0,0 +1,534 @@
+//! Return tabular data as a Wolfram Language [`Dataset`][ref/Dataset]<sub>WL</sub>,
+//! using [Serde](https://serde.rs).
+//!
+//! *This module is only available if the `"serde"` feature of `wolfram-library-link`
+//! is enabled.*
+//!
+//! [`from_iter()`] converts the rows produced by an iterator of [`Serialize`] values
+//! into a `Dataset` expression:
+//!
+//! ```wolfram
+//! Dataset[{
+//!     <| "name" -> "Alice", "age" -> 32 |>,
+//!     <| "name" -> "Bob", "age" -> 41 |>
+//! }]
+//! ```
+//!
+//! Use [`to_expr()`] to convert a single value.
+//!
+//! # Serialized representation
+//!
+//! Serde data model                   | Wolfram Language expression
+//! -----------------------------------|---------------------------------------------------
+//! `bool`                             | `True` or `False`
+//! integers                           | `Integer`
+//! `f32`, `f64`                       | `Real`, or `Indeterminate` for NaN values
+//! `char`, `str`                      | `String`
+//! bytes, sequences, tuples           | `List[...]`
+//! `None`                             | [`Missing[]`][ref/Missing]<sub>WL</sub>
+//! `Some(value)`                      | `value`
+//! `()`, unit structs                 | `Null`
+//! newtype structs                    | the wrapped value
+//! maps, structs                      | [`Association`][ref/Association]<sub>WL</sub>`[key -> value, ...]`
+//! unit variants                      | `"Variant"`
+//! newtype, tuple and struct variants | `<| "Variant" -> value |>`
+//!
+//! Struct field names and variant names are converted to strings. Map keys are converted
+//! using the rules above.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use serde::Serialize;
+//! use wolfram_library_link::{self as wll, dataset, expr::Expr};
+//!
+//! #[derive(Serialize)]
+//! struct Planet {
+//!     name: &'static str,
+//!     moons: u32,
+//!     radius_km: f64,
+//! }
+//!
+//! wll::export_wstp![planets(_)];
+//!
+//! fn planets(_args: Vec<Expr>) -> Expr {
+//!     let planets = vec![
+//!         Planet { name: "Earth", moons: 1, radius_km: 6371.0 },
+//!         Planet { name: "Mars", moons: 2, radius_km: 3389.5 },
+//!     ];
+//!
+//!     dataset::from_iter(planets).unwrap()
+//! }
+//! # }
+//! ```
+//!
+//! [ref/Dataset]: https://reference.wolfram.com/language/ref/Dataset.html
+//! [ref/Missing]: https://reference.wolfram.com/language/ref/Missing.html
+//! [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+
+use std::fmt;
+
+use serde::ser::{self, Serialize};
+
+use crate::{
+    convert::{list, normal_elements, ToExpr},
+    expr::{Expr, Symbol},
+};
+
+/// Convert the rows produced by `iter` into a
+/// [`Dataset`][ref/Dataset]<sub>WL</sub>`[{<|...|>, ...}]` expression.
+///
+/// Each row must serialize to an [`Association`][ref/Association]<sub>WL</sub> (e.g. a
+/// struct or a map), and every row must have the same keys, in the same order.
+///
+/// Returns an error if a row could not be serialized, or if the rows do not have
+/// consistent keys.
+///
+/// [ref/Dataset]: https://reference.wolfram.com/language/ref/Dataset.html
+/// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+pub fn from_iter<I>(iter: I) -> Result<Expr, String>
+where
+    I: IntoIterator,
+    I::Item: Serialize,
+{
+    let mut rows: Vec<Expr> = Vec::new();
+    let mut columns: Option<Vec<Expr>> = None;
+
+    for (index, item) in iter.into_iter().enumerate() {
+        let row = to_expr(&item)?;
+
+        // Use 1-based indexing, to match Wolfram Language part numbers.
+        let position = index + 1;
+
+        let keys: Vec<Expr> = normal_elements(&row, "System`Association")
+            .ok_or_else(|| {
+                format!(
+                    "row {} did not serialize to an Association: {}",
+                    position, row
+                )
+            })?
+            .iter()
+            .map(|rule| match normal_elements(rule, "System`Rule") {
+                Some([key, _]) => key.clone(),
+                _ => unreachable!("Association elements are always rules"),
+            })
+            .collect();
+
+        match columns {
+            Some(ref columns) if *columns != keys => {
+                return Err(format!(
+                    "row {} has keys {}, which differ from the keys of row 1: {}",
+                    position,
+                    list(keys),
+                    list(columns.clone())
+                ));
+            },
+            Some(_) => (),
+            None => columns = Some(keys),
+        }
+
+        rows.push(row);
+    }
+
+    Ok(Expr::normal(Symbol::new("System`Dataset"), vec![list(
+        rows,
+    )]))
+}
+
+/// Convert `value` into an [`Expr`], using the representation described in the
+/// [module documentation](self).
+pub fn to_expr<T: Serialize + ?Sized>(value: &T) -> Result<Expr, String> {
+    value
+        .serialize(Serializer)
+        .map_err(|Error(message)| message)
+}
+
+//======================================
+// Serializer
+//======================================
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl ser::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+struct Serializer;
+
+/// Serializer for sequences, tuples and tuple structs.
+struct SeqSerializer {
+    elements: Vec<Expr>,
+    /// Variant name, if this is a tuple variant.
+    variant: Option<&'static str>,
+}
+
+/// Serializer for maps, structs, and struct variants.
+struct MapSerializer {
+    rules: Vec<Expr>,
+    next_key: Option<Expr>,
+    /// Variant name, if this is a struct variant.
+    variant: Option<&'static str>,
+}
+
+fn rule(key: Expr, value: Expr) -> Expr {
+    Expr::normal(Symbol::new("System`Rule"), vec![key, value])
+}
+
+fn association(rules: Vec<Expr>) -> Expr {
+    Expr::normal(Symbol::new("System`Association"), rules)
+}
+
+/// Construct `<| "variant" -> value |>`.
+fn variant_association(variant: &str, value: Expr) -> Expr {
+    association(vec![rule(Expr::string(variant), value)])
+}
+
+impl ser::Serializer for Serializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    type SerializeSeq = SeqSerializer;
+    type SerializeTuple = SeqSerializer;
+    type SerializeTupleStruct = SeqSerializer;
+    type SerializeTupleVariant = SeqSerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, v: bool) -> Result<Expr, Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Expr, Error> {
+        Ok(Expr::from(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Expr, Error> {
+        self.serialize_i64(i64::from(v))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Expr, Error> {
+        let v = i64::try_from(v)
+            .map_err(|_| Error(format!("integer value overflows i64: {}", v)))?;
+
+        self.serialize_i64(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Expr, Error> {
+        self.serialize_f64(f64::from(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Expr, Error> {
+        Ok(v.to_expr())
+    }
+
+    fn serialize_char(self, v: char) -> Result<Expr, Error> {
+        Ok(Expr::string(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Expr, Error> {
+        Ok(Expr::string(v))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<Expr, Error> {
+        Ok(list(
+            v.iter().map(|&byte| Expr::from(i64::from(byte))).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<Expr, Error> {
+        Ok(Expr::normal(Symbol::new("System`Missing"), vec![]))
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Expr, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Expr, Error> {
+        Ok(Expr::symbol(Symbol::new("System`Null")))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<Expr, Error> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<Expr, Error> {
+        Ok(Expr::string(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<Expr, Error> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Expr, Error> {
+        Ok(variant_association(variant, value.serialize(Serializer)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer {
+            elements: Vec::with_capacity(len.unwrap_or(0)),
+            variant: None,
+        })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<SeqSerializer, Error> {
+        Ok(SeqSerializer {
+            elements: Vec::with_capacity(len),
+            variant: Some(variant),
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            rules: Vec::with_capacity(len.unwrap_or(0)),
+            next_key: None,
+            variant: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, Error> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<MapSerializer, Error> {
+        Ok(MapSerializer {
+            rules: Vec::with_capacity(len),
+            next_key: None,
+            variant: Some(variant),
+        })
+    }
+}
+
+impl SeqSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.elements.push(value.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Expr, Error> {
+        let SeqSerializer { elements, variant } = self;
+
+        Ok(match variant {
+            Some(variant) => variant_association(variant, list(elements)),
+            None => list(elements),
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTuple for SeqSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_element<T: Serialize + ?Sized>(
+        &mut self,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeTupleVariant for SeqSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl MapSerializer {
+    fn push_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.rules
+            .push(rule(Expr::string(key), value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn finish(self) -> Result<Expr, Error> {
+        let MapSerializer {
+            rules,
+            next_key,
+            variant,
+        } = self;
+
+        debug_assert!(next_key.is_none());
+
+        Ok(match variant {
+            Some(variant) => variant_association(variant, association(rules)),
+            None => association(rules),
+        })
+    }
+}
+
+impl ser::SerializeMap for MapSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+        self.next_key = Some(key.serialize(Serializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+        let key = self
+            .next_key
+            .take()
+            .ok_or_else(|| Error("map value serialized before its key".to_owned()))?;
+
+        self.rules.push(rule(key, value.serialize(Serializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStruct for MapSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for MapSerializer {
+    type Ok = Expr;
+    type Error = Error;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), Error> {
+        self.push_field(key, value)
+    }
+
+    fn end(self) -> Result<Expr, Error> {
+        self.finish()
+    }
+}
@@ -0,0 +1,395 @@
+//! Debugging utilities.
+//!
+//! # Evaluation transcripts
+//!
+//! When a sequence of callbacks into the Wolfram Kernel misbehaves, it can be difficult
+//! to tell which evaluation went wrong. Call [`start_transcript()`] to record every
+//! expression evaluated using [`evaluate()`][crate::evaluate] or
+//! [`try_evaluate()`][crate::try_evaluate], along with the result of the evaluation and
+//! when it happened. The recorded evaluations can then be inspected using
+//! [`transcript()`], or replayed in a Wolfram Language session.
+//!
+//! Recording is disabled by default, and has negligible cost when disabled. Every
+//! recorded evaluation is kept until [`clear_transcript()`] is called, so recording
+//! should not be left enabled indefinitely.
+//!
+//! The transcript is also available from the Wolfram Language by loading the
+//! `wll_transcript` WSTP function that is exported automatically by every library that
+//! uses `wolfram-library-link`:
+//!
+//! ```wolfram
+//! LibraryFunctionLoad["library", "wll_transcript", LinkObject, LinkObject][]
+//! ```
+//!
+//! which returns a list of associations with the keys `"Expression"`, `"Result"`,
+//! `"Time"` and `"Duration"`. The expression and result are wrapped in
+//! [`HoldComplete`][ref/HoldComplete]<sub>WL</sub> to prevent them from being
+//! evaluated again.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     debug,
+//!     expr::{Expr, Symbol},
+//! };
+//!
+//! debug::start_transcript();
+//!
+//! wll::evaluate(&Expr::normal(Symbol::new("System`Plus"), vec![
+//!     Expr::from(2),
+//!     Expr::from(2),
+//! ]));
+//!
+//! debug::stop_transcript();
+//!
+//! for entry in debug::transcript() {
+//!     println!("{} => {:?} ({:?})", entry.expr, entry.result, entry.duration);
+//! }
+//! ```
+//!
+//! # Leak checks
+//!
+//! A [`NumericArray`] passed using the `"Manual"` memory management mode, for example
+//! as a [`Manual`][crate::memory::Manual] parameter, is owned by the library, which must
+//! free it, return it, or explicitly give up ownership of it using
+//! [`NumericArray::into_raw()`]. Arrays that are forgotten instead (e.g. using
+//! [`std::mem::forget()`]) are leaked, which is the most common *LibraryLink* memory
+//! leak.
+//!
+//! Call [`start_leak_check()`] to record every `"Manual"` array received by a function
+//! exported using [`export!`][crate::export], and check that each of them has been
+//! dropped, returned, or converted using `into_raw()` by the time the function returns.
+//! Arrays that have not are reported as [`Leak`]s, which can be inspected using
+//! [`leaks()`]. Note that arrays that are kept alive on purpose after the function
+//! returns, for example by storing them in a `static`, are reported as well.
+//!
+//! Leak checking is disabled by default, and has negligible cost when disabled.
+//!
+//! The leaks are also available from the Wolfram Language by loading the `wll_leaks`
+//! WSTP function that is exported automatically by every library that uses
+//! `wolfram-library-link`:
+//!
+//! ```wolfram
+//! LibraryFunctionLoad["library", "wll_leaks", LinkObject, LinkObject][]
+//! ```
+//!
+//! which returns a list of associations with the keys `"Function"`, `"Type"`,
+//! `"Dimensions"` and `"Time"`.
+//!
+//! [ref/HoldComplete]: https://reference.wolfram.com/language/ref/HoldComplete.html
+
+use std::{
+    collections::HashMap,
+    panic::{self, AssertUnwindSafe},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use once_cell::sync::Lazy;
+use ref_cast::RefCast;
+
+use crate::{
+    convert::ToExpr,
+    expr::{Expr, Number, Symbol},
+    sys, NumericArray, NumericArrayDataType,
+};
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+
+static TRANSCRIPT: Lazy<Mutex<Vec<TranscriptEntry>>> =
+    Lazy::new(|| Mutex::new(Vec::new()));
+
+/// An evaluation recorded in the transcript.
+///
+/// See [`transcript()`].
+#[derive(Debug, Clone)]
+pub struct TranscriptEntry {
+    /// The expression that was sent to the Kernel to be evaluated.
+    pub expr: Expr,
+    /// The result of the evaluation, or a description of the error that occurred.
+    pub result: Result<Expr, String>,
+    /// When the evaluation started.
+    pub time: SystemTime,
+    /// How long the evaluation took.
+    pub duration: Duration,
+}
+
+/// Start recording evaluations in the transcript.
+///
+/// Evaluations recorded previously are kept. Use [`clear_transcript()`] to remove
+/// them.
+pub fn start_transcript() {
+    RECORDING.store(true, Ordering::SeqCst);
+}
+
+/// Stop recording evaluations in the transcript.
+///
+/// Evaluations recorded previously are kept.
+pub fn stop_transcript() {
+    RECORDING.store(false, Ordering::SeqCst);
+}
+
+/// Get the evaluations recorded since the transcript was last cleared, in the order
+/// they happened.
+pub fn transcript() -> Vec<TranscriptEntry> {
+    TRANSCRIPT.lock().unwrap().clone()
+}
+
+/// Remove all evaluations recorded in the transcript.
+pub fn clear_transcript() {
+    TRANSCRIPT.lock().unwrap().clear();
+}
+
+/// Call `evaluate`, recording `expr` and the result of the evaluation in the
+/// transcript if recording is enabled.
+///
+/// `result` gets the returned expression or error message from the value returned by
+/// `evaluate`.
+pub(crate) fn record<T, F, R>(expr: &Expr, evaluate: F, result: R) -> T
+where
+    F: FnOnce() -> T,
+    R: FnOnce(&T) -> Result<Expr, String>,
+{
+    if !RECORDING.load(Ordering::SeqCst) {
+        return evaluate();
+    }
+
+    let time = SystemTime::now();
+    let start = Instant::now();
+
+    let evaluated = evaluate();
+
+    let entry = TranscriptEntry {
+        expr: expr.clone(),
+        result: result(&evaluated),
+        time,
+        duration: start.elapsed(),
+    };
+
+    TRANSCRIPT.lock().unwrap().push(entry);
+
+    evaluated
+}
+
+impl TranscriptEntry {
+    /// Construct an [`Association`][ref/Association]<sub>WL</sub> describing this
+    /// evaluation.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub(crate) fn to_expr(&self) -> Expr {
+        let TranscriptEntry {
+            ref expr,
+            ref result,
+            time,
+            duration,
+        } = *self;
+
+        let rule = |key: &str, value: Expr| {
+            Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(key), value])
+        };
+
+        let hold = |expr: &Expr| {
+            Expr::normal(Symbol::new("System`HoldComplete"), vec![expr.clone()])
+        };
+
+        let result = match result {
+            Ok(value) => hold(value),
+            Err(_) => result.to_expr(),
+        };
+
+        let seconds =
+            |duration: Duration| Expr::number(Number::real(duration.as_secs_f64()));
+
+        let time = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => {
+                Expr::normal(Symbol::new("System`FromUnixTime"), vec![seconds(
+                    since_epoch,
+                )])
+            },
+            Err(_) => Expr::normal(Symbol::new("System`Missing"), vec![Expr::string(
+                "NotAvailable",
+            )]),
+        };
+
+        let duration = Expr::normal(Symbol::new("System`Quantity"), vec![
+            seconds(duration),
+            Expr::string("Seconds"),
+        ]);
+
+        Expr::normal(Symbol::new("System`Association"), vec![
+            rule("Expression", hold(expr)),
+            rule("Result", result),
+            rule("Time", time),
+            rule("Duration", duration),
+        ])
+    }
+}
+
+//======================================
+// Leak checks
+//======================================
+
+static LEAK_CHECKING: AtomicBool = AtomicBool::new(false);
+
+/// `"Manual"` arrays received by the current function call that have not been dropped
+/// or converted using `into_raw()` yet, indexed by the address of the raw array.
+static PENDING_MANUAL: Lazy<Mutex<HashMap<usize, PendingArray>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static LEAKS: Lazy<Mutex<Vec<Leak>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Data type and dimensions of a `"Manual"` array that has not been released yet.
+type PendingArray = (NumericArrayDataType, Vec<usize>);
+
+/// A `"Manual"` [`NumericArray`] that was leaked by a library function.
+///
+/// See [`leaks()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Leak {
+    /// The name of the function that received the array.
+    pub function: String,
+    /// The data type of the array.
+    pub data_type: NumericArrayDataType,
+    /// The dimensions of the array.
+    pub dimensions: Vec<usize>,
+    /// When the function that received the array returned.
+    pub time: SystemTime,
+}
+
+/// Start checking for leaked `"Manual"` arrays.
+///
+/// Leaks detected previously are kept. Use [`clear_leaks()`] to remove them.
+pub fn start_leak_check() {
+    LEAK_CHECKING.store(true, Ordering::SeqCst);
+}
+
+/// Stop checking for leaked `"Manual"` arrays.
+///
+/// Leaks detected previously are kept.
+pub fn stop_leak_check() {
+    LEAK_CHECKING.store(false, Ordering::SeqCst);
+    PENDING_MANUAL.lock().unwrap().clear();
+}
+
+/// Get the leaks detected since they were last cleared, in the order they happened.
+pub fn leaks() -> Vec<Leak> {
+    LEAKS.lock().unwrap().clone()
+}
+
+/// Remove all detected leaks.
+pub fn clear_leaks() {
+    LEAKS.lock().unwrap().clear();
+}
+
+/// Record that the current function received `array` using the `"Manual"` memory
+/// management mode, if leak checking is enabled.
+pub(crate) fn track_manual(raw: sys::MNumericArray) {
+    if !LEAK_CHECKING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    // `raw` was received from the Kernel, so it may reuse the address of an array
+    // released earlier.
+    crate::released::acquire(raw);
+
+    let array: &NumericArray = NumericArray::ref_cast(&raw);
+
+    PENDING_MANUAL.lock().unwrap().insert(
+        raw as usize,
+        (array.data_type(), array.dimensions().to_vec()),
+    );
+}
+
+/// Record that `raw` has been freed, or that ownership of it has been given up.
+pub(crate) fn release_manual(raw: sys::MNumericArray) {
+    if !LEAK_CHECKING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    PENDING_MANUAL.lock().unwrap().remove(&(raw as usize));
+}
+
+/// Call the exported function `function` using `call`, reporting any `"Manual"` array
+/// received by it that is still pending when it returns as a leak.
+pub(crate) fn check_leaks<T, F>(function: &str, call: F) -> T
+where
+    F: FnOnce() -> T,
+{
+    if !LEAK_CHECKING.load(Ordering::SeqCst) {
+        return call();
+    }
+
+    // Set aside the arrays received by the calling function, if this is a nested call.
+    let outer = std::mem::take(&mut *PENDING_MANUAL.lock().unwrap());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(call));
+
+    let pending = std::mem::replace(&mut *PENDING_MANUAL.lock().unwrap(), outer);
+
+    if !pending.is_empty() {
+        let time = SystemTime::now();
+
+        LEAKS.lock().unwrap().extend(pending.into_values().map(
+            |(data_type, dimensions)| Leak {
+                function: function.to_owned(),
+                data_type,
+                dimensions,
+                time,
+            },
+        ));
+    }
+
+    match result {
+        Ok(value) => value,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+impl Leak {
+    /// Construct an [`Association`][ref/Association]<sub>WL</sub> describing this leak.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub(crate) fn to_expr(&self) -> Expr {
+        let Leak {
+            ref function,
+            data_type,
+            ref dimensions,
+            time,
+        } = *self;
+
+        let rule = |key: &str, value: Expr| {
+            Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(key), value])
+        };
+
+        let dimensions = dimensions
+            .iter()
+            .map(|&dim| Expr::from(i64::try_from(dim).unwrap_or(i64::MAX)))
+            .collect();
+
+        let time = match time.duration_since(UNIX_EPOCH) {
+            Ok(since_epoch) => {
+                Expr::normal(Symbol::new("System`FromUnixTime"), vec![Expr::number(
+                    Number::real(since_epoch.as_secs_f64()),
+                )])
+            },
+            Err(_) => Expr::normal(Symbol::new("System`Missing"), vec![Expr::string(
+                "NotAvailable",
+            )]),
+        };
+
+        Expr::normal(Symbol::new("System`Association"), vec![
+            rule("Function", Expr::string(function)),
+            rule("Type", Expr::string(data_type.name())),
+            rule(
+                "Dimensions",
+                Expr::normal(Symbol::new("System`List"), dimensions),
+            ),
+            rule("Time", time),
+        ])
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+use crate::{expr::Expr, EvaluationError};
+
+/// Expressions queued by [`evaluate_later()`], in the order they were queued.
+static PENDING: Lazy<Mutex<Vec<Expr>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+/// Queue `expr` to be evaluated by the Wolfram Kernel at the next safe point, instead
+/// of evaluating it immediately.
+///
+/// This is useful for logging and bookkeeping evaluations that should not block the
+/// hot path of a library function. Queued expressions are evaluated in the order they
+/// were queued:
+///
+/// * when a function exported using [`export!`][crate::export] or
+///   [`export_wstp!`][crate::export_wstp] returns,
+/// * before the next callback into the Kernel, like [`evaluate()`][crate::evaluate],
+///   so that the callback observes the effects of the queued expressions, or
+/// * when [`run_pending_evaluations()`] is called.
+///
+/// The results of queued evaluations are discarded, and errors that occur while
+/// evaluating them are ignored. If an exported function panics, queued expressions are
+/// left in the queue until the next safe point.
+///
+/// Unlike [`evaluate()`][crate::evaluate], this function can be called from any thread.
+/// The queued expressions are always evaluated on the main Kernel thread.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{
+///     self as wll,
+///     expr::{Expr, Symbol},
+/// };
+///
+/// wll::export![checked_sqrt(_)];
+///
+/// fn checked_sqrt(x: f64) -> f64 {
+///     if x < 0.0 {
+///         // Runs after `checked_sqrt()` returns.
+///         wll::evaluate_later(Expr::normal(Symbol::new("System`Print"), vec![
+///             Expr::string(format!("negative input: {}", x)),
+///         ]));
+///
+///         return 0.0;
+///     }
+///
+///     x.sqrt()
+/// }
+/// # }
+/// ```
+pub fn evaluate_later(expr: Expr) {
+    let mut pending = PENDING
+        .lock()
+        .expect("failed to acquire evaluate_later() lock");
+
+    pending.push(expr);
+}
+
+/// Evaluate any expressions queued by [`evaluate_later()`] immediately.
+///
+/// Returns the number of expressions that were evaluated. If this is called while
+/// another evaluation is in progress, for example from a library function called by
+/// that evaluation, the expressions are left in the queue.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+pub fn run_pending_evaluations() -> usize {
+    crate::assert_main_thread();
+
+    let pending: Vec<Expr> = {
+        let mut pending = PENDING
+            .lock()
+            .expect("failed to acquire evaluate_later() lock");
+
+        std::mem::take(&mut *pending)
+    };
+
+    let mut pending = pending.into_iter();
+    let mut count = 0;
+
+    // Note: The queue has already been emptied, so evaluating these expressions does
+    //       not recurse back into run_pending_evaluations() for the same expressions.
+    while let Some(expr) = pending.next() {
+        if let Err(EvaluationError::Reentrant) = crate::try_evaluate(&expr) {
+            // The callback link is in use by an outer evaluation. Put the remaining
+            // expressions back at the front of the queue, so that they are evaluated,
+            // in order, once the outer evaluation has finished.
+            let mut queue = PENDING
+                .lock()
+                .expect("failed to acquire evaluate_later() lock");
+
+            let queued = std::mem::take(&mut *queue);
+
+            *queue = std::iter::once(expr).chain(pending).chain(queued).collect();
+
+            break;
+        }
+
+        count += 1;
+    }
+
+    count
+}
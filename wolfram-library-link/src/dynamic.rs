@@ -0,0 +1,290 @@
+//! Library functions registered at runtime.
+//!
+//! Functions registered using [`register_native()`] are not exported from the library
+//! under their own name. Instead, every dynamically registered function is called
+//! through the [`wll_dynamic_function()`] dispatcher, whose first argument is the index
+//! of the function to call. The loading code generated by
+//! [`generate_loader!`][crate::generate_loader] hides this extra argument.
+
+use std::{
+    os::raw::c_uint,
+    sync::{Arc, RwLock},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    expr::{Expr, Symbol},
+    macro_utils::{self, CallStats, LibraryLinkFunction},
+    sys::{self, MArgument},
+    NativeFunction,
+};
+
+pub(crate) struct DynamicFunction {
+    pub name: String,
+    pub func: Arc<dyn for<'a> NativeFunction<'a> + Send + Sync>,
+    pub stats: CallStats,
+}
+
+static FUNCTIONS: Lazy<RwLock<Vec<Arc<DynamicFunction>>>> =
+    Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Register a native library function at runtime.
+///
+/// This is the runtime counterpart to [`export!`][crate::export], which can be used
+/// when the set of functions provided by a library depends on runtime configuration.
+/// Functions should be registered from the library initialization function designated
+/// by [`#[init]`][crate::init].
+///
+/// `func` must implement [`NativeFunction`], which is typically satisfied by a function
+/// pointer, e.g. `add as fn(i64, i64) -> i64`. To register a closure, use
+/// [`register_native_raw()`].
+///
+/// Dynamically registered functions are loaded by the function generated by
+/// [`generate_loader!`][crate::generate_loader], and are included in the result of
+/// [`exported_functions()`][crate::exported_functions].
+///
+/// # Panics
+///
+/// This function will panic if a function with the same name has already been exported
+/// or registered by this library.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link as wll;
+///
+/// wll::generate_loader![load_plugin_functions];
+///
+/// #[wll::init]
+/// fn init() {
+///     if std::env::var_os("ENABLE_EXPERIMENTAL").is_some() {
+///         wll::register_native("square", square as fn(i64) -> i64);
+///     }
+/// }
+///
+/// fn square(x: i64) -> i64 {
+///     x * x
+/// }
+/// # }
+/// ```
+pub fn register_native<F>(name: &str, func: F)
+where
+    F: for<'a> NativeFunction<'a> + Send + Sync + 'static,
+{
+    register(name, Arc::new(func))
+}
+
+/// Register a native library function that uses raw [`MArgument`]s at runtime.
+///
+/// Unlike [`register_native()`], `func` can be any closure. Because the parameter and
+/// return types of `func` cannot be determined automatically, they must be specified
+/// explicitly, using the same Wolfram Language expressions that would be passed to
+/// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub>.
+///
+/// # Panics
+///
+/// This function will panic if a function with the same name has already been exported
+/// or registered by this library.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{
+///     self as wll,
+///     expr::{Expr, Symbol},
+///     FromArg,
+/// };
+///
+/// let offset: i64 = 10;
+///
+/// let integer = Expr::symbol(Symbol::new("System`Integer"));
+///
+/// wll::register_native_raw("add_offset", vec![integer.clone()], integer, move |args, ret| {
+///     let x = unsafe { i64::from_arg(&args[0]) };
+///
+///     unsafe { *ret.integer = x + offset }
+/// });
+/// ```
+///
+/// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+pub fn register_native_raw<F>(
+    name: &str,
+    parameter_types: Vec<Expr>,
+    return_type: Expr,
+    func: F,
+) where
+    F: Fn(&[MArgument], MArgument) + Send + Sync + 'static,
+{
+    register(
+        name,
+        Arc::new(RawClosure {
+            func,
+            parameter_types,
+            return_type,
+        }),
+    )
+}
+
+fn register(name: &str, func: Arc<dyn for<'a> NativeFunction<'a> + Send + Sync>) {
+    let is_exported = inventory::iter::<LibraryLinkFunction>
+        .into_iter()
+        .any(|exported| exported.name() == name);
+
+    let mut functions = FUNCTIONS.write().unwrap();
+
+    if is_exported || functions.iter().any(|dynamic| dynamic.name == name) {
+        // Drop `functions` to avoid poisoning FUNCTIONS when we panic.
+        drop(functions);
+        panic!(
+            "library function with name '{}' has already been registered",
+            name
+        );
+    }
+
+    functions.push(Arc::new(DynamicFunction {
+        name: name.to_owned(),
+        func,
+        stats: CallStats::new(),
+    }));
+}
+
+/// Get all dynamically registered functions, in registration order.
+pub(crate) fn functions() -> Vec<Arc<DynamicFunction>> {
+    FUNCTIONS.read().unwrap().clone()
+}
+
+impl DynamicFunction {
+    /// Generate the code that loads this function, given the `index` of this function
+    /// in [`functions()`].
+    ///
+    /// ```wolfram
+    /// With[{func = LibraryFunctionLoad[library, "wll_dynamic_function", {Integer, ...}, ret]},
+    ///     func[index, ##]&
+    /// ]
+    /// ```
+    pub(crate) fn loading_code(
+        &self,
+        library: Expr,
+        index: usize,
+    ) -> Result<Expr, String> {
+        fn sys(name: &str) -> Symbol {
+            Symbol::new(&format!("System`{}", name))
+        }
+
+        let (mut params, ret) = self.func.signature()?;
+        params.insert(0, Expr::from(sys("Integer")));
+
+        let var = Expr::from(Symbol::new("RustLink`Private`dynamicFunc"));
+
+        let load_call = Expr::normal(sys("LibraryFunctionLoad"), vec![
+            library,
+            Expr::string("wll_dynamic_function"),
+            Expr::normal(sys("List"), params),
+            ret,
+        ]);
+
+        let index = i64::try_from(index).expect("function index overflows i64");
+
+        Ok(Expr::normal(sys("With"), vec![
+            Expr::normal(sys("List"), vec![Expr::normal(sys("Set"), vec![
+                var.clone(),
+                load_call,
+            ])]),
+            Expr::normal(sys("Function"), vec![Expr::normal(var, vec![
+                Expr::from(index),
+                Expr::normal(sys("SlotSequence"), vec![Expr::from(1)]),
+            ])]),
+        ]))
+    }
+}
+
+//======================================
+// Dispatcher
+//======================================
+
+/// Native function exported by every library that uses `wolfram-library-link`, which
+/// calls the dynamically registered function whose index is given by the first
+/// argument.
+#[no_mangle]
+pub unsafe extern "C" fn wll_dynamic_function(
+    lib_data: sys::WolframLibraryData,
+    argc: sys::mint,
+    args: *mut MArgument,
+    res: MArgument,
+) -> c_uint {
+    if argc < 1 {
+        return sys::LIBRARY_FUNCTION_ERROR;
+    }
+
+    let func: Option<Arc<DynamicFunction>> = usize::try_from(*(*args).integer)
+        .ok()
+        .and_then(|index| FUNCTIONS.read().unwrap().get(index).cloned());
+
+    let func = match func {
+        Some(func) => func,
+        None => return sys::LIBRARY_FUNCTION_ERROR,
+    };
+
+    func.stats.record(|| {
+        macro_utils::call_native_wolfram_library_function(
+            lib_data,
+            &func.name,
+            args.add(1),
+            argc - 1,
+            res,
+            Dispatch(&*func.func),
+            &macro_utils::ExportOptions::new(),
+        )
+    })
+}
+
+/// Adapter for calling a `dyn NativeFunction` using
+/// [`call_native_wolfram_library_function()`][macro_utils::call_native_wolfram_library_function].
+struct Dispatch<'f>(&'f (dyn for<'a> NativeFunction<'a> + Send + Sync));
+
+impl<'a, 'f> NativeFunction<'a> for Dispatch<'f> {
+    unsafe fn call(&self, args: &'a [MArgument], ret: MArgument) {
+        self.0.call(args, ret)
+    }
+
+    unsafe fn try_call(
+        &self,
+        args: &'a [MArgument],
+        ret: MArgument,
+    ) -> Result<(), String> {
+        self.0.try_call(args, ret)
+    }
+
+    fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
+        self.0.signature()
+    }
+
+    fn parameter_count(&self) -> Option<usize> {
+        self.0.parameter_count()
+    }
+}
+
+struct RawClosure<F> {
+    func: F,
+    parameter_types: Vec<Expr>,
+    return_type: Expr,
+}
+
+impl<'a, F> NativeFunction<'a> for RawClosure<F>
+where
+    F: Fn(&[MArgument], MArgument),
+{
+    unsafe fn call(&self, args: &'a [MArgument], ret: MArgument) {
+        (self.func)(args, ret)
+    }
+
+    fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
+        Ok((self.parameter_types.clone(), self.return_type.clone()))
+    }
+
+    fn parameter_count(&self) -> Option<usize> {
+        Some(self.parameter_types.len())
+    }
+}
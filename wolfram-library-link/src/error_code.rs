@@ -0,0 +1,268 @@
+use std::{collections::BTreeMap, fmt, os::raw::c_uint, sync::Mutex};
+
+use once_cell::sync::Lazy;
+
+use crate::expr::{Expr, Symbol};
+
+// Chosen arbitrarily. Avoids clashing with `LIBRARY_FUNCTION_ERROR` and related error
+// codes.
+const OFFSET: c_uint = 1000;
+
+/// Start of the range of error codes reserved for [`ErrorCode::User`].
+const USER_OFFSET: c_uint = 2000;
+
+/// Error set by [`set_function_error()`], returned when the current function returns.
+static FUNCTION_ERROR: Lazy<Mutex<Option<ErrorCode>>> = Lazy::new(|| Mutex::new(None));
+
+/// Failure tags registered using [`register_error_tag()`], by user error code.
+static USER_ERROR_TAGS: Lazy<Mutex<BTreeMap<u32, String>>> =
+    Lazy::new(|| Mutex::new(BTreeMap::new()));
+
+/// Error codes returned by functions exported using [`export!`][crate::export] and
+/// [`export_wstp!`][crate::export_wstp].
+///
+/// If no error occurred, [`LIBRARY_NO_ERROR`][crate::sys::LIBRARY_NO_ERROR] is
+/// returned. Otherwise, the function call evaluates to
+/// <code>[LibraryFunctionError][ref/LibraryFunctionError]["LIBRARY_USER_ERROR", <i>code</i>]</code>,
+/// where *code* is the value returned by [`ErrorCode::to_raw()`].
+///
+/// The wrapper code generated by the export macros uses separate error codes for each
+/// kind of failure, which makes the source of the error clearer when something goes
+/// wrong. Library functions can report their own, domain-specific errors using
+/// [`ErrorCode::User`] and [`set_function_error()`].
+///
+/// | Error code                                 | Raw value        |
+/// |--------------------------------------------|------------------|
+/// | [`ErrorCode::FailedToInit`]                | `1001`           |
+/// | [`ErrorCode::FailedWithPanic`]             | `1002`           |
+/// | [`ErrorCode::ArgumentCountMismatch`]       | `1003`           |
+/// | [`ErrorCode::ArgumentConversionFailed`]    | `1004`           |
+/// | <code>[ErrorCode::User]\(<i>n</i>)</code>  | `2000 + n`       |
+///
+/// [ref/LibraryFunctionError]: https://reference.wolfram.com/language/ref/LibraryFunctionError.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// A call to [`initialize()`][crate::initialize] failed.
+    FailedToInit,
+    /// The library code panicked.
+    FailedWithPanic,
+    /// The function was called with a number of arguments that does not match its
+    /// number of parameters.
+    ///
+    /// A description of the mismatch is available from [`last_error()`][crate::last_error].
+    ArgumentCountMismatch,
+    /// An argument could not be converted to the type of the corresponding parameter,
+    /// because [`TryFromArg::try_from_arg()`][crate::TryFromArg::try_from_arg] failed.
+    ///
+    /// A description of the error is available from [`last_error()`][crate::last_error].
+    ArgumentConversionFailed,
+    /// A library-defined error code, set using [`set_function_error()`].
+    ///
+    /// The meaning of user error codes is up to the library. The code must be at most
+    /// [`ErrorCode::MAX_USER_CODE`].
+    User(u32),
+}
+
+impl ErrorCode {
+    /// The largest code that can be used with [`ErrorCode::User`].
+    pub const MAX_USER_CODE: u32 = 999;
+
+    /// Get the raw error code returned to the Wolfram Kernel.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if this is a [`ErrorCode::User`] code greater than
+    /// [`ErrorCode::MAX_USER_CODE`].
+    pub fn to_raw(self) -> c_uint {
+        match self {
+            ErrorCode::FailedToInit => OFFSET + 1,
+            ErrorCode::FailedWithPanic => OFFSET + 2,
+            ErrorCode::ArgumentCountMismatch => OFFSET + 3,
+            ErrorCode::ArgumentConversionFailed => OFFSET + 4,
+            ErrorCode::User(code) => {
+                if code > ErrorCode::MAX_USER_CODE {
+                    panic!(
+                        "user error code {} is out of range (maximum: {})",
+                        code,
+                        ErrorCode::MAX_USER_CODE
+                    );
+                }
+
+                USER_OFFSET + code
+            },
+        }
+    }
+
+    /// Get the error code corresponding to a raw error code returned to the Wolfram
+    /// Kernel.
+    ///
+    /// Returns `None` if `code` is not one of the error codes used by
+    /// `wolfram-library-link`.
+    pub fn from_raw(code: c_uint) -> Option<ErrorCode> {
+        let error = match code {
+            1001 => ErrorCode::FailedToInit,
+            1002 => ErrorCode::FailedWithPanic,
+            1003 => ErrorCode::ArgumentCountMismatch,
+            1004 => ErrorCode::ArgumentConversionFailed,
+            _ => match code.checked_sub(USER_OFFSET) {
+                Some(user_code) if user_code <= ErrorCode::MAX_USER_CODE => {
+                    ErrorCode::User(user_code)
+                },
+                _ => return None,
+            },
+        };
+
+        Some(error)
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorCode::FailedToInit => write!(f, "library initialization failed"),
+            ErrorCode::FailedWithPanic => write!(f, "library function panicked"),
+            ErrorCode::ArgumentCountMismatch => write!(f, "wrong number of arguments"),
+            ErrorCode::ArgumentConversionFailed => {
+                write!(f, "argument conversion failed")
+            },
+            ErrorCode::User(code) => write!(f, "user error {}", code),
+        }
+    }
+}
+
+/// Set the error code returned by the current library function.
+///
+/// When the function returns, the error code is returned to the Wolfram Kernel instead
+/// of the function's return value, and the call evaluates to
+/// <code>[LibraryFunctionError][ref/LibraryFunctionError]["LIBRARY_USER_ERROR", <i>code</i>]</code>.
+/// If a [`Failure`][ref/Failure]<sub>WL</sub> tag has been registered for `code` using
+/// [`register_error_tag()`], functions loaded using the function generated by
+/// [`generate_loader!`][crate::generate_loader] evaluate to a `Failure` with that tag
+/// instead.
+///
+/// This function only has an effect when called from a function exported using
+/// [`export!`][crate::export]. If it is called more than once, the last error code is
+/// returned.
+///
+/// # Panics
+///
+/// This function will panic if `code` is a [`ErrorCode::User`] code greater than
+/// [`ErrorCode::MAX_USER_CODE`].
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, ErrorCode};
+///
+/// const NEGATIVE_INPUT: ErrorCode = ErrorCode::User(1);
+///
+/// #[wll::init]
+/// fn init() {
+///     wll::register_error_tag(NEGATIVE_INPUT, "NegativeInput");
+/// }
+///
+/// wll::export![checked_sqrt(_)];
+///
+/// fn checked_sqrt(x: f64) -> f64 {
+///     if x < 0.0 {
+///         wll::set_function_error(NEGATIVE_INPUT);
+///         return 0.0;
+///     }
+///
+///     x.sqrt()
+/// }
+/// # }
+/// ```
+///
+/// [ref/LibraryFunctionError]: https://reference.wolfram.com/language/ref/LibraryFunctionError.html
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+pub fn set_function_error(code: ErrorCode) {
+    // Check that the code is in range now, instead of panicking in wrapper code.
+    let _: c_uint = code.to_raw();
+
+    *FUNCTION_ERROR.lock().unwrap() = Some(code);
+}
+
+/// Register the [`Failure`][ref/Failure]<sub>WL</sub> tag used by the loader function
+/// generated by [`generate_loader!`][crate::generate_loader] to report the user error
+/// `code`.
+///
+/// Functions exported using [`export!`][crate::export] that are loaded using the
+/// loader function evaluate to
+///
+/// ```wolfram
+/// Failure[tag, <| "ErrorCode" -> code, "Function" -> name |>]
+/// ```
+///
+/// when they return `code`, instead of a
+/// [`LibraryFunctionError`][ref/LibraryFunctionError]<sub>WL</sub>. The tag must be
+/// registered before the loader function is called, for example in the library
+/// [`#[init]`][crate::init] function. See [`set_function_error()`] for an example.
+///
+/// # Panics
+///
+/// This function will panic if `code` is not a [`ErrorCode::User`] code, or if it is
+/// greater than [`ErrorCode::MAX_USER_CODE`].
+///
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+/// [ref/LibraryFunctionError]: https://reference.wolfram.com/language/ref/LibraryFunctionError.html
+pub fn register_error_tag(code: ErrorCode, tag: &str) {
+    let user_code = match code {
+        ErrorCode::User(user_code) => user_code,
+        _ => panic!(
+            "cannot register Failure tag for built-in error code: {:?}",
+            code
+        ),
+    };
+
+    let _: c_uint = code.to_raw();
+
+    USER_ERROR_TAGS
+        .lock()
+        .unwrap()
+        .insert(user_code, tag.to_owned());
+}
+
+/// Clear the error set by [`set_function_error()`], returning it if one was set.
+pub(crate) fn take_function_error() -> Option<ErrorCode> {
+    FUNCTION_ERROR.lock().unwrap().take()
+}
+
+/// Construct the rules used by the loader to convert the error codes that have a
+/// registered tag into `Failure[..]` objects.
+///
+/// ```wolfram
+/// LibraryFunctionError[_, code] :> Failure[tag, <| "ErrorCode" -> code, "Function" -> name |>]
+/// ```
+pub(crate) fn failure_rules(function: &str) -> Vec<Expr> {
+    fn sys(name: &str) -> Symbol {
+        Symbol::new(&format!("System`{}", name))
+    }
+
+    let tags = USER_ERROR_TAGS.lock().unwrap();
+
+    tags.iter()
+        .map(|(&user_code, tag)| {
+            let code = Expr::from(i64::from(ErrorCode::User(user_code).to_raw()));
+
+            let pattern = Expr::normal(sys("LibraryFunctionError"), vec![
+                Expr::normal(sys("Blank"), vec![]),
+                code.clone(),
+            ]);
+
+            let failure = Expr::normal(sys("Failure"), vec![
+                Expr::string(tag),
+                Expr::normal(sys("Association"), vec![
+                    Expr::normal(sys("Rule"), vec![Expr::string("ErrorCode"), code]),
+                    Expr::normal(sys("Rule"), vec![
+                        Expr::string("Function"),
+                        Expr::string(function),
+                    ]),
+                ]),
+            ]);
+
+            Expr::normal(sys("RuleDelayed"), vec![pattern, failure])
+        })
+        .collect()
+}
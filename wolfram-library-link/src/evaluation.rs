@@ -0,0 +1,257 @@
+use std::fmt;
+
+use crate::{
+    expr::{Expr, ExprKind, Symbol},
+    wstp::Link,
+};
+
+/// The result of evaluating an expression by calling back into the Wolfram Kernel,
+/// along with any output produced during the evaluation.
+///
+/// `EvalResult` is returned by [`evaluate()`][crate::evaluate].
+///
+/// Evaluated code can produce output in addition to its result, for example by calling
+/// [`Print`][ref/Print]<sub>WL</sub> or issuing a message. The Kernel sends this output
+/// as separate packets before the result, which are collected here. Output produced
+/// before an evaluation was aborted is collected as well.
+///
+/// [ref/Print]: https://reference.wolfram.com/language/ref/Print.html
+#[derive(Debug, Clone, PartialEq)]
+#[must_use]
+pub struct EvalResult {
+    /// The value returned by the evaluation, or the reason the evaluation failed.
+    pub value: Result<Expr, EvaluationError>,
+    /// Text printed during the evaluation, in the order it was printed.
+    pub output: Vec<String>,
+    /// Text of the messages issued during the evaluation, in the order they were
+    /// issued.
+    pub messages: Vec<String>,
+}
+
+impl EvalResult {
+    /// Returns `true` if the evaluation was aborted.
+    pub fn is_aborted(&self) -> bool {
+        self.value == Err(EvaluationError::Aborted)
+    }
+
+    /// Get the value returned by the evaluation, discarding any output.
+    pub fn into_result(self) -> Result<Expr, EvaluationError> {
+        self.value
+    }
+
+    /// Get the value returned by the evaluation.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the evaluation failed.
+    #[track_caller]
+    pub fn unwrap(self) -> Expr {
+        match self.value {
+            Ok(value) => value,
+            Err(err) => panic!("evaluation of expression failed: {}", err),
+        }
+    }
+
+    fn from_error(err: EvaluationError) -> Self {
+        EvalResult {
+            value: Err(err),
+            output: Vec::new(),
+            messages: Vec::new(),
+        }
+    }
+}
+
+/// The result of a successful evaluation, along with any output produced during the
+/// evaluation.
+///
+/// Use [`evaluate()`][crate::evaluate], which returns an [`EvalResult`], instead.
+#[deprecated(note = "use `EvalResult`, which is returned by `evaluate()`")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvaluationOutcome {
+    /// The value returned by the evaluation.
+    pub value: Expr,
+    /// Text printed during the evaluation, in the order it was printed.
+    pub output: Vec<String>,
+    /// Text of the messages issued during the evaluation, in the order they were
+    /// issued.
+    pub messages: Vec<String>,
+}
+
+/// Error returned when evaluating an expression by calling back into the Wolfram
+/// Kernel fails.
+///
+/// This distinguishes evaluations that were interrupted, like an abort requested by
+/// the user, from problems communicating with the Kernel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvaluationError {
+    /// The evaluation was aborted, and returned [`$Aborted`][ref/$Aborted]<sub>WL</sub>.
+    ///
+    /// This usually means the user requested an abort. Library functions should return
+    /// as quickly as possible.
+    ///
+    /// [ref/$Aborted]: https://reference.wolfram.com/language/ref/$Aborted.html
+    Aborted,
+    /// The evaluation called [`Throw`][ref/Throw]<sub>WL</sub>, and the thrown value
+    /// was not caught by a corresponding [`Catch`][ref/Catch]<sub>WL</sub>.
+    ///
+    /// [ref/Throw]: https://reference.wolfram.com/language/ref/Throw.html
+    /// [ref/Catch]: https://reference.wolfram.com/language/ref/Catch.html
+    UncaughtThrow {
+        /// The value that was thrown.
+        value: Expr,
+        /// The tag the value was thrown with, if any.
+        tag: Option<Expr>,
+    },
+    /// The evaluation was not started, because the Kernel callback link was already in
+    /// use by another evaluation.
+    ///
+    /// This happens when a library function that was called during an evaluation
+    /// started by [`evaluate()`][crate::evaluate] calls back into the Kernel itself.
+    /// Nested evaluations are not supported, because they would interleave their
+    /// packets with the packets of the outer evaluation on the shared callback link.
+    Reentrant,
+    /// A WSTP transport error occurred, or the response sent by the Kernel was not
+    /// valid.
+    Link(String),
+}
+
+impl fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvaluationError::Aborted => write!(f, "evaluation was aborted"),
+            EvaluationError::UncaughtThrow { value, tag: None } => {
+                write!(f, "uncaught Throw[{}]", value)
+            },
+            EvaluationError::UncaughtThrow {
+                value,
+                tag: Some(tag),
+            } => write!(f, "uncaught Throw[{}, {}]", value, tag),
+            EvaluationError::Reentrant => write!(
+                f,
+                "cannot evaluate an expression while another evaluation is in progress \
+                 (evaluate() was called recursively from within an evaluation)"
+            ),
+            EvaluationError::Link(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
+
+impl From<EvaluationError> for String {
+    fn from(err: EvaluationError) -> String {
+        err.to_string()
+    }
+}
+
+/// Evaluate `expr` by sending it to the Kernel on the callback link.
+pub(crate) fn evaluate(expr: &Expr) -> EvalResult {
+    let mut result = match crate::evaluate_with_link(expr, read_packets) {
+        Ok(result) => result,
+        Err(err) => return EvalResult::from_error(err),
+    };
+
+    if let Ok(ref value) = result.value {
+        if let Err(err) = check_returned_value(value) {
+            result.value = Err(err);
+        }
+    }
+
+    result
+}
+
+/// Check whether `value`, the result of an evaluation, indicates that the evaluation
+/// was interrupted.
+///
+/// An aborted evaluation returns `$Aborted`, and a `Throw` that is not caught returns
+/// `Hold[Throw[value]]` or `Hold[Throw[value, tag]]`.
+fn check_returned_value(value: &Expr) -> Result<(), EvaluationError> {
+    match value.kind() {
+        ExprKind::Symbol(symbol) if symbol.as_str() == "System`$Aborted" => {
+            Err(EvaluationError::Aborted)
+        },
+        ExprKind::Normal(hold) if hold.has_head(&Symbol::new("System`Hold")) => {
+            let throw = match hold.elements() {
+                [throw] => throw,
+                _ => return Ok(()),
+            };
+
+            match throw.kind() {
+                ExprKind::Normal(throw)
+                    if throw.has_head(&Symbol::new("System`Throw")) =>
+                {
+                    match throw.elements() {
+                        [value] => Err(EvaluationError::UncaughtThrow {
+                            value: value.clone(),
+                            tag: None,
+                        }),
+                        [value, tag] => Err(EvaluationError::UncaughtThrow {
+                            value: value.clone(),
+                            tag: Some(tag.clone()),
+                        }),
+                        _ => Ok(()),
+                    }
+                },
+                _ => Ok(()),
+            }
+        },
+        _ => Ok(()),
+    }
+}
+
+/// Read the packets sent by the Kernel in response to an `EvaluatePacket`, up to and
+/// including the `ReturnPacket` containing the result.
+pub(crate) fn read_packets(link: &mut Link) -> Result<EvalResult, String> {
+    let mut output = Vec::new();
+    let mut messages = Vec::new();
+
+    // A `MessagePacket[symbol, tag]` is followed by a `TextPacket` containing the
+    // formatted message text.
+    let mut is_message_text = false;
+
+    loop {
+        let packet: Expr = link.get_expr().map_err(|e| e.to_string())?;
+
+        let normal = match packet.kind() {
+            ExprKind::Normal(normal) => normal,
+            _ => {
+                return Err(format!(
+                    "evaluation returned unexpected non-packet expression: {}",
+                    packet
+                ))
+            },
+        };
+
+        let head = match normal.head().kind() {
+            ExprKind::Symbol(head) => head.as_str(),
+            _ => "",
+        };
+
+        match (head, normal.elements()) {
+            ("System`ReturnPacket", [value]) => {
+                return Ok(EvalResult {
+                    value: Ok(value.clone()),
+                    output,
+                    messages,
+                });
+            },
+            ("System`TextPacket", [text]) => {
+                let text = match text.kind() {
+                    ExprKind::String(text) => text.trim_end_matches('\n').to_owned(),
+                    _ => text.to_string(),
+                };
+
+                if is_message_text {
+                    messages.push(text);
+                } else {
+                    output.push(text);
+                }
+
+                is_message_text = false;
+            },
+            ("System`MessagePacket", _) => is_message_text = true,
+            // Ignore other packet types, like `OutputNamePacket` and `InputNamePacket`.
+            _ => is_message_text = false,
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Condvar, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use crate::library_data::is_main_thread;
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Queue of closures that must be run on the main Kernel thread.
+///
+/// Callbacks into the Wolfram Kernel — e.g. [`evaluate()`][crate::evaluate] or
+/// [`aborted()`][crate::aborted] — can only be made from the main Kernel thread.
+/// `MainThreadExecutor` allows code running on other threads to submit closures that
+/// make those callbacks. The submitted closures are run the next time the main thread
+/// calls [`MainThreadExecutor::poll()`].
+///
+/// `MainThreadExecutor` is cheap to clone; clones share the same queue.
+///
+/// # Example
+///
+/// Log progress messages from worker threads while a computation is running:
+///
+/// ```no_run
+/// # mod scope {
+/// use std::thread;
+///
+/// use wolfram_library_link::{
+///     self as wll,
+///     expr::{Expr, Symbol},
+///     MainThreadExecutor,
+/// };
+///
+/// wll::export![parallel_work(_)];
+///
+/// fn parallel_work(workers: i64) -> i64 {
+///     let executor = MainThreadExecutor::new();
+///
+///     let handles: Vec<_> = (0..workers)
+///         .map(|index| {
+///             let executor = executor.clone();
+///
+///             thread::spawn(move || {
+///                 // ... do some work ...
+///
+///                 let message = format!("worker {} finished", index);
+///
+///                 executor
+///                     .spawn(move || {
+///                         wll::evaluate(&Expr::normal(Symbol::new("System`Print"), vec![
+///                             Expr::string(message),
+///                         ]))
+///                     })
+///                     .wait();
+///
+///                 index
+///             })
+///         })
+///         .collect();
+///
+///     // Run submitted closures until all of the workers have finished.
+///     while !handles.iter().all(|handle| handle.is_finished()) {
+///         executor.poll();
+///         thread::yield_now();
+///     }
+///
+///     handles.into_iter().map(|handle| handle.join().unwrap()).sum()
+/// }
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct MainThreadExecutor {
+    queue: Arc<Mutex<VecDeque<Job>>>,
+}
+
+/// Handle to the result of a closure submitted using [`MainThreadExecutor::spawn()`].
+///
+/// The result can be retrieved by calling [`MainThreadTask::wait()`] from a thread
+/// other than the main Kernel thread, or by awaiting the `MainThreadTask`, which
+/// implements [`Future`].
+pub struct MainThreadTask<R> {
+    state: Arc<TaskState<R>>,
+}
+
+struct TaskState<R> {
+    inner: Mutex<TaskInner<R>>,
+    completed: Condvar,
+}
+
+struct TaskInner<R> {
+    result: Option<thread::Result<R>>,
+    waker: Option<Waker>,
+}
+
+/// Completes a task when its closure has been run.
+///
+/// If the closure is dropped without being run, because the executor and all of its
+/// clones were dropped first, the task is completed with a panic instead, so that
+/// [`MainThreadTask::wait()`] does not block forever.
+struct Completion<R> {
+    state: Option<Arc<TaskState<R>>>,
+}
+
+/// Panic payload of a task whose closure was never run.
+const EXECUTOR_DROPPED: &str =
+    "MainThreadTask: the MainThreadExecutor was dropped before the closure was run";
+
+//======================================
+// Impls
+//======================================
+
+impl MainThreadExecutor {
+    /// Construct a new executor with an empty queue.
+    pub fn new() -> Self {
+        MainThreadExecutor {
+            queue: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+
+    /// Submit `func` to be run on the main Kernel thread.
+    ///
+    /// `func` is run during the next call to [`MainThreadExecutor::poll()`]. The
+    /// returned [`MainThreadTask`] can be used to retrieve the value returned by `func`.
+    ///
+    /// If `func` panics, the panic is caught, and is resumed on the thread that
+    /// retrieves the result from the `MainThreadTask`.
+    pub fn spawn<F, R>(&self, func: F) -> MainThreadTask<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let state = Arc::new(TaskState {
+            inner: Mutex::new(TaskInner {
+                result: None,
+                waker: None,
+            }),
+            completed: Condvar::new(),
+        });
+
+        let completion = Completion {
+            state: Some(Arc::clone(&state)),
+        };
+
+        let job: Job = Box::new(move || {
+            let result = panic::catch_unwind(AssertUnwindSafe(func));
+
+            completion.complete(result);
+        });
+
+        self.queue.lock().unwrap().push_back(job);
+
+        MainThreadTask { state }
+    }
+
+    /// Run all closures that are currently queued, and return the number of closures
+    /// that were run.
+    ///
+    /// Closures submitted while `poll()` is running (including by the closures
+    /// themselves) are run before `poll()` returns.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if called from a thread other than the main Kernel
+    /// thread.
+    pub fn poll(&self) -> usize {
+        crate::assert_main_thread();
+
+        let mut count = 0;
+
+        loop {
+            // Don't hold the lock while `job` runs, so that it can submit new closures.
+            let job = match self.queue.lock().unwrap().pop_front() {
+                Some(job) => job,
+                None => return count,
+            };
+
+            job();
+
+            count += 1;
+        }
+    }
+
+    /// Returns the number of closures waiting to be run.
+    pub fn pending(&self) -> usize {
+        self.queue.lock().unwrap().len()
+    }
+}
+
+impl<R> MainThreadTask<R> {
+    /// Returns `true` if the submitted closure has finished running.
+    pub fn is_finished(&self) -> bool {
+        self.state.inner.lock().unwrap().result.is_some()
+    }
+
+    /// Block the current thread until the submitted closure has been run, and return
+    /// its result.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the submitted closure panicked, or if the executor
+    /// and all of its clones were dropped before the closure was run.
+    ///
+    /// This function will also panic if called from the main Kernel thread, because the
+    /// closure can never run while the main thread is blocked.
+    pub fn wait(self) -> R {
+        assert!(
+            !is_main_thread() || self.is_finished(),
+            "MainThreadTask::wait(): waiting on the main Kernel thread would deadlock"
+        );
+
+        let mut inner = self.state.inner.lock().unwrap();
+
+        loop {
+            if let Some(result) = inner.result.take() {
+                return unwrap_result(result);
+            }
+
+            inner = self.state.completed.wait(inner).unwrap();
+        }
+    }
+}
+
+impl<R> Completion<R> {
+    fn complete(mut self, result: thread::Result<R>) {
+        if let Some(state) = self.state.take() {
+            state.complete(result);
+        }
+    }
+}
+
+impl<R> Drop for Completion<R> {
+    fn drop(&mut self) {
+        if let Some(state) = self.state.take() {
+            state.complete(Err(Box::new(EXECUTOR_DROPPED)));
+        }
+    }
+}
+
+impl<R> TaskState<R> {
+    fn complete(&self, result: thread::Result<R>) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.result = Some(result);
+
+        if let Some(waker) = inner.waker.take() {
+            waker.wake();
+        }
+
+        self.completed.notify_all();
+    }
+}
+
+impl Default for MainThreadExecutor {
+    fn default() -> Self {
+        MainThreadExecutor::new()
+    }
+}
+
+impl<R> Future for MainThreadTask<R> {
+    type Output = R;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<R> {
+        let mut inner = self.state.inner.lock().unwrap();
+
+        match inner.result.take() {
+            Some(result) => Poll::Ready(unwrap_result(result)),
+            None => {
+                inner.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+fn unwrap_result<R>(result: thread::Result<R>) -> R {
+    match result {
+        Ok(value) => value,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
+
+impl fmt::Debug for MainThreadExecutor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MainThreadExecutor")
+            .field("pending", &self.pending())
+            .finish()
+    }
+}
+
+impl<R> fmt::Debug for MainThreadTask<R> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MainThreadTask")
+            .field("is_finished", &self.is_finished())
+            .finish()
+    }
+}
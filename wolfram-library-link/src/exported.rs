@@ -0,0 +1,175 @@
+use std::{fmt, sync::Arc};
+
+use crate::{
+    dynamic::DynamicFunction,
+    expr::{Expr, Symbol},
+    macro_utils::LibraryLinkFunction,
+};
+
+/// Metadata describing a function exported using [`export!`][crate::export] or
+/// [`export_wstp!`][crate::export_wstp], or registered using
+/// [`register_native()`][crate::register_native].
+///
+/// Use [`exported_functions()`] to get the functions exported by this library.
+#[derive(Clone)]
+pub struct ExportedFunction {
+    func: Function,
+}
+
+#[derive(Clone)]
+enum Function {
+    Exported(&'static LibraryLinkFunction),
+    Dynamic(Arc<DynamicFunction>),
+}
+
+/// The calling convention used by an [`ExportedFunction`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExportedFunctionKind {
+    /// Function exported using [`export!`][crate::export] or registered using
+    /// [`register_native()`][crate::register_native].
+    Native,
+    /// Function exported using [`export_wstp!`][crate::export_wstp].
+    Wstp,
+}
+
+/// Get metadata for all functions exported by this library using
+/// [`export!`][crate::export] and [`export_wstp!`][crate::export_wstp], or registered
+/// using [`register_native()`][crate::register_native].
+///
+/// The order of the returned functions is unspecified.
+///
+/// This information is also available from the Wolfram Language by loading the
+/// `wll_exported_functions` WSTP function that is exported automatically by every
+/// library that uses `wolfram-library-link`:
+///
+/// ```wolfram
+/// LibraryFunctionLoad["library", "wll_exported_functions", LinkObject, LinkObject][]
+/// ```
+///
+/// which returns a list of associations with the keys `"Name"`, `"Kind"`,
+/// `"Parameters"` and `"Return"`.
+pub fn exported_functions() -> Vec<ExportedFunction> {
+    let exported = inventory::iter::<LibraryLinkFunction>
+        .into_iter()
+        .map(Function::Exported);
+
+    let dynamic = crate::dynamic::functions()
+        .into_iter()
+        .map(Function::Dynamic);
+
+    exported
+        .chain(dynamic)
+        .map(|func| ExportedFunction { func })
+        .collect()
+}
+
+impl ExportedFunction {
+    /// The name of the exported function, as used with
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub>.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub fn name(&self) -> &str {
+        match self.func {
+            Function::Exported(func) => func.name(),
+            Function::Dynamic(ref func) => &func.name,
+        }
+    }
+
+    /// Whether this is a native or a WSTP function.
+    pub fn kind(&self) -> ExportedFunctionKind {
+        match self.func {
+            Function::Exported(LibraryLinkFunction::Native { .. }) => {
+                ExportedFunctionKind::Native
+            },
+            Function::Exported(LibraryLinkFunction::Wstp { .. }) => {
+                ExportedFunctionKind::Wstp
+            },
+            Function::Dynamic(_) => ExportedFunctionKind::Native,
+        }
+    }
+
+    /// Whether this function was exported using the `#[thread_safe]` attribute of
+    /// [`export!`][crate::export], and can be called concurrently from any thread.
+    pub fn is_thread_safe(&self) -> bool {
+        match self.func {
+            Function::Exported(LibraryLinkFunction::Native { options, .. }) => {
+                options.is_thread_safe()
+            },
+            Function::Exported(LibraryLinkFunction::Wstp { .. }) => false,
+            Function::Dynamic(_) => false,
+        }
+    }
+
+    /// The parameter and return types of this function, as used with
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub>.
+    ///
+    /// The parameter types are returned as a `List[...]` expression for native
+    /// functions, and as `LinkObject` for WSTP functions.
+    ///
+    /// Returns an error if the signature cannot be determined, which is currently the
+    /// case for native functions with the signature `fn(&[MArgument], MArgument)`.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub fn signature(&self) -> Result<(Expr, Expr), String> {
+        let (params, ret) = match self.func {
+            Function::Exported(LibraryLinkFunction::Native { signature, .. }) => {
+                signature()?
+            },
+            Function::Dynamic(ref func) => func.func.signature()?,
+            Function::Exported(LibraryLinkFunction::Wstp { .. }) => {
+                let link_object = Expr::symbol(Symbol::new("System`LinkObject"));
+
+                return Ok((link_object.clone(), link_object));
+            },
+        };
+
+        Ok((Expr::normal(Symbol::new("System`List"), params), ret))
+    }
+
+    /// Construct an [`Association`][ref/Association]<sub>WL</sub> describing this
+    /// function.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub(crate) fn to_expr(&self) -> Expr {
+        let rule = |key: &str, value: Expr| {
+            Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(key), value])
+        };
+
+        let kind = match self.kind() {
+            ExportedFunctionKind::Native => "Native",
+            ExportedFunctionKind::Wstp => "WSTP",
+        };
+
+        let mut fields = vec![
+            rule("Name", Expr::string(self.name())),
+            rule("Kind", Expr::string(kind)),
+        ];
+
+        match self.signature() {
+            Ok((params, ret)) => {
+                fields.push(rule("Parameters", params));
+                fields.push(rule("Return", ret));
+            },
+            Err(message) => {
+                let missing = Expr::normal(Symbol::new("System`Missing"), vec![
+                    Expr::string("Unknown"),
+                    Expr::string(message),
+                ]);
+
+                fields.push(rule("Parameters", missing.clone()));
+                fields.push(rule("Return", missing));
+            },
+        }
+
+        Expr::normal(Symbol::new("System`Association"), fields)
+    }
+}
+
+impl fmt::Debug for ExportedFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExportedFunction")
+            .field("name", &self.name())
+            .field("kind", &self.kind())
+            .finish()
+    }
+}
@@ -1,10 +1,11 @@
-use std::{ffi::c_void, marker::PhantomData, os::raw::c_int};
+use std::{ffi::c_void, marker::PhantomData, mem::MaybeUninit, os::raw::c_int};
 
 use static_assertions::assert_type_eq_all;
 
 use crate::{
     rtl,
     sys::{self, mbool, mint, MImage_CS_Type::*, MImage_Data_Type::*},
+    NumericArray, NumericArrayType,
 };
 
 /// Native Wolfram [`Image`][ref/Image]<sub>WL</sub> or
@@ -37,6 +38,7 @@ pub enum ImageType {
 }
 
 /// Color space used by an [`Image`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 #[repr(i32)]
 #[allow(missing_docs)]
 pub enum ColorSpace {
@@ -244,6 +246,24 @@ unsafe impl ImageData for f64 {
 // Impls
 //======================================
 
+impl Image {
+    /// Attempt to resolve this `Image` into a `&Image<T>` of the specified data type.
+    ///
+    /// If the data type of this image does not match `T`, `None` will be returned.
+    pub fn try_kind<T: ImageData>(&self) -> Option<&Image<T>> {
+        /// See `NumericArray::try_kind()` for the purpose of this function.
+        unsafe fn trans<T: ImageData>(image: &Image) -> &Image<T> {
+            std::mem::transmute(image)
+        }
+
+        if self.data_type_raw() == T::TYPE.as_raw() {
+            return Some(unsafe { trans(self) });
+        }
+
+        None
+    }
+}
+
 impl<T: ImageData> Image<T> {
     /// Access the data in this [`Image`] as a flat buffer.
     ///
@@ -307,6 +327,365 @@ impl<T: ImageData> Image<T> {
     }
 }
 
+impl<T: ImageData> Image<T>
+where
+    T::STORAGE: NumericArrayType,
+{
+    /// Copy the data in this image into a new [`NumericArray`].
+    ///
+    /// The dimensions of the array depend on the [interleaving][Image::is_interleaved]
+    /// of this image:
+    ///
+    /// * interleaved images have dimensions `{rows, columns, channels}`, or
+    ///   `{slices, rows, columns, channels}` for 3D images, and
+    /// * planar images have dimensions `{channels, rows, columns}`, or
+    ///   `{channels, slices, rows, columns}` for 3D images.
+    ///
+    /// As in [`ImageData`][ref/ImageData]<sub>WL</sub>, the channel axis is omitted if
+    /// this image has a single channel.
+    ///
+    /// Bitmapped images are converted to an array of `i8` values that are either 0
+    /// or 1.
+    ///
+    /// [ref/ImageData]: https://reference.wolfram.com/language/ref/ImageData.html
+    pub fn to_numeric_array(&self) -> NumericArray<T::STORAGE> {
+        let channels = self.channels();
+
+        let mut dimensions = Vec::with_capacity(4);
+
+        if self.rank() == 3 {
+            dimensions.push(self.slice_count());
+        }
+
+        dimensions.push(self.row_count());
+        dimensions.push(self.column_count());
+
+        if channels != 1 {
+            if self.is_interleaved() {
+                dimensions.push(channels);
+            } else {
+                dimensions.insert(0, channels);
+            }
+        }
+
+        NumericArray::from_array(&dimensions, self.as_slice())
+    }
+
+    /// Construct a new 2D image from the data in `data`.
+    ///
+    /// This is the inverse of [`Image::to_numeric_array()`]. `data` must have dimensions
+    /// `{rows, columns}` for a single-channel image, or otherwise `{rows, columns,
+    /// channels}` if `interleaving` is `true`, and `{channels, rows, columns}` if
+    /// `interleaving` is `false`.
+    ///
+    /// # Example
+    ///
+    /// Invert the colors of an image:
+    ///
+    /// ```no_run
+    /// # mod scope {
+    /// use wolfram_library_link::{self as wll, Image};
+    ///
+    /// wll::export![inverted(_)];
+    ///
+    /// fn inverted(image: &Image<f64>) -> Image<f64> {
+    ///     let mut data = image.to_numeric_array();
+    ///
+    ///     for value in data.as_slice_mut().unwrap() {
+    ///         *value = 1.0 - *value;
+    ///     }
+    ///
+    ///     Image::from_numeric_array(&data, image.color_space(), image.is_interleaved())
+    ///         .unwrap()
+    /// }
+    /// # }
+    /// ```
+    pub fn from_numeric_array(
+        data: &NumericArray<T::STORAGE>,
+        space: ColorSpace,
+        interleaving: bool,
+    ) -> Result<Image<T>, String> {
+        let (rows, columns, channels) = match *data.dimensions() {
+            [rows, columns] => (rows, columns, 1),
+            [rows, columns, channels] if interleaving => (rows, columns, channels),
+            [channels, rows, columns] => (rows, columns, channels),
+            ref dimensions => {
+                return Err(format!(
+                    "expected NumericArray of rank 2 or 3 for image data, got rank {}",
+                    dimensions.len()
+                ))
+            },
+        };
+
+        let mut image =
+            UninitImage::<T>::try_new_2d(columns, rows, channels, space, interleaving)
+                .map_err(|err_code| {
+                    format!("failed to create image: error code {}", err_code)
+                })?;
+
+        for (elem, value) in image.as_slice_mut().iter_mut().zip(data.as_slice()) {
+            elem.write(*value);
+        }
+
+        // Safety: The image has the same number of elements as `data`, and every
+        //         element was initialized above.
+        Ok(unsafe { image.assume_init() })
+    }
+}
+
+impl<T: ImageData> Image<T> {
+    //
+    // Channels
+    //
+
+    /// Extract the channel with the specified 1-based index into a new single-channel
+    /// [`Gray`][ColorSpace::Gray] image.
+    ///
+    /// See also [`ColorSeparate`][ref/ColorSeparate]<sub>WL</sub>.
+    ///
+    /// [ref/ColorSeparate]: https://reference.wolfram.com/language/ref/ColorSeparate.html
+    pub fn channel(&self, channel: usize) -> Result<Image<T>, String> {
+        let channels = self.channels();
+
+        if channel == 0 || channel > channels {
+            return Err(format!(
+                "channel index {} is out of bounds for image with {} channels",
+                channel, channels
+            ));
+        }
+
+        let plane = self.plane(channel - 1)?;
+
+        new_image_from_planes(
+            self.row_count(),
+            self.column_count(),
+            &[plane],
+            ColorSpace::Gray,
+            false,
+        )
+    }
+
+    /// Construct a new image by combining single-channel images, which must all have
+    /// the same dimensions.
+    ///
+    /// See also [`ColorCombine`][ref/ColorCombine]<sub>WL</sub>.
+    ///
+    /// [ref/ColorCombine]: https://reference.wolfram.com/language/ref/ColorCombine.html
+    pub fn from_channels(
+        channels: &[&Image<T>],
+        space: ColorSpace,
+        interleaving: bool,
+    ) -> Result<Image<T>, String> {
+        let first = channels
+            .first()
+            .ok_or_else(|| "expected at least one channel image".to_owned())?;
+
+        let (rows, columns) = (first.row_count(), first.column_count());
+
+        let mut planes = Vec::with_capacity(channels.len());
+
+        for image in channels {
+            if image.channels() != 1 {
+                return Err(format!(
+                    "expected single-channel image, got image with {} channels",
+                    image.channels()
+                ));
+            }
+
+            if (image.row_count(), image.column_count()) != (rows, columns) {
+                return Err("channel images must all have the same dimensions".to_owned());
+            }
+
+            planes.push(image.plane(0)?);
+        }
+
+        new_image_from_planes(rows, columns, &planes, space, interleaving)
+    }
+
+    /// Construct a copy of this image with `alpha` added as its alpha channel.
+    ///
+    /// `alpha` must be a single-channel image with the same dimensions as this image. If
+    /// this image already has an alpha channel, it is replaced.
+    ///
+    /// See also [`SetAlphaChannel`][ref/SetAlphaChannel]<sub>WL</sub>.
+    ///
+    /// [ref/SetAlphaChannel]: https://reference.wolfram.com/language/ref/SetAlphaChannel.html
+    pub fn add_alpha_channel(&self, alpha: &Image<T>) -> Result<Image<T>, String> {
+        let mut planes = self.color_planes()?;
+
+        if alpha.channels() != 1 {
+            return Err("alpha channel image must have a single channel".to_owned());
+        }
+
+        if (alpha.row_count(), alpha.column_count())
+            != (self.row_count(), self.column_count())
+        {
+            return Err(
+                "alpha channel image must have the same dimensions as the image"
+                    .to_owned(),
+            );
+        }
+
+        planes.push(alpha.plane(0)?);
+
+        new_image_from_planes(
+            self.row_count(),
+            self.column_count(),
+            &planes,
+            self.color_space(),
+            self.is_interleaved(),
+        )
+    }
+
+    /// Construct a copy of this image without its alpha channel.
+    ///
+    /// If this image does not have an alpha channel, an unmodified copy is returned.
+    ///
+    /// See also [`RemoveAlphaChannel`][ref/RemoveAlphaChannel]<sub>WL</sub>.
+    ///
+    /// [ref/RemoveAlphaChannel]: https://reference.wolfram.com/language/ref/RemoveAlphaChannel.html
+    pub fn remove_alpha_channel(&self) -> Result<Image<T>, String> {
+        let planes = self.color_planes()?;
+
+        new_image_from_planes(
+            self.row_count(),
+            self.column_count(),
+            &planes,
+            self.color_space(),
+            self.is_interleaved(),
+        )
+    }
+
+    /// Convert this image to the color space `space`.
+    ///
+    /// Conversions between [`Gray`][ColorSpace::Gray] and [`RGB`][ColorSpace::RGB]
+    /// images are supported. Gray values are computed from RGB values using the
+    /// weights `0.299`, `0.587`, and `0.114`. An alpha channel is preserved.
+    ///
+    /// Returns an error for other conversions; use
+    /// [`ColorConvert`][ref/ColorConvert]<sub>WL</sub> for those instead.
+    ///
+    /// [ref/ColorConvert]: https://reference.wolfram.com/language/ref/ColorConvert.html
+    pub fn convert_color_space(&self, space: ColorSpace) -> Result<Image<T>, String> {
+        let current = self.color_space();
+
+        if current == space {
+            return self.try_clone().map_err(|err_code| {
+                format!("failed to copy image: error code {}", err_code)
+            });
+        }
+
+        // Perform the conversion on real values in the range 0 to 1.
+        let real: Image<f64> = self.convert_type(false)?;
+
+        let planes = real.color_planes()?;
+
+        let mut converted = match (current, space, planes.len()) {
+            (ColorSpace::Gray, ColorSpace::RGB, 1) => vec![planes[0].clone(); 3],
+            (ColorSpace::RGB, ColorSpace::Gray, 3) => {
+                let (r, g, b) = (&planes[0], &planes[1], &planes[2]);
+
+                let gray = (0..r.len())
+                    .map(|i| 0.299 * r[i] + 0.587 * g[i] + 0.114 * b[i])
+                    .collect();
+
+                vec![gray]
+            },
+            (current, space, channels) => {
+                return Err(format!(
+                    "conversion from {:?} image with {} channels to {:?} is not supported",
+                    current, channels, space
+                ));
+            },
+        };
+
+        if real.has_alpha_channel() {
+            converted.push(real.plane(real.channels() - 1)?);
+        }
+
+        let converted = new_image_from_planes::<f64>(
+            self.row_count(),
+            self.column_count(),
+            &converted,
+            space,
+            false,
+        )?;
+
+        converted.convert_type(self.is_interleaved())
+    }
+
+    /// Copy the channel with the specified 0-based index out of this 2D image.
+    fn plane(&self, channel: usize) -> Result<Vec<T::STORAGE>, String> {
+        if self.rank() != 2 {
+            return Err("only 2D images are supported".to_owned());
+        }
+
+        let data = self.as_slice();
+        let channels = self.channels();
+        let pixels = self.row_count() * self.column_count();
+
+        let plane = if self.is_interleaved() {
+            data.iter()
+                .skip(channel)
+                .step_by(channels)
+                .copied()
+                .collect()
+        } else {
+            data[channel * pixels..(channel + 1) * pixels].to_vec()
+        };
+
+        Ok(plane)
+    }
+
+    /// Copy the channels of this image, excluding the alpha channel, if any.
+    fn color_planes(&self) -> Result<Vec<Vec<T::STORAGE>>, String> {
+        let mut channels = self.channels();
+
+        if self.has_alpha_channel() {
+            channels -= 1;
+        }
+
+        (0..channels).map(|channel| self.plane(channel)).collect()
+    }
+}
+
+/// Construct a new 2D image from the flattened data of each channel.
+fn new_image_from_planes<T: ImageData>(
+    rows: usize,
+    columns: usize,
+    planes: &[Vec<T::STORAGE>],
+    space: ColorSpace,
+    interleaving: bool,
+) -> Result<Image<T>, String> {
+    let channels = planes.len();
+    let pixels = rows * columns;
+
+    debug_assert!(planes.iter().all(|plane| plane.len() == pixels));
+
+    let mut image =
+        UninitImage::<T>::try_new_2d(columns, rows, channels, space, interleaving)
+            .map_err(|err_code| {
+                format!("failed to create image: error code {}", err_code)
+            })?;
+
+    let data = image.as_slice_mut();
+
+    for (channel, plane) in planes.iter().enumerate() {
+        for (pixel, value) in plane.iter().enumerate() {
+            let index = if interleaving {
+                pixel * channels + channel
+            } else {
+                channel * pixels + pixel
+            };
+
+            data[index].write(*value);
+        }
+    }
+
+    // Safety: Every element of every channel was initialized above.
+    Ok(unsafe { image.assume_init() })
+}
+
 impl<T> Image<T> {
     //
     // Properties
@@ -411,6 +790,48 @@ impl<T> Image<T> {
         usize::try_from(count).expect("Image share count mint overflows usize")
     }
 
+    /// *LibraryLink C API Documentation:* [`MImage_clone`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MImage_clone.html)
+    pub fn try_clone(&self) -> Result<Image<T>, sys::errcode_t> {
+        let mut clone: sys::MImage = std::ptr::null_mut();
+
+        let err_code = unsafe { rtl::MImage_clone(self.as_raw(), &mut clone) };
+
+        if err_code != 0 || clone.is_null() {
+            return Err(err_code);
+        }
+
+        Ok(Image(clone, PhantomData))
+    }
+
+    /// Convert this image to the data type `T2`, scaling values as necessary.
+    ///
+    /// For example, converting an `Image<u8>` to an `Image<f64>` maps the values `0` to
+    /// `255` to the range `0.0` to `1.0`.
+    ///
+    /// *LibraryLink C API Documentation:* [`MImage_convertType`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MImage_convertType.html)
+    pub fn convert_type<T2: ImageData>(
+        &self,
+        interleaving: bool,
+    ) -> Result<Image<T2>, String> {
+        let converted: sys::MImage = unsafe {
+            rtl::MImage_convertType(
+                self.as_raw(),
+                T2::TYPE.as_raw(),
+                mbool::from(interleaving),
+            )
+        };
+
+        if converted.is_null() {
+            return Err(format!(
+                "failed to convert image of type {} to {}",
+                self.data_type().name(),
+                T2::TYPE.name()
+            ));
+        }
+
+        Ok(Image(converted, PhantomData))
+    }
+
     //
     // Raw Image's
     //
@@ -420,6 +841,15 @@ impl<T> Image<T> {
         Image(raw, PhantomData)
     }
 
+    /// Erase the concrete `T` data type associated with this `Image`.
+    ///
+    /// Use [`Image::try_kind()`] to convert back into an `&Image<T>`.
+    pub fn into_generic(self) -> Image {
+        let Image(raw, PhantomData) = self;
+
+        Image(raw, PhantomData)
+    }
+
     /// Extract the raw [`MImage`][sys::MImage] instance from this `Image`.
     pub unsafe fn into_raw(self) -> sys::MImage {
         let raw = self.as_raw();
@@ -512,6 +942,24 @@ impl<T: ImageData> UninitImage<T> {
         unsafe { std::ptr::write_bytes(data_ptr, 0, len) }
     }
 
+    /// Mutable access to the elements of this [`UninitImage`], in the order they are
+    /// stored in the underlying flat data buffer.
+    ///
+    /// See [`UninitNumericArray::as_slice_mut()`][crate::UninitNumericArray::as_slice_mut]
+    /// for an example of how to initialize the elements of an uninitialized buffer.
+    pub fn as_slice_mut(&mut self) -> &mut [MaybeUninit<T::STORAGE>] {
+        let UninitImage(raw, PhantomData) = *self;
+
+        unsafe {
+            let data_ptr: *mut c_void = rtl::MImage_getRawData(raw);
+            let len: mint = rtl::MImage_getFlattenedLength(raw);
+            let len = usize::try_from(len)
+                .expect("UninitImage flattened length overflows usize");
+
+            std::slice::from_raw_parts_mut(data_ptr as *mut MaybeUninit<T::STORAGE>, len)
+        }
+    }
+
     /// Set the value of the specified pixel and channel.
     ///
     /// # Panics
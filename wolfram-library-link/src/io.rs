@@ -0,0 +1,526 @@
+//! Forward output written to the process standard streams to the Wolfram Kernel.
+//!
+//! When a library is loaded into a Wolfram Kernel that is controlled by a notebook
+//! front end, anything written to the process `stdout` or `stderr` (e.g. using
+//! [`println!`]) is not visible to the user. [`redirect_std_streams()`] replaces the
+//! standard streams with pipes, and the captured output is forwarded to the Kernel as
+//! [`Print`][ref/Print]<sub>WL</sub> output.
+//!
+//! Output captured by the redirected streams is forwarded automatically after each
+//! call to a function exported using [`export!`][crate::export] returns. Use
+//! [`flush_std_streams()`] to forward captured output immediately, for example from
+//! within a long-running computation.
+//!
+//! Output written by C dependencies of the library is captured as well, because the
+//! redirection happens at the level of the process file descriptors.
+//!
+//! To print output from code that is parameterized over a [`Write`][io::Write]
+//! implementation, use [`print_writer()`] instead.
+//!
+//! [`restore_std_streams()`] puts the original standard streams back in place.
+//!
+//! Redirection is intended for use with a Kernel controlled by a notebook front end.
+//! Output written by other threads while captured output is being forwarded may be
+//! written to the original standard streams instead of being forwarded. Redirection is
+//! currently only supported on Unix-like platforms.
+//!
+//! [ref/Print]: https://reference.wolfram.com/language/ref/Print.html
+
+use std::{
+    io::{self, Write},
+    sync::{Mutex, MutexGuard},
+};
+
+use crate::{
+    expr::{Expr, Symbol},
+    library_data::is_main_thread,
+    EvaluationError,
+};
+
+#[cfg(unix)]
+use self::unix::Redirect;
+
+static REDIRECT: Mutex<Option<Redirect>> = Mutex::new(None);
+
+/// Redirect the process `stdout` and `stderr` streams to the Wolfram Kernel.
+///
+/// See the [module documentation](self) for more information.
+///
+/// Calling this function while the standard streams are redirected has no effect.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link as wll;
+///
+/// #[wll::init]
+/// fn init() {
+///     wll::io::redirect_std_streams().expect("failed to redirect std streams");
+/// }
+///
+/// wll::export![hello()];
+///
+/// fn hello() {
+///     // Printed in the notebook when `hello()` returns.
+///     println!("Hello from Rust!");
+/// }
+/// # }
+/// ```
+pub fn redirect_std_streams() -> io::Result<()> {
+    let mut redirect = lock_redirect();
+
+    if redirect.is_none() {
+        *redirect = Some(Redirect::new()?);
+    }
+
+    Ok(())
+}
+
+/// Restore the process `stdout` and `stderr` streams replaced by
+/// [`redirect_std_streams()`].
+///
+/// Output that was captured but not yet forwarded to the Kernel is written to the
+/// restored streams.
+///
+/// If the standard streams are not redirected, this function does nothing.
+pub fn restore_std_streams() -> io::Result<()> {
+    match lock_redirect().take() {
+        Some(redirect) => redirect.restore(),
+        None => Ok(()),
+    }
+}
+
+/// Returns `true` if the standard streams are currently redirected by
+/// [`redirect_std_streams()`].
+pub fn is_redirected() -> bool {
+    lock_redirect().is_some()
+}
+
+/// Forward any output captured by [`redirect_std_streams()`] to the Kernel.
+///
+/// Output written to `stdout` and `stderr` is printed as a separate
+/// [`Print`][ref/Print]<sub>WL</sub> cell for each stream.
+///
+/// If the standard streams have not been redirected, this function does nothing.
+///
+/// An error is returned if a `Print` evaluation fails. Output that has not been printed
+/// when the error occurs is written to the original standard streams instead.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel
+/// thread.
+///
+/// [ref/Print]: https://reference.wolfram.com/language/ref/Print.html
+pub fn flush_std_streams() -> Result<(), EvaluationError> {
+    if !is_redirected() {
+        return Ok(());
+    }
+
+    crate::assert_main_thread();
+
+    // Don't hold the lock while evaluating `Print`, which could call back into this
+    // library.
+    let (stdout, stderr) = match lock_redirect().as_ref() {
+        Some(redirect) => redirect.take_output(),
+        None => return Ok(()),
+    };
+
+    if let Err(err) = print_output(&stdout) {
+        write_to_original_streams(&stdout, &stderr);
+        return Err(err);
+    }
+
+    if let Err(err) = print_output(&stderr) {
+        write_to_original_streams(&[], &stderr);
+        return Err(err);
+    }
+
+    // If the Kernel itself writes to `stdout` (e.g. when running in a terminal instead
+    // of under a notebook front end), the `Print` evaluations above will have written
+    // to the redirected stream. Pass that output on to the original streams, where the
+    // Kernel meant it to go, instead of printing it again on the next flush.
+    if let Some(redirect) = lock_redirect().as_ref() {
+        let (stdout, stderr) = redirect.take_output();
+        redirect.write_to_original(&stdout, &stderr);
+    }
+
+    Ok(())
+}
+
+/// Report an error that occurred while forwarding captured output to the Kernel, by
+/// writing it to the original `stderr` stream.
+pub(crate) fn log_error(message: &str) {
+    write_to_original_streams(&[], format!("{}\n", message).as_bytes());
+}
+
+fn print_output(output: &[u8]) -> Result<(), EvaluationError> {
+    let output = String::from_utf8_lossy(output);
+    let output = output.trim_end_matches('\n');
+
+    if output.is_empty() {
+        return Ok(());
+    }
+
+    let _: Expr = crate::try_evaluate(&Expr::normal(Symbol::new("System`Print"), vec![
+        Expr::string(output),
+    ]))?;
+
+    Ok(())
+}
+
+/// Write `stdout` and `stderr` to the original standard streams replaced by
+/// [`redirect_std_streams()`], if they are still redirected.
+fn write_to_original_streams(stdout: &[u8], stderr: &[u8]) {
+    if let Some(redirect) = lock_redirect().as_ref() {
+        redirect.write_to_original(stdout, stderr);
+    }
+}
+
+fn lock_redirect() -> MutexGuard<'static, Option<Redirect>> {
+    REDIRECT.lock().unwrap()
+}
+
+//======================================
+// PrintWriter
+//======================================
+
+/// [`Write`] implementation that prints to the notebook.
+///
+/// Data written to a `PrintWriter` is buffered, and each call to
+/// [`flush()`][Write::flush] prints the buffered data as a single
+/// [`Print`][ref/Print]<sub>WL</sub> cell. Any remaining data is flushed when the
+/// `PrintWriter` is dropped.
+///
+/// Use [`print_writer()`] to construct a `PrintWriter`.
+///
+/// [ref/Print]: https://reference.wolfram.com/language/ref/Print.html
+#[derive(Debug)]
+pub struct PrintWriter {
+    buffer: Vec<u8>,
+}
+
+/// Construct a new [`PrintWriter`], whose flushes print to the notebook.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::Write;
+///
+/// use wolfram_library_link as wll;
+///
+/// let mut writer = wll::io::print_writer();
+///
+/// writeln!(writer, "Processed {} items", 42).unwrap();
+/// writer.flush().unwrap();
+/// ```
+pub fn print_writer() -> PrintWriter {
+    PrintWriter { buffer: Vec::new() }
+}
+
+impl Write for PrintWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+
+        Ok(buf.len())
+    }
+
+    /// Print the buffered data to the notebook.
+    ///
+    /// Returns an error if this function is called from a thread other than the main
+    /// Kernel thread, or if the evaluation of `Print[...]` failed. The buffered data is
+    /// retained if an error occurs.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        if !is_main_thread() {
+            return Err(io::Error::other(
+                "PrintWriter can only be flushed from the main Kernel thread",
+            ));
+        }
+
+        let text = String::from_utf8_lossy(&self.buffer);
+        let text = text.trim_end_matches('\n');
+
+        crate::try_evaluate(&Expr::normal(Symbol::new("System`Print"), vec![
+            Expr::string(text),
+        ]))
+        .map_err(|err| io::Error::other(err.to_string()))?;
+
+        self.buffer.clear();
+
+        Ok(())
+    }
+}
+
+impl Drop for PrintWriter {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
+
+//======================================
+// Unix implementation
+//======================================
+
+#[cfg(unix)]
+mod unix {
+    use std::{
+        io::{self, Write},
+        os::raw::c_int,
+        sync::{Arc, Mutex},
+        thread,
+    };
+
+    pub(super) struct Redirect {
+        stdout: Arc<Pipe>,
+        stderr: Arc<Pipe>,
+    }
+
+    /// Pipe that replaces one of the standard stream file descriptors.
+    ///
+    /// A background thread waits for data to become available on the read end of the
+    /// pipe and moves it into `buffer`, so that writers never block on a full pipe.
+    /// Reads only happen while `buffer` is locked, which ensures that `take_output()`
+    /// sees everything written to the pipe before it was called.
+    struct Pipe {
+        /// The standard stream file descriptor replaced by this pipe.
+        target_fd: c_int,
+        /// Duplicate of the original `target_fd`, used to restore it.
+        original_fd: c_int,
+        read_fd: c_int,
+        buffer: Mutex<Vec<u8>>,
+    }
+
+    impl Redirect {
+        pub(super) fn new() -> io::Result<Redirect> {
+            let stdout = Pipe::replace(libc::STDOUT_FILENO)?;
+
+            let stderr = match Pipe::replace(libc::STDERR_FILENO) {
+                Ok(stderr) => stderr,
+                Err(err) => {
+                    let _ = stdout.restore();
+                    return Err(err);
+                },
+            };
+
+            Ok(Redirect { stdout, stderr })
+        }
+
+        pub(super) fn take_output(&self) -> (Vec<u8>, Vec<u8>) {
+            // Write out any data buffered by the Rust standard library.
+            let _ = io::stdout().flush();
+            let _ = io::stderr().flush();
+
+            (self.stdout.take(), self.stderr.take())
+        }
+
+        /// Write `stdout` and `stderr` to the original standard streams.
+        pub(super) fn write_to_original(&self, stdout: &[u8], stderr: &[u8]) {
+            self.stdout.write_to_original(stdout);
+            self.stderr.write_to_original(stderr);
+        }
+
+        /// Restore the original standard streams, and write any captured output that
+        /// has not been taken yet to them.
+        pub(super) fn restore(self) -> io::Result<()> {
+            // Flush the Rust standard library buffers while they still write to the
+            // pipes.
+            let _ = io::stdout().flush();
+            let _ = io::stderr().flush();
+
+            let restored = self.stdout.restore().and(self.stderr.restore());
+
+            let (stdout, stderr) = self.take_output();
+            self.write_to_original(&stdout, &stderr);
+
+            restored
+        }
+    }
+
+    impl Pipe {
+        fn replace(target_fd: c_int) -> io::Result<Arc<Pipe>> {
+            let mut fds: [c_int; 2] = [0; 2];
+
+            let original_fd = unsafe { check(libc::dup(target_fd))? };
+
+            let result = unsafe { Pipe::open(target_fd, &mut fds) };
+
+            if let Err(err) = result {
+                unsafe { libc::close(original_fd) };
+                return Err(err);
+            }
+
+            let pipe = Arc::new(Pipe {
+                target_fd,
+                original_fd,
+                read_fd: fds[0],
+                buffer: Mutex::new(Vec::new()),
+            });
+
+            let reader = Arc::clone(&pipe);
+
+            thread::Builder::new()
+                .name(format!("wll-redirect-fd{}", target_fd))
+                .spawn(move || reader.run())?;
+
+            Ok(pipe)
+        }
+
+        /// Create a new pipe, storing its file descriptors in `fds`, and replace
+        /// `target_fd` with its write end.
+        unsafe fn open(target_fd: c_int, fds: &mut [c_int; 2]) -> io::Result<()> {
+            check(libc::pipe(fds.as_mut_ptr()))?;
+
+            let [read_fd, write_fd] = *fds;
+
+            let result = check(libc::fcntl(read_fd, libc::F_GETFL))
+                .and_then(|flags| {
+                    check(libc::fcntl(
+                        read_fd,
+                        libc::F_SETFL,
+                        flags | libc::O_NONBLOCK,
+                    ))
+                })
+                .and_then(|_| check(libc::dup2(write_fd, target_fd)));
+
+            libc::close(write_fd);
+
+            if result.is_err() {
+                libc::close(read_fd);
+            }
+
+            result.map(|_| ())
+        }
+
+        /// Put the original file descriptor back in place of the pipe.
+        ///
+        /// This closes the write end of the pipe, so the background thread exits once
+        /// it has read everything written to the pipe.
+        fn restore(&self) -> io::Result<()> {
+            unsafe { check(libc::dup2(self.original_fd, self.target_fd))? };
+
+            Ok(())
+        }
+
+        fn write_to_original(&self, mut data: &[u8]) {
+            while !data.is_empty() {
+                let count = unsafe {
+                    libc::write(self.original_fd, data.as_ptr() as *const _, data.len())
+                };
+
+                if count > 0 {
+                    data = &data[count as usize..];
+                } else if count == 0
+                    || io::Error::last_os_error().kind() != io::ErrorKind::Interrupted
+                {
+                    return;
+                }
+            }
+        }
+
+        fn run(&self) {
+            loop {
+                let mut poll_fd = libc::pollfd {
+                    fd: self.read_fd,
+                    events: libc::POLLIN,
+                    revents: 0,
+                };
+
+                if unsafe { libc::poll(&mut poll_fd, 1, -1) } < 0 {
+                    if io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return;
+                }
+
+                let mut buffer = self.buffer.lock().unwrap();
+                if !self.drain_into(&mut buffer) {
+                    return;
+                }
+            }
+        }
+
+        fn take(&self) -> Vec<u8> {
+            let mut buffer = self.buffer.lock().unwrap();
+            let _ = self.drain_into(&mut buffer);
+
+            std::mem::take(&mut *buffer)
+        }
+
+        /// Read all data currently available in the pipe into `buffer`.
+        ///
+        /// Returns `false` if the write end of the pipe has been closed.
+        fn drain_into(&self, buffer: &mut Vec<u8>) -> bool {
+            let mut chunk = [0u8; 4096];
+
+            loop {
+                let count = unsafe {
+                    libc::read(self.read_fd, chunk.as_mut_ptr() as *mut _, chunk.len())
+                };
+
+                match count {
+                    0 => return false,
+                    count if count > 0 => {
+                        buffer.extend_from_slice(&chunk[..count as usize])
+                    },
+                    _ => {
+                        return match io::Error::last_os_error().kind() {
+                            io::ErrorKind::WouldBlock => true,
+                            io::ErrorKind::Interrupted => continue,
+                            _ => false,
+                        }
+                    },
+                }
+            }
+        }
+    }
+
+    impl Drop for Pipe {
+        fn drop(&mut self) {
+            unsafe {
+                libc::close(self.read_fd);
+                libc::close(self.original_fd);
+            }
+        }
+    }
+
+    fn check(result: c_int) -> io::Result<c_int> {
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(result)
+    }
+}
+
+//======================================
+// Unsupported platforms
+//======================================
+
+#[cfg(not(unix))]
+struct Redirect;
+
+#[cfg(not(unix))]
+impl Redirect {
+    fn new() -> io::Result<Redirect> {
+        Err(io::Error::other(
+            "redirecting std streams is not supported on this platform",
+        ))
+    }
+
+    fn take_output(&self) -> (Vec<u8>, Vec<u8>) {
+        unreachable!()
+    }
+
+    fn write_to_original(&self, _: &[u8], _: &[u8]) {
+        unreachable!()
+    }
+
+    fn restore(self) -> io::Result<()> {
+        unreachable!()
+    }
+}
@@ -0,0 +1,469 @@
+//! Run crash-prone code in a separate helper process.
+//!
+//! Native code wrapped by a library, for example a C dependency, can crash in ways that
+//! cannot be caught by Rust: a segmentation fault or a call to `abort()` terminates the
+//! entire process, which for a library loaded by the Wolfram Kernel means the Kernel
+//! itself. The functions in this module run a designated function in a forked helper
+//! process instead, so that a crash only terminates the helper, and is reported to the
+//! Wolfram Language as a [`Failure`][ref/Failure]<sub>WL</sub>.
+//!
+//! The result computed by the helper is sent back to the library over a pipe:
+//!
+//! * [`run_isolated()`] sends raw bytes, which the function is responsible for
+//!   serializing and deserializing.
+//! * [`put_isolated()`] sends an [`Expr`] serialized to the [WXF][WXF] binary format,
+//!   and writes it to a WSTP [`Link`], for use in functions exported using
+//!   [`export_wstp!`][crate::export_wstp].
+//!
+//! # Restrictions
+//!
+//! The helper process is a copy of the Kernel process created using `fork()`. The
+//! isolated function:
+//!
+//! * **must not** call back into the Wolfram Kernel, for example using
+//!   [`evaluate()`][crate::evaluate], or use any [`Link`] it did not create. The Kernel
+//!   and its links belong to the parent process.
+//! * cannot modify the state of the parent process. Changes it makes to global
+//!   variables, caches, or memory it was given are lost when the helper exits.
+//! * must not depend on other threads. Only the thread that called
+//!   [`run_isolated()`] exists in the helper process, and locks held by other threads
+//!   at the time of the fork are never released.
+//!
+//! The Wolfram Kernel is a multithreaded process, so the last restriction limits what
+//! the isolated function can safely do. For that reason [`run_isolated()`] and
+//! [`put_isolated()`] are `unsafe`; see [`run_isolated()`] for the exact requirements.
+//!
+//! Forking a process is not free, so isolation is best suited to coarse-grained calls
+//! into code that is known or suspected to be unreliable.
+//!
+//! If the user aborts the evaluation while the helper is running, the helper is killed
+//! and [`IsolationError::Aborted`] is returned.
+//!
+//! This module requires the `"isolation"` feature of `wolfram-library-link`, and is
+//! only supported on Unix-like platforms.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     expr::Expr,
+//!     isolation,
+//!     wstp::Link,
+//!     LinkArgs,
+//! };
+//!
+//! wll::export_wstp![parse_untrusted(&mut Link)];
+//!
+//! fn parse_untrusted(link: &mut Link) {
+//!     let input: String = {
+//!         let mut args = LinkArgs::new(link).unwrap();
+//!         args.expect_count(1).unwrap();
+//!         args.next().unwrap()
+//!     };
+//!
+//!     // If the parser crashes, this function returns a `Failure[..]` instead.
+//!     //
+//!     // Safety: `unreliable_c_parser()` does not take any locks, print, or panic.
+//!     unsafe {
+//!         isolation::put_isolated(link, || Expr::from(unreliable_c_parser(&input)))
+//!             .unwrap();
+//!     }
+//! }
+//! # fn unreliable_c_parser(_: &str) -> i64 { todo!() }
+//! # }
+//! ```
+//!
+//! [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+//! [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+
+use std::{error::Error, fmt, os::raw::c_int};
+
+use crate::{
+    compression,
+    expr::{Expr, Symbol},
+    wstp::Link,
+};
+
+/// Error returned when an isolated function did not produce a result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IsolationError {
+    /// The helper process was terminated by a signal, for example `SIGSEGV`.
+    Crashed {
+        /// The number of the signal that terminated the helper process.
+        signal: c_int,
+    },
+    /// The helper process exited before sending a result, for example because the
+    /// isolated function called `std::process::exit()`.
+    Exited {
+        /// The exit status of the helper process.
+        status: c_int,
+    },
+    /// The isolated function panicked.
+    Panicked(String),
+    /// The helper process was killed because the user aborted the evaluation.
+    Aborted,
+    /// The helper process could not be started, or communication with it failed.
+    Io(String),
+}
+
+impl IsolationError {
+    /// Construct a [`Failure`][ref/Failure]<sub>WL</sub> describing this error.
+    ///
+    /// ```wolfram
+    /// Failure["RustIsolatedProcessFailed", <|
+    ///     "MessageTemplate" -> "Rust isolated process failed: `message`",
+    ///     "MessageParameters" -> <| "message" -> "..." |>
+    /// |>]
+    /// ```
+    ///
+    /// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+    pub fn to_failure(&self) -> Expr {
+        fn rule(lhs: &str, rhs: Expr) -> Expr {
+            Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(lhs), rhs])
+        }
+
+        Expr::normal(Symbol::new("System`Failure"), vec![
+            Expr::string("RustIsolatedProcessFailed"),
+            Expr::normal(Symbol::new("System`Association"), vec![
+                rule(
+                    "MessageTemplate",
+                    Expr::string("Rust isolated process failed: `message`"),
+                ),
+                rule(
+                    "MessageParameters",
+                    Expr::normal(Symbol::new("System`Association"), vec![rule(
+                        "message",
+                        Expr::string(self.to_string()),
+                    )]),
+                ),
+            ]),
+        ])
+    }
+}
+
+impl fmt::Display for IsolationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IsolationError::Crashed { signal } => {
+                write!(f, "helper process was terminated by signal {}", signal)
+            },
+            IsolationError::Exited { status } => write!(
+                f,
+                "helper process exited with status {} before sending a result",
+                status
+            ),
+            IsolationError::Panicked(message) => {
+                write!(f, "isolated function panicked: {}", message)
+            },
+            IsolationError::Aborted => write!(f, "helper process was aborted"),
+            IsolationError::Io(message) => {
+                write!(f, "error communicating with helper process: {}", message)
+            },
+        }
+    }
+}
+
+impl Error for IsolationError {}
+
+/// Call `func` in a forked helper process, and return the bytes it produced.
+///
+/// See the [module documentation](self) for the restrictions on `func`.
+///
+/// If the helper process crashes, exits, or panics before `func` returns, an error
+/// describing what happened is returned instead.
+///
+/// # Safety
+///
+/// `fork()` copies only the calling thread into the helper process. Any lock that
+/// another thread of the Kernel process holds at the time of the fork stays locked
+/// forever in the helper, and the data it protects may be in an inconsistent state.
+/// The Wolfram Kernel runs many threads, so `func`, and everything it calls, must:
+///
+/// * only allocate memory if the global allocator is safe to use after `fork()`. The
+///   system allocators of Linux and macOS are; a custom `#[global_allocator]` may not
+///   be.
+/// * not lock any `Mutex`, `RwLock`, or other lock that another thread could hold,
+///   including the locks taken by `lazy_static`/`once_cell` initializers and the
+///   internal locks of C libraries.
+/// * not print to [`stdout()`][std::io::stdout] or [`stderr()`][std::io::stderr],
+///   which are protected by locks.
+/// * not panic, unless the panic hook is safe to run after `fork()`. The default panic
+///   hook locks `stderr()` to print the panic message.
+/// * not call back into the Wolfram Kernel, or use a [`Link`] it did not create.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::isolation::{self, IsolationError};
+///
+/// fn checksum(data: &[u8]) -> Result<u64, IsolationError> {
+///     // Safety: `unreliable_c_checksum()` does not take any locks, print, or panic.
+///     let bytes = unsafe {
+///         isolation::run_isolated(|| {
+///             unreliable_c_checksum(data).to_le_bytes().to_vec()
+///         })?
+///     };
+///
+///     Ok(u64::from_le_bytes(bytes.try_into().unwrap()))
+/// }
+/// # fn unreliable_c_checksum(_: &[u8]) -> u64 { todo!() }
+/// ```
+pub unsafe fn run_isolated<F>(func: F) -> Result<Vec<u8>, IsolationError>
+where
+    F: FnOnce() -> Vec<u8>,
+{
+    imp::run_isolated(func)
+}
+
+/// Call `func` in a forked helper process, and write the expression it returned to
+/// `link`.
+///
+/// See the [module documentation](self) for the restrictions on `func`.
+///
+/// The expression is sent to the parent process in the [WXF][WXF] binary format, and
+/// written to `link` as:
+///
+/// ```wolfram
+/// BinaryDeserialize[ByteArray[{...}]]
+/// ```
+///
+/// which the Wolfram Language evaluates to the original expression. If the helper
+/// process failed to produce a result, the
+/// [`Failure`][ref/Failure]<sub>WL</sub> returned by
+/// [`IsolationError::to_failure()`] is written instead.
+///
+/// An error is only returned if writing to `link` fails.
+///
+/// # Safety
+///
+/// `func` must meet the requirements described in the
+/// [safety section of `run_isolated()`](run_isolated#safety). Serializing the returned
+/// expression to WXF allocates memory in the helper process.
+///
+/// [WXF]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+pub unsafe fn put_isolated<F>(link: &mut Link, func: F) -> Result<(), String>
+where
+    F: FnOnce() -> Expr,
+{
+    let wxf = match run_isolated(|| compression::to_wxf(&func())) {
+        Ok(wxf) => wxf,
+        Err(error) => {
+            return link
+                .put_expr(&error.to_failure())
+                .map_err(|err| err.to_string())
+        },
+    };
+
+    link.put_function("System`BinaryDeserialize", 1)
+        .and_then(|()| link.put_function("System`ByteArray", 1))
+        .and_then(|()| link.put_u8_array(&wxf, &[wxf.len()]))
+        .map_err(|err| format!("failed to write isolated result: {}", err))
+}
+
+//======================================
+// Unix implementation
+//======================================
+
+#[cfg(unix)]
+mod imp {
+    use std::{
+        any::Any,
+        fs::File,
+        io::{self, Read, Write},
+        os::{
+            raw::c_int,
+            unix::io::{AsRawFd, FromRawFd},
+        },
+        panic::{self, AssertUnwindSafe},
+    };
+
+    use super::IsolationError;
+
+    /// First byte written by the helper process if the function returned normally.
+    const STATUS_OK: u8 = 0;
+    /// First byte written by the helper process if the function panicked.
+    const STATUS_PANICKED: u8 = 1;
+
+    /// How often, in milliseconds, the parent checks whether the evaluation has been
+    /// aborted while waiting for the helper process.
+    const ABORT_POLL_INTERVAL_MS: c_int = 100;
+
+    pub(super) fn run_isolated<F>(func: F) -> Result<Vec<u8>, IsolationError>
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        let mut fds: [c_int; 2] = [0; 2];
+
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } < 0 {
+            return Err(io_error(io::Error::last_os_error()));
+        }
+
+        let [read_fd, write_fd] = fds;
+
+        // Write out any data buffered by the Rust standard library, so that it isn't
+        // written twice.
+        let _ = io::stdout().flush();
+        let _ = io::stderr().flush();
+
+        match unsafe { libc::fork() } {
+            -1 => {
+                let error = io::Error::last_os_error();
+                unsafe {
+                    libc::close(read_fd);
+                    libc::close(write_fd);
+                }
+                Err(io_error(error))
+            },
+            0 => {
+                // Helper process.
+                unsafe { libc::close(read_fd) };
+                run_helper(write_fd, func)
+            },
+            pid => {
+                unsafe { libc::close(write_fd) };
+                // Safety: `read_fd` is a newly created file descriptor that is not used
+                //         anywhere else. `File` closes it when dropped.
+                let pipe = unsafe { File::from_raw_fd(read_fd) };
+                wait_for_helper(pid, pipe)
+            },
+        }
+    }
+
+    /// Call `func`, write its result to `write_fd`, and exit the helper process.
+    fn run_helper<F>(write_fd: c_int, func: F) -> !
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        let (status, payload) = match panic::catch_unwind(AssertUnwindSafe(func)) {
+            Ok(bytes) => (STATUS_OK, bytes),
+            Err(payload) => (STATUS_PANICKED, panic_message(payload).into_bytes()),
+        };
+
+        // Safety: `write_fd` is the write end of the pipe, which is only used here.
+        let mut pipe = unsafe { File::from_raw_fd(write_fd) };
+
+        let written = pipe
+            .write_all(&[status])
+            .and_then(|()| pipe.write_all(&payload));
+
+        // Exit without running destructors or `atexit()` handlers, which belong to the
+        // parent process.
+        unsafe { libc::_exit(if written.is_ok() { 0 } else { 1 }) }
+    }
+
+    /// Read the result written by the helper process `pid` to `pipe`, and reap the
+    /// helper once it has exited.
+    fn wait_for_helper(
+        pid: libc::pid_t,
+        mut pipe: File,
+    ) -> Result<Vec<u8>, IsolationError> {
+        let check_aborts = crate::on_kernel_thread();
+
+        let mut output = Vec::new();
+        let mut chunk = [0u8; 4096];
+
+        loop {
+            if check_aborts {
+                let mut poll_fd = libc::pollfd {
+                    fd: pipe.as_raw_fd(),
+                    events: libc::POLLIN,
+                    revents: 0,
+                };
+
+                let ready =
+                    unsafe { libc::poll(&mut poll_fd, 1, ABORT_POLL_INTERVAL_MS) };
+
+                if ready == 0 {
+                    if crate::aborted() {
+                        unsafe { libc::kill(pid, libc::SIGKILL) };
+                        let _ = wait_pid(pid);
+                        return Err(IsolationError::Aborted);
+                    }
+                    continue;
+                }
+            }
+
+            match pipe.read(&mut chunk) {
+                Ok(0) => break,
+                Ok(count) => output.extend_from_slice(&chunk[..count]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => {
+                    unsafe { libc::kill(pid, libc::SIGKILL) };
+                    let _ = wait_pid(pid);
+                    return Err(io_error(err));
+                },
+            }
+        }
+
+        let status = wait_pid(pid).map_err(io_error)?;
+
+        if libc::WIFSIGNALED(status) {
+            return Err(IsolationError::Crashed {
+                signal: libc::WTERMSIG(status),
+            });
+        }
+
+        match output.split_first() {
+            Some((&STATUS_OK, payload)) if libc::WEXITSTATUS(status) == 0 => {
+                Ok(payload.to_vec())
+            },
+            Some((&STATUS_PANICKED, message)) => Err(IsolationError::Panicked(
+                String::from_utf8_lossy(message).into_owned(),
+            )),
+            _ => Err(IsolationError::Exited {
+                status: libc::WEXITSTATUS(status),
+            }),
+        }
+    }
+
+    fn wait_pid(pid: libc::pid_t) -> io::Result<c_int> {
+        let mut status: c_int = 0;
+
+        loop {
+            if unsafe { libc::waitpid(pid, &mut status, 0) } >= 0 {
+                return Ok(status);
+            }
+
+            let error = io::Error::last_os_error();
+            if error.kind() != io::ErrorKind::Interrupted {
+                return Err(error);
+            }
+        }
+    }
+
+    fn panic_message(payload: Box<dyn Any + Send>) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "Rust panic (no message)".to_owned()
+        }
+    }
+
+    fn io_error(error: io::Error) -> IsolationError {
+        IsolationError::Io(error.to_string())
+    }
+}
+
+//======================================
+// Unsupported platforms
+//======================================
+
+#[cfg(not(unix))]
+mod imp {
+    use super::IsolationError;
+
+    pub(super) fn run_isolated<F>(_: F) -> Result<Vec<u8>, IsolationError>
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        Err(IsolationError::Io(
+            "running functions in a helper process is not supported on this platform"
+                .to_owned(),
+        ))
+    }
+}
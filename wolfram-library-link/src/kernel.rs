@@ -0,0 +1,126 @@
+//! Information about the Wolfram Kernel session that loaded this library.
+//!
+//! The functions in this module get their values by calling back into the Kernel the
+//! first time any of them is called. The values are cached, so later calls are cheap,
+//! and can be made from any thread.
+//!
+//! # Example
+//!
+//! Describe the environment the library is running in:
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{self as wll, kernel};
+//!
+//! wll::export![environment()];
+//!
+//! fn environment() -> String {
+//!     format!(
+//!         "Wolfram Kernel {} (process ID: {}, system ID: {})",
+//!         kernel::version_string().unwrap(),
+//!         kernel::process_id().unwrap(),
+//!         kernel::system_id().unwrap(),
+//!     )
+//! }
+//! # }
+//! ```
+
+use once_cell::sync::OnceCell;
+
+use crate::{
+    expr::{Expr, ExprKind, Symbol},
+    EvaluationError,
+};
+
+static KERNEL_INFO: OnceCell<KernelInfo> = OnceCell::new();
+
+struct KernelInfo {
+    version_string: String,
+    process_id: u32,
+    system_id: String,
+}
+
+/// Get the [`$Version`][ref/$Version]<sub>WL</sub> of the Kernel.
+///
+/// # Panics
+///
+/// The first call to a function in this module must be made from the main Kernel
+/// thread. See [`evaluate()`][crate::evaluate].
+///
+/// [ref/$Version]: https://reference.wolfram.com/language/ref/$Version.html
+pub fn version_string() -> Result<&'static str, EvaluationError> {
+    Ok(&kernel_info()?.version_string)
+}
+
+/// Get the [`$ProcessID`][ref/$ProcessID]<sub>WL</sub> of the Kernel.
+///
+/// # Panics
+///
+/// The first call to a function in this module must be made from the main Kernel
+/// thread. See [`evaluate()`][crate::evaluate].
+///
+/// [ref/$ProcessID]: https://reference.wolfram.com/language/ref/$ProcessID.html
+pub fn process_id() -> Result<u32, EvaluationError> {
+    Ok(kernel_info()?.process_id)
+}
+
+/// Get the [`$SystemID`][ref/$SystemID]<sub>WL</sub> of the platform the Kernel is
+/// running on, for example `"MacOSX-ARM64"`.
+///
+/// # Panics
+///
+/// The first call to a function in this module must be made from the main Kernel
+/// thread. See [`evaluate()`][crate::evaluate].
+///
+/// [ref/$SystemID]: https://reference.wolfram.com/language/ref/$SystemID.html
+pub fn system_id() -> Result<&'static str, EvaluationError> {
+    Ok(&kernel_info()?.system_id)
+}
+
+/// Get the cached Kernel information, evaluating `{$Version, $ProcessID, $SystemID}` if
+/// it has not been cached yet.
+///
+/// If the evaluation fails, nothing is cached, and the next call will try again.
+fn kernel_info() -> Result<&'static KernelInfo, EvaluationError> {
+    KERNEL_INFO.get_or_try_init(|| {
+        let expr = Expr::normal(Symbol::new("System`List"), vec![
+            Expr::symbol(Symbol::new("System`$Version")),
+            Expr::symbol(Symbol::new("System`$ProcessID")),
+            Expr::symbol(Symbol::new("System`$SystemID")),
+        ]);
+
+        let value = crate::try_evaluate(&expr)?;
+
+        let invalid = || {
+            EvaluationError::Link(format!(
+                "kernel information: unexpected result of evaluation: {}",
+                value
+            ))
+        };
+
+        let elements = match value.kind() {
+            ExprKind::Normal(list) if list.has_head(&Symbol::new("System`List")) => {
+                list.elements()
+            },
+            _ => return Err(invalid()),
+        };
+
+        match elements {
+            [version, process_id, system_id] => {
+                match (version.kind(), process_id.kind(), system_id.kind()) {
+                    (
+                        ExprKind::String(version),
+                        ExprKind::Integer(process_id),
+                        ExprKind::String(system_id),
+                    ) => Ok(KernelInfo {
+                        version_string: version.clone(),
+                        process_id: u32::try_from(*process_id).map_err(|_| invalid())?,
+                        system_id: system_id.clone(),
+                    }),
+                    _ => Err(invalid()),
+                }
+            },
+            _ => Err(invalid()),
+        }
+    })
+}
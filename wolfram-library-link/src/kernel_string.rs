@@ -0,0 +1,151 @@
+use std::{ffi::CStr, fmt, os::raw::c_char, str::Utf8Error};
+
+use crate::{
+    expr::{Expr, Symbol},
+    rtl,
+    sys::MArgument,
+    FromArg,
+};
+
+/// UTF-8 string owned by the Wolfram Kernel.
+///
+/// Strings passed into a *LibraryLink* function via [`MArgument::utf8string`] are owned
+/// by the Kernel, and must be released by calling
+/// [`UTF8String_disown()`][rtl::UTF8String_disown] when they are no longer needed.
+/// `KernelString` calls `UTF8String_disown()` automatically when it is dropped.
+///
+/// `KernelString` is primarily useful when writing raw `extern "C"` *LibraryLink*
+/// functions. Functions exported using [`export!`][crate::export] should typically use
+/// [`String`] or [`CString`][std::ffi::CString] as the parameter type instead.
+///
+/// `KernelString` can also be used as a parameter type to borrow a string argument
+/// without copying it. Use [`as_c_str()`][KernelString::as_c_str] or
+/// [`to_str()`][KernelString::to_str] to access the contents, which are borrowed from
+/// the `KernelString` and so cannot outlive it. The Kernel's copy of the string is
+/// disowned when the `KernelString` is dropped, including if the function panics.
+///
+/// ```
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, KernelString};
+///
+/// wll::export![is_hidden_file(_)];
+///
+/// fn is_hidden_file(name: KernelString) -> bool {
+///     name.as_c_str().to_bytes().starts_with(b".")
+/// }
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// use std::os::raw::c_uint;
+///
+/// use wolfram_library_link::{
+///     self as wll,
+///     sys::{self, mint, MArgument, LIBRARY_FUNCTION_ERROR, LIBRARY_NO_ERROR},
+///     KernelString,
+/// };
+///
+/// #[no_mangle]
+/// pub unsafe extern "C" fn string_length(
+///     lib_data: sys::WolframLibraryData,
+///     arg_count: mint,
+///     args: *mut MArgument,
+///     res: MArgument,
+/// ) -> c_uint {
+///     if wll::initialize(lib_data).is_err() || arg_count != 1 {
+///         return LIBRARY_FUNCTION_ERROR;
+///     }
+///
+///     // Disowned automatically when `string` is dropped.
+///     let string = KernelString::from_raw(*(*args).utf8string);
+///
+///     let length = match string.to_str() {
+///         Ok(str) => str.chars().count(),
+///         Err(_) => return LIBRARY_FUNCTION_ERROR,
+///     };
+///
+///     *res.integer = length as mint;
+///
+///     LIBRARY_NO_ERROR
+/// }
+/// ```
+pub struct KernelString {
+    raw: *mut c_char,
+}
+
+impl KernelString {
+    /// Take ownership of a string allocated by the Wolfram Kernel.
+    ///
+    /// # Safety
+    ///
+    /// `raw` must be a valid, nul-terminated string that was passed into the current
+    /// library by the Kernel, e.g. via [`MArgument::utf8string`], and that has not
+    /// already been disowned.
+    pub unsafe fn from_raw(raw: *mut c_char) -> KernelString {
+        debug_assert!(!raw.is_null());
+
+        KernelString { raw }
+    }
+
+    /// Release ownership of the string without disowning it.
+    ///
+    /// The caller becomes responsible for calling
+    /// [`UTF8String_disown()`][rtl::UTF8String_disown] on the returned pointer.
+    pub fn into_raw(self) -> *mut c_char {
+        let KernelString { raw } = self;
+
+        // Don't run Drop on `self`; ownership of this value is being given to the caller.
+        std::mem::forget(self);
+
+        raw
+    }
+
+    /// Get the contents of this string as a [`CStr`].
+    pub fn as_c_str(&self) -> &CStr {
+        let KernelString { raw } = *self;
+
+        unsafe { CStr::from_ptr(raw) }
+    }
+
+    /// Get the contents of this string as a [`str`].
+    ///
+    /// The Kernel always passes UTF-8 encoded strings, so this should only fail if the
+    /// string came from another source.
+    pub fn to_str(&self) -> Result<&str, Utf8Error> {
+        self.as_c_str().to_str()
+    }
+}
+
+impl Drop for KernelString {
+    fn drop(&mut self) {
+        let KernelString { raw } = *self;
+
+        unsafe { rtl::UTF8String_disown(raw) }
+    }
+}
+
+impl AsRef<CStr> for KernelString {
+    fn as_ref(&self) -> &CStr {
+        self.as_c_str()
+    }
+}
+
+impl fmt::Debug for KernelString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("KernelString")
+            .field(&self.as_c_str())
+            .finish()
+    }
+}
+
+impl<'a> FromArg<'a> for KernelString {
+    unsafe fn from_arg(arg: &'a MArgument) -> KernelString {
+        KernelString::from_raw(*arg.utf8string)
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
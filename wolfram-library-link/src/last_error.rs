@@ -0,0 +1,35 @@
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+
+static LAST_ERROR: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// Get a description of the most recent error detected by the wrapper code of a
+/// function exported using [`export!`][crate::export] or registered using
+/// [`register_native()`][crate::register_native].
+///
+/// The wrapper code records an error message when a function could not be called at
+/// all, for example because it was loaded with a
+/// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature that has the
+/// wrong number of parameters. The message names the function and describes what it
+/// expected and what it received.
+///
+/// This information is also available from the Wolfram Language by loading the
+/// `wll_last_error` WSTP function that is exported automatically by every library that
+/// uses `wolfram-library-link`:
+///
+/// ```wolfram
+/// LibraryFunctionLoad["library", "wll_last_error", LinkObject, LinkObject][]
+/// ```
+///
+/// which returns the message as a string, or `Missing["NotAvailable"]` if no error has
+/// been recorded.
+///
+/// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+pub fn last_error() -> Option<String> {
+    LAST_ERROR.lock().unwrap().clone()
+}
+
+pub(crate) fn set_last_error(message: String) {
+    *LAST_ERROR.lock().unwrap() = Some(message);
+}
@@ -66,6 +66,20 @@
 //! Note that the error message may include more information if the `"nightly"`
 //! [feature][cargo-features] of `wolfram-library-link` is enabled.
 //!
+//! ## Catch crashes in wrapped native code
+//!
+//! Crashes caused by native code, like a segmentation fault in a C dependency, cannot be
+//! caught like panics, and normally terminate the Wolfram Kernel. On Unix-like
+//! platforms, the `"crash-guard"` feature enables the `crash_guard` module, whose
+//! `call_guarded()` function calls a C function with handlers installed for the
+//! `SIGSEGV`, `SIGFPE` and `SIGBUS` signals. If the C function crashes, `call_guarded()`
+//! returns an error describing the crash instead.
+//!
+//! This is a last resort, intended for libraries that wrap third-party native code they
+//! don't control. The C library may be left in an inconsistent state, so the Kernel
+//! should be restarted after a crash is reported. To run crash-prone code in a separate
+//! process instead, use the `isolation` module, enabled by the `"isolation"` feature.
+//!
 //! [WL]: https://wolfram.com/language
 //! [library-link-guide]: https://reference.wolfram.com/language/guide/LibraryLink.html
 //! [library-function-load]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
@@ -76,11 +90,41 @@
 #![warn(missing_docs)]
 
 mod args;
+#[cfg(feature = "arrow")]
+pub mod arrow;
+mod assoc;
 mod async_tasks;
+mod background_write;
+mod bool_array;
+pub mod build;
+pub mod callback;
+mod cancel;
 mod catch_panic;
+#[cfg(feature = "compression")]
+pub mod compression;
+pub mod convert;
+#[cfg(all(feature = "crash-guard", unix))]
+pub mod crash_guard;
 mod data_store;
+#[cfg(feature = "serde")]
+pub mod dataset;
+pub mod debug;
+mod deferred;
+mod dynamic;
+mod error_code;
+mod evaluation;
+mod executor;
+mod exported;
 mod image;
+pub mod io;
+#[cfg(feature = "isolation")]
+pub mod isolation;
+pub mod kernel;
+mod kernel_string;
+mod last_error;
 mod library_data;
+mod link_args;
+mod links;
 /// This module is *semver exempt*. This is not intended to be part of the public API of
 /// wolfram-library-link.
 ///
@@ -88,8 +132,28 @@ mod library_data;
 #[doc(hidden)]
 pub mod macro_utils;
 pub mod managed;
+pub mod memory;
 mod numeric_array;
+mod numeric_array_view;
+mod overloads;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+mod paths;
+mod raw_args;
+pub mod registry;
+mod released;
 pub mod rtl;
+pub mod sandbox;
+mod shared_borrows;
+mod signatures;
+mod sparse_array;
+#[cfg(feature = "standalone-rtl")]
+mod standalone;
+mod stats;
+mod test_stubs;
+pub mod watchdog;
+mod wide_string;
+mod wl_source;
 
 
 // Note: This is exported as doc(inline) so that it shows up in the 'Modules' section of
@@ -108,20 +172,53 @@ pub use wstp;
 pub use inventory;
 
 pub use self::{
-    args::{FromArg, IntoArg, NativeFunction, WstpFunction},
-    async_tasks::AsyncTaskObject,
-    data_store::{DataStore, DataStoreNode, DataStoreNodeValue, Nodes},
+    args::{FromArg, IntoArg, NativeFunction, TryFromArg, WstpFunction},
+    assoc::Assoc,
+    async_tasks::{event_shapes, AsyncTaskObject, Event, EventField, EventShape},
+    background_write::write_in_background,
+    bool_array::BoolArray,
+    cancel::{cancel, CancelReason, CancelToken},
+    data_store::{DataStore, DataStoreNode, DataStoreNodeValue, DataStoreValue, Nodes},
+    deferred::{evaluate_later, run_pending_evaluations},
+    dynamic::{register_native, register_native_raw},
+    error_code::{register_error_tag, set_function_error, ErrorCode},
+    evaluation::{EvalResult, EvaluationError},
+    executor::{MainThreadExecutor, MainThreadTask},
+    exported::{exported_functions, ExportedFunction, ExportedFunctionKind},
     image::{ColorSpace, Image, ImageData, ImageType, Pixel, UninitImage},
-    library_data::{get_library_data, initialize, WolframLibraryData},
+    kernel_string::KernelString,
+    last_error::last_error,
+    library_data::{get_library_data, initialize, on_kernel_thread, WolframLibraryData},
+    link_args::LinkArgs,
+    links::{wstp_environment, WstpEnvironment},
+    memory::{memory_usage, start_memory_tracking, stop_memory_tracking, MemoryUsage},
     numeric_array::{
         NumericArray, NumericArrayConvertMethod, NumericArrayDataType, NumericArrayKind,
-        NumericArrayType, UninitNumericArray,
+        NumericArraySummary, NumericArrayType, SharedSliceMut, UninitNumericArray,
     },
+    numeric_array_view::NumericArrayView,
+    paths::{validate_path, PathAccess},
+    raw_args::{RawArgs, RawReturn},
+    signatures::check_signatures,
+    sparse_array::SparseArray,
+    stats::{stats, CallTiming, FunctionStats},
+    wide_string::WideString,
 };
 
+#[allow(deprecated)]
+pub use self::evaluation::EvaluationOutcome;
+
+#[cfg(feature = "standalone-rtl")]
+pub use self::standalone::initialize_standalone;
 
 
-use std::sync::Mutex;
+
+use std::{
+    ffi::{c_void, CString},
+    mem::MaybeUninit,
+    os::raw::c_char,
+    sync::{Mutex, TryLockError},
+};
 
 use once_cell::sync::Lazy;
 
@@ -147,10 +244,8 @@ use crate::expr::{Expr, ExprKind, Symbol};
 /// # Panics
 ///
 /// Any panics thrown during the executation of `#[init]` will automatically be caught,
-/// and an error code will be returned to the Wolfram Kernel.
-///
-// TODO: Mention which error code specifically:
-//         `macro_utils::error_code::FAILED_WITH_PANIC`.
+/// and the error code [`ErrorCode::FailedWithPanic`] will be returned to the Wolfram
+/// Kernel.
 ///
 /// If the initialization function panics, the Kernel will prevent other LibraryLink
 /// functions exported from that library from being loaded.
@@ -187,21 +282,157 @@ const BACKTRACE_ENV_VAR: &str = "LIBRARY_LINK_RUST_BACKTRACE";
 
 /// Evaluate `expr` by calling back into the Wolfram Kernel.
 ///
-/// TODO: Specify and document what happens if the evaluation of `expr` triggers a
-///       kernel abort (such as a `Throw[]` in the code).
-pub fn evaluate(expr: &Expr) -> Expr {
-    match try_evaluate(expr) {
-        Ok(returned) => returned,
-        Err(msg) => panic!(
-            "evaluate(): evaluation of expression failed: {}: \n\texpression: {}",
-            msg, expr
-        ),
-    }
+/// Returns an [`EvalResult`] containing the value returned by the evaluation, along
+/// with any text printed and messages issued during the evaluation. Failed
+/// evaluations do not panic: if the evaluation was aborted, called `Throw[]` without a
+/// matching `Catch[]`, or a WSTP transport error occurred, [`EvalResult::value`]
+/// contains an [`EvaluationError`] describing what went wrong.
+///
+/// Aborts and uncaught `Throw[]`s are detected from the returned value, so an
+/// expression that evaluates to `$Aborted` or `Hold[Throw[..]]` without being
+/// interrupted is reported the same way.
+///
+/// Use [`EvalResult::unwrap()`] to get the returned value and panic if the evaluation
+/// failed, or [`try_evaluate()`] to get only the returned value as a [`Result`].
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{self as wll, expr::{Expr, Symbol}};
+///
+/// // Print["Hello"]; 1 + 1
+/// let expr = Expr::normal(Symbol::new("System`CompoundExpression"), vec![
+///     Expr::normal(Symbol::new("System`Print"), vec![Expr::string("Hello")]),
+///     Expr::normal(Symbol::new("System`Plus"), vec![Expr::from(1), Expr::from(1)]),
+/// ]);
+///
+/// let result = wll::evaluate(&expr);
+///
+/// assert_eq!(result.value, Ok(Expr::from(2)));
+/// assert_eq!(result.output, vec!["Hello".to_owned()]);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+pub fn evaluate(expr: &Expr) -> EvalResult {
+    debug::record(
+        expr,
+        || evaluation::evaluate(expr),
+        |result| result.value.clone().map_err(|err| err.to_string()),
+    )
 }
 
 /// Attempt to evaluate `expr`, returning an error if a WSTP transport error occurred
 /// or evaluation failed.
-pub fn try_evaluate(expr: &Expr) -> Result<Expr, String> {
+///
+/// This is equivalent to `evaluate(expr).into_result()`. Any text printed and
+/// messages issued during the evaluation are discarded. See [`evaluate()`] for
+/// details.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{self as wll, expr::{Expr, Symbol}, EvaluationError};
+///
+/// // Throw[1]
+/// let expr = Expr::normal(Symbol::new("System`Throw"), vec![Expr::from(1)]);
+///
+/// match wll::try_evaluate(&expr) {
+///     Err(EvaluationError::UncaughtThrow { value, .. }) => assert_eq!(value, Expr::from(1)),
+///     _ => panic!("expected uncaught Throw"),
+/// }
+/// ```
+pub fn try_evaluate(expr: &Expr) -> Result<Expr, EvaluationError> {
+    evaluate(expr).into_result()
+}
+
+/// Evaluate `expr`, returning the result along with any text printed and messages
+/// issued during the evaluation.
+///
+/// Use [`evaluate()`], which returns the same information as an [`EvalResult`],
+/// instead.
+#[deprecated(note = "use `evaluate()`, which returns the output of the evaluation too")]
+#[allow(deprecated)]
+pub fn evaluate_with_outcome(expr: &Expr) -> Result<EvaluationOutcome, EvaluationError> {
+    let EvalResult {
+        value,
+        output,
+        messages,
+    } = evaluate(expr);
+
+    value.map(|value| EvaluationOutcome {
+        value,
+        output,
+        messages,
+    })
+}
+
+/// Textual forms that [`evaluate_to_string()`] can format a result in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StringFormat {
+    /// [`InputForm`](https://reference.wolfram.com/language/ref/InputForm.html)
+    InputForm,
+    /// [`OutputForm`](https://reference.wolfram.com/language/ref/OutputForm.html)
+    OutputForm,
+}
+
+/// Evaluate `expr` and return the result formatted as a string.
+///
+/// This is equivalent to evaluating
+/// [`ToString`](https://reference.wolfram.com/language/ref/ToString.html)`[expr, format]`,
+/// but avoids constructing an [`Expr`] for the result.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{self as wll, expr::{Expr, Symbol}, StringFormat};
+///
+/// let expr = Expr::normal(Symbol::new("System`Range"), vec![Expr::from(3)]);
+///
+/// let string = wll::evaluate_to_string(&expr, StringFormat::InputForm).unwrap();
+///
+/// assert_eq!(string, "{1, 2, 3}");
+/// ```
+pub fn evaluate_to_string(expr: &Expr, format: StringFormat) -> Result<String, String> {
+    let format = match format {
+        StringFormat::InputForm => "System`InputForm",
+        StringFormat::OutputForm => "System`OutputForm",
+    };
+
+    let to_string = Expr::normal(Symbol::new("System`ToString"), vec![
+        expr.clone(),
+        Expr::symbol(Symbol::new(format)),
+    ]);
+
+    evaluate_with_link(&to_string, read_returned_string).map_err(String::from)
+}
+
+/// Evaluate `expr` and return the result serialized as [WXF][wxf] bytes.
+///
+/// This is equivalent to evaluating
+/// [`ExportByteArray`](https://reference.wolfram.com/language/ref/ExportByteArray.html)`[expr, "WXF"]`.
+///
+/// [wxf]: https://reference.wolfram.com/language/tutorial/WXFFormatDescription.html
+pub fn evaluate_to_wxf(expr: &Expr) -> Result<Vec<u8>, String> {
+    // Normal[ExportByteArray[expr, "WXF"]]
+    let export = Expr::normal(Symbol::new("System`Normal"), vec![Expr::normal(
+        Symbol::new("System`ExportByteArray"),
+        vec![expr.clone(), Expr::string("WXF")],
+    )]);
+
+    evaluate_with_link(&export, read_returned_bytes).map_err(String::from)
+}
+
+/// Send `EvaluatePacket[expr]` to the Kernel, and read the result using `read`.
+pub(crate) fn evaluate_with_link<F, R>(expr: &Expr, read: F) -> Result<R, EvaluationError>
+where
+    F: FnOnce(&mut Link) -> Result<R, String>,
+{
+    // Evaluate any expressions queued by evaluate_later() first, so that their effects
+    // are visible to `expr`.
+    let _: usize = run_pending_evaluations();
+
     with_link(|link: &mut Link| {
         // Send an EvaluatePacket['expr].
         let _: () = link
@@ -213,24 +444,43 @@ pub fn try_evaluate(expr: &Expr) -> Result<Expr, String> {
 
         let _: () = process_wstp_link(link)?;
 
-        let return_packet: Expr = link.get_expr().map_err(|e| e.to_string())?;
-
-        let returned_expr = match return_packet.kind() {
-            ExprKind::Normal(normal) => {
-                debug_assert!(normal.has_head(&Symbol::new("System`ReturnPacket")));
-                debug_assert!(normal.elements().len() == 1);
-                normal.elements()[0].clone()
-            },
-            _ => {
-                return Err(format!(
-                    "try_evaluate(): returned expression was not ReturnPacket: {}",
-                    return_packet
-                ))
-            },
-        };
-
-        Ok(returned_expr)
+        read(link)
     })
+    .and_then(|result| result.map_err(EvaluationError::Link))
+}
+
+/// Read a `ReturnPacket[_String]` from `link`, skipping any intermediate packets.
+fn read_returned_string(link: &mut Link) -> Result<String, String> {
+    let value = evaluation::read_packets(link)?.value?;
+
+    match value.kind() {
+        ExprKind::String(string) => Ok(string.clone()),
+        _ => Err(format!("evaluation result was not a String: {}", value)),
+    }
+}
+
+fn read_returned_bytes(link: &mut Link) -> Result<Vec<u8>, String> {
+    let value = evaluation::read_packets(link)?.value?;
+
+    let elements = match value.kind() {
+        ExprKind::Normal(list) if list.has_head(&Symbol::new("System`List")) => {
+            list.elements()
+        },
+        _ => return Err(format!("evaluation result was not a ByteArray: {}", value)),
+    };
+
+    elements
+        .iter()
+        .map(|elem| match elem.kind() {
+            ExprKind::Integer(byte) => u8::try_from(*byte).ok(),
+            _ => None,
+        })
+        .map(|byte| {
+            byte.ok_or_else(|| {
+                format!("evaluation result was not a ByteArray: {}", value)
+            })
+        })
+        .collect()
 }
 
 /// Returns `true` if the user has requested that the current evaluation be aborted.
@@ -255,6 +505,121 @@ pub fn aborted() -> bool {
     val == 1
 }
 
+/// Issue the message `LibraryFunction::<name>` from the current library function.
+///
+/// The message must have been defined in the Wolfram Language before it is issued, e.g.:
+///
+/// ```wolfram
+/// LibraryFunction::outOfRange = "The specified index is out of range.";
+/// ```
+///
+/// # Example
+///
+/// ```no_run
+/// # use wolfram_library_link as wll;
+/// fn element_at(list: &[i64], index: usize) -> i64 {
+///     match list.get(index) {
+///         Some(elem) => *elem,
+///         None => {
+///             wll::message("outOfRange");
+///             0
+///         },
+///     }
+/// }
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `name` contains a nul byte.
+///
+/// *LibraryLink C Function:* [`Message`][rtl::Message]
+pub fn message(name: &str) {
+    assert_main_thread();
+
+    let name = CString::new(name).expect("message(): name contains nul byte");
+
+    unsafe { rtl::Message(name.as_ptr()) }
+}
+
+/// Evaluate Wolfram Language `code` and return the result as a scalar value of type `T`.
+///
+/// This is cheaper than [`evaluate()`] for simple numeric results, because the
+/// evaluation does not go through the WSTP link.
+///
+/// If the evaluation fails, or the result cannot be converted to `T`, the error code
+/// returned by the Kernel is returned.
+///
+/// # Example
+///
+/// ```no_run
+/// # use wolfram_library_link as wll;
+/// let value: i64 = wll::evaluate_expression("Fibonacci[10]").unwrap();
+///
+/// assert_eq!(value, 55);
+/// ```
+///
+/// # Panics
+///
+/// This function will panic if `code` contains a nul byte.
+///
+/// *LibraryLink C Function:* [`evaluateExpression`][rtl::evaluateExpression]
+pub fn evaluate_expression<T: EvaluateExpressionType>(
+    code: &str,
+) -> Result<T, sys::errcode_t> {
+    assert_main_thread();
+
+    let code = CString::new(code).expect("evaluate_expression(): code contains nul byte");
+
+    let lib = get_library_data().raw_library_data;
+
+    let mut result = MaybeUninit::<T>::uninit();
+
+    let err_code: sys::errcode_t = unsafe {
+        rtl::evaluateExpression(
+            lib,
+            code.as_ptr() as *mut c_char,
+            // Rank of the expected result.
+            0,
+            T::TYPE as mint,
+            result.as_mut_ptr() as *mut c_void,
+        )
+    };
+
+    if err_code != 0 {
+        return Err(err_code);
+    }
+
+    Ok(unsafe { result.assume_init() })
+}
+
+/// Scalar types that can be returned by [`evaluate_expression()`].
+///
+/// This trait is sealed and cannot be implemented outside of this crate.
+pub trait EvaluateExpressionType: Copy + private::Sealed {
+    #[doc(hidden)]
+    const TYPE: u32;
+}
+
+impl EvaluateExpressionType for mint {
+    const TYPE: u32 = sys::MType_Integer;
+}
+
+impl EvaluateExpressionType for sys::mreal {
+    const TYPE: u32 = sys::MType_Real;
+}
+
+impl EvaluateExpressionType for sys::mcomplex {
+    const TYPE: u32 = sys::MType_Complex;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for crate::sys::mint {}
+    impl Sealed for crate::sys::mreal {}
+    impl Sealed for crate::sys::mcomplex {}
+}
+
 fn process_wstp_link(link: &mut Link) -> Result<(), String> {
     assert_main_thread();
 
@@ -275,12 +640,21 @@ fn process_wstp_link(link: &mut Link) -> Result<(), String> {
 }
 
 /// Enforce exclusive access to the link returned by `getWSLINK()`.
-fn with_link<F: FnOnce(&mut Link) -> R, R>(f: F) -> R {
+///
+/// Returns [`EvaluationError::Reentrant`] if the link is already in use, which happens
+/// when a library function called during an evaluation started by [`evaluate()`]
+/// calls back into the Kernel itself. Using the link from that nested call would
+/// interleave its packets with the packets of the outer evaluation.
+fn with_link<F: FnOnce(&mut Link) -> R, R>(f: F) -> Result<R, EvaluationError> {
     assert_main_thread();
 
     static LOCK: Lazy<Mutex<()>> = Lazy::new(|| Default::default());
 
-    let _guard = LOCK.lock().expect("failed to acquire LINK lock");
+    let _guard = match LOCK.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::WouldBlock) => return Err(EvaluationError::Reentrant),
+        Err(TryLockError::Poisoned(_)) => panic!("failed to acquire LINK lock"),
+    };
 
     let lib = get_library_data().raw_library_data;
 
@@ -293,7 +667,7 @@ fn with_link<F: FnOnce(&mut Link) -> R, R>(f: F) -> R {
     //      other references to the underling link object.
     let link = unsafe { Link::unchecked_ref_cast_mut(&mut unsafe_link) };
 
-    f(link)
+    Ok(f(link))
 }
 
 #[inline]
@@ -312,13 +686,36 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// * Catch any panics that occur.
 ///   - If a panic does occur, the function will return
 ///     [`LIBRARY_FUNCTION_ERROR`][crate::sys::LIBRARY_FUNCTION_ERROR].
+/// * Check that the number of arguments matches the number of function parameters.
+///   - If it does not, the function will return error code `1003`, and record a message
+///     naming the function and its expected signature, which can be retrieved using
+///     [`last_error()`].
+/// * Convert each argument to the type of the corresponding function parameter.
+///   - If a [`TryFromArg`] conversion fails, for example because an integer argument
+///     does not fit in a `u8` parameter, the function will not be called, and will
+///     return error code `1004`, recording a message describing the invalid argument
+///     which can be retrieved using [`last_error()`].
+/// * Return the error code set by the function using [`set_function_error()`], if any.
+///
+/// See [`ErrorCode`] for a list of the error codes returned by exported functions.
 ///
 // * Extract the function arguments from the raw [`MArgument`] array.
 // * Store the function return value in the raw [`MArgument`] return value field.
 ///
 /// # Syntax
 ///
-/// Export a function with a single argument.
+/// Export a function.
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export;
+/// # fn square(x: i64) -> i64 { x }
+/// export![square];
+/// # }
+/// ```
+///
+/// Export a function, stating its number of parameters explicitly using `_`
+/// placeholders. This form is required for functions that take raw [`MArgument`][crate::sys::MArgument]s.
 ///
 /// ```
 /// # mod scope {
@@ -328,13 +725,35 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// # }
 /// ```
 ///
+/// Export a function, annotating some of its parameters with the
+/// [memory management mode](#memory-management-modes) used to pass them.
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::{export, NumericArray};
+/// # fn append(a: &NumericArray<f64>, b: NumericArray<f64>) {}
+/// export![append(#[automatic] _, #[manual] _)];
+/// # }
+/// ```
+///
+/// Export a function that can be called [concurrently](#thread-safe-functions), from
+/// any thread.
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export;
+/// # fn square(x: i64) -> i64 { x }
+/// export![#[thread_safe] square(_)];
+/// # }
+/// ```
+///
 /// Export a function using the specified low-level shared library symbol name.
 ///
 /// ```
 /// # mod scope {
 /// # use wolfram_library_link::export;
 /// # fn square(x: i64) -> i64 { x }
-/// export![square(_) as WL_square];
+/// export![square as WL_square];
 /// # }
 /// ```
 ///
@@ -346,7 +765,7 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// # fn square(x: i64) -> i64 { x }
 /// # fn add_two(a: i64, b: i64) -> i64 { a + b }
 /// export![
-///     square(_);
+///     square;
 ///     add_two(_, _) as AddTwo;
 /// ];
 /// # }
@@ -361,6 +780,46 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 // export![pub square(_)];
 // ```
 ///
+/// # Symbol name prefix
+///
+/// By default, a function exported as `square` is exported from the compiled library
+/// under the symbol name `square`. When several libraries are loaded into the same
+/// Wolfram Kernel process, functions with the same name exported by different libraries
+/// can collide.
+///
+/// If the `WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX` environment variable is set when the
+/// library is compiled, its value is prepended to the symbol name of every function
+/// exported using `export!`, [`export_overloads!`], and [`export_wstp!`]. The prefix
+/// can be set from the `build.rs` script of the library:
+///
+/// ```ignore
+/// // build.rs
+/// fn main() {
+///     println!("cargo:rustc-env=WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX=MyLib_");
+/// }
+/// ```
+///
+/// The function above would then be loaded using:
+///
+/// ```wolfram
+/// LibraryFunctionLoad["...", "MyLib_square", {Integer}, Integer]
+/// ```
+///
+/// The names used by the loader function generated by [`generate_loader!`] are not
+/// prefixed, because the loader already uses the correct symbol names to load each
+/// function. The names of loader functions are not prefixed.
+///
+/// The WSTP functions exported automatically by `wolfram-library-link` itself, like
+/// `wll_exported_functions` and `wll_last_error`, are prefixed only if the environment
+/// variable is set when `wolfram-library-link` is compiled, because a variable set
+/// using `cargo:rustc-env` only applies to the library that sets it, and not to its
+/// dependencies. Setting the variable in the environment that `cargo` is run in
+/// applies it to both:
+///
+/// ```text
+/// $ WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX=MyLib_ cargo build
+/// ```
+///
 /// # Examples
 ///
 /// ### Primitive data types
@@ -440,17 +899,104 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// ```
 ///
 ///
-// TODO: Add a "Memory Management" section to this comment and discuss "Constant".
-//
-// ```wolfram
-// LibraryFunctionLoad[
-//     "...", "total_i64",
-//     {
-//         {LibraryDataType[NumericArray, "Integer64"], "Constant"}
-//     },
-//     Integer
-// ]
-// ```
+/// # Memory management modes
+///
+/// The Rust type of a container parameter determines the default
+/// [memory management mode][modes] used to pass it. For example, a `&NumericArray<T>`
+/// parameter is passed as `"Constant"`, and a `NumericArray<T>` parameter is passed as
+/// `"Shared"`. See [Parameter types](#parameter-types) and the [`memory`] module.
+///
+/// A different mode can be used by annotating the parameter placeholder with one of
+/// `#[automatic]`, `#[constant]`, `#[manual]`, or `#[shared]`. The annotation is used
+/// by the loader function generated by [`generate_loader!`], and is reported by
+/// [`exported_functions()`]:
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::{export, NumericArray};
+/// fn doubled(mut list: NumericArray<i64>) -> NumericArray<i64> {
+///     for elem in list.as_slice_mut().expect("array is not shared") {
+///         *elem *= 2;
+///     }
+///
+///     list
+/// }
+///
+/// export![doubled(#[manual] _)];
+/// # }
+/// ```
+///
+/// ```wolfram
+/// LibraryFunctionLoad[
+///     "...", "doubled",
+///     {
+///         {LibraryDataType[NumericArray, "Integer64"], "Manual"}
+///     },
+///     LibraryDataType[NumericArray, "Integer64"]
+/// ]
+/// ```
+///
+/// Only modes that are compatible with the ownership implied by the Rust type can be
+/// used. A borrowed parameter, like `&NumericArray<T>`, can be annotated as
+/// `#[automatic]` or `#[constant]`, and an owned parameter, like `NumericArray<T>`, can
+/// be annotated as `#[manual]` or `#[shared]`. If an annotation is not compatible with
+/// the parameter type, the function cannot be loaded by the loader function.
+///
+/// # Thread-safe functions
+///
+/// By default, the wrapper generated by `export!` assumes that it is called by the
+/// Kernel on the main Kernel thread. After the function returns, the wrapper checks for
+/// leaked containers, forwards captured standard output, and runs evaluations queued by
+/// worker threads, all of which must happen on the main Kernel thread.
+///
+/// A function exported with the `#[thread_safe]` attribute may also be called
+/// concurrently from other threads, for example by a parallel compiled function or by
+/// native code that holds a pointer to the exported symbol. When such a function is
+/// called from a thread other than the main Kernel thread, the wrapper skips those
+/// steps, and only converts the arguments, calls the function, and stores its result.
+/// The function itself must not call back into the Kernel. Panics in a thread-safe
+/// function are caught without replacing the process-wide panic hook, so concurrent
+/// calls do not interfere with each other.
+///
+/// The main Kernel thread is recorded when the library is initialized by
+/// [`#[init]`][crate::init], or by the first call of a function that is not thread safe.
+/// Calls of thread-safe functions never record the main Kernel thread, so a library
+/// that only exports thread-safe functions should use `#[init]`.
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export;
+/// fn hypot(x: f64, y: f64) -> f64 {
+///     x.hypot(y)
+/// }
+///
+/// export![#[thread_safe] hypot(_, _)];
+/// # }
+/// ```
+///
+/// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> has no option that
+/// declares a library function as thread safe, so the loader function generated by
+/// [`generate_loader!`] loads thread-safe functions in the same way as any other
+/// function. Use [`ExportedFunction::is_thread_safe()`] to check whether an exported
+/// function was declared thread safe.
+///
+/// # Timing
+///
+/// The total time spent in each exported function is always recorded, and can be
+/// retrieved using [`stats()`]. A function exported with the `#[timing]` attribute also
+/// records the time spent in its fastest, slowest, and most recent calls, which are
+/// reported as the [`timing`][FunctionStats::timing] of its statistics:
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export;
+/// # fn multiply(a: f64, b: f64) -> f64 { a * b }
+/// export![#[timing] multiply(_, _)];
+/// # }
+/// ```
+///
+/// Attributes can be combined, for example `#[thread_safe] #[timing] multiply(_, _)`.
+/// See also the `timing` option of [`generate_loader!`].
 ///
 /// # Parameter types
 ///
@@ -480,15 +1026,27 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// -----------------------------------|---------------------------------------
 /// [`bool`]                           | `"Boolean"`
 /// [`mint`]                           | `Integer`
+/// `i8`, `i16`, `i32`, `isize`, `u8`, `u16`, `u32`, `u64`, `usize` | `Integer` (see [`TryFromArg`])
 /// [`mreal`][crate::sys::mreal]       | `Real`
 /// [`mcomplex`][crate::sys::mcomplex] | `Complex`
 /// [`String`]                         | `String`
 /// [`CString`][std::ffi::CString]     | `String`
+/// [`KernelString`]                   | `String`
+/// [`WideString`]                     | `String`
 /// [`&NumericArray`][NumericArray]    | a. `LibraryDataType[NumericArray]` <br/> b. `{LibraryDataType[NumericArray], "Constant"}`[^1]
 /// [`NumericArray`]                   | a. `{LibraryDataType[NumericArray], "Manual"}`[^1] <br/> b. `{LibraryDataType[NumericArray], "Shared"}`[^1]
 /// [`&NumericArray<T>`][NumericArray] | a. `LibraryDataType[NumericArray, `[`"..."`][ref/NumericArray]`]`[^1] <br/> b. `{LibraryDataType[NumericArray, "..."], "Constant"}`[^1]
 /// [`NumericArray<T>`]                | a. `{LibraryDataType[NumericArray, "..."], "Manual"}`[^1] <br/> b. `{LibraryDataType[NumericArray, "..."], "Shared"}`[^1]
+/// [`Constant<T>`][memory::Constant]  | `{LibraryDataType[NumericArray, "..."], "Constant"}`[^1]
+/// [`Manual<T>`][memory::Manual]      | `{LibraryDataType[NumericArray, "..."], "Manual"}`[^1]
+/// [`Shared<T>`][memory::Shared]      | `{LibraryDataType[NumericArray, "..."], "Shared"}`[^1]
+/// [`&BoolArray`][BoolArray]          | `{LibraryDataType[NumericArray, "UnsignedInteger8"], "Constant"}`
+/// [`BoolArray`]                      | `{LibraryDataType[NumericArray, "UnsignedInteger8"], "Shared"}`
+/// [`&SparseArray`][SparseArray]      | `{LibraryDataType[SparseArray], "Constant"}`
+/// [`SparseArray`]                    | `{LibraryDataType[SparseArray], "Shared"}`
 /// [`DataStore`]                      | `"DataStore"`
+/// [`Handle<T>`][managed::Handle]     | `Integer`
+/// [`Handle<T>`][registry::Handle]    | `Integer`
 ///
 /// # Return types
 ///
@@ -509,8 +1067,12 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 /// [`f32`]                            | `Real`
 /// [`mcomplex`][crate::sys::mcomplex] | `Complex`
 /// [`String`]                         | `String`
+/// [`&str`][str]                      | `String`
+/// [`WideString`]                     | `String`
 /// [`NumericArray`]                   | `LibraryDataType[NumericArray]`
 /// [`NumericArray<T>`]                | `LibraryDataType[NumericArray, `[`"..."`][ref/NumericArray][^1]`]`
+/// [`BoolArray`]                      | `LibraryDataType[NumericArray, "UnsignedInteger8"]`
+/// [`SparseArray`]                    | `LibraryDataType[SparseArray]`
 /// [`DataStore`]                      | `"DataStore"`
 ///
 /// [^1]: The Details and Options section of the Wolfram Language
@@ -519,7 +1081,7 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 ///
 /// [ref/NumericArray]: https://reference.wolfram.com/language/ref/NumericArray.html
 /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
-
+/// [modes]: https://reference.wolfram.com/language/LibraryLink/tutorial/InteractionWithWolframLanguage.html#97446640
 // # Design constraints
 //
 // The current design of this macro is intended to accommodate the following constraints:
@@ -553,11 +1115,18 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 // clever macro operations. This leads naturally to the creation of the `NativeFunction`
 // trait, which is implemented for all suitable `fn(..) -> _` types.
 //
-// Constraint 1b is unable to be met completely by the current implementation due to
-// limitations with Rust's coercion from `fn(A, B, ..) -> C {some_name}` to
+// Constraint 1b cannot be met using the `NativeFunction` impls for `fn(..) -> _` types
+// alone, due to limitations with Rust's coercion from `fn(A, B, ..) -> C {some_name}` to
 // `fn(A, B, ..) -> C`. The coercion requires that the number of parameters (`foo(_, _)`)
-// be made explicit, even if their types can be elided. If eliding the number of fn(..)
-// arguments were permitted, `export![foo]` could work.
+// be made explicit, even if their types can be elided.
+//
+// Instead, `export![foo]` wraps the `fn(A, B, ..) -> C {some_name}` function item in a
+// `NativeFunctionItem<F, Params>`, which implements `NativeFunction` for each
+// `Params = fn(A, B, ..) -> C` where `F: Fn(A, B, ..) -> C`. Because `F` implements `Fn`
+// for exactly one number of parameters, only one of those impls can apply, and the type
+// checker infers `Params` from it. The `export![foo(_, _)]` form, which performs the
+// coercion instead, is still supported, and is required for raw
+// `fn(&[MArgument], MArgument)` functions.
 //
 // To satisfy constraint 2, this implementation creates a private module with the same
 // name as the function that is being wrapped. This is required because in Rust (as in
@@ -590,57 +1159,337 @@ fn bool_from_mbool(boole: sys::mbool) -> bool {
 // ```
 #[macro_export]
 macro_rules! export {
-    ($vis:vis $name:ident($($argc:ty),*) as $exported:ident) => {
+    // Generate the wrapper for the function `super::$name`. `$native` is an expression
+    // that evaluates to `super::$name` as a value that implements `NativeFunction`.
+    // `$mode` is the memory management mode each parameter was annotated with, if any.
+    // Each `$attr` is the name of an `ExportOptions` method.
+    (@native $vis:vis $name:ident as $exported:ident = $native:expr;
+        modes = [$($mode:expr),*]; attrs = [$($attr:ident),*]) => {
         $vis mod $name {
-            #[no_mangle]
+            pub static STATS: $crate::macro_utils::CallStats =
+                $crate::macro_utils::CallStats::new();
+
+            pub static OPTIONS: $crate::macro_utils::ExportOptions =
+                $crate::macro_utils::ExportOptions::new()$(.$attr())*;
+
+            // Rebuild the library if the symbol name prefix used by `__export_symbol!`
+            // changes.
+            const _: Option<&str> = option_env!("WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX");
+
+            #[export_name = $crate::__export_symbol!($exported)]
             pub unsafe extern "C" fn $exported(
                 lib: $crate::sys::WolframLibraryData,
                 argc: $crate::sys::mint,
                 args: *mut $crate::sys::MArgument,
                 res: $crate::sys::MArgument,
             ) -> std::os::raw::c_uint {
-                // Cast away the unique `fn(...) {some_name}` function type to get the
-                // generic `fn(...)` type.
-                // The number of `$argc` is required for type inference of the variadic
-                // `fn(..) -> _` type to work. See constraint 2a.
-                let func: fn($($argc),*) -> _ = super::$name;
+                let func = $native;
 
-                $crate::macro_utils::call_native_wolfram_library_function(
-                    lib,
-                    args,
-                    argc,
-                    res,
-                    func
-                )
+                STATS.record_with(&OPTIONS, || {
+                    $crate::macro_utils::call_native_wolfram_library_function(
+                        lib,
+                        stringify!($exported),
+                        args,
+                        argc,
+                        res,
+                        func,
+                        &OPTIONS,
+                    )
+                })
             }
+
+            pub static SIGNATURE: $crate::macro_utils::SignatureFn = || {
+                let func = $native;
+
+                $crate::macro_utils::signature_with_memory_modes(
+                    $crate::NativeFunction::signature(&func),
+                    &[$($mode),*],
+                )
+            };
+
+            pub static RETURN_WRAPPER: $crate::macro_utils::ReturnWrapperFn = || {
+                let func = $native;
+
+                $crate::NativeFunction::return_wrapper(&func)
+            };
         }
 
         // Register this exported function.
         $crate::inventory::submit! {
             $crate::macro_utils::LibraryLinkFunction::Native {
                 name: stringify!($exported),
-                signature: || {
-                    let func: fn($($argc),*) -> _ = $name;
-                    let func: &dyn $crate::NativeFunction<'_> = &func;
-
-                    func.signature()
-                }
+                symbol: $crate::__export_symbol!($exported),
+                module: module_path!(),
+                stats: &$name::STATS,
+                signature: $name::SIGNATURE,
+                return_wrapper: $name::RETURN_WRAPPER,
+                options: &$name::OPTIONS,
             }
         }
     };
 
+    (
+        $(#[$attr:ident])* $vis:vis
+        $name:ident($($(#[$mode:ident])? $argc:ty),*) as $exported:ident
+    ) => {
+        // Cast away the unique `fn(...) {some_name}` function type to get the generic
+        // `fn(...)` type. See constraint 1b.
+        $crate::export![
+            @native $vis $name as $exported = super::$name as fn($($argc),*) -> _;
+            modes = [$($crate::__memory_mode!($($mode)?)),*];
+            attrs = [$($attr),*]
+        ];
+    };
+
+    ($(#[$attr:ident])* $vis:vis $name:ident as $exported:ident) => {
+        // Wrap the unique `fn(...) {some_name}` function type, whose number of parameters
+        // is inferred by the `NativeFunction` impls for `NativeFunctionItem`.
+        $crate::export![
+            @native $vis $name as $exported =
+                $crate::macro_utils::NativeFunctionItem::new(super::$name);
+            modes = [];
+            attrs = [$($attr),*]
+        ];
+    };
+
     // Convert export![name(..)] to export![name(..) as name].
-    ($vis:vis $name:ident($($argc:ty),*)) => {
-        $crate::export![$vis $name($($argc),*) as $name];
+    ($(#[$attr:ident])* $vis:vis $name:ident($($(#[$mode:ident])? $argc:ty),*)) => {
+        $crate::export![$(#[$attr])* $vis $name($($(#[$mode])? $argc),*) as $name];
     };
 
-    ($($vis:vis $name:ident($($argc:ty),*) $(as $exported:ident)?);* $(;)?) => {
+    // Convert export![name] to export![name as name].
+    ($(#[$attr:ident])* $vis:vis $name:ident) => {
+        $crate::export![$(#[$attr])* $vis $name as $name];
+    };
+
+    ($(
+        $(#[$attr:ident])* $vis:vis
+        $name:ident $(($($(#[$mode:ident])? $argc:ty),*))? $(as $exported:ident)?
+    );* $(;)?) => {
         $(
-            $crate::export![$vis $name($($argc),*) $(as $exported)?];
+            $crate::export![
+                $(#[$attr])* $vis $name $(($($(#[$mode])? $argc),*))? $(as $exported)?
+            ];
         )*
     };
 }
 
+/// Expands to the [`MemoryMode`][crate::memory::MemoryMode] named by a parameter
+/// annotation in [`export!`], or `None` if the parameter was not annotated.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __memory_mode {
+    () => {
+        None
+    };
+    (automatic) => {
+        Some($crate::memory::MemoryMode::Automatic)
+    };
+    (constant) => {
+        Some($crate::memory::MemoryMode::Constant)
+    };
+    (manual) => {
+        Some($crate::memory::MemoryMode::Manual)
+    };
+    (shared) => {
+        Some($crate::memory::MemoryMode::Shared)
+    };
+}
+
+/// Export several native functions, and load them as a single overloaded Wolfram
+/// Language function.
+///
+/// Each variant is exported exactly as if it were passed to [`export!`]. In addition, the
+/// loader function generated by [`generate_loader!`] will load a function under the
+/// overloaded name, which calls the first variant whose parameter types match the
+/// arguments it is called with. If no variant matches, a
+/// [`Failure`][ref/Failure]<sub>WL</sub> object is returned.
+///
+/// Variants are matched on the Wolfram Language types implied by their
+/// [`FromArg::parameter_type()`]. For example, an `i64` parameter matches `_Integer`, and
+/// a `&NumericArray<f64>` parameter matches only `"Real64"` numeric arrays.
+///
+/// # Syntax
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export_overloads;
+/// # fn square_i64(x: i64) -> i64 { x }
+/// # fn square_f64(x: f64) -> f64 { x }
+/// export_overloads![square: square_i64, square_f64];
+/// # }
+/// ```
+///
+/// As with [`export!`], the number of parameters of each variant may be stated
+/// explicitly, and multiple overloaded functions can be declared in one invocation:
+///
+/// ```
+/// # mod scope {
+/// # use wolfram_library_link::export_overloads;
+/// # fn square_i64(x: i64) -> i64 { x }
+/// # fn square_f64(x: f64) -> f64 { x }
+/// # fn join_two(a: String, b: String) -> String { a }
+/// # fn join_three(a: String, b: String, c: String) -> String { a }
+/// export_overloads![
+///     square: square_i64(_), square_f64(_);
+///     join: join_two, join_three;
+/// ];
+/// # }
+/// ```
+///
+/// # Example
+///
+/// ```
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, NumericArray};
+///
+/// fn total_i64(list: &NumericArray<i64>) -> i64 {
+///     list.as_slice().iter().sum()
+/// }
+///
+/// fn total_f64(list: &NumericArray<f64>) -> f64 {
+///     list.as_slice().iter().sum()
+/// }
+///
+/// wll::export_overloads![total: total_i64, total_f64];
+///
+/// wll::generate_loader![load_functions];
+/// # }
+/// ```
+///
+/// ```wolfram
+/// functions = LibraryFunctionLoad["...", "load_functions", LinkObject, LinkObject]["..."];
+///
+/// (* Calls total_i64 *)
+/// functions["total"][NumericArray[{1, 2, 3}, "Integer64"]]
+///
+/// (* Calls total_f64 *)
+/// functions["total"][NumericArray[{1.5, 2.5}, "Real64"]]
+/// ```
+///
+/// The variants can still be loaded individually, using their own names.
+///
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+#[macro_export]
+macro_rules! export_overloads {
+    ($name:ident: $($variant:ident $(($($argc:ty),*))?),+ $(,)?) => {
+        $crate::export![$($variant $(($($argc),*))?);+];
+
+        $crate::inventory::submit! {
+            $crate::macro_utils::Overloads {
+                name: stringify!($name),
+                module: module_path!(),
+                variants: &[$(stringify!($variant)),+],
+            }
+        }
+    };
+
+    ($($name:ident: $($variant:ident $(($($argc:ty),*))?),+);* $(;)?) => {
+        $(
+            $crate::export_overloads![$name: $($variant $(($($argc),*))?),+];
+        )*
+    };
+}
+
+/// Export constant values as native *LibraryLink* functions that take no arguments.
+///
+/// Each constant is exported as if it were a function returning the value, passed to
+/// [`export!`]. This allows Wolfram Language code to query metadata about the library,
+/// like its version or the features it was built with, without a hand-written function
+/// for each value.
+///
+/// The type of each constant must implement [`IntoArg`]. As in a Rust `const` item, the
+/// lifetime of a `&str` constant is inferred to be `'static`.
+///
+/// # Example
+///
+/// ```
+/// # mod scope {
+/// use wolfram_library_link as wll;
+///
+/// wll::export_constant![
+///     library_version: &str = env!("CARGO_PKG_VERSION");
+///     max_dimensions: i64 = 3;
+///     debug_build: bool = cfg!(debug_assertions);
+/// ];
+/// # }
+/// ```
+///
+/// ```wolfram
+/// LibraryFunctionLoad["...", "library_version", {}, String][]
+/// ```
+///
+/// Exported constants are also included in the functions loaded by the loader function
+/// generated by [`generate_loader!`].
+#[macro_export]
+macro_rules! export_constant {
+    ($vis:vis $name:ident: $ty:ty = $value:expr) => {
+        #[allow(non_snake_case)]
+        fn $name() -> impl $crate::IntoArg {
+            const VALUE: $ty = $value;
+
+            VALUE
+        }
+
+        $crate::export![$vis $name];
+    };
+
+    ($($vis:vis $name:ident: $ty:ty = $value:expr);* $(;)?) => {
+        $(
+            $crate::export_constant![$vis $name: $ty = $value];
+        )*
+    };
+}
+
+/// Generate a test that checks the signatures of the functions exported by this library
+/// against a Wolfram Language spec file.
+///
+/// The path to the spec file is relative to the directory containing the `Cargo.toml`
+/// file of the library. See [`check_signatures()`] for a description of the spec format.
+///
+/// The signatures of exported functions are determined by the [`FromArg`] and
+/// [`IntoArg`] implementations of their parameter and return types, so they can only be
+/// checked once the library has been compiled. The generated test fails, and `cargo test`
+/// reports the mismatches, if they don't match the spec.
+///
+/// # Example
+///
+/// ```
+/// # mod scope {
+/// use wolfram_library_link as wll;
+///
+/// fn square(x: i64) -> i64 {
+///     x * x
+/// }
+///
+/// wll::export![square];
+///
+/// // Generates `#[test] fn library_function_signatures() { .. }`.
+/// wll::check_signatures!("LibraryFunctions.wl");
+/// # }
+/// ```
+///
+/// where `LibraryFunctions.wl` contains:
+///
+/// ```wolfram
+/// <|
+///     "square" -> {{Integer}, Integer}
+/// |>
+/// ```
+#[macro_export]
+macro_rules! check_signatures {
+    ($path:literal) => {
+        #[test]
+        fn library_function_signatures() {
+            let spec = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", $path));
+
+            if let Err(message) = $crate::check_signatures(spec) {
+                panic!("{}", message);
+            }
+        }
+    };
+}
+
 /// Export the specified functions as native *LibraryLink* WSTP functions.
 ///
 /// To be exported by this macro, the specified function(s) must implement
@@ -770,7 +1619,14 @@ macro_rules! export_wstp {
             // the expansion of $argc. Always `Link` or `Vec<Expr>` at the moment.
             use super::*;
 
-            #[no_mangle]
+            pub static STATS: $crate::macro_utils::CallStats =
+                $crate::macro_utils::CallStats::new();
+
+            // Rebuild the library if the symbol name prefix used by `__export_symbol!`
+            // changes.
+            const _: Option<&str> = option_env!("WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX");
+
+            #[export_name = $crate::__export_symbol!($exported)]
             pub unsafe extern "C" fn $exported(
                 lib: $crate::sys::WolframLibraryData,
                 raw_link: $crate::wstp::sys::WSLINK,
@@ -786,16 +1642,23 @@ macro_rules! export_wstp {
                 // but this does not:
                 //   let func: fn(_) = super::$name;
 
-                $crate::macro_utils::call_wstp_wolfram_library_function(
-                    lib,
-                    raw_link,
-                    func
-                )
+                STATS.record(|| {
+                    $crate::macro_utils::call_wstp_wolfram_library_function(
+                        lib,
+                        raw_link,
+                        func
+                    )
+                })
             }
+        }
 
-            // Register this exported function.
-            $crate::inventory::submit! {
-                $crate::macro_utils::LibraryLinkFunction::Wstp { name: stringify!($exported) }
+        // Register this exported function.
+        $crate::inventory::submit! {
+            $crate::macro_utils::LibraryLinkFunction::Wstp {
+                name: stringify!($exported),
+                symbol: $crate::__export_symbol!($exported),
+                module: module_path!(),
+                stats: &$name::STATS,
             }
         }
     };
@@ -812,6 +1675,43 @@ macro_rules! export_wstp {
     };
 }
 
+/// Expands to the symbol name that a function exported as `$exported` is exported
+/// under.
+///
+/// The symbol name starts with the value of the `WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX`
+/// environment variable, if it was set when the library was compiled. See
+/// [`export!`][crate::export#symbol-name-prefix].
+///
+/// When the `hot-reload` feature is enabled, the symbol name has a suffix that is unique
+/// to each build of the library, set by the build script of the library. See
+/// [`generate_loader!`][crate::generate_loader#hot-reloading].
+#[cfg(not(feature = "hot-reload"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_symbol {
+    ($exported:ident) => {
+        $crate::macro_utils::export_symbol!($exported)
+    };
+}
+
+#[cfg(feature = "hot-reload")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __export_symbol {
+    ($exported:ident) => {
+        concat!(
+            $crate::macro_utils::export_symbol!($exported),
+            "_",
+            env!(
+                "WOLFRAM_LIBRARY_LINK_BUILD_ID",
+                "the `hot-reload` feature of wolfram-library-link requires a build \
+                script that sets WOLFRAM_LIBRARY_LINK_BUILD_ID; see the documentation \
+                of `generate_loader!`"
+            )
+        )
+    };
+}
+
 // TODO: Allow any type which implements FromExpr in wrapper parameter lists?
 
 /// Generate and export a "loader" function, which returns an Association containing the
@@ -829,6 +1729,42 @@ macro_rules! export_wstp {
 /// generate_loader![load_my_library];
 /// ```
 ///
+/// Generate a loader function that also assigns each loaded function to a symbol in the
+/// specified context, and groups the loaded functions by the Rust module they were
+/// exported from. Either option can be used on its own. See
+/// [Contexts and modules](#contexts-and-modules).
+///
+/// ```
+/// # use wolfram_library_link::generate_loader;
+/// generate_loader![load_my_library, context = "MyLib`PackagePrivate`", group_by_module];
+/// ```
+///
+/// Generate a loader function that defines a symbol in the specified context for each
+/// function, which loads the function the first time it is called. See
+/// [Lazy loading](#lazy-loading).
+///
+/// ```
+/// # use wolfram_library_link::generate_loader;
+/// generate_loader![load_my_library, context = "MyLib`PackagePrivate`", lazy];
+/// ```
+///
+/// Generate a loader function that also defines functions in the specified context that
+/// unload and reload the library. See
+/// [Unloading and reloading](#unloading-and-reloading).
+///
+/// ```
+/// # use wolfram_library_link::generate_loader;
+/// generate_loader![load_my_library, context = "MyLib`PackagePrivate`", reload];
+/// ```
+///
+/// Generate a loader function that measures each call to functions exported using
+/// `#[timing]` from the Wolfram Language. See [Timing](#timing).
+///
+/// ```
+/// # use wolfram_library_link::generate_loader;
+/// generate_loader![load_my_library, context = "MyLib`PackagePrivate`", timing];
+/// ```
+///
 /// # Example
 ///
 /// The following Rust program exports three primary functions via LibraryLink:
@@ -917,6 +1853,20 @@ macro_rules! export_wstp {
 /// `generate_loader!` has automatically mapped the Rust paramater and return types onto
 /// the appropriate Wolfram LibraryLink types.
 ///
+/// Functions whose parameter and return types cannot be determined, such as functions
+/// with a raw `fn(&[MArgument], MArgument)` signature, are mapped onto a
+/// [`Failure`][ref/Failure]<sub>WL</sub> explaining why the function could not be
+/// loaded:
+///
+/// ```wolfram
+/// Failure["RustLinkUnsupportedSignature", <|
+///     "MessageTemplate" -> "Function `1` could not be loaded: `2`",
+///     "MessageParameters" -> {"raw_square", "..."}
+/// |>]
+/// ```
+///
+/// Such functions can still be loaded by calling `LibraryFunctionLoad` manually.
+///
 /// Functions from the library can be called by applying arguments to the appropriate
 /// value from the `functions` association:
 ///
@@ -932,10 +1882,174 @@ macro_rules! export_wstp {
 /// functions["time_since_epoch"][]
 /// ```
 ///
+/// # Contexts and modules
+///
+/// For libraries that export many functions, the loader function can be configured to
+/// make the loaded functions easier to organize.
+///
+/// If a `context` is specified, each function that is loaded successfully is also
+/// assigned to a symbol in that context. Because `_` cannot be used in a Wolfram
+/// Language symbol name, the symbol name is the `camelCase` form of the function name:
+///
+/// ```wolfram
+/// (* With context = "MyLib`PackagePrivate`" *)
+/// MyLib`PackagePrivate`flatTotalI64[NumericArray[{1, 2, 3}, "Integer64"]]
+/// ```
+///
+/// The `lazy`, `reload`, and `timing` options described below define symbols in the
+/// loader context, and can only be used together with `context`. Specifying one of
+/// them without a `context` is a compile error.
+///
+/// If `group_by_module` is specified, the returned Association contains a nested
+/// Association for each Rust module that functions were exported from, relative to the
+/// root of the crate. Functions exported from the crate root, and functions registered
+/// using [`register_native()`], are not nested. For example, a `flat_total_i64` function
+/// exported from the `arrays::totals` module is loaded as:
+///
+/// ```wolfram
+/// functions["arrays", "totals", "flat_total_i64"]
+/// ```
+///
+/// # Lazy loading
+///
+/// Loading every function exported by a library can take a noticeable amount of time
+/// for libraries that export many functions. If the `lazy` option is specified, the
+/// loader function does not load any functions. Instead, it defines each symbol in the
+/// loader `context` to load its function the first time the symbol is called, and to
+/// then replace its own definition with the loaded function:
+///
+/// ```wolfram
+/// MyLib`PackagePrivate`flatTotalI64[args___] := (
+///     Clear[MyLib`PackagePrivate`flatTotalI64];
+///     MyLib`PackagePrivate`flatTotalI64 = LibraryFunctionLoad[...];
+///     MyLib`PackagePrivate`flatTotalI64[args]
+/// )
+/// ```
+///
+/// The Association returned by the loader function contains the symbols, instead of
+/// the loaded functions. The `lazy` option requires the `context` option.
+///
+/// # Unloading and reloading
+///
+/// If the `reload` option is specified, the loader function also defines two functions
+/// in the loader `context`, which make it possible to load a rebuilt library into a
+/// running Kernel in a single evaluation:
+///
+/// * `unloadLibrary[]` unloads each function loaded by the loader function using
+///   [`LibraryFunctionUnload`][ref/LibraryFunctionUnload]<sub>WL</sub>, clears the
+///   symbols the functions were assigned to, and unloads the library using
+///   [`LibraryUnload`][ref/LibraryUnload]<sub>WL</sub>.
+/// * `reloadLibrary[]` calls `unloadLibrary[]`, and then loads and calls the loader
+///   function from the current library file, returning the new Association of loaded
+///   functions.
+///
+/// ```wolfram
+/// (* After rebuilding the library *)
+/// functions = MyLib`PackagePrivate`reloadLibrary[];
+/// ```
+///
+/// The `reload` option requires the `context` option. See also
+/// [Hot reloading](#hot-reloading).
+///
+/// # Timing
+///
+/// If the `timing` option is specified, each function exported using the `#[timing]`
+/// attribute of [`export!`] is wrapped in a function that measures each call using
+/// [`AbsoluteTiming`][ref/AbsoluteTiming]<sub>WL</sub>, and adds the elapsed time to
+/// the `$functionTimings` Association in the loader `context`:
+///
+/// ```wolfram
+/// functions["multiply"][2.0, 3.0];
+///
+/// MyLib`PackagePrivate`$functionTimings
+/// (* <|"multiply" -> 0.000012|> *)
+/// ```
+///
+/// The times measured in the Wolfram Language include the overhead of passing the
+/// arguments and result to and from the library, and can be compared with the times
+/// measured by the library itself, which are returned by [`stats()`].
+///
+/// The `timing` option requires the `context` option.
+///
+/// # Test stubs
+///
+/// Calling the loader function with the string `"TestStubs"` as a second argument
+/// returns the source code of a `.wlt` test file skeleton instead, which contains a
+/// [`VerificationTest`][ref/VerificationTest]<sub>WL</sub> for each function the loader
+/// function would load:
+///
+/// ```wolfram
+/// Export["Tests.wlt", loadFunctions[library, "TestStubs"], "Text"]
+/// ```
+///
+/// Each test loads its function and calls it with placeholder arguments of the correct
+/// types. The expected result of every test is `Missing["TODO"]`, which should be
+/// replaced with the actual expected value:
+///
+/// ```wolfram
+/// VerificationTest[
+///     LibraryFunctionLoad["example_library", "add2", {Integer, Integer}, Integer][0, 0]
+///     ,
+///     Missing["TODO"]
+///     ,
+///     TestID -> "add2"
+/// ]
+/// ```
+///
+/// # Hot reloading
+///
+/// By default, a library that has been rebuilt cannot be reloaded into a running Kernel
+/// without first calling [`LibraryUnload`][ref/LibraryUnload]<sub>WL</sub>, and any
+/// functions that were loaded from the previous build of the library will conflict with
+/// the functions loaded from the new build.
+///
+/// When the `hot-reload` feature of `wolfram-library-link` is enabled:
+///
+/// * functions exported by [`export!`] and [`export_wstp!`] are exported under a name
+///   with a suffix that is unique to each build of the library, and
+/// * the loader function unloads the library and loads the functions from the current
+///   library file each time it is called.
+///
+/// Calling the loader function again after rebuilding the library will then load the
+/// new versions of all exported functions.
+///
+/// ```toml
+/// [dependencies]
+/// wolfram-library-link = { version = "*", features = ["hot-reload"] }
+/// ```
+///
+/// The build-specific suffix is read from the `WOLFRAM_LIBRARY_LINK_BUILD_ID`
+/// environment variable when the library is compiled, which must be set by the build
+/// script of the library. Cargo runs a build script that does not print any
+/// `cargo:rerun-if-changed` lines each time a file in the package changes, so the
+/// following `build.rs` generates a new ID for each rebuild of the library:
+///
+/// ```no_run
+/// use std::time::{SystemTime, UNIX_EPOCH};
+///
+/// fn main() {
+///     let nanos = SystemTime::now()
+///         .duration_since(UNIX_EPOCH)
+///         .map(|duration| duration.as_nanos())
+///         .unwrap_or(0);
+///
+///     println!("cargo:rustc-env=WOLFRAM_LIBRARY_LINK_BUILD_ID={:x}", nanos);
+/// }
+/// ```
+///
+/// Because exported function names are not stable when this feature is enabled,
+/// functions can only be loaded using the loader function. This feature is intended for
+/// use during development.
+///
+/// [ref/AbsoluteTiming]: https://reference.wolfram.com/language/ref/AbsoluteTiming.html
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
 /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+/// [ref/LibraryFunctionUnload]: https://reference.wolfram.com/language/ref/LibraryFunctionUnload.html
+/// [ref/LibraryUnload]: https://reference.wolfram.com/language/ref/LibraryUnload.html
+/// [ref/VerificationTest]: https://reference.wolfram.com/language/ref/VerificationTest.html
 #[macro_export]
 macro_rules! generate_loader {
-    ($name:ident) => {
+    ($name:ident $(, $option:ident $(= $value:literal)?)* $(,)?) => {
         // TODO: Use this anonymous `const` trick in export! and export_wstp! too.
         const _: () = {
             #[no_mangle]
@@ -943,7 +2057,20 @@ macro_rules! generate_loader {
                 lib: $crate::sys::WolframLibraryData,
                 raw_link: $crate::wstp::sys::WSLINK,
             ) -> std::os::raw::c_uint {
-                $crate::macro_utils::load_library_functions_impl(lib, raw_link)
+                // Each option is the name of a `LoaderOptions` method, which is called
+                // with the option value, if any. The options are validated in a `const`,
+                // so that invalid options are a compile error.
+                const OPTIONS: $crate::macro_utils::LoaderOptions =
+                    $crate::macro_utils::LoaderOptions::new()
+                        $(.$option($($value)?))*
+                        .validate();
+
+                $crate::macro_utils::load_library_functions_impl(
+                    lib,
+                    raw_link,
+                    stringify!($name),
+                    OPTIONS,
+                )
             }
         };
     };
@@ -10,20 +10,26 @@ use crate::sys::{
     MOutputStream, MTensor, WSENV, WSLINK,
 };
 
-#[derive(Copy, Clone)]
-struct Data {
-    /// The `ThreadId` of the Wolfram Kernel's main thread.
-    ///
-    /// The main evaluation loop of the Wolfram Kernel is largely a single-threaded
-    /// program, and it's functions are not all necessarily designed to be used from
-    /// multiple threads at once. This value, used in [`assert_main_thread()`], is used to
-    /// ensure that the safe API's provided by `wolfram-library-link` are only called from
-    /// the main Kernel thread.
-    main_thread_id: thread::ThreadId,
-    library_data: WolframLibraryData,
-}
+static LIBRARY_DATA: OnceCell<WolframLibraryData> = OnceCell::new();
+
+/// The `ThreadId` of the Wolfram Kernel's main thread.
+///
+/// The main evaluation loop of the Wolfram Kernel is largely a single-threaded
+/// program, and it's functions are not all necessarily designed to be used from
+/// multiple threads at once. This value, used in [`assert_main_thread()`], is used to
+/// ensure that the safe API's provided by `wolfram-library-link` are only called from
+/// the main Kernel thread.
+///
+/// This is recorded separately from [`LIBRARY_DATA`], which can also be initialized by a
+/// `#[thread_safe]` function called from a thread other than the main Kernel thread.
+static MAIN_THREAD_ID: OnceCell<thread::ThreadId> = OnceCell::new();
 
-static LIBRARY_DATA: OnceCell<Data> = OnceCell::new();
+/// Description of the error that occurred while registering the managers of
+/// `#[derive(ManagedExpression)]` types, if registration failed.
+///
+/// Registration only happens once, so the error is recorded to be reported by every
+/// later initialization, instead of only by the first one.
+static REGISTRATION_ERROR: OnceCell<String> = OnceCell::new();
 
 /// Initialize static data for the current Wolfram library.
 ///
@@ -75,16 +81,98 @@ static LIBRARY_DATA: OnceCell<Data> = OnceCell::new();
 ///
 /// [lib-init]: https://reference.wolfram.com/language/LibraryLink/tutorial/LibraryStructure.html#280210622
 pub unsafe fn initialize(data: sys::WolframLibraryData) -> Result<(), ()> {
+    initialize_library_data(data)?;
+
+    if MAIN_THREAD_ID.set(thread::current().id()).is_ok() {
+        // Install the signal handlers used by `crash_guard::call_guarded()` up front,
+        // instead of while a library function is running.
+        #[cfg(all(feature = "crash-guard", unix))]
+        crate::crash_guard::install();
+
+        // Register the managers of `#[derive(ManagedExpression)]` types the first time
+        // this library is initialized.
+        let result = crate::catch_panic::call_and_catch_panic(
+            crate::managed::register_derived_managers,
+        );
+
+        if let Err(panic) = result {
+            let _: Result<(), String> = REGISTRATION_ERROR.set(format!(
+                "failed to register managed expression managers: {}",
+                panic.message()
+            ));
+        }
+    }
+
+    check_registration()
+}
+
+/// Initialize the library from the wrapper of a function exported by this library.
+///
+/// The managers of `#[derive(ManagedExpression)]` types must be registered before
+/// the Kernel can create managed expressions, so a library that derives
+/// `ManagedExpression` must be initialized when it is loaded, by its
+/// `WolframLibrary_initialize()` function. If that didn't happen, every function fails,
+/// instead of only the evaluations of `CreateManagedLibraryExpression[..]` that happen
+/// before the first function call.
+///
+/// # Safety
+///
+/// See [`initialize()`]. `thread_safe` must be `true` if the exported function may be
+/// called from a thread other than the main Kernel thread.
+pub(crate) unsafe fn initialize_from_function(
+    data: sys::WolframLibraryData,
+    thread_safe: bool,
+) -> Result<(), ()> {
+    if MAIN_THREAD_ID.get().is_none() && crate::managed::has_derived_managers() {
+        crate::last_error::set_last_error(
+            "library uses #[derive(ManagedExpression)], but was not initialized when \
+            it was loaded. Use #[init] to define an initialization function."
+                .to_owned(),
+        );
+
+        return Err(());
+    }
+
+    if thread_safe {
+        // A thread other than the main Kernel thread must not be recorded as the main
+        // thread.
+        initialize_library_data(data)?;
+
+        check_registration()
+    } else {
+        initialize(data)
+    }
+}
+
+/// Initialize the lazy Wolfram Runtime Library bindings, without recording the current
+/// thread as the main Kernel thread.
+///
+/// Used to initialize the library from `#[thread_safe]` functions, which may be called
+/// from threads other than the main Kernel thread.
+///
+/// # Safety
+///
+/// `data` must be a valid and fully initialized [`sys::WolframLibraryData`] instance
+/// created by the Wolfram Kernel and passed into the current LibraryLink function.
+unsafe fn initialize_library_data(data: sys::WolframLibraryData) -> Result<(), ()> {
     let library_data = WolframLibraryData::new(data)?;
 
-    let _: Result<(), Data> = LIBRARY_DATA.set(Data {
-        main_thread_id: thread::current().id(),
-        library_data,
-    });
+    let _: Result<(), WolframLibraryData> = LIBRARY_DATA.set(library_data);
 
     Ok(())
 }
 
+/// Fail if registering the managers of `#[derive(ManagedExpression)]` types failed.
+fn check_registration() -> Result<(), ()> {
+    match REGISTRATION_ERROR.get() {
+        Some(error) => {
+            crate::last_error::set_last_error(error.clone());
+            Err(())
+        },
+        None => Ok(()),
+    }
+}
+
 /// Get the [`WolframLibraryData`] instance recorded by the last call to [`initialize()`].
 ///
 /// Prefer to use the lazy function bindings from the [`rtl`][crate::rtl] module instead
@@ -94,18 +182,52 @@ pub fn get_library_data() -> WolframLibraryData {
 
     // TODO: Include a comment here mentioning that the library could/should provide a
     //       WolframLibrary_initialize() function which calls initialize_library_data()?
-    data.expect(
+    *data.expect(
         "get_library_data: global Wolfram LIBRARY_DATA static is not initialized.",
     )
-    .library_data
+}
+
+/// Returns `true` if [`initialize()`] has been called successfully.
+#[cfg(feature = "standalone-rtl")]
+pub(crate) fn is_initialized() -> bool {
+    LIBRARY_DATA.get().is_some()
 }
 
 pub(crate) fn is_main_thread() -> bool {
-    let data = LIBRARY_DATA
+    let main_thread_id = MAIN_THREAD_ID
         .get()
-        .expect("global LIBRARY_DATA static is not initialized");
+        .expect("global MAIN_THREAD_ID static is not initialized");
+
+    *main_thread_id == thread::current().id()
+}
 
-    data.main_thread_id == thread::current().id()
+/// Returns `true` if the current thread is the main Wolfram Kernel thread.
+///
+/// Most callbacks into the Kernel, like [`evaluate()`][crate::evaluate], are only safe
+/// to call from the main Kernel thread, which is the thread that library functions are
+/// called on. Libraries that start their own threads can use this function to check that
+/// they only call back into the Kernel from the right thread.
+///
+/// Returns `false` if [`initialize()`] has not been called on the main Kernel thread yet,
+/// because the main Kernel thread is not known. Calls of `#[thread_safe]` functions do
+/// not record the main Kernel thread.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link as wll;
+///
+/// fn log_progress(percent: f64) {
+///     debug_assert!(wll::on_kernel_thread(), "log_progress() called from worker thread");
+///
+///     let _ = wll::evaluate_expression::<f64>(&format!("$Progress = {}", percent));
+/// }
+/// ```
+pub fn on_kernel_thread() -> bool {
+    match MAIN_THREAD_ID.get() {
+        Some(main_thread_id) => *main_thread_id == thread::current().id(),
+        None => false,
+    }
 }
 
 /// Assert that the current thread is the main Kernel thread.
@@ -0,0 +1,175 @@
+use crate::{convert::FromExpr, expr::Expr, wstp::Link};
+
+/// Typed reader for the arguments of a function exported using
+/// [`export_wstp!`][crate::export_wstp].
+///
+/// The arguments of a WSTP function are sent on its [`Link`] as a
+/// [`List`][ref/List]<sub>WL</sub> expression. `LinkArgs` checks the `List` head,
+/// and reads each argument in turn, converting it to a Rust type using [`FromExpr`].
+///
+/// Every method returns a [`String`] error describing the problem if the arguments
+/// do not match what was expected. Argument positions in error messages use 1-based
+/// indexing, to match Wolfram Language part numbers. Return the error as part of a
+/// `Result<T, String>` using [`ToExpr`][crate::convert::ToExpr] to send it to the
+/// Wolfram Language as a [`Failure`][ref/Failure]<sub>WL</sub> object.
+///
+/// Any arguments that have not been read when a `LinkArgs` is dropped are discarded,
+/// so that the return value can be written to the link even if reading the arguments
+/// stopped early because of an error.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, convert::ToExpr, wstp::Link, LinkArgs};
+///
+/// wll::export_wstp![string_repeat(&mut Link)];
+///
+/// fn string_repeat(link: &mut Link) {
+///     let result: Result<String, String> = LinkArgs::new(link).and_then(|mut args| {
+///         args.expect_count(2)?;
+///
+///         let string: String = args.next()?;
+///         let count: i64 = args.next()?;
+///
+///         let count = usize::try_from(count)
+///             .map_err(|_| format!("expected non-negative count, got {}", count))?;
+///
+///         Ok(string.repeat(count))
+///     });
+///
+///     link.put_expr(&result.to_expr()).unwrap();
+/// }
+/// # }
+/// ```
+///
+/// [ref/List]: https://reference.wolfram.com/language/ref/List.html
+/// [ref/Failure]: https://reference.wolfram.com/language/ref/Failure.html
+pub struct LinkArgs<'l> {
+    link: &'l mut Link,
+    count: usize,
+    /// Number of arguments that have been read.
+    position: usize,
+}
+
+impl<'l> LinkArgs<'l> {
+    /// Begin reading the arguments on `link`, checking that they are a `List`
+    /// expression.
+    pub fn new(link: &'l mut Link) -> Result<LinkArgs<'l>, String> {
+        match link.test_head("System`List") {
+            Ok(count) => Ok(LinkArgs {
+                link,
+                count,
+                position: 0,
+            }),
+            Err(err) => {
+                link.clear_error();
+                let _ = link.new_packet();
+
+                Err(format!("expected List of arguments: {}", err))
+            },
+        }
+    }
+
+    /// Get the total number of arguments.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Returns `true` if the function was called with no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// Get the number of arguments that have not been read yet.
+    pub fn remaining(&self) -> usize {
+        self.count - self.position
+    }
+
+    /// Check that the function was called with exactly `count` arguments.
+    ///
+    /// ```text
+    /// expected 2 arguments, but was called with 3 arguments
+    /// ```
+    pub fn expect_count(&self, count: usize) -> Result<(), String> {
+        if self.count != count {
+            return Err(format!(
+                "expected {} {}, but was called with {} {}",
+                count,
+                plural(count),
+                self.count,
+                plural(self.count)
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Read the next argument and convert it to `T`.
+    ///
+    /// ```text
+    /// argument 2: expected Integer, got "Hello"
+    /// ```
+    // `LinkArgs` is not an `Iterator`: the type of each argument is chosen by the caller.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next<T: FromExpr>(&mut self) -> Result<T, String> {
+        let expr = self.next_expr()?;
+
+        T::from_expr(&expr).map_err(|err| format!("argument {}: {}", self.position, err))
+    }
+
+    /// Read the next argument as an [`Expr`].
+    pub fn next_expr(&mut self) -> Result<Expr, String> {
+        if self.position == self.count {
+            return Err(format!(
+                "expected at least {} {}, but was called with {} {}",
+                self.count + 1,
+                plural(self.count + 1),
+                self.count,
+                plural(self.count)
+            ));
+        }
+
+        self.position += 1;
+
+        match self.link.get_expr() {
+            Ok(expr) => Ok(expr),
+            Err(err) => {
+                // The argument may have been partially read, so discard the rest of the
+                // packet even if this was the last argument.
+                let message = format!("argument {}: {}", self.position, err);
+
+                self.link.clear_error();
+                let _ = self.link.new_packet();
+                self.position = self.count;
+
+                Err(message)
+            },
+        }
+    }
+
+    /// Read all of the remaining arguments, converting each of them to `T`.
+    pub fn rest<T: FromExpr>(&mut self) -> Result<Vec<T>, String> {
+        (0..self.remaining()).map(|_| self.next()).collect()
+    }
+
+    fn discard_remaining(&mut self) {
+        if self.position < self.count {
+            let _ = self.link.new_packet();
+            self.position = self.count;
+        }
+    }
+}
+
+impl Drop for LinkArgs<'_> {
+    fn drop(&mut self) {
+        self.discard_remaining();
+    }
+}
+
+fn plural(count: usize) -> &'static str {
+    match count {
+        1 => "argument",
+        _ => "arguments",
+    }
+}
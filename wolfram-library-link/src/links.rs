@@ -0,0 +1,208 @@
+//! Opening WSTP links to other processes using the Kernel's WSTP environment.
+
+use std::{
+    ffi::CString,
+    os::raw::{c_char, c_int},
+    path::Path,
+};
+
+use wstp::{Link, Protocol};
+
+use crate::{get_library_data, rtl, sys};
+
+/// Handle to the WSTP environment used by the Wolfram Kernel.
+///
+/// Use [`wstp_environment()`] to get the environment of the current Kernel.
+///
+/// The environment is owned by the Kernel and must not be deinitialized by this
+/// library. This is why `WstpEnvironment` is a non-owning handle, instead of an
+/// environment type from the [`wstp`] crate: those own the environment they wrap, and
+/// deinitialize it when they are dropped, which would invalidate every link the Kernel
+/// has open. Use [`raw_env()`][WstpEnvironment::raw_env] to pass the environment to
+/// `wstp` functions that accept a raw [`WSENV`][wstp::sys::WSENV].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WstpEnvironment(wstp::sys::WSENV);
+
+impl WstpEnvironment {
+    /// Get the raw [`WSENV`][wstp::sys::WSENV] pointer wrapped by this handle.
+    ///
+    /// The returned environment can be used to create additional WSTP links, for
+    /// example to other kernels or processes, without initializing a second WSTP
+    /// environment in the current process.
+    pub fn raw_env(&self) -> wstp::sys::WSENV {
+        let WstpEnvironment(env) = *self;
+
+        env
+    }
+}
+
+/// Get the WSTP environment used by the Wolfram Kernel.
+///
+/// # Example
+///
+/// Open a new loopback link using the Kernel's environment:
+///
+/// ```no_run
+/// # use wolfram_library_link as wll;
+/// use wll::wstp::sys::{WSLoopbackOpen, WSLINK};
+///
+/// let env = wll::wstp_environment();
+///
+/// let mut err: std::os::raw::c_int = 0;
+/// let link: WSLINK = unsafe { WSLoopbackOpen(env.raw_env(), &mut err) };
+/// ```
+///
+/// *LibraryLink C Function:* [`getWSLINKEnvironment`][rtl::getWSLINKEnvironment]
+pub fn wstp_environment() -> WstpEnvironment {
+    let lib = get_library_data().raw_library_data;
+
+    let env: sys::WSENV = unsafe { rtl::getWSLINKEnvironment(lib) };
+
+    debug_assert!(!env.is_null());
+
+    WstpEnvironment(env as wstp::sys::WSENV)
+}
+
+impl WstpEnvironment {
+    /// Open a new WSTP link using this environment, configured by the specified
+    /// command-line style arguments.
+    ///
+    /// The link is activated before it is returned.
+    ///
+    /// See the [`WSOpenArgcArgv`][WSOpenArgcArgv] documentation for the
+    /// supported arguments.
+    ///
+    /// # Example
+    ///
+    /// Connect to a link created using
+    /// [`LinkCreate`][ref/LinkCreate]<sub>WL</sub>`["my-link"]`:
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link as wll;
+    /// let env = wll::wstp_environment();
+    ///
+    /// let link = env
+    ///     .open_link(&["-linkmode", "connect", "-linkname", "my-link"])
+    ///     .unwrap();
+    /// ```
+    ///
+    /// [WSOpenArgcArgv]: https://reference.wolfram.com/language/ref/c/WSOpenArgcArgv.html
+    /// [ref/LinkCreate]: https://reference.wolfram.com/language/ref/LinkCreate.html
+    pub fn open_link(&self, args: &[&str]) -> Result<Link, String> {
+        let WstpEnvironment(env) = *self;
+
+        // The first argument is conventionally the program name, and is ignored.
+        let args: Vec<CString> = std::iter::once("wolfram-library-link")
+            .chain(args.iter().copied())
+            .map(|arg| {
+                CString::new(arg).map_err(|_| {
+                    format!("WSTP link argument contains nul byte: {:?}", arg)
+                })
+            })
+            .collect::<Result<_, String>>()?;
+
+        let mut argv: Vec<*mut c_char> =
+            args.iter().map(|arg| arg.as_ptr() as *mut c_char).collect();
+
+        let argc = c_int::try_from(argv.len()).expect("too many WSTP link arguments");
+
+        let mut err: c_int = 0;
+
+        let raw_link: wstp::sys::WSLINK =
+            unsafe { wstp::sys::WSOpenArgcArgv(env, argc, argv.as_mut_ptr(), &mut err) };
+
+        if raw_link.is_null() || err != 0 {
+            return Err(format!(
+                "failed to open WSTP link with arguments {:?} (error code {})",
+                &args[1..],
+                err
+            ));
+        }
+
+        let mut link = unsafe { Link::unchecked_new(raw_link) };
+
+        link.activate()
+            .map_err(|err| format!("failed to activate WSTP link: {}", err))?;
+
+        Ok(link)
+    }
+
+    /// Launch a new Wolfram Kernel process, and return a link connected to it.
+    ///
+    /// `kernel` is the path to a `WolframKernel` executable. The kernel can be used to
+    /// evaluate expressions independently of the Kernel that loaded this library.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link as wll;
+    /// use std::path::Path;
+    ///
+    /// use wll::expr::{Expr, Symbol};
+    ///
+    /// let env = wll::wstp_environment();
+    ///
+    /// let mut kernel = env
+    ///     .launch_kernel(Path::new("/Applications/Mathematica.app/Contents/MacOS/WolframKernel"))
+    ///     .unwrap();
+    ///
+    /// kernel
+    ///     .put_expr(&Expr::normal(Symbol::new("System`EvaluatePacket"), vec![
+    ///         Expr::normal(Symbol::new("System`Plus"), vec![Expr::from(2), Expr::from(2)]),
+    ///     ]))
+    ///     .unwrap();
+    /// ```
+    pub fn launch_kernel(&self, kernel: &Path) -> Result<Link, String> {
+        let kernel = kernel.to_str().ok_or_else(|| {
+            format!("kernel path is not valid UTF-8: {}", kernel.display())
+        })?;
+
+        self.open_link(&[
+            "-linkmode",
+            "launch",
+            "-linkname",
+            &format!("\"{}\" -wstp", kernel),
+        ])
+    }
+
+    /// Connect to a WSTP link with the specified name.
+    ///
+    /// The link must have been created by another process, e.g. by evaluating
+    /// [`LinkCreate`][ref/LinkCreate]<sub>WL</sub>.
+    ///
+    /// [ref/LinkCreate]: https://reference.wolfram.com/language/ref/LinkCreate.html
+    pub fn connect(&self, protocol: Protocol, name: &str) -> Result<Link, String> {
+        self.open_link(&[
+            "-linkmode",
+            "connect",
+            "-linkprotocol",
+            protocol_name(protocol),
+            "-linkname",
+            name,
+        ])
+    }
+
+    /// Create a new WSTP link with the specified name, that other processes can
+    /// connect to.
+    ///
+    /// Because the link is activated before it is returned, this function blocks until
+    /// another process connects to the link.
+    pub fn listen(&self, protocol: Protocol, name: &str) -> Result<Link, String> {
+        self.open_link(&[
+            "-linkmode",
+            "listen",
+            "-linkprotocol",
+            protocol_name(protocol),
+            "-linkname",
+            name,
+        ])
+    }
+}
+
+fn protocol_name(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::SharedMemory => "SharedMemory",
+        Protocol::TCPIP => "TCPIP",
+        Protocol::IntraProcess => "IntraProcess",
+    }
+}
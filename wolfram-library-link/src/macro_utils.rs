@@ -1,39 +1,18 @@
-use std::os::raw::{c_int, c_uint};
+use std::{
+    collections::HashSet,
+    os::raw::{c_int, c_uint},
+};
 
 use wstp::{self, Link};
 
 use crate::{
     catch_panic::{call_and_catch_panic, CaughtPanic},
-    expr::{Expr, Symbol},
+    expr::{Expr, ExprKind, Symbol},
+    memory::MemoryMode,
     sys::{self, MArgument, LIBRARY_NO_ERROR},
-    NativeFunction, WstpFunction,
+    ErrorCode, NativeFunction, WstpFunction,
 };
 
-/// Error codes returned by macro-generated wrapper code.
-///
-/// If no error occured, [`sys::LIBRARY_NO_ERROR`] is returned.
-///
-/// Using separate error codes for macro-generated code makes the source of the error
-/// clearer when something goes wrong in wrapper code.
-//
-// TODO: Make this module public somewhere and document these error code in export!,
-//       export_wstp!, and Overview.md.
-mod error_code {
-    use std::os::raw::c_uint;
-
-    // Chosen arbitrarily. Avoids clashing with `LIBRARY_FUNCTION_ERROR` and related
-    // error codes.
-    const OFFSET: c_uint = 1000;
-
-    /// A call to [initialize()][crate::initialize] failed.
-    pub const FAILED_TO_INIT: c_uint = OFFSET + 1;
-
-    /// The library code panicked.
-    //
-    // TODO: Wherever this code is set, also set a $LastError-like variable.
-    pub const FAILED_WITH_PANIC: c_uint = OFFSET + 2;
-}
-
 //==================
 // WSTP helpers
 //==================
@@ -46,8 +25,8 @@ unsafe fn call_wstp_link_wolfram_library_function<
     function: F,
 ) -> c_uint {
     // Initialize the library.
-    if crate::initialize(libdata).is_err() {
-        return error_code::FAILED_TO_INIT;
+    if crate::library_data::initialize_from_function(libdata, false).is_err() {
+        return ErrorCode::FailedToInit.to_raw();
     }
 
     let link = Link::unchecked_ref_cast_mut(&mut unsafe_link);
@@ -55,6 +34,8 @@ unsafe fn call_wstp_link_wolfram_library_function<
     let result: Result<(), CaughtPanic> =
         call_and_catch_panic(std::panic::AssertUnwindSafe(|| {
             let _: () = function(link);
+
+            let _: usize = crate::run_pending_evaluations();
         }));
 
     match result {
@@ -107,16 +88,20 @@ fn write_panic_failure_to_link(
 
 pub unsafe fn call_native_wolfram_library_function<'a, F: NativeFunction<'a>>(
     lib_data: sys::WolframLibraryData,
+    name: &str,
     args: *mut MArgument,
     argc: sys::mint,
     res: MArgument,
     func: F,
+    options: &ExportOptions,
 ) -> c_uint {
     use std::panic::AssertUnwindSafe;
 
     // Initialize the library.
-    if crate::initialize(lib_data).is_err() {
-        return error_code::FAILED_TO_INIT;
+    if crate::library_data::initialize_from_function(lib_data, options.thread_safe)
+        .is_err()
+    {
+        return ErrorCode::FailedToInit.to_raw();
     }
 
     let argc = match usize::try_from(argc) {
@@ -124,18 +109,126 @@ pub unsafe fn call_native_wolfram_library_function<'a, F: NativeFunction<'a>>(
         Err(_) => return sys::LIBRARY_FUNCTION_ERROR,
     };
 
+    // Check the number of arguments before calling `func`, so that a mismatch between
+    // the LibraryFunctionLoad[..] signature and the Rust function signature is reported
+    // with a description of what went wrong, instead of as a generic panic.
+    //
+    // Note: The types of the arguments cannot be checked, because MArgument values do
+    //       not record the type of the value they store.
+    if let Some(param_count) = func.parameter_count() {
+        if argc != param_count {
+            crate::last_error::set_last_error(argument_count_mismatch_message(
+                name, &func, argc,
+            ));
+
+            return ErrorCode::ArgumentCountMismatch.to_raw();
+        }
+    }
+
     // FIXME: This isn't safe! 'a could be 'static, and then the user could store the
     //        `&mut Link` reference beyond the lifetime of this function.
     //        E.g. `fn foo(link: &'static mut str) { ... }`
     let args: &[MArgument] = std::slice::from_raw_parts(args, argc);
 
-    if call_and_catch_panic(AssertUnwindSafe(move || func.call(args, res))).is_err() {
+    // Discard any error set by a previous function that was not exported using
+    // `export!`, and so did not return it.
+    let _: Option<ErrorCode> = crate::error_code::take_function_error();
+
+    // A `#[thread_safe]` function may be called concurrently from threads other than
+    // the main Kernel thread. Skip the steps that call back into the Kernel, or that
+    // track the arguments of the current call in global state.
+    let on_kernel_thread = !options.thread_safe || crate::on_kernel_thread();
+
+    let call = AssertUnwindSafe(move || {
+        if !on_kernel_thread {
+            return func.try_call(args, res);
+        }
+
+        let result = crate::debug::check_leaks(name, || func.try_call(args, res));
+
+        // Forward any output captured by `io::redirect_std_streams()`. The result of
+        // `func` has already been written, so a failure here is only logged, instead
+        // of turning a successful call into a failed one.
+        if let Err(err) = crate::io::flush_std_streams() {
+            crate::io::log_error(&format!(
+                "{}: failed to print captured output: {}",
+                name, err
+            ));
+        }
+
+        let _: usize = crate::run_pending_evaluations();
+
+        result
+    });
+
+    // `call_and_catch_panic()` temporarily replaces the process-wide panic hook, which
+    // races with concurrent calls of `#[thread_safe]` functions on other threads.
+    let result = if options.thread_safe {
+        crate::catch_panic::call_and_catch_panic_concurrent(call)
+    } else {
+        call_and_catch_panic(call)
+    };
+
+    match result {
+        Ok(Ok(())) => (),
+        // An argument could not be converted by `FromArg::checked_from_arg()`, so
+        // `func` was not called.
+        Ok(Err(message)) => {
+            crate::last_error::set_last_error(format!("{}: {}", name, message));
+
+            return ErrorCode::ArgumentConversionFailed.to_raw();
+        },
         // TODO: Store the panic into a "LAST_ERROR" static, and provide an accessor to
         //       get it from WL? E.g. RustLink`GetLastError[<optional func name>].
-        return error_code::FAILED_WITH_PANIC;
+        Err(_) => return ErrorCode::FailedWithPanic.to_raw(),
+    }
+
+    // Return the error set by `set_function_error()`, if any.
+    match crate::error_code::take_function_error() {
+        Some(code) => code.to_raw(),
+        None => sys::LIBRARY_NO_ERROR,
+    }
+}
+
+/// Describe a call to the native function `name` that passed `argc` arguments.
+///
+/// ```text
+/// square: expected 1 argument, but was called with 2 arguments. Expected signature:
+/// LibraryFunctionLoad[_, "square", {Integer}, Integer]
+/// ```
+fn argument_count_mismatch_message<'a, F: NativeFunction<'a>>(
+    name: &str,
+    func: &F,
+    argc: usize,
+) -> String {
+    let plural = |count: usize| match count {
+        1 => "argument",
+        _ => "arguments",
     };
 
-    sys::LIBRARY_NO_ERROR
+    let param_count = func.parameter_count().unwrap_or(0);
+
+    let mut message = format!(
+        "{}: expected {} {}, but was called with {} {}.",
+        name,
+        param_count,
+        plural(param_count),
+        argc,
+        plural(argc)
+    );
+
+    if let Ok((param_tys, return_ty)) = func.signature() {
+        let param_tys: Vec<String> = param_tys.iter().map(ToString::to_string).collect();
+
+        message.push_str(&format!(
+            " Expected signature: LibraryFunctionLoad[_, \"{}\", {{{}}}, {}]",
+            name,
+            param_tys.join(", "),
+            return_ty
+        ));
+    }
+
+    message
 }
 
 pub unsafe fn call_wstp_wolfram_library_function<
@@ -158,9 +251,89 @@ pub unsafe fn call_wstp_wolfram_library_function<
 // Automatic Loader
 //======================================
 
+/// Used by `__export_symbol!` to apply the `WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX` prefix.
+pub use wolfram_library_link_macros::__export_symbol as export_symbol;
+
+pub use crate::stats::CallStats;
+
+pub use crate::args::NativeFunctionItem;
+
+pub use crate::overloads::Overloads;
+
+/// Function that returns the [`NativeFunction::signature()`] of an exported function.
+///
+/// See [`LibraryLinkFunction::Native::signature`].
+pub type SignatureFn = fn() -> Result<(Vec<Expr>, Expr), String>;
+
+/// Apply the memory management modes that the parameters of an exported function were
+/// annotated with in [`export!`][crate::export] to its `signature`.
+pub fn signature_with_memory_modes(
+    signature: Result<(Vec<Expr>, Expr), String>,
+    modes: &[Option<MemoryMode>],
+) -> Result<(Vec<Expr>, Expr), String> {
+    let (mut params, ret) = signature?;
+
+    crate::memory::apply_memory_modes(&mut params, modes)?;
+
+    Ok((params, ret))
+}
+
+/// Function that returns the [`NativeFunction::return_wrapper()`] of an exported
+/// function.
+pub type ReturnWrapperFn = fn() -> Option<Expr>;
+
+/// Options of a function exported by [`export!`][crate::export].
+///
+/// Each attribute accepted by `export!` is the name of a method of this type.
+#[derive(Debug, Copy, Clone, Default)]
+pub struct ExportOptions {
+    /// Whether the function can be called concurrently, from any thread.
+    thread_safe: bool,
+    /// Whether to record the time spent in individual calls to the function.
+    timing: bool,
+}
+
+impl ExportOptions {
+    /// Construct the default options, used by functions exported without attributes.
+    pub const fn new() -> Self {
+        ExportOptions {
+            thread_safe: false,
+            timing: false,
+        }
+    }
+
+    /// `#[thread_safe]`
+    pub const fn thread_safe(mut self) -> Self {
+        self.thread_safe = true;
+        self
+    }
+
+    /// `#[timing]`
+    pub const fn timing(mut self) -> Self {
+        self.timing = true;
+        self
+    }
+
+    pub(crate) fn is_thread_safe(&self) -> bool {
+        self.thread_safe
+    }
+
+    pub(crate) fn is_timed(&self) -> bool {
+        self.timing
+    }
+}
+
 pub enum LibraryLinkFunction {
     Native {
         name: &'static str,
+        /// The name of the exported symbol that implements this function.
+        ///
+        /// This is the same as `name`, unless the `hot-reload` feature is enabled.
+        symbol: &'static str,
+        /// The path of the Rust module this function was exported from, as returned by
+        /// [`module_path!()`].
+        module: &'static str,
+        stats: &'static CallStats,
         /// # Implementation note on the type of this field
         ///
         /// In an ideal world, the type of this field would be something like
@@ -189,24 +362,66 @@ pub enum LibraryLinkFunction {
         /// that is constructed in the macro-generated code (and where the concrete
         /// function type is still available) to avoid trying and failing to box up or
         /// return the `NativeFunction` trait object.
-        signature: fn() -> Result<(Vec<Expr>, Expr), String>,
+        signature: SignatureFn,
+        /// See [`LibraryLinkFunction::Native::signature`] for why this is a `fn()`.
+        return_wrapper: ReturnWrapperFn,
+        /// Attributes the function was exported with.
+        options: &'static ExportOptions,
     },
     Wstp {
         name: &'static str,
+        /// See [`LibraryLinkFunction::Native::symbol`].
+        symbol: &'static str,
+        /// See [`LibraryLinkFunction::Native::module`].
+        module: &'static str,
+        stats: &'static CallStats,
     },
 }
 
 inventory::collect!(LibraryLinkFunction);
 
+/// Options of a loader function generated by
+/// [`generate_loader!`][crate::generate_loader].
+///
+/// Each option accepted by `generate_loader!` is the name of a method of this type.
+#[derive(Debug, Default)]
+pub struct LoaderOptions {
+    /// Context that each loaded function is assigned to a symbol in.
+    context: Option<&'static str>,
+    /// Whether to group the loaded functions into nested Associations, by the Rust
+    /// module each function was exported from.
+    group_by_module: bool,
+    /// Whether to load each function the first time its symbol is used, instead of
+    /// when the loader function is called.
+    lazy: bool,
+    /// Whether to define functions that unload and reload the library in the loader
+    /// context.
+    reload: bool,
+    /// Whether to measure calls to functions exported using `#[timing]` with
+    /// `AbsoluteTiming`.
+    timing: bool,
+}
+
+/// Loaded functions, and nested groups of loaded functions, in a single Association.
+#[derive(Default)]
+struct LoaderGroup {
+    fields: Vec<Expr>,
+    children: Vec<(String, LoaderGroup)>,
+}
+
 pub unsafe fn load_library_functions_impl(
     lib_data: sys::WolframLibraryData,
     raw_link: wstp::sys::WSLINK,
+    loader_name: &str,
+    options: LoaderOptions,
 ) -> c_uint {
     call_wstp_link_wolfram_library_function(lib_data, raw_link, |link: &mut Link| {
         let arg_count: usize =
             link.test_head("List").expect("expected 'List' expression");
 
-        if arg_count != 1 {
+        // The optional second argument is either "TestStubs", or is passed by the
+        // reloading code generated by `library_reload_expr()`.
+        if arg_count != 1 && arg_count != 2 {
             panic!(
                 "expected 1 argument: the name of or file path to the dynamic library"
             );
@@ -220,41 +435,786 @@ pub unsafe fn load_library_functions_impl(
             std::path::PathBuf::from(path.to_str())
         };
 
-        let expr = library_function_load_expr(path);
+        let option: Option<Expr> = match arg_count {
+            2 => Some(link.get_expr().expect("failed to read loader argument")),
+            _ => None,
+        };
+
+        let expr = match option.as_ref().map(Expr::kind) {
+            Some(ExprKind::String(option)) if option == "TestStubs" => {
+                Expr::string(crate::test_stubs::test_stubs(path))
+            },
+            None if cfg!(feature = "hot-reload") => {
+                library_reload_expr(path, loader_name)
+            },
+            Some(_) if !cfg!(feature = "hot-reload") => {
+                panic!("expected second argument to be \"TestStubs\"")
+            },
+            _ if options.reload => {
+                let functions = library_function_load_expr(path.clone(), &options);
+
+                with_unload_and_reload_expr(functions, path, loader_name, &options)
+            },
+            _ => library_function_load_expr(path, &options),
+        };
 
         link.put_expr(&expr)
             .expect("failed to write loader Association");
     })
 }
 
-fn library_function_load_expr(library: std::path::PathBuf) -> Expr {
-    let mut fields = Vec::new();
-    let rule = Symbol::new("System`Rule");
+/// Generate code that unloads `library`, loads the loader function from the (possibly
+/// rebuilt) library file, and calls it.
+///
+/// ```wolfram
+/// (
+///     LibraryUnload[library];
+///     LibraryFunctionLoad[library, loader_name, LinkObject, LinkObject][library, True]
+/// )
+/// ```
+///
+/// Because the names of exported functions differ between builds when the `hot-reload`
+/// feature is enabled, the functions must be loaded using names provided by the new
+/// build of the library.
+fn library_reload_expr(library: std::path::PathBuf, loader_name: &str) -> Expr {
+    fn sys(name: &str) -> Symbol {
+        Symbol::new(&format!("System`{}", name))
+    }
+
+    let library = Expr::string(
+        library
+            .to_str()
+            .expect("unable to convert library file path to str"),
+    );
+    let link_object = Expr::from(sys("LinkObject"));
+
+    let load_loader = Expr::normal(sys("LibraryFunctionLoad"), vec![
+        library.clone(),
+        Expr::string(loader_name),
+        link_object.clone(),
+        link_object,
+    ]);
+
+    Expr::normal(sys("CompoundExpression"), vec![
+        Expr::normal(sys("LibraryUnload"), vec![library.clone()]),
+        Expr::normal(load_loader, vec![library, Expr::from(sys("True"))]),
+    ])
+}
+
+/// Generate code that defines the `unloadLibrary[]` and `reloadLibrary[]` functions in
+/// the loader context, and then evaluates to `functions`:
+///
+/// ```wolfram
+/// (
+///     Context`unloadLibrary[] := (
+///         If[MatchQ[Context`name1, _LibraryFunction],
+///             LibraryFunctionUnload[Context`name1]
+///         ];
+///         ...
+///         Clear[Context`name1, ...];
+///         Quiet[LibraryUnload[library]];
+///     );
+///     Context`reloadLibrary[] := (
+///         Context`unloadLibrary[];
+///         LibraryFunctionLoad[library, loader_name, LinkObject, LinkObject][library]
+///     );
+///     functions
+/// )
+/// ```
+fn with_unload_and_reload_expr(
+    functions: Expr,
+    library: std::path::PathBuf,
+    loader_name: &str,
+    options: &LoaderOptions,
+) -> Expr {
+    fn sys(name: &str) -> Symbol {
+        Symbol::new(&format!("System`{}", name))
+    }
+
+    let symbol = |name: &str| {
+        options
+            .symbol(name)
+            .expect("the 'reload' loader option requires the 'context' option")
+    };
+
+    let library = Expr::string(
+        library
+            .to_str()
+            .expect("unable to convert library file path to str"),
+    );
+    let link_object = Expr::from(sys("LinkObject"));
+
+    let mut unload = Vec::new();
+    let mut symbols = Vec::new();
+
+    for name in function_names() {
+        let symbol = symbol(&name);
+
+        unload.push(Expr::normal(sys("If"), vec![
+            Expr::normal(sys("MatchQ"), vec![
+                symbol.clone(),
+                Expr::normal(sys("Blank"), vec![Expr::from(sys("LibraryFunction"))]),
+            ]),
+            Expr::normal(sys("LibraryFunctionUnload"), vec![symbol.clone()]),
+        ]));
+
+        symbols.push(symbol);
+    }
+
+    unload.push(Expr::normal(sys("Clear"), symbols));
+    // The library may already have been unloaded, e.g. by the loader function of a
+    // library built with the `hot-reload` feature.
+    unload.push(Expr::normal(sys("Quiet"), vec![Expr::normal(
+        sys("LibraryUnload"),
+        vec![library.clone()],
+    )]));
+    unload.push(Expr::from(sys("Null")));
+
+    let unload_library = symbol("unload_library");
+
+    let load_loader = Expr::normal(sys("LibraryFunctionLoad"), vec![
+        library.clone(),
+        Expr::string(loader_name),
+        link_object.clone(),
+        link_object,
+    ]);
+
+    let reload = Expr::normal(sys("CompoundExpression"), vec![
+        Expr::normal(unload_library.clone(), vec![]),
+        Expr::normal(load_loader, vec![library]),
+    ]);
+
+    Expr::normal(sys("CompoundExpression"), vec![
+        Expr::normal(sys("SetDelayed"), vec![
+            Expr::normal(unload_library, vec![]),
+            Expr::normal(sys("CompoundExpression"), unload),
+        ]),
+        Expr::normal(sys("SetDelayed"), vec![
+            Expr::normal(symbol("reload_library"), vec![]),
+            reload,
+        ]),
+        functions,
+    ])
+}
+
+/// Get the names of all functions that the loader function loads.
+fn function_names() -> Vec<String> {
+    let exported = inventory::iter::<LibraryLinkFunction>
+        .into_iter()
+        .map(|func| func.name().to_owned());
+
+    let overloads = inventory::iter::<Overloads>
+        .into_iter()
+        .map(|overloads| overloads.name.to_owned());
+
+    let dynamic = crate::dynamic::functions()
+        .into_iter()
+        .map(|func| func.name.clone());
+
+    let mut names: Vec<String> = exported.chain(overloads).chain(dynamic).collect();
+
+    names.sort();
+    names.dedup();
+
+    names
+}
+
+//======================================
+// Functions exported by every library
+//======================================
+
+// Rebuild this crate if the symbol name prefix used by `__export_symbol!` changes.
+const _: Option<&str> = option_env!("WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX");
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns a description of each function exported by the library.
+///
+/// See [`exported_functions()`][crate::exported_functions].
+#[export_name = crate::__export_symbol!(wll_exported_functions)]
+pub unsafe extern "C" fn wll_exported_functions(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || {
+        list(
+            crate::exported_functions()
+                .iter()
+                .map(|func| func.to_expr()),
+        )
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns the call statistics of each function exported by the library.
+///
+/// See [`stats()`][crate::stats].
+#[export_name = crate::__export_symbol!(wll_function_stats)]
+pub unsafe extern "C" fn wll_function_stats(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || {
+        list(crate::stats().iter().map(|stats| stats.to_expr()))
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns the most recent error message recorded by macro-generated wrapper code.
+///
+/// See [`last_error()`][crate::last_error].
+#[export_name = crate::__export_symbol!(wll_last_error)]
+pub unsafe extern "C" fn wll_last_error(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || match crate::last_error() {
+        Some(message) => Expr::string(message),
+        None => Expr::normal(Symbol::new("System`Missing"), vec![Expr::string(
+            "NotAvailable",
+        )]),
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// cancels the [`CancelToken`][crate::CancelToken] with the specified ID.
+///
+/// See [`cancel()`][crate::cancel].
+#[export_name = crate::__export_symbol!(wll_cancel)]
+pub unsafe extern "C" fn wll_cancel(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_link_wolfram_library_function(lib_data, raw_link, |link: &mut Link| {
+        let arg_count: usize =
+            link.test_head("List").expect("expected 'List' expression");
+
+        if arg_count != 1 {
+            panic!("expected 1 argument, got {}", arg_count);
+        }
+
+        let id: i64 = link.get_i64().expect("expected Integer token ID");
+
+        let cancelled = match u64::try_from(id) {
+            Ok(id) => crate::cancel(id),
+            Err(_) => false,
+        };
+
+        link.put_symbol(if cancelled {
+            "System`True"
+        } else {
+            "System`False"
+        })
+        .expect("failed to write cancel result");
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns the leaked `"Manual"` arrays detected by leak checking.
+///
+/// See [`debug::leaks()`][crate::debug::leaks].
+#[export_name = crate::__export_symbol!(wll_leaks)]
+pub unsafe extern "C" fn wll_leaks(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || {
+        list(crate::debug::leaks().iter().map(|leak| leak.to_expr()))
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns the shape of each event raised by the library.
+///
+/// See [`event_shapes()`][crate::event_shapes].
+#[export_name = crate::__export_symbol!(wll_event_shapes)]
+pub unsafe extern "C" fn wll_event_shapes(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || {
+        let shapes: Vec<Expr> = crate::event_shapes()
+            .iter()
+            .map(|shape| {
+                Expr::normal(Symbol::new("System`Rule"), vec![
+                    Expr::string(&shape.name),
+                    shape.to_expr(),
+                ])
+            })
+            .collect();
+
+        Expr::normal(Symbol::new("System`Association"), shapes)
+    })
+}
+
+/// WSTP function exported by every library that uses `wolfram-library-link`, which
+/// returns the evaluations recorded in the evaluation transcript.
+///
+/// See [`debug::transcript()`][crate::debug::transcript].
+#[export_name = crate::__export_symbol!(wll_transcript)]
+pub unsafe extern "C" fn wll_transcript(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+) -> c_uint {
+    call_wstp_getter_function(lib_data, raw_link, || {
+        list(
+            crate::debug::transcript()
+                .iter()
+                .map(|entry| entry.to_expr()),
+        )
+    })
+}
+
+/// Implementation of a WSTP function that takes no arguments, and returns the
+/// expression returned by `func`.
+unsafe fn call_wstp_getter_function(
+    lib_data: sys::WolframLibraryData,
+    raw_link: wstp::sys::WSLINK,
+    func: fn() -> Expr,
+) -> c_uint {
+    call_wstp_link_wolfram_library_function(lib_data, raw_link, |link: &mut Link| {
+        let arg_count: usize =
+            link.test_head("List").expect("expected 'List' expression");
+
+        if arg_count != 0 {
+            panic!("expected 0 arguments, got {}", arg_count);
+        }
+
+        link.put_expr(&func()).expect("failed to write result");
+    })
+}
+
+/// `List[elements...]`
+fn list(elements: impl Iterator<Item = Expr>) -> Expr {
+    Expr::normal(Symbol::new("System`List"), elements.collect())
+}
+
+pub(crate) fn library_function_load_expr(
+    library: std::path::PathBuf,
+    options: &LoaderOptions,
+) -> Expr {
+    // The module path, name, and loading code of each function.
+    let mut functions: Vec<(&str, String, Expr)> = Vec::new();
+
+    // Names of the functions that have been loaded successfully.
+    let mut loaded: HashSet<String> = HashSet::new();
+
+    // Functions whose signature cannot be loaded using `LibraryFunctionLoad`, for
+    // example `fn(&[MArgument], MArgument)` functions, are included as a `Failure`
+    // instead of being omitted, so that they can be diagnosed from WL. A `Failure` never
+    // takes precedence over a loaded function with the same name.
+    let mut push = |module: &'static str, name: &str, code: Result<Expr, String>| {
+        let code = match code {
+            Ok(code) => {
+                loaded.insert(name.to_owned());
+                options.assign(name, code)
+            },
+            Err(_) if loaded.contains(name) => return,
+            Err(message) => unsupported_signature_failure(name, &message),
+        };
+
+        functions.push((module, name.to_owned(), code));
+    };
 
     for func in inventory::iter::<LibraryLinkFunction> {
         let code = match func.loading_code(&library) {
-            Ok(code) => code,
-            // TODO: Generate a message? Return a Failure[..]? Doing nothing seems
-            //       reasonable too. This only currently fails for
-            //       `fn(&[MArgument], MArgument)` functions.
-            Err(_) => continue,
+            Ok(code) if func.is_timed() => Ok(options.time_calls(func.name(), code)),
+            code => code,
+        };
+
+        push(func.module(), func.name(), code);
+    }
+
+    // Functions exported using `export_overloads!`. These are added after the
+    // individual exported functions, so that an overloaded function takes precedence
+    // over an exported function with the same name.
+    for overloads in inventory::iter::<Overloads> {
+        push(
+            overloads.module,
+            overloads.name,
+            overloads.loading_code(&library),
+        );
+    }
+
+    // Functions registered using `register_native()`. These are not associated with a
+    // module.
+    let library_expr = Expr::string(
+        library
+            .to_str()
+            .expect("unable to convert library file path to str"),
+    );
+
+    for (index, func) in crate::dynamic::functions().iter().enumerate() {
+        push(
+            "",
+            &func.name,
+            func.loading_code(library_expr.clone(), index),
+        );
+    }
+
+    let mut root = LoaderGroup::default();
+
+    for (module, name, code) in functions {
+        let group = if options.group_by_module {
+            // Skip the crate name, which is the first segment of every module path.
+            module
+                .split("::")
+                .skip(1)
+                .fold(&mut root, |group, segment| group.child(segment))
+        } else {
+            &mut root
+        };
+
+        group
+            .fields
+            .push(Expr::normal(Symbol::new("System`Rule"), vec![
+                Expr::string(name),
+                code,
+            ]));
+    }
+
+    let functions = root.into_association();
+
+    if !options.timing {
+        return functions;
+    }
+
+    // If[!AssociationQ[Context`$functionTimings], Context`$functionTimings = <||>]
+    let sys = |name: &str| Symbol::new(&format!("System`{}", name));
+    let timings = options.timings_symbol();
+
+    Expr::normal(sys("CompoundExpression"), vec![
+        Expr::normal(sys("If"), vec![
+            Expr::normal(sys("Not"), vec![Expr::normal(sys("AssociationQ"), vec![
+                timings.clone(),
+            ])]),
+            Expr::normal(sys("Set"), vec![
+                timings,
+                Expr::normal(sys("Association"), vec![]),
+            ]),
+        ]),
+        functions,
+    ])
+}
+
+/// Construct the `Failure` that the loader function returns in place of a function
+/// called `name` that could not be loaded, because of the error `message`.
+fn unsupported_signature_failure(name: &str, message: &str) -> Expr {
+    let sys = |name: &str| Symbol::new(&format!("System`{}", name));
+    let rule =
+        |lhs: &str, rhs: Expr| Expr::normal(sys("Rule"), vec![Expr::string(lhs), rhs]);
+
+    Expr::normal(sys("Failure"), vec![
+        Expr::string("RustLinkUnsupportedSignature"),
+        Expr::normal(sys("Association"), vec![
+            rule(
+                "MessageTemplate",
+                Expr::string("Function `1` could not be loaded: `2`"),
+            ),
+            rule(
+                "MessageParameters",
+                Expr::normal(sys("List"), vec![
+                    Expr::string(name),
+                    Expr::string(message),
+                ]),
+            ),
+        ]),
+    ])
+}
+
+impl LoaderOptions {
+    /// Construct the default options, used by `generate_loader![name]`.
+    pub const fn new() -> Self {
+        LoaderOptions {
+            context: None,
+            group_by_module: false,
+            lazy: false,
+            reload: false,
+            timing: false,
+        }
+    }
+
+    /// `context = ".."`
+    pub const fn context(mut self, context: &'static str) -> Self {
+        self.context = Some(context);
+        self
+    }
+
+    /// `group_by_module`
+    pub const fn group_by_module(mut self) -> Self {
+        self.group_by_module = true;
+        self
+    }
+
+    /// `lazy`
+    pub const fn lazy(mut self) -> Self {
+        self.lazy = true;
+        self
+    }
+
+    /// `reload`
+    pub const fn reload(mut self) -> Self {
+        self.reload = true;
+        self
+    }
+
+    /// `timing`
+    pub const fn timing(mut self) -> Self {
+        self.timing = true;
+        self
+    }
+
+    /// Check that the options are compatible with each other.
+    ///
+    /// This is evaluated in a `const` context by `generate_loader!`, so that invalid
+    /// combinations of options are reported when the library is compiled.
+    pub const fn validate(self) -> Self {
+        if self.context.is_none() {
+            if self.lazy {
+                panic!(
+                    "generate_loader!: the 'lazy' option requires the 'context' option"
+                )
+            }
+
+            if self.reload {
+                panic!(
+                    "generate_loader!: the 'reload' option requires the 'context' option"
+                )
+            }
+
+            if self.timing {
+                panic!(
+                    "generate_loader!: the 'timing' option requires the 'context' option"
+                )
+            }
+        }
+
+        self
+    }
+
+    /// If the `timing` option was specified, wrap the `code` that loads the function
+    /// called `name` so that the time spent in each call, as measured by
+    /// `AbsoluteTiming`, is added to `Context`$functionTimings[name]`. `timedFunc` and
+    /// `timing` are in the `RustLink`Private`` context:
+    ///
+    /// ```wolfram
+    /// With[{timedFunc = code},
+    ///     Function[
+    ///         With[{timing = AbsoluteTiming[timedFunc[##]]},
+    ///             Context`$functionTimings[name] =
+    ///                 Lookup[Context`$functionTimings, name, 0] + First[timing];
+    ///             Last[timing]
+    ///         ]
+    ///     ]
+    /// ]
+    /// ```
+    fn time_calls(&self, name: &str, code: Expr) -> Expr {
+        if !self.timing {
+            return code;
+        }
+
+        let sys = |name: &str| Symbol::new(&format!("System`{}", name));
+
+        let timings = self.timings_symbol();
+        let func = Expr::from(Symbol::new("RustLink`Private`timedFunc"));
+        let timing = Expr::from(Symbol::new("RustLink`Private`timing"));
+
+        let call =
+            Expr::normal(func.clone(), vec![Expr::normal(sys("SlotSequence"), vec![
+                Expr::from(1),
+            ])]);
+
+        let accumulate = Expr::normal(sys("Set"), vec![
+            Expr::normal(timings.clone(), vec![Expr::string(name)]),
+            Expr::normal(sys("Plus"), vec![
+                Expr::normal(sys("Lookup"), vec![
+                    timings,
+                    Expr::string(name),
+                    Expr::from(0),
+                ]),
+                Expr::normal(sys("First"), vec![timing.clone()]),
+            ]),
+        ]);
+
+        let body = Expr::normal(sys("With"), vec![
+            Expr::normal(sys("List"), vec![Expr::normal(sys("Set"), vec![
+                timing.clone(),
+                Expr::normal(sys("AbsoluteTiming"), vec![call]),
+            ])]),
+            Expr::normal(sys("CompoundExpression"), vec![
+                accumulate,
+                Expr::normal(sys("Last"), vec![timing]),
+            ]),
+        ]);
+
+        Expr::normal(sys("With"), vec![
+            Expr::normal(sys("List"), vec![Expr::normal(sys("Set"), vec![
+                func, code,
+            ])]),
+            Expr::normal(sys("Function"), vec![body]),
+        ])
+    }
+
+    /// Get the `Context`$functionTimings` symbol that the `timing` option accumulates
+    /// the time spent in each function in.
+    fn timings_symbol(&self) -> Expr {
+        self.symbol("$function_timings")
+            .expect("the 'timing' loader option requires the 'context' option")
+    }
+
+    /// If a context was specified, wrap `code` in an assignment to the symbol that the
+    /// function called `name` is loaded into:
+    ///
+    /// ```wolfram
+    /// Set[Context`name, code]
+    /// ```
+    ///
+    /// If the `lazy` option was specified, the symbol is instead defined to load the
+    /// function and replace its own definition when it is first called:
+    ///
+    /// ```wolfram
+    /// (
+    ///     Context`name[args___] := (
+    ///         Clear[Context`name];
+    ///         Context`name = code;
+    ///         Context`name[args]
+    ///     );
+    ///     Context`name
+    /// )
+    /// ```
+    fn assign(&self, name: &str, code: Expr) -> Expr {
+        let symbol = match self.symbol(name) {
+            Some(symbol) => symbol,
+            None if self.lazy => {
+                panic!("the 'lazy' loader option requires the 'context' option")
+            },
+            None => return code,
         };
 
-        fields.push(Expr::normal(&rule, vec![Expr::string(func.name()), code]));
+        let sys = |name: &str| Symbol::new(&format!("System`{}", name));
+
+        let set = Expr::normal(sys("Set"), vec![symbol.clone(), code]);
+
+        if !self.lazy {
+            return set;
+        }
+
+        let args = Expr::from(Symbol::new("RustLink`Private`args"));
+
+        let load_and_call = Expr::normal(sys("CompoundExpression"), vec![
+            Expr::normal(sys("Clear"), vec![symbol.clone()]),
+            set,
+            Expr::normal(symbol.clone(), vec![args.clone()]),
+        ]);
+
+        Expr::normal(sys("CompoundExpression"), vec![
+            Expr::normal(sys("SetDelayed"), vec![
+                Expr::normal(symbol.clone(), vec![Expr::normal(sys("Pattern"), vec![
+                    args,
+                    Expr::normal(sys("BlankNullSequence"), vec![]),
+                ])]),
+                load_and_call,
+            ]),
+            symbol,
+        ])
     }
 
-    Expr::normal(Symbol::new("System`Association"), fields)
+    /// Get the symbol in the loader context that the function called `name` is loaded
+    /// into, or `None` if no context was specified.
+    ///
+    /// The symbol name is the `camelCase` form of `name`, because `_` cannot be used in a
+    /// Wolfram Language symbol name.
+    fn symbol(&self, name: &str) -> Option<Expr> {
+        let context = self.context?.trim_end_matches('`');
+
+        let symbol = Symbol::new(&format!("{}`{}", context, symbol_name(name)));
+
+        Some(Expr::from(symbol))
+    }
+}
+
+impl LoaderGroup {
+    /// Get the nested group called `name`, creating it if it does not exist.
+    fn child(&mut self, name: &str) -> &mut LoaderGroup {
+        let index = match self.children.iter().position(|(child, _)| child == name) {
+            Some(index) => index,
+            None => {
+                self.children
+                    .push((name.to_owned(), LoaderGroup::default()));
+                self.children.len() - 1
+            },
+        };
+
+        &mut self.children[index].1
+    }
+
+    fn into_association(self) -> Expr {
+        let LoaderGroup {
+            mut fields,
+            children,
+        } = self;
+
+        for (name, child) in children {
+            fields.push(Expr::normal(Symbol::new("System`Rule"), vec![
+                Expr::string(name),
+                child.into_association(),
+            ]));
+        }
+
+        Expr::normal(Symbol::new("System`Association"), fields)
+    }
+}
+
+/// Convert a Rust function name, like `flat_total_i64`, to a Wolfram Language symbol
+/// name, like `flatTotalI64`.
+fn symbol_name(name: &str) -> String {
+    let mut symbol = String::with_capacity(name.len());
+    let mut capitalize = false;
+
+    for c in name.chars() {
+        if c == '_' {
+            capitalize = !symbol.is_empty();
+        } else if capitalize {
+            symbol.extend(c.to_uppercase());
+            capitalize = false;
+        } else {
+            symbol.push(c);
+        }
+    }
+
+    symbol
 }
 
 impl LibraryLinkFunction {
-    fn name(&self) -> &str {
+    pub(crate) fn name(&self) -> &str {
         match self {
             LibraryLinkFunction::Native { name, .. } => name,
-            LibraryLinkFunction::Wstp { name } => name,
+            LibraryLinkFunction::Wstp { name, .. } => name,
         }
     }
 
-    fn loading_code(&self, library: &std::path::PathBuf) -> Result<Expr, String> {
+    pub(crate) fn module(&self) -> &'static str {
+        match *self {
+            LibraryLinkFunction::Native { module, .. } => module,
+            LibraryLinkFunction::Wstp { module, .. } => module,
+        }
+    }
+
+    pub(crate) fn stats(&self) -> &'static CallStats {
+        match *self {
+            LibraryLinkFunction::Native { stats, .. } => stats,
+            LibraryLinkFunction::Wstp { stats, .. } => stats,
+        }
+    }
+
+    /// Whether this function was exported using the `#[timing]` attribute.
+    pub(crate) fn is_timed(&self) -> bool {
+        match *self {
+            LibraryLinkFunction::Native { options, .. } => options.is_timed(),
+            LibraryLinkFunction::Wstp { .. } => false,
+        }
+    }
+
+    pub(crate) fn loading_code(&self, library: &std::path::Path) -> Result<Expr, String> {
         fn sys(name: &str) -> Symbol {
             Symbol::new(&format!("System`{}", name))
         }
@@ -268,14 +1228,69 @@ impl LibraryLinkFunction {
         );
 
         let code = match self {
-            LibraryLinkFunction::Native { name, signature } => {
+            LibraryLinkFunction::Native {
+                name: _,
+                symbol,
+                module: _,
+                stats: _,
+                signature,
+                return_wrapper,
+                options: _,
+            } => {
                 let (args, ret) = signature()?;
 
-                Expr::normal(&lib_func_load, vec![
+                let load_call = Expr::normal(&lib_func_load, vec![
                     library.clone(),
-                    Expr::string(*name),
+                    Expr::string(*symbol),
                     Expr::normal(sys("List"), args),
                     ret,
+                ]);
+
+                let var = Expr::from(Symbol::new("RustLink`Private`nativeFunc"));
+
+                // var[##]
+                let call = Expr::normal(var.clone(), vec![Expr::normal(
+                    sys("SlotSequence"),
+                    vec![Expr::from(1)],
+                )]);
+
+                let mut failure_rules = crate::error_code::failure_rules(self.name());
+
+                let body = match (return_wrapper(), failure_rules.is_empty()) {
+                    (None, true) => return Ok(load_call),
+                    // wrapper[var[##]]
+                    (Some(wrapper), true) => Expr::normal(wrapper, vec![call]),
+                    // Replace[var[##], {
+                    //     LibraryFunctionError[_, code] :> Failure[tag, <| ... |>],
+                    //     ...,
+                    //     result_ :> wrapper[result]
+                    // }]
+                    (wrapper, false) => {
+                        if let Some(wrapper) = wrapper {
+                            let result = Symbol::new("RustLink`Private`result");
+
+                            failure_rules.push(Expr::normal(sys("RuleDelayed"), vec![
+                                Expr::normal(sys("Pattern"), vec![
+                                    Expr::from(result.clone()),
+                                    Expr::normal(sys("Blank"), vec![]),
+                                ]),
+                                Expr::normal(wrapper, vec![Expr::from(result)]),
+                            ]));
+                        }
+
+                        Expr::normal(sys("Replace"), vec![
+                            call,
+                            Expr::normal(sys("List"), failure_rules),
+                        ])
+                    },
+                };
+
+                // With[{var = LibraryFunctionLoad[...]}, Function[body]]
+                Expr::normal(sys("With"), vec![
+                    Expr::normal(sys("List"), vec![Expr::normal(sys("Set"), vec![
+                        var, load_call,
+                    ])]),
+                    Expr::normal(sys("Function"), vec![body]),
                 ])
             },
             /*
@@ -293,10 +1308,15 @@ impl LibraryLinkFunction {
                     ]
                 ]
             */
-            LibraryLinkFunction::Wstp { name } => {
+            LibraryLinkFunction::Wstp {
+                name: _,
+                symbol,
+                module: _,
+                stats: _,
+            } => {
                 let load_call = Expr::normal(&lib_func_load, vec![
                     library.clone(),
-                    Expr::string(*name),
+                    Expr::string(*symbol),
                     link_object.clone(),
                     link_object,
                 ]);
@@ -347,11 +1367,11 @@ pub unsafe fn init_with_user_function(
     user_init_func: fn(),
 ) -> c_int {
     if let Err(()) = crate::initialize(lib) {
-        return error_code::FAILED_TO_INIT as c_int;
+        return ErrorCode::FailedToInit.to_raw() as c_int;
     }
 
     if let Err(_) = call_and_catch_panic(user_init_func) {
-        error_code::FAILED_WITH_PANIC as c_int
+        ErrorCode::FailedWithPanic.to_raw() as c_int
     } else {
         sys::LIBRARY_NO_ERROR as c_int
     }
@@ -17,6 +17,73 @@
 //! In this way, managed expressions allow memory-management of Rust objects to be
 //! performed indirectly based on the lifetime of a Wolfram Language expression.
 //!
+//! # Deriving `ManagedExpression`
+//!
+//! For the common case of associating a single Rust value with each managed expression,
+//! [`#[derive(ManagedExpression)]`][derive@ManagedExpression] generates all the
+//! necessary boilerplate:
+//!
+//! * a [`ManagedExpression`] impl, including storage for the instances associated with
+//!   each live managed expression,
+//! * a library expression manager that creates a new [`Default`] instance on each
+//!   [`Create(Id)`][ManagedExpressionEvent::Create] event, and drops the instance on the
+//!   corresponding [`Drop(Id)`][ManagedExpressionEvent::Drop] event. The manager is
+//!   registered automatically when the library is initialized, so a library that uses
+//!   `#[derive(ManagedExpression)]` must use [`#[init]`][crate::init] (or call
+//!   [`initialize()`][crate::initialize] from `WolframLibrary_initialize()`). Otherwise
+//!   the managers would not be registered when the library is loaded, and every
+//!   function exported by the library fails with
+//!   [`ErrorCode::FailedToInit`][crate::ErrorCode::FailedToInit].
+//! * a constructor and a destructor library function, exported as `<type>_new` and
+//!   `<type>_delete`, where `<type>` is the name of the type in snake case. Each takes
+//!   the ID of a managed expression. The constructor replaces the instance with a new
+//!   [`Default`] instance, and the destructor releases the managed expression using
+//!   [`Handle::release()`].
+//!
+//! Exported functions can then take a [`Handle<T>`] parameter to access the instance
+//! associated with a managed expression:
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{self as wll, managed::{Handle, ManagedExpression}};
+//!
+//! #[derive(Default, ManagedExpression)]
+//! #[managed_expression(name = "Counter")]
+//! struct Counter {
+//!     count: i64,
+//! }
+//!
+//! #[wll::init]
+//! fn init() {}
+//!
+//! wll::export![increment(_)];
+//!
+//! fn increment(counter: Handle<Counter>) -> i64 {
+//!     let mut counter = counter.lock();
+//!     counter.count += 1;
+//!     counter.count
+//! }
+//! # }
+//! ```
+//!
+//! ```wolfram
+//! counter = CreateManagedLibraryExpression["Counter", Counter];
+//!
+//! increment = LibraryFunctionLoad["...", "increment", {Integer}, Integer];
+//!
+//! increment[ManagedLibraryExpressionID[counter]]
+//!
+//! delete = LibraryFunctionLoad["...", "counter_delete", {Integer}, "Void"];
+//!
+//! delete[ManagedLibraryExpressionID[counter]]
+//! ```
+//!
+//! If `name` is not specified, the name of the type is used as the manager name.
+//!
+//! At most 8 library expression managers can be registered by a library, including
+//! the managers registered by `#[derive(ManagedExpression)]`. Registering a manager when
+//! all 8 are in use will panic.
+//!
 //  TODO: Expand and polish this section: # Alternatives
 //
 //  * Canonical WL expression representation
@@ -39,11 +106,35 @@
 //! [Managed Library Expressions]: https://reference.wolfram.com/language/LibraryLink/tutorial/InteractionWithWolframLanguage.html#353220453
 //! [ref/CreateManagedLibraryExpression]: https://reference.wolfram.com/language/ref/CreateManagedLibraryExpression.html
 
-use std::{ffi::CString, sync::Mutex};
+use std::{
+    collections::HashMap,
+    ffi::CString,
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+};
 
 use once_cell::sync::Lazy;
 
-use crate::{rtl, sys};
+use crate::{
+    expr::{Expr, Symbol},
+    rtl,
+    sys::{self, MArgument},
+    TryFromArg,
+};
+
+/// Derive a [`ManagedExpression`] impl and register a library expression manager for a
+/// type.
+///
+/// The type must implement [`Default`] and [`Send`]. The optional
+/// `#[managed_expression(name = "...")]` attribute sets the name of the manager used
+/// with
+/// [`CreateManagedLibraryExpression`][ref/CreateManagedLibraryExpression]<sub>WL</sub>.
+///
+/// This also exports a constructor and a destructor library function for the type. See
+/// the [module documentation](self#deriving-managedexpression) for an example.
+///
+/// [ref/CreateManagedLibraryExpression]: https://reference.wolfram.com/language/ref/CreateManagedLibraryExpression.html
+pub use wolfram_library_link_macros::ManagedExpression;
 
 /// Lifecycle events triggered by the creation and deallocation of managed expressions.
 pub enum ManagedExpressionEvent {
@@ -77,6 +168,11 @@ impl ManagedExpressionEvent {
 pub type Id = u32;
 
 /// Register a new callback function for handling managed expression events.
+///
+/// # Panics
+///
+/// At most 8 managers can be registered at a time. This function will panic if all 8
+/// are already in use, or if the manager could not be registered.
 pub fn register_library_expression_manager(
     name: &str,
     manage_instance: fn(ManagedExpressionEvent),
@@ -84,6 +180,219 @@ pub fn register_library_expression_manager(
     register_using_next_slot(name, manage_instance)
 }
 
+//======================================
+// ManagedExpression
+//======================================
+
+/// Rust type whose instances are associated with managed expressions.
+///
+/// This trait should be implemented using
+/// [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+pub trait ManagedExpression: Default + Send + Sized + 'static {
+    /// Name of the library expression manager for this type.
+    const NAME: &'static str;
+
+    #[doc(hidden)]
+    fn instances() -> &'static Instances<Self>;
+}
+
+/// Storage for the instances of a [`ManagedExpression`] type, indexed by managed
+/// expression [`Id`].
+#[doc(hidden)]
+pub struct Instances<T> {
+    map: Lazy<RwLock<HashMap<Id, Arc<Mutex<T>>>>>,
+}
+
+/// Handle to the instance of `T` associated with a managed expression.
+///
+/// `Handle<T>` can be used as the parameter type of a function exported using
+/// [`export!`][crate::export]. The corresponding Wolfram Language argument is the
+/// [`ManagedLibraryExpressionID`][ref/ManagedLibraryExpressionID]<sub>WL</sub> of the
+/// managed expression.
+///
+/// Conversion from an argument fails if there is no live instance of `T` with the
+/// specified [`Id`] (see [`TryFromArg`]).
+///
+/// The instance remains valid for the lifetime of the handle, even if the managed
+/// expression is deallocated in the meantime.
+///
+/// [ref/ManagedLibraryExpressionID]: https://reference.wolfram.com/language/ref/ManagedLibraryExpressionID.html
+pub struct Handle<T> {
+    id: Id,
+    instance: Arc<Mutex<T>>,
+}
+
+/// Register the library expression manager for `T`.
+///
+/// This function is called automatically for types that use
+/// [`#[derive(ManagedExpression)]`][derive@ManagedExpression] when the library is
+/// initialized.
+pub fn register<T: ManagedExpression>() {
+    register_library_expression_manager(T::NAME, manage_instances::<T>)
+}
+
+fn manage_instances<T: ManagedExpression>(event: ManagedExpressionEvent) {
+    let mut map = T::instances().map.write().unwrap();
+
+    match event {
+        ManagedExpressionEvent::Create(id) => {
+            map.insert(id, Arc::new(Mutex::new(T::default())));
+        },
+        ManagedExpressionEvent::Drop(id) => {
+            if let Some(instance) = map.remove(&id) {
+                // Don't drop the instance while holding the lock on the map.
+                drop(map);
+                drop(instance);
+            }
+        },
+    }
+}
+
+/// Constructor exported by [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+#[doc(hidden)]
+pub fn derived_new<T: ManagedExpression>(handle: Handle<T>) {
+    *handle.lock() = T::default();
+}
+
+/// Destructor exported by [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+#[doc(hidden)]
+pub fn derived_delete<T: ManagedExpression>(handle: Handle<T>) {
+    if let Err(err) = handle.release() {
+        panic!("{}", err)
+    }
+}
+
+/// Manager registered by [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+#[doc(hidden)]
+pub struct DerivedManager {
+    pub register: fn(),
+}
+
+inventory::collect!(DerivedManager);
+
+/// Register the managers of all types that use
+/// [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+pub(crate) fn register_derived_managers() {
+    for manager in inventory::iter::<DerivedManager> {
+        (manager.register)()
+    }
+}
+
+/// Returns `true` if this library has any types that use
+/// [`#[derive(ManagedExpression)]`][derive@ManagedExpression].
+pub(crate) fn has_derived_managers() -> bool {
+    inventory::iter::<DerivedManager>
+        .into_iter()
+        .next()
+        .is_some()
+}
+
+impl<T> Instances<T> {
+    #[allow(missing_docs)]
+    pub const fn new() -> Self {
+        Instances {
+            map: Lazy::new(|| RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl<T> Default for Instances<T> {
+    fn default() -> Self {
+        Instances::new()
+    }
+}
+
+impl<T: ManagedExpression> Handle<T> {
+    /// Get a handle to the instance associated with the managed expression `id`.
+    ///
+    /// Returns `None` if there is no live instance with the specified [`Id`].
+    pub fn from_id(id: Id) -> Option<Handle<T>> {
+        let map = T::instances().map.read().unwrap();
+
+        let instance = Arc::clone(map.get(&id)?);
+
+        Some(Handle { id, instance })
+    }
+}
+
+impl<T> Handle<T> {
+    /// Get the [`Id`] of the managed expression associated with this instance.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Acquire exclusive access to the instance.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if a previous access to this instance panicked.
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.instance.lock().unwrap()
+    }
+}
+
+impl<T: ManagedExpression> Handle<T> {
+    /// Release the managed expression associated with this instance.
+    ///
+    /// The library expression manager for `T` will receive a
+    /// [`Drop(Id)`][ManagedExpressionEvent::Drop] event, and the instance is dropped
+    /// once the last handle to it has been dropped.
+    pub fn release(&self) -> Result<(), String> {
+        let name = CString::new(T::NAME).expect("failed to allocate C string");
+
+        let err_code: std::os::raw::c_int = unsafe {
+            rtl::releaseManagedLibraryExpression(name.as_ptr(), sys::mint::from(self.id))
+        };
+
+        if err_code != 0 {
+            return Err(format!(
+                "failed to release managed expression '{}' with id {} (error code {})",
+                T::NAME,
+                self.id,
+                err_code
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle {
+            id: self.id,
+            instance: Arc::clone(&self.instance),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handle").field("id", &self.id).finish()
+    }
+}
+
+impl<T: ManagedExpression> TryFromArg<'_> for Handle<T> {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Handle<T>, String> {
+        let id: sys::mint = *arg.integer;
+
+        Id::try_from(id)
+            .ok()
+            .and_then(Handle::from_id)
+            .ok_or_else(|| {
+                format!(
+                    "no instance of managed expression '{}' with id {}",
+                    T::NAME,
+                    id
+                )
+            })
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Integer"))
+    }
+}
+
 //======================================
 // C wrapper functions
 //======================================
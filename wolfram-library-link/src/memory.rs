@@ -0,0 +1,583 @@
+//! Memory management of *LibraryLink* container values.
+//!
+//! # Memory management modes
+//!
+//! When a [`NumericArray`] is passed from the Wolfram Language into a library function,
+//! the parameter type used in
+//! [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> specifies which of the
+//! [memory management modes][modes] is used. Each mode implies a different ownership
+//! contract between the Kernel and the library.
+//!
+//! The [`Constant`], [`Manual`], and [`Shared`] wrapper types make that contract
+//! visible in the type of a function parameter, and provide only the operations that are
+//! valid for that mode:
+//!
+//! Rust parameter type        | Memory management mode | Ownership
+//! ---------------------------|------------------------|---------------------------------------
+//! [`Constant<T>`][Constant]  | `"Constant"`           | Borrowed from the Kernel, read-only
+//! [`Manual<T>`][Manual]      | `"Manual"`             | Owned uniquely by the library, freed on drop
+//! [`Shared<T>`][Shared]      | `"Shared"`             | Shared with the Kernel, disowned on drop
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     memory::{Constant, Manual},
+//!     NumericArray,
+//! };
+//!
+//! wll::export![total(_); doubled(_)];
+//!
+//! fn total(array: Constant<i64>) -> i64 {
+//!     array.as_slice().iter().sum()
+//! }
+//!
+//! fn doubled(mut array: Manual<i64>) -> NumericArray<i64> {
+//!     for elem in array.as_slice_mut() {
+//!         *elem *= 2;
+//!     }
+//!
+//!     array.into_inner()
+//! }
+//! # }
+//! ```
+//!
+//! ```wolfram
+//! total = LibraryFunctionLoad[
+//!     "...", "total",
+//!     {{LibraryDataType[NumericArray, "Integer64"], "Constant"}},
+//!     Integer
+//! ];
+//! ```
+//!
+//! [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+//! [modes]: https://reference.wolfram.com/language/LibraryLink/tutorial/InteractionWithWolframLanguage.html#97446640
+
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+use once_cell::sync::Lazy;
+use ref_cast::RefCast;
+
+use crate::{
+    convert::normal_elements,
+    expr::{Expr, ExprKind, Symbol},
+    sys::MArgument,
+    FromArg, NumericArray, NumericArrayDataType, NumericArrayType, SharedSliceMut,
+};
+
+/// Memory management mode used to pass a container between the Kernel and a library.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MemoryMode {
+    /// The Kernel passes a copy of the container if it is shared elsewhere, and frees it
+    /// when the function returns.
+    Automatic,
+    /// The Kernel passes the container itself, which must not be modified or freed by
+    /// the library.
+    Constant,
+    /// The Kernel passes the container, and the library becomes responsible for freeing
+    /// it.
+    Manual,
+    /// The Kernel and the library share the container. The library must disown it when
+    /// it is no longer used.
+    Shared,
+}
+
+/// A [`NumericArray`] passed using the `"Constant"` memory management mode.
+///
+/// The array is borrowed from the Kernel for the duration of the call, and must not be
+/// modified.
+pub struct Constant<'a, T = ()>(&'a NumericArray<T>);
+
+/// A [`NumericArray`] passed using the `"Manual"` memory management mode.
+///
+/// The library owns the array uniquely. It is freed when the `Manual` is dropped,
+/// unless ownership is transferred using [`Manual::into_inner()`].
+pub struct Manual<T = ()>(NumericArray<T>);
+
+/// A [`NumericArray`] passed using the `"Shared"` memory management mode.
+///
+/// The array is shared between the Kernel and the library, and modifications made by
+/// the library are visible to the Wolfram Language. The library's reference to the
+/// array is released when the `Shared` is dropped, or by calling [`Shared::disown()`].
+///
+/// Use [`Shared::share_count()`] to inspect how many references the library holds, and
+/// [`Shared::make_owned_copy()`] to obtain a private copy when the shared array should
+/// not be modified in place.
+pub struct Shared<T = ()>(NumericArray<T>);
+
+/// Memory currently held by *LibraryLink* container values created by this library.
+///
+/// Use [`memory_usage()`] to get the current memory usage.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The number of live [`NumericArray`][crate::NumericArray] values.
+    pub numeric_arrays: usize,
+    /// The total size in bytes of the elements of all live
+    /// [`NumericArray`][crate::NumericArray] values.
+    pub numeric_array_bytes: usize,
+    /// The number of live [`DataStore`][crate::DataStore] values.
+    pub data_stores: usize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum Container {
+    NumericArray { bytes: usize },
+    DataStore,
+}
+
+static TRACKING: AtomicBool = AtomicBool::new(false);
+
+/// Containers allocated by this library that are still owned by a Rust value, indexed
+/// by the address of the raw container object.
+static TRACKED: Lazy<Mutex<HashMap<usize, Container>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Start tracking the memory held by *LibraryLink* containers created by this library.
+///
+/// Tracking is disabled by default, and has negligible cost when disabled. While it is
+/// enabled, every container allocation and deallocation briefly acquires a global lock.
+pub fn start_memory_tracking() {
+    TRACKING.store(true, Ordering::SeqCst);
+}
+
+/// Stop tracking the memory held by *LibraryLink* containers.
+///
+/// Containers that were being tracked are forgotten, and are not counted by
+/// [`memory_usage()`] if tracking is started again.
+pub fn stop_memory_tracking() {
+    TRACKING.store(false, Ordering::SeqCst);
+    TRACKED.lock().unwrap().clear();
+}
+
+/// Get the memory currently held by *LibraryLink* containers created by this library.
+///
+/// Only containers allocated while tracking is enabled are counted. Use
+/// [`start_memory_tracking()`] to enable tracking.
+///
+/// A container is counted from the time it is allocated by this library (e.g. using
+/// [`NumericArray::from_slice()`][crate::NumericArray::from_slice],
+/// [`NumericArray::try_clone()`][crate::NumericArray::try_clone], or
+/// [`DataStore::new()`][crate::DataStore::new]), until it is either dropped, or
+/// ownership of it is given to the Kernel or another container (e.g. by returning it
+/// from an exported function, or by calling `into_raw()`).
+///
+/// Containers received from the Kernel as arguments are not counted.
+///
+/// A value that keeps increasing between calls into the library typically indicates that
+/// containers are being leaked, e.g. by using [`std::mem::forget()`] or by
+/// storing them in a static that is never cleared.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{self as wll, NumericArray};
+///
+/// wll::start_memory_tracking();
+///
+/// let before = wll::memory_usage();
+///
+/// let array = NumericArray::<f64>::from_slice(&[1.0, 2.0, 3.0]);
+/// assert_eq!(wll::memory_usage().numeric_array_bytes, before.numeric_array_bytes + 24);
+///
+/// drop(array);
+/// assert_eq!(wll::memory_usage(), before);
+/// ```
+pub fn memory_usage() -> MemoryUsage {
+    let tracked = TRACKED.lock().unwrap();
+
+    let mut usage = MemoryUsage::default();
+
+    for container in tracked.values() {
+        match *container {
+            Container::NumericArray { bytes } => {
+                usage.numeric_arrays += 1;
+                usage.numeric_array_bytes += bytes;
+            },
+            Container::DataStore => usage.data_stores += 1,
+        }
+    }
+
+    usage
+}
+
+/// Start tracking the container at `raw`, which was allocated by this library, if
+/// tracking is enabled.
+pub(crate) fn track<P>(raw: *mut P, container: Container) {
+    if !TRACKING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    TRACKED.lock().unwrap().insert(raw as usize, container);
+}
+
+/// Stop tracking the container at `raw`, if it is being tracked.
+pub(crate) fn untrack<P>(raw: *mut P) {
+    // TRACKED is cleared when tracking is disabled.
+    if !TRACKING.load(Ordering::SeqCst) {
+        return;
+    }
+
+    TRACKED.lock().unwrap().remove(&(raw as usize));
+}
+
+//======================================
+// MemoryMode
+//======================================
+
+impl MemoryMode {
+    /// Get the string name of this mode, suitable for use in a
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> parameter type
+    /// specification.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub fn name(&self) -> &'static str {
+        match self {
+            MemoryMode::Automatic => "Automatic",
+            MemoryMode::Constant => "Constant",
+            MemoryMode::Manual => "Manual",
+            MemoryMode::Shared => "Shared",
+        }
+    }
+}
+
+impl MemoryMode {
+    fn from_name(name: &str) -> Option<MemoryMode> {
+        let mode = match name {
+            "Automatic" => MemoryMode::Automatic,
+            "Constant" => MemoryMode::Constant,
+            "Manual" => MemoryMode::Manual,
+            "Shared" => MemoryMode::Shared,
+            _ => return None,
+        };
+
+        Some(mode)
+    }
+
+    /// Returns `true` if a parameter whose type uses the memory management mode `self`
+    /// by default can also safely be passed using `mode`.
+    ///
+    /// Borrowed containers are never freed or disowned by the library, so they can be
+    /// passed using any mode in which the Kernel keeps ownership. Owned containers are
+    /// disowned if they are shared, and freed otherwise, so they can be passed using any
+    /// mode that transfers a reference to the library.
+    fn accepts(&self, mode: MemoryMode) -> bool {
+        use MemoryMode::{Automatic, Constant, Manual, Shared};
+
+        matches!(
+            (*self, mode),
+            (Constant, Constant | Automatic)
+                | (Shared, Shared | Manual)
+                | (Manual, Manual)
+        )
+    }
+}
+
+/// Override the memory management modes of `params`, the parameter types of an exported
+/// function, with the modes the parameters were annotated with in
+/// [`export!`][crate::export].
+///
+/// Parameters whose mode is `None` are left unchanged. An error is returned if an
+/// annotated parameter is not a container, or if its Rust type is not compatible with
+/// the annotated mode.
+pub(crate) fn apply_memory_modes(
+    params: &mut [Expr],
+    modes: &[Option<MemoryMode>],
+) -> Result<(), String> {
+    for (index, (param, mode)) in params.iter_mut().zip(modes).enumerate() {
+        let mode = match mode {
+            Some(mode) => *mode,
+            None => continue,
+        };
+
+        // {type, "<mode>"}
+        let current = match normal_elements(param, "System`List") {
+            Some([data_type, default]) => match default.kind() {
+                ExprKind::String(default) => MemoryMode::from_name(default)
+                    .map(|default| (data_type.clone(), default)),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        let (data_type, default) = match current {
+            Some(current) => current,
+            None => {
+                return Err(format!(
+                    "parameter {} of type {} is not a container, and cannot be \
+                    annotated with a memory management mode",
+                    index + 1,
+                    param
+                ))
+            },
+        };
+
+        if !default.accepts(mode) {
+            return Err(format!(
+                "parameter {} is passed using the \"{}\" memory management mode, and \
+                cannot be annotated as \"{}\"",
+                index + 1,
+                default.name(),
+                mode.name()
+            ));
+        }
+
+        // `Automatic` is specified using the symbol, not a string.
+        let mode = match mode {
+            MemoryMode::Automatic => Expr::from(Symbol::new("System`Automatic")),
+            _ => Expr::string(mode.name()),
+        };
+
+        *param = Expr::normal(Symbol::new("System`List"), vec![data_type, mode]);
+    }
+
+    Ok(())
+}
+
+//======================================
+// Constant, Manual, and Shared
+//======================================
+
+impl<'a, T> Constant<'a, T> {
+    /// Get the borrowed array.
+    pub fn get(&self) -> &'a NumericArray<T> {
+        self.0
+    }
+}
+
+impl<T> Manual<T> {
+    /// Take ownership of the array, e.g. to return it from a library function.
+    pub fn into_inner(self) -> NumericArray<T> {
+        self.0
+    }
+}
+
+impl<T: NumericArrayType> Manual<T> {
+    /// Access the elements of this array as a mutable flat buffer.
+    ///
+    /// Unlike [`NumericArray::as_slice_mut()`], this function cannot fail, because a
+    /// `"Manual"` array is never shared with the Kernel.
+    pub fn as_slice_mut(&mut self) -> &mut [T] {
+        // Safety: The library has unique access to arrays passed as "Manual".
+        unsafe { self.0.as_slice_mut_unchecked() }
+    }
+}
+
+impl<T> Shared<T> {
+    /// Returns the share count of this array.
+    ///
+    /// This is always at least 1 while the `Shared` is alive.
+    pub fn share_count(&self) -> usize {
+        self.0.share_count()
+    }
+
+    /// Release the library's reference to this array.
+    ///
+    /// This is equivalent to dropping the `Shared`.
+    ///
+    /// *LibraryLink C API Documentation:* [`MNumericArray_disown`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MNumericArray_disown.html)
+    pub fn disown(self) {
+        drop(self)
+    }
+
+    /// Create a deep copy of this array that is owned solely by this library.
+    ///
+    /// The copy has a [share count][NumericArray::share_count] of 0, so it can be
+    /// mutated using [`NumericArray::as_slice_mut()`] without affecting the array shared
+    /// with the Kernel. Use this when modifying the shared array in place would not be
+    /// safe.
+    ///
+    /// *LibraryLink C API Documentation:* [`MNumericArray_clone`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MNumericArray_clone.html)
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the array could not be copied.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod scope {
+    /// use wolfram_library_link::{self as wll, memory::Shared, NumericArray};
+    ///
+    /// wll::export![negated(_)];
+    ///
+    /// fn negated(array: Shared<f64>) -> NumericArray<f64> {
+    ///     let mut copy = array.make_owned_copy();
+    ///
+    ///     // The shared array is no longer needed.
+    ///     array.disown();
+    ///
+    ///     for elem in copy.as_slice_mut().expect("copy is not shared") {
+    ///         *elem = -*elem;
+    ///     }
+    ///
+    ///     copy
+    /// }
+    /// # }
+    /// ```
+    pub fn make_owned_copy(&self) -> NumericArray<T> {
+        self.0.clone()
+    }
+}
+
+impl<T: NumericArrayType> Shared<T> {
+    /// Mutably borrow the elements of this array.
+    ///
+    /// See [`NumericArray::borrow_shared_mut()`].
+    ///
+    /// # Safety
+    ///
+    /// See [`NumericArray::borrow_shared_mut()`].
+    #[track_caller]
+    pub unsafe fn borrow_mut(&mut self) -> SharedSliceMut<'_, T> {
+        self.0.borrow_shared_mut()
+    }
+}
+
+impl<'a, T> Deref for Constant<'a, T> {
+    type Target = NumericArray<T>;
+
+    fn deref(&self) -> &NumericArray<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Manual<T> {
+    type Target = NumericArray<T>;
+
+    fn deref(&self) -> &NumericArray<T> {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Manual<T> {
+    fn deref_mut(&mut self) -> &mut NumericArray<T> {
+        &mut self.0
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = NumericArray<T>;
+
+    fn deref(&self) -> &NumericArray<T> {
+        &self.0
+    }
+}
+
+impl<'a, T> fmt::Debug for Constant<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Constant").field(&self.0).finish()
+    }
+}
+
+impl<T> fmt::Debug for Manual<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Manual").field(&self.0).finish()
+    }
+}
+
+impl<T> fmt::Debug for Shared<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Shared").field(&self.0).finish()
+    }
+}
+
+//--------------
+// FromArg impls
+//--------------
+
+/// `{LibraryDataType[NumericArray, "<type>"], "<mode>"}`, or
+/// `{NumericArray, "<mode>"}` if `data_type` is `None`.
+fn parameter_type(data_type: Option<NumericArrayDataType>, mode: MemoryMode) -> Expr {
+    let numeric_array = Expr::from(Symbol::new("System`NumericArray"));
+
+    let data_type = match data_type {
+        Some(data_type) => Expr::normal(Symbol::new("System`LibraryDataType"), vec![
+            numeric_array,
+            Expr::string(data_type.name()),
+        ]),
+        None => numeric_array,
+    };
+
+    Expr::normal(Symbol::new("System`List"), vec![
+        data_type,
+        Expr::string(mode.name()),
+    ])
+}
+
+impl<'a, T: NumericArrayType> FromArg<'a> for Constant<'a, T> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Constant<'a, T> {
+        crate::released::acquire(*arg.numeric);
+
+        Constant(NumericArray::ref_cast(&*arg.numeric))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(Some(T::TYPE), MemoryMode::Constant)
+    }
+}
+
+impl<'a> FromArg<'a> for Constant<'a, ()> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Constant<'a, ()> {
+        crate::released::acquire(*arg.numeric);
+
+        Constant(NumericArray::ref_cast(&*arg.numeric))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(None, MemoryMode::Constant)
+    }
+}
+
+impl<'a, T: NumericArrayType> FromArg<'a> for Manual<T> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Manual<T> {
+        crate::debug::track_manual(*arg.numeric);
+
+        Manual(NumericArray::from_raw(*arg.numeric))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(Some(T::TYPE), MemoryMode::Manual)
+    }
+}
+
+impl<'a> FromArg<'a> for Manual<()> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Manual<()> {
+        crate::debug::track_manual(*arg.numeric);
+
+        Manual(NumericArray::from_raw(*arg.numeric))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(None, MemoryMode::Manual)
+    }
+}
+
+impl<'a, T: NumericArrayType> FromArg<'a> for Shared<T> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Shared<T> {
+        Shared(NumericArray::<T>::from_arg(arg))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(Some(T::TYPE), MemoryMode::Shared)
+    }
+}
+
+impl<'a> FromArg<'a> for Shared<()> {
+    unsafe fn from_arg(arg: &'a MArgument) -> Shared<()> {
+        Shared(NumericArray::<()>::from_arg(arg))
+    }
+
+    fn parameter_type() -> Expr {
+        parameter_type(None, MemoryMode::Shared)
+    }
+}
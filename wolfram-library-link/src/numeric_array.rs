@@ -2,10 +2,18 @@ use std::ffi::c_void;
 use std::fmt;
 use std::marker::PhantomData;
 use std::mem::MaybeUninit;
+use std::ops::{Deref, DerefMut};
 
+use ref_cast::RefCast;
 use static_assertions::{assert_eq_align, assert_eq_size, assert_not_impl_any};
 
-use crate::{rtl, sys};
+use crate::{
+    convert::{list, mismatch, normal_elements},
+    expr::{Expr, ExprKind, Number, Symbol},
+    memory,
+    released::{self, Release},
+    rtl, shared_borrows, sys,
+};
 
 #[rustfmt::skip]
 use crate::sys::MNumericArray_Data_Type::{
@@ -46,12 +54,29 @@ use crate::sys::MNumericArray_Convert_Method::*;
 #[derive(ref_cast::RefCast)]
 pub struct NumericArray<T = ()>(sys::MNumericArray, PhantomData<T>);
 
+/// Mutable borrow of the elements of a `"Shared"` [`NumericArray`].
+///
+/// Use [`NumericArray::borrow_shared_mut()`] to construct a `SharedSliceMut`.
+pub struct SharedSliceMut<'a, T> {
+    slice: &'a mut [T],
+}
+
 /// Represents an allocated [`NumericArray`] whose elements have not yet been initialized.
 ///
 /// Use [`as_slice_mut()`][`UninitNumericArray::as_slice_mut()`] to initialize the
 /// elements of this [`UninitNumericArray`].
 pub struct UninitNumericArray<T: NumericArrayType>(sys::MNumericArray, PhantomData<T>);
 
+/// Summary of the data type and dimensions of a [`NumericArray`], which implements
+/// [`Display`][fmt::Display].
+///
+/// Use [`NumericArray::summary()`] to construct a `NumericArraySummary`.
+#[derive(Debug, Copy, Clone)]
+pub struct NumericArraySummary<'a> {
+    data_type: NumericArrayDataType,
+    dimensions: &'a [usize],
+}
+
 // Guard against accidental `derive(Copy)` annotations.
 assert_not_impl_any!(NumericArray: Copy);
 assert_not_impl_any!(UninitNumericArray<i64>: Copy);
@@ -387,6 +412,27 @@ impl<T: NumericArrayType> NumericArray<T> {
             .expect("failed to create NumericArray from slice")
     }
 
+    /// Construct a new one-dimensional [`NumericArray`] with no elements.
+    ///
+    /// This is useful for functions that return a "maybe empty" result.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the array could not be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let array = NumericArray::<f64>::empty();
+    ///
+    /// assert_eq!(array.dimensions(), &[0]);
+    /// assert!(array.as_slice().is_empty());
+    /// ```
+    pub fn empty() -> NumericArray<T> {
+        NumericArray::from_slice(&[])
+    }
+
     /// Fallible alternative to [`NumericArray::from_slice()`].
     pub fn try_from_slice(data: &[T]) -> Result<NumericArray<T>, sys::errcode_t> {
         let dim1 = data.len();
@@ -421,8 +467,12 @@ impl<T: NumericArrayType> NumericArray<T> {
     /// This function will return an error if:
     ///
     /// * `dimensions` is empty
-    /// * the product of `dimensions` is 0
-    /// * `data.len()` is not equal to the product of `dimensions`
+    /// * the array could not be allocated
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `data.len()` is not equal to the product of
+    /// `dimensions`.
     pub fn try_from_array(
         dimensions: &[usize],
         data: &[T],
@@ -433,19 +483,56 @@ impl<T: NumericArrayType> NumericArray<T> {
     }
 
     /// Access the elements stored in this [`NumericArray`] as a flat buffer.
+    ///
+    /// # Panics
+    ///
+    /// In builds with `debug_assertions` enabled, this function will panic if the data
+    /// of this array is currently mutably borrowed using
+    /// [`borrow_shared_mut()`][NumericArray::borrow_shared_mut].
+    #[track_caller]
     pub fn as_slice(&self) -> &[T] {
+        // The data pointer of an empty array is not guaranteed to be non-null.
+        if self.is_empty() {
+            return &[];
+        }
+
         let ptr: *mut c_void = self.data_ptr();
 
         debug_assert!(!ptr.is_null());
 
+        shared_borrows::assert_not_mut_borrowed(ptr, "borrow");
+
         // Assert that `ptr` is aligned to `T`.
-        debug_assert!(ptr as usize % std::mem::size_of::<T>() == 0);
+        debug_assert!((ptr as usize).is_multiple_of(std::mem::size_of::<T>()));
 
         let ptr = ptr as *const T;
 
         unsafe { std::slice::from_raw_parts(ptr, self.flattened_length()) }
     }
 
+    /// Get the only element of this array, if it contains exactly one element.
+    ///
+    /// This is true for rank-0 arrays, and for arrays whose dimensions are all 1, like
+    /// `{5}` or `{{5}}`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// assert_eq!(NumericArray::from_slice(&[5]).as_scalar(), Some(&5));
+    /// assert_eq!(NumericArray::from_array(&[1, 1], &[5]).as_scalar(), Some(&5));
+    ///
+    /// assert_eq!(NumericArray::from_slice(&[1, 2]).as_scalar(), None);
+    /// assert_eq!(NumericArray::<i64>::empty().as_scalar(), None);
+    /// ```
+    #[track_caller]
+    pub fn as_scalar(&self) -> Option<&T> {
+        match self.as_slice() {
+            [value] => Some(value),
+            _ => None,
+        }
+    }
+
     /// Access the elements stored in this [`NumericArray`] as a mutable flat buffer.
     ///
     /// If the [`share_count()`][NumericArray::share_count] of this array is >= 1, this
@@ -467,18 +554,253 @@ impl<T: NumericArrayType> NumericArray<T> {
     /// to determine whether mutation of a `NumericArray` is safe. Prefer to use
     /// [`UninitNumericArray`] to create and initialize a numeric array value instead of
     /// mutating an existing `NumericArray`.
+    ///
+    /// In builds with `debug_assertions` enabled, this function will panic if the data
+    /// of this array is currently mutably borrowed using
+    /// [`borrow_shared_mut()`][NumericArray::borrow_shared_mut].
+    #[track_caller]
     pub unsafe fn as_slice_mut_unchecked(&mut self) -> &mut [T] {
+        if self.is_empty() {
+            return &mut [];
+        }
+
         let ptr: *mut c_void = self.data_ptr();
 
         debug_assert!(!ptr.is_null());
 
+        shared_borrows::assert_not_mut_borrowed(ptr, "mutably borrow");
+
         // Assert that `ptr` is aligned to `T`.
-        debug_assert!(ptr as usize % std::mem::size_of::<T>() == 0);
+        debug_assert!((ptr as usize).is_multiple_of(std::mem::size_of::<T>()));
 
         let ptr = ptr as *mut T;
 
         std::slice::from_raw_parts_mut(ptr, self.flattened_length())
     }
+
+    /// Mutably borrow the elements of a `"Shared"` [`NumericArray`].
+    ///
+    /// Mutations made through the returned guard are visible to the Wolfram Language,
+    /// and to any other `NumericArray` value that refers to the same shared array.
+    ///
+    /// In builds with `debug_assertions` enabled, the borrow is tracked at runtime until
+    /// the guard is dropped. Any other attempt to access the same array data during
+    /// that time — e.g. from a different call to this library that was passed the same
+    /// shared array, or from another thread — will panic with a message describing
+    /// both accesses, instead of silently racing.
+    ///
+    /// # Panics
+    ///
+    /// In builds with `debug_assertions` enabled, this function will panic if the data
+    /// of this array is already mutably borrowed.
+    ///
+    /// # Safety
+    ///
+    /// The Wolfram Language must not access the array while the returned guard is
+    /// alive. This is always the case if the guard is dropped before the exported
+    /// function that received the array returns.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # mod scope {
+    /// use wolfram_library_link::{self as wll, NumericArray};
+    ///
+    /// wll::export![scale_in_place(_, _)];
+    ///
+    /// fn scale_in_place(mut array: NumericArray<f64>, factor: f64) {
+    ///     let mut data = unsafe { array.borrow_shared_mut() };
+    ///
+    ///     for elem in data.iter_mut() {
+    ///         *elem *= factor;
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    #[track_caller]
+    pub unsafe fn borrow_shared_mut(&mut self) -> SharedSliceMut<'_, T> {
+        // There is no data to borrow, and so no borrow to track.
+        if self.is_empty() {
+            return SharedSliceMut { slice: &mut [] };
+        }
+
+        let ptr: *mut c_void = self.data_ptr();
+
+        debug_assert!(!ptr.is_null());
+
+        // Assert that `ptr` is aligned to `T`.
+        debug_assert!((ptr as usize).is_multiple_of(std::mem::size_of::<T>()));
+
+        shared_borrows::acquire_mut(ptr);
+
+        SharedSliceMut {
+            slice: std::slice::from_raw_parts_mut(ptr as *mut T, self.flattened_length()),
+        }
+    }
+
+    /// Change the dimensions of this array, keeping its elements in the same
+    /// row-major order.
+    ///
+    /// This function will return an error if:
+    ///
+    /// * `dimensions` is empty
+    /// * the product of `dimensions` is not equal to the
+    ///   [`flattened_length()`][NumericArray::flattened_length] of this array
+    /// * the new array could not be allocated
+    ///
+    /// If `dimensions` are the current dimensions of this array, `self` is returned
+    /// without any copying. Otherwise, the elements are copied into a new array:
+    /// *LibraryLink* does not provide a way to change the dimensions of an existing
+    /// numeric array object.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let array = NumericArray::from_slice(&[1, 2, 3, 4, 5, 6]);
+    ///
+    /// let array = array.reshape(&[2, 3]).unwrap();
+    ///
+    /// assert_eq!(array.dimensions(), &[2, 3]);
+    /// assert_eq!(array.as_slice(), &[1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert!(array.reshape(&[4, 2]).is_err());
+    /// ```
+    pub fn reshape(self, dimensions: &[usize]) -> Result<NumericArray<T>, String> {
+        if self.dimensions() == dimensions {
+            return Ok(self);
+        }
+
+        if dimensions.is_empty() {
+            return Err("NumericArray dimensions cannot be empty".to_owned());
+        }
+
+        let length: usize = dimensions.iter().product();
+
+        if length != self.flattened_length() {
+            return Err(format!(
+                "cannot reshape NumericArray with dimensions {:?} ({} elements) to \
+                 dimensions {:?} ({} elements)",
+                self.dimensions(),
+                self.flattened_length(),
+                dimensions,
+                length
+            ));
+        }
+
+        let uninit =
+            UninitNumericArray::try_from_dimensions(dimensions).map_err(|err| {
+                format!("failed to allocate NumericArray: error code {}", err)
+            })?;
+
+        Ok(uninit.init_from_slice(self.as_slice()))
+    }
+
+    /// Convert this array into a one-dimensional array containing the same elements.
+    ///
+    /// If this array is already one-dimensional, `self` is returned without any copying.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the new array could not be allocated.
+    pub fn flatten(self) -> NumericArray<T> {
+        let length = self.flattened_length();
+
+        self.reshape(&[length])
+            .unwrap_or_else(|err| panic!("NumericArray::flatten(): {}", err))
+    }
+
+    /// Insert a new axis of length 1 into the dimensions of this array at position
+    /// `index`, increasing the [`rank()`][NumericArray::rank] by one.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `index` is greater than the rank of this array, or if
+    /// the new array could not be allocated.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let array = NumericArray::from_array(&[2, 3], &[1, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(array.clone().insert_axis(0).dimensions(), &[1, 2, 3]);
+    /// assert_eq!(array.insert_axis(2).dimensions(), &[2, 3, 1]);
+    /// ```
+    pub fn insert_axis(self, index: usize) -> NumericArray<T> {
+        let rank = self.rank();
+
+        assert!(
+            index <= rank,
+            "NumericArray::insert_axis(): index {} is greater than array rank {}",
+            index,
+            rank
+        );
+
+        let mut dimensions = self.dimensions().to_vec();
+        dimensions.insert(index, 1);
+
+        self.reshape(&dimensions)
+            .unwrap_or_else(|err| panic!("NumericArray::insert_axis(): {}", err))
+    }
+}
+
+impl NumericArray<f64> {
+    /// Returns `true` if `self` and `other` have the same dimensions, and each element
+    /// of `self` differs from the corresponding element of `other` by at most
+    /// `tolerance`.
+    ///
+    /// Use `==` to compare arrays exactly.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let a = NumericArray::from_slice(&[0.1f64 + 0.2, 1.0]);
+    /// let b = NumericArray::from_slice(&[0.3f64, 1.0]);
+    ///
+    /// assert!(a != b);
+    /// assert!(a.approx_eq(&b, 1e-12));
+    /// ```
+    pub fn approx_eq(&self, other: &NumericArray<f64>, tolerance: f64) -> bool {
+        approx_eq_by(self, other, |a, b| (a - b).abs() <= tolerance)
+    }
+}
+
+impl NumericArray<f32> {
+    /// Returns `true` if `self` and `other` have the same dimensions, and each element
+    /// of `self` differs from the corresponding element of `other` by at most
+    /// `tolerance`.
+    ///
+    /// See [`NumericArray::<f64>::approx_eq()`][NumericArray::approx_eq].
+    pub fn approx_eq(&self, other: &NumericArray<f32>, tolerance: f32) -> bool {
+        approx_eq_by(self, other, |a, b| (a - b).abs() <= tolerance)
+    }
+}
+
+impl NumericArray<sys::mcomplex> {
+    /// Returns `true` if `self` and `other` have the same dimensions, and the absolute
+    /// value of the difference between each element of `self` and the corresponding
+    /// element of `other` is at most `tolerance`.
+    ///
+    /// See [`NumericArray::<f64>::approx_eq()`][NumericArray::approx_eq].
+    pub fn approx_eq(&self, other: &NumericArray<sys::mcomplex>, tolerance: f64) -> bool {
+        approx_eq_by(self, other, |a, b| {
+            (a.re() - b.re()).hypot(a.im() - b.im()) <= tolerance
+        })
+    }
+}
+
+fn approx_eq_by<T: NumericArrayType>(
+    a: &NumericArray<T>,
+    b: &NumericArray<T>,
+    elem_eq: impl Fn(&T, &T) -> bool,
+) -> bool {
+    a.dimensions() == b.dimensions()
+        && a.as_slice()
+            .iter()
+            .zip(b.as_slice())
+            .all(|(a, b)| elem_eq(a, b))
 }
 
 impl<T> NumericArray<T> {
@@ -505,6 +827,25 @@ impl<T> NumericArray<T> {
 
     /// Construct a `NumericArray<T>` from a raw [`MNumericArray`][sys::MNumericArray].
     ///
+    /// This is the inverse of [`NumericArray::into_raw()`].
+    ///
+    /// # Ownership
+    ///
+    /// The returned `NumericArray` takes ownership of one reference to `array`. When the
+    /// `NumericArray` is dropped, that reference is released according to the
+    /// [share count][NumericArray::share_count] of `array`:
+    ///
+    /// * If the share count is 0, the array is owned solely by this library, and is freed
+    ///   using [`MNumericArray_free()`][rtl::MNumericArray_free]. This is the case for
+    ///   arrays created by this library, and for arrays passed as `"Manual"` arguments.
+    /// * If the share count is greater than 0, the array is shared with the Kernel, and
+    ///   is released using [`MNumericArray_disown()`][rtl::MNumericArray_disown]. This
+    ///   is the case for arrays passed as `"Shared"` arguments.
+    ///
+    /// Arrays passed as `Automatic` or `"Constant"` arguments are owned by the Kernel,
+    /// and must not be passed to this function. Use
+    /// [`NumericArray::ref_cast()`][ref_cast::RefCast::ref_cast] to borrow them instead.
+    ///
     /// # Safety
     ///
     /// The following conditions must be met for safe usage of this function:
@@ -514,19 +855,66 @@ impl<T> NumericArray<T> {
     ///   - be `()`, representing an array with dynamic element type, or
     ///   - `T` must satisfy [`NumericArrayType`], and the element type of `array` must
     ///     be the same as `T`.
-    // TODO: Add something about the reference count in the above list?
+    /// * the caller must own the reference to `array` being transferred, and must not
+    ///   free, disown, or otherwise use that reference after calling this function
+    ///
+    /// In builds with `debug_assertions` enabled, dropping two `NumericArray`s that own
+    /// the same reference, or using an array after it has been released or returned to
+    /// the Kernel, panics with a description of how the array was released, instead of
+    /// silently causing undefined behavior.
+    ///
+    /// # Example
+    ///
+    /// Take back ownership of an array that was temporarily handed to a C library:
+    ///
+    /// ```no_run
+    /// use wolfram_library_link::{sys, NumericArray};
+    ///
+    /// extern "C" {
+    ///     /// Takes ownership of `array`, and returns it when processing is complete.
+    ///     fn process_array(array: sys::MNumericArray) -> sys::MNumericArray;
+    /// }
+    ///
+    /// let array: NumericArray<f64> = NumericArray::from_slice(&[1.0, 2.0, 3.0]);
+    ///
+    /// let array: NumericArray<f64> = unsafe {
+    ///     let raw = process_array(array.into_raw());
+    ///     NumericArray::from_raw(raw)
+    /// };
+    /// ```
     pub unsafe fn from_raw(array: sys::MNumericArray) -> NumericArray<T> {
+        released::acquire(array);
+
         NumericArray(array, PhantomData)
     }
 
     /// Convert this `NumericArray` into a raw [`MNumericArray`][sys::MNumericArray]
     /// object.
-    pub unsafe fn into_raw(self) -> sys::MNumericArray {
+    ///
+    /// This is the inverse of [`NumericArray::from_raw()`]. The element data is not
+    /// copied.
+    ///
+    /// # Ownership
+    ///
+    /// The reference owned by this `NumericArray` is transferred to the caller, which
+    /// becomes responsible for releasing it, either by:
+    ///
+    /// * returning it to the Kernel, e.g. as the result of a *LibraryLink* function,
+    /// * converting it back into a `NumericArray` using [`NumericArray::from_raw()`], or
+    /// * calling [`MNumericArray_disown()`][rtl::MNumericArray_disown] if the array
+    ///   [share count][NumericArray::share_count] is greater than 0, or
+    ///   [`MNumericArray_free()`][rtl::MNumericArray_free] otherwise.
+    ///
+    /// The returned array is no longer counted by [`memory_usage()`][crate::memory_usage].
+    pub fn into_raw(self) -> sys::MNumericArray {
         let NumericArray(raw, PhantomData) = self;
 
         // Don't run Drop on `self`; ownership of this value is being given to the caller.
         std::mem::forget(self);
 
+        memory::untrack(raw);
+        crate::debug::release_manual(raw);
+
         raw
     }
 
@@ -534,6 +922,8 @@ impl<T> NumericArray<T> {
     pub fn data_ptr(&self) -> *mut c_void {
         let NumericArray(numeric_array, _) = *self;
 
+        released::assert_live(numeric_array, "access the data of");
+
         unsafe { data_ptr(numeric_array) }
     }
 
@@ -549,6 +939,8 @@ impl<T> NumericArray<T> {
     pub fn data_type_raw(&self) -> sys::numericarray_data_t {
         let NumericArray(numeric_array, _) = *self;
 
+        released::assert_live(numeric_array, "get the type of");
+
         unsafe { rtl::MNumericArray_getType(numeric_array) }
     }
 
@@ -570,10 +962,20 @@ impl<T> NumericArray<T> {
         len
     }
 
+    /// Returns `true` if this array contains no elements.
+    ///
+    /// This is true if any of the [`dimensions()`][NumericArray::dimensions] of this
+    /// array is 0.
+    pub fn is_empty(&self) -> bool {
+        self.flattened_length() == 0
+    }
+
     /// *LibraryLink C API Documentation:* [`MNumericArray_getRank`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MNumericArray_getRank.html)
     pub fn rank(&self) -> usize {
         let NumericArray(numeric_array, _) = *self;
 
+        released::assert_live(numeric_array, "get the rank of");
+
         let rank: sys::mint = unsafe { rtl::MNumericArray_getRank(numeric_array) };
 
         let rank = usize::try_from(rank).expect("NumericArray rank overflows usize");
@@ -599,7 +1001,10 @@ impl<T> NumericArray<T> {
 
         let rank = self.rank();
 
-        debug_assert!(rank != 0);
+        // A rank-0 array has no dimensions, and its dimensions pointer may be null.
+        if rank == 0 {
+            return &[];
+        }
 
         let dims: *const crate::sys::mint =
             unsafe { rtl::MNumericArray_getDimensions(numeric_array) };
@@ -624,11 +1029,42 @@ impl<T> NumericArray<T> {
     pub fn share_count(&self) -> usize {
         let NumericArray(raw, PhantomData) = *self;
 
+        released::assert_live(raw, "get the share count of");
+
         let count: sys::mint = unsafe { rtl::MNumericArray_shareCount(raw) };
 
         usize::try_from(count).expect("NumericArray share count mint overflows usize")
     }
 
+    /// Get a value that formats a one-line summary of this array, similar to the summary
+    /// box displayed for a [`NumericArray`][ref/NumericArray]<sub>WL</sub> by the Wolfram
+    /// Language, using [`Display`][fmt::Display].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let array = NumericArray::from_array(&[2, 3], &[1i64, 2, 3, 4, 5, 6]);
+    ///
+    /// assert_eq!(
+    ///     array.summary().to_string(),
+    ///     "NumericArray[Type: Integer64, Dimensions: {2, 3}]"
+    /// );
+    /// ```
+    ///
+    /// [ref/NumericArray]: https://reference.wolfram.com/language/ref/NumericArray.html
+    pub fn summary(&self) -> NumericArraySummary<'_> {
+        NumericArraySummary {
+            data_type: self.data_type(),
+            dimensions: self.dimensions(),
+        }
+    }
+
+    /// Borrow this array as a `NumericArray` of unknown element type.
+    pub(crate) fn as_generic(&self) -> &NumericArray {
+        NumericArray::ref_cast(&self.0)
+    }
+
     /// Returns true if `self` and `other` are pointers to the name underlying
     /// numeric array object.
     pub fn ptr_eq<T2>(&self, other: &NumericArray<T2>) -> bool {
@@ -646,25 +1082,62 @@ impl<T> NumericArray<T> {
         method: NumericArrayConvertMethod,
         tolerance: sys::mreal,
     ) -> Result<NumericArray<T2>, sys::errcode_t> {
-        let NumericArray(self_raw, PhantomData) = *self;
+        let new_raw = unsafe { convert_type(self.0, T2::TYPE, method, tolerance)? };
 
-        let mut new_raw: sys::MNumericArray = std::ptr::null_mut();
+        Ok(unsafe { NumericArray::<T2>::from_raw(new_raw) })
+    }
 
-        let err_code: sys::errcode_t = unsafe {
-            rtl::MNumericArray_convertType(
-                &mut new_raw,
-                self_raw,
-                T2::TYPE.as_raw(),
-                method.as_raw(),
-                tolerance,
-            )
-        };
+    /// Convert this array to a new array with element type `data_type`.
+    ///
+    /// This is the dynamically typed equivalent of [`NumericArray::convert_to()`],
+    /// useful when the target element type is only known at run time.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::{NumericArray, NumericArrayDataType, NumericArrayConvertMethod};
+    /// let array: NumericArray<i64> = NumericArray::from_slice(&[1, 2, 3]);
+    ///
+    /// let array: NumericArray = array
+    ///     .convert_to_type(NumericArrayDataType::Real64, NumericArrayConvertMethod::Check, 0.0)
+    ///     .unwrap();
+    ///
+    /// assert_eq!(array.data_type(), NumericArrayDataType::Real64);
+    /// ```
+    ///
+    /// *LibraryLink C API Documentation:* [`MNumericArray_convertType`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MNumericArray_convertType.html)
+    pub fn convert_to_type(
+        &self,
+        data_type: NumericArrayDataType,
+        method: NumericArrayConvertMethod,
+        tolerance: sys::mreal,
+    ) -> Result<NumericArray, sys::errcode_t> {
+        let new_raw = unsafe { convert_type(self.0, data_type, method, tolerance)? };
+
+        Ok(unsafe { NumericArray::<()>::from_raw(new_raw) })
+    }
 
-        if err_code != 0 || new_raw.is_null() {
+    /// Create a copy of this array, returning an error if the copy could not be
+    /// allocated.
+    ///
+    /// The [`Clone`] implementation for [`NumericArray`] panics if this function
+    /// returns an error.
+    ///
+    /// *LibraryLink C API Documentation:* [`MNumericArray_clone`](https://reference.wolfram.com/language/LibraryLink/ref/callback/MNumericArray_clone.html)
+    pub fn try_clone(&self) -> Result<NumericArray<T>, sys::errcode_t> {
+        let NumericArray(raw, PhantomData) = *self;
+
+        let mut new: sys::MNumericArray = std::ptr::null_mut();
+
+        let err_code: sys::errcode_t = unsafe { rtl::MNumericArray_clone(raw, &mut new) };
+
+        if err_code != 0 || new.is_null() {
             return Err(err_code);
         }
 
-        Ok(unsafe { NumericArray::<T2>::from_raw(new_raw) })
+        unsafe { track(new) };
+
+        Ok(unsafe { NumericArray::<T>::from_raw(new) })
     }
 }
 
@@ -680,6 +1153,47 @@ unsafe fn flattened_length(numeric_array: sys::MNumericArray) -> usize {
     len
 }
 
+unsafe fn convert_type(
+    numeric_array: sys::MNumericArray,
+    data_type: NumericArrayDataType,
+    method: NumericArrayConvertMethod,
+    tolerance: sys::mreal,
+) -> Result<sys::MNumericArray, sys::errcode_t> {
+    let mut new_raw: sys::MNumericArray = std::ptr::null_mut();
+
+    let err_code: sys::errcode_t = rtl::MNumericArray_convertType(
+        &mut new_raw,
+        numeric_array,
+        data_type.as_raw(),
+        method.as_raw(),
+        tolerance,
+    );
+
+    if err_code != 0 || new_raw.is_null() {
+        return Err(err_code);
+    }
+
+    track(new_raw);
+
+    Ok(new_raw)
+}
+
+/// Record that `numeric_array` was allocated by this library.
+///
+/// See [`memory_usage()`][crate::memory_usage].
+unsafe fn track(numeric_array: sys::MNumericArray) {
+    let data_type: sys::numericarray_data_t = rtl::MNumericArray_getType(numeric_array);
+
+    let element_size = NumericArrayDataType::try_from(data_type)
+        .map(|data_type| data_type.element_size())
+        .unwrap_or(0);
+
+    let bytes = flattened_length(numeric_array) * element_size;
+
+    memory::track(numeric_array, memory::Container::NumericArray { bytes });
+    released::acquire(numeric_array);
+}
+
 //======================================
 // UninitNumericArray
 //======================================
@@ -698,18 +1212,24 @@ impl<T: NumericArrayType> UninitNumericArray<T> {
 
     /// Try to construct a new uninitialized NumericArray with the specified dimensions.
     ///
+    /// Dimensions of length 0 are allowed, and construct an empty array, whose
+    /// [`as_slice_mut()`][UninitNumericArray::as_slice_mut] slice is empty.
+    ///
     /// This function will return an error if:
     ///
-    /// * `dimensions` is empty.
-    /// * the product of `dimensions` is equal to 0.
+    /// * `dimensions` is empty, returning
+    ///   [`LIBRARY_RANK_ERROR`][sys::LIBRARY_RANK_ERROR]. *LibraryLink* cannot
+    ///   construct rank-0 arrays; use a one-dimensional array of length 1 to store a
+    ///   single value.
     /// * the underlying allocation function returns `NULL`.
     pub fn try_from_dimensions(
         dimensions: &[usize],
     ) -> Result<UninitNumericArray<T>, sys::errcode_t> {
-        assert!(!dimensions.is_empty());
+        if dimensions.is_empty() {
+            return Err(sys::LIBRARY_RANK_ERROR as sys::errcode_t);
+        }
 
         let rank = dimensions.len();
-        debug_assert!(rank > 0);
 
         unsafe {
             let mut numeric_array: sys::MNumericArray = std::ptr::null_mut();
@@ -725,6 +1245,8 @@ impl<T: NumericArrayType> UninitNumericArray<T> {
                 return Err(err_code);
             }
 
+            track(numeric_array);
+
             Ok(UninitNumericArray(numeric_array, PhantomData))
         }
     }
@@ -779,6 +1301,10 @@ impl<T: NumericArrayType> UninitNumericArray<T> {
         unsafe {
             let len = flattened_length(numeric_array);
 
+            if len == 0 {
+                return &mut [];
+            }
+
             let ptr: *mut c_void = data_ptr(numeric_array);
             let ptr = ptr as *mut MaybeUninit<T>;
 
@@ -857,6 +1383,24 @@ impl NumericArrayDataType {
     }
 }
 
+impl NumericArrayDataType {
+    /// The size in bytes of a single element of this type.
+    pub(crate) fn element_size(&self) -> usize {
+        match self {
+            NumericArrayDataType::Bit8 | NumericArrayDataType::UBit8 => 1,
+            NumericArrayDataType::Bit16 | NumericArrayDataType::UBit16 => 2,
+            NumericArrayDataType::Bit32
+            | NumericArrayDataType::UBit32
+            | NumericArrayDataType::Real32 => 4,
+            NumericArrayDataType::Bit64
+            | NumericArrayDataType::UBit64
+            | NumericArrayDataType::Real64
+            | NumericArrayDataType::ComplexReal32 => 8,
+            NumericArrayDataType::ComplexReal64 => 16,
+        }
+    }
+}
+
 impl NumericArrayConvertMethod {
     #[allow(missing_docs)]
     pub fn as_raw(self) -> sys::numericarray_convert_method_t {
@@ -870,43 +1414,188 @@ impl NumericArrayConvertMethod {
 
 impl<T> Clone for NumericArray<T> {
     fn clone(&self) -> NumericArray<T> {
-        let NumericArray(raw, PhantomData) = *self;
+        match self.try_clone() {
+            Ok(clone) => clone,
+            Err(err_code) => {
+                panic!("NumericArray clone failed with error code: {}", err_code)
+            },
+        }
+    }
+}
 
-        unsafe {
-            let mut new: sys::MNumericArray = std::ptr::null_mut();
-            let err_code: sys::errcode_t = rtl::MNumericArray_clone(raw, &mut new);
+impl<'a, T> Deref for SharedSliceMut<'a, T> {
+    type Target = [T];
 
-            if err_code != 0 || new.is_null() {
-                panic!("NumericArray clone failed with error code: {}", err_code);
-            }
+    fn deref(&self) -> &[T] {
+        self.slice
+    }
+}
 
-            NumericArray::<T>::from_raw(new)
+impl<'a, T> DerefMut for SharedSliceMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        self.slice
+    }
+}
+
+impl<'a, T> Drop for SharedSliceMut<'a, T> {
+    fn drop(&mut self) {
+        if !self.slice.is_empty() {
+            shared_borrows::release_mut(self.slice.as_ptr());
         }
     }
 }
 
 impl<T> Drop for NumericArray<T> {
     fn drop(&mut self) {
-        if self.share_count() > 0 {
+        let NumericArray(raw, PhantomData) = *self;
+
+        memory::untrack(raw);
+        crate::debug::release_manual(raw);
+
+        // Panics if `raw` has already been freed or disowned, instead of releasing it a
+        // second time.
+        released::release(raw, "NumericArray", Release::Dropped);
+
+        let share_count: sys::mint = unsafe { rtl::MNumericArray_shareCount(raw) };
+
+        if share_count > 0 {
             // This is a "Shared" numeric array, so we should decrement the reference
             // count.
-            let NumericArray(raw, PhantomData) = *self;
             unsafe { rtl::MNumericArray_disown(raw) }
         } else {
             // This is a "Manual" numeric array (or one created within Rust), so we should
             // free its memory directly.
-            let NumericArray(raw, PhantomData) = *self;
             unsafe { rtl::MNumericArray_free(raw) }
         }
     }
 }
 
+/// Arrays are equal if they have the same data type, the same dimensions, and equal
+/// elements.
+///
+/// Floating-point elements are compared using `==`, so an array containing a NaN
+/// element is not equal to itself. Use `approx_eq()` to compare floating-point arrays
+/// with a tolerance.
+impl<T1, T2> PartialEq<NumericArray<T2>> for NumericArray<T1> {
+    fn eq(&self, other: &NumericArray<T2>) -> bool {
+        /// Compare the elements of `a` and `b`, which must both have elements of type `E`.
+        unsafe fn elements_eq<E: PartialEq, T1, T2>(
+            a: &NumericArray<T1>,
+            b: &NumericArray<T2>,
+        ) -> bool {
+            let len = a.flattened_length();
+
+            let a = std::slice::from_raw_parts(a.data_ptr() as *const E, len);
+            let b = std::slice::from_raw_parts(b.data_ptr() as *const E, len);
+
+            a == b
+        }
+
+        if self.data_type() != other.data_type()
+            || self.dimensions() != other.dimensions()
+        {
+            return false;
+        }
+
+        unsafe {
+            use NumericArrayDataType::*;
+
+            match self.data_type() {
+                Bit8 => elements_eq::<i8, _, _>(self, other),
+                Bit16 => elements_eq::<i16, _, _>(self, other),
+                Bit32 => elements_eq::<i32, _, _>(self, other),
+                Bit64 => elements_eq::<i64, _, _>(self, other),
+
+                UBit8 => elements_eq::<u8, _, _>(self, other),
+                UBit16 => elements_eq::<u16, _, _>(self, other),
+                UBit32 => elements_eq::<u32, _, _>(self, other),
+                UBit64 => elements_eq::<u64, _, _>(self, other),
+
+                Real32 => elements_eq::<f32, _, _>(self, other),
+                Real64 => elements_eq::<f64, _, _>(self, other),
+
+                ComplexReal32 => elements_eq::<[f32; 2], _, _>(self, other),
+                ComplexReal64 => elements_eq::<[f64; 2], _, _>(self, other),
+            }
+        }
+    }
+}
+
+/// The maximum number of elements shown by the [`Debug`][fmt::Debug] implementation for
+/// [`NumericArray`].
+const DEBUG_PREVIEW_LEN: usize = 10;
+
 impl<T> fmt::Debug for NumericArray<T> {
+    /// Formats the data type, dimensions, and the first few elements of this array.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        f.debug_struct("NumericArray")
-            .field("raw", &self.0)
+        let mut debug = f.debug_struct("NumericArray");
+
+        debug
             .field("data_type", &self.data_type())
-            .finish()
+            .field("dimensions", &self.dimensions());
+
+        // NumericArray::kind() does not support ComplexReal32 arrays.
+        if self.data_type() != NumericArrayDataType::ComplexReal32 {
+            debug.field("elements", &ElementsPreview(self.as_generic()));
+        }
+
+        debug.finish()
+    }
+}
+
+/// Formats at most [`DEBUG_PREVIEW_LEN`] elements of a [`NumericArray`], followed by the
+/// number of elements omitted.
+struct ElementsPreview<'a>(&'a NumericArray);
+
+impl<'a> fmt::Debug for ElementsPreview<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn preview<E: fmt::Debug>(f: &mut fmt::Formatter, elements: &[E]) -> fmt::Result {
+            let mut list = f.debug_list();
+
+            list.entries(elements.iter().take(DEBUG_PREVIEW_LEN));
+
+            if elements.len() > DEBUG_PREVIEW_LEN {
+                let omitted = elements.len() - DEBUG_PREVIEW_LEN;
+                list.entry(&format_args!("... {} more", omitted));
+            }
+
+            list.finish()
+        }
+
+        match self.0.kind() {
+            NumericArrayKind::Bit8(array) => preview(f, array.as_slice()),
+            NumericArrayKind::Bit16(array) => preview(f, array.as_slice()),
+            NumericArrayKind::Bit32(array) => preview(f, array.as_slice()),
+            NumericArrayKind::Bit64(array) => preview(f, array.as_slice()),
+            NumericArrayKind::UBit8(array) => preview(f, array.as_slice()),
+            NumericArrayKind::UBit16(array) => preview(f, array.as_slice()),
+            NumericArrayKind::UBit32(array) => preview(f, array.as_slice()),
+            NumericArrayKind::UBit64(array) => preview(f, array.as_slice()),
+            NumericArrayKind::Real32(array) => preview(f, array.as_slice()),
+            NumericArrayKind::Real64(array) => preview(f, array.as_slice()),
+            NumericArrayKind::ComplexReal64(array) => preview(f, array.as_slice()),
+        }
+    }
+}
+
+impl<'a> fmt::Display for NumericArraySummary<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let NumericArraySummary {
+            data_type,
+            dimensions,
+        } = *self;
+
+        write!(f, "NumericArray[Type: {}, Dimensions: {{", data_type.name())?;
+
+        for (index, dim) in dimensions.iter().enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+
+            write!(f, "{}", dim)?;
+        }
+
+        f.write_str("}]")
     }
 }
 
@@ -944,3 +1633,267 @@ impl TryFrom<u32> for NumericArrayDataType {
         Ok(ok)
     }
 }
+
+//======================================
+// Expression Conversions
+//======================================
+
+/// Convert `array` to a
+/// [`NumericArray`][ref/NumericArray]<code>[<i>data</i>, &quot;<i>type</i>&quot;]</code>
+/// expression, where *data* is a nested list of the elements of `array`.
+///
+/// An error is returned if `array` has type `ComplexReal32`, or contains an element that
+/// cannot be represented as an [`Expr`], like an `UnsignedInteger64` value larger than
+/// [`i64::MAX`] or a NaN real value.
+///
+/// [ref/NumericArray]: https://reference.wolfram.com/language/ref/NumericArray.html
+pub(crate) fn numeric_array_to_expr(array: &NumericArray) -> Result<Expr, String> {
+    fn elements<T: NumericArrayType + Copy>(
+        array: &NumericArray<T>,
+        to_expr: impl Fn(T) -> Option<Expr>,
+        type_name: &str,
+    ) -> Result<Vec<Expr>, String> {
+        array
+            .as_slice()
+            .iter()
+            .map(|&elem| {
+                to_expr(elem).ok_or_else(|| {
+                    format!(
+                        "{} NumericArray element cannot be converted to Expr",
+                        type_name
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn integer<T: TryInto<i64>>(elem: T) -> Option<Expr> {
+        elem.try_into().ok().map(Expr::from)
+    }
+
+    fn real(elem: f64) -> Option<Expr> {
+        match elem.is_nan() {
+            true => None,
+            false => Some(Expr::number(Number::real(elem))),
+        }
+    }
+
+    let data_type = array.data_type();
+    let type_name = data_type.name();
+
+    // NumericArray::kind() does not support ComplexReal32 arrays.
+    if data_type == NumericArrayDataType::ComplexReal32 {
+        return Err(format!(
+            "{} NumericArray cannot be converted to Expr",
+            type_name
+        ));
+    }
+
+    let elements = match array.kind() {
+        NumericArrayKind::Bit8(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::Bit16(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::Bit32(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::Bit64(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::UBit8(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::UBit16(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::UBit32(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::UBit64(array) => elements(array, integer, type_name)?,
+        NumericArrayKind::Real32(array) => {
+            elements(array, |elem| real(f64::from(elem)), type_name)?
+        },
+        NumericArrayKind::Real64(array) => elements(array, real, type_name)?,
+        NumericArrayKind::ComplexReal64(array) => elements(
+            array,
+            |elem: sys::mcomplex| {
+                let [re, im] = elem.ri;
+                Some(Expr::normal(Symbol::new("System`Complex"), vec![
+                    real(re)?,
+                    real(im)?,
+                ]))
+            },
+            type_name,
+        )?,
+    };
+
+    let data = nested_list(array.dimensions(), &mut elements.into_iter());
+
+    Ok(Expr::normal(Symbol::new("System`NumericArray"), vec![
+        data,
+        Expr::string(type_name),
+    ]))
+}
+
+/// Construct a `NumericArray` from a
+/// [`NumericArray`][ref/NumericArray]<code>[<i>data</i>, &quot;<i>type</i>&quot;]</code>
+/// expression, where *data* is a rectangular nested list of numbers.
+///
+/// An error is returned if *data* is not rectangular, if any element of *data* is not
+/// representable as *type*, or if *data* is empty.
+///
+/// [ref/NumericArray]: https://reference.wolfram.com/language/ref/NumericArray.html
+pub(crate) fn numeric_array_from_expr(expr: &Expr) -> Result<NumericArray, String> {
+    fn build<T: NumericArrayType>(
+        dimensions: &[usize],
+        leaves: &[&Expr],
+        from_expr: impl Fn(&Expr) -> Option<T>,
+        type_name: &str,
+    ) -> Result<NumericArray, String> {
+        let data = leaves
+            .iter()
+            .map(|leaf| {
+                from_expr(leaf)
+                    .ok_or_else(|| mismatch(&format!("{} value", type_name), leaf))
+            })
+            .collect::<Result<Vec<T>, String>>()?;
+
+        let array = NumericArray::try_from_array(dimensions, &data).map_err(|code| {
+            format!(
+                "failed to create NumericArray with dimensions {:?} (error code: {})",
+                dimensions, code
+            )
+        })?;
+
+        Ok(array.into_generic())
+    }
+
+    fn integer<T: TryFrom<i64>>(expr: &Expr) -> Option<T> {
+        match expr.kind() {
+            ExprKind::Integer(int) => T::try_from(*int).ok(),
+            _ => None,
+        }
+    }
+
+    fn real(expr: &Expr) -> Option<f64> {
+        match expr.kind() {
+            ExprKind::Integer(int) => Some(*int as f64),
+            ExprKind::Real(real) => Some(**real),
+            _ => None,
+        }
+    }
+
+    fn complex(expr: &Expr) -> Option<sys::mcomplex> {
+        if let Some(re) = real(expr) {
+            return Some(sys::mcomplex::new(re, 0.0));
+        }
+
+        match normal_elements(expr, "System`Complex") {
+            Some([re, im]) => Some(sys::mcomplex::new(real(re)?, real(im)?)),
+            _ => None,
+        }
+    }
+
+    let (data, type_name) = match normal_elements(expr, "System`NumericArray") {
+        Some([data, type_name]) => match type_name.kind() {
+            ExprKind::String(type_name) => (data, type_name.as_str()),
+            _ => return Err(mismatch("NumericArray type name String", type_name)),
+        },
+        _ => return Err(mismatch("NumericArray[data, \"type\"]", expr)),
+    };
+
+    let mut dimensions = Vec::new();
+    let mut current = data;
+
+    while let Some(elements) = normal_elements(current, "System`List") {
+        dimensions.push(elements.len());
+
+        match elements.first() {
+            Some(first) => current = first,
+            None => break,
+        }
+    }
+
+    if dimensions.is_empty() {
+        return Err(mismatch("NumericArray data List", data));
+    }
+
+    let mut leaves = Vec::new();
+    flatten(data, &dimensions, &mut leaves)?;
+
+    use NumericArrayDataType::*;
+
+    let data_type = [
+        Bit8,
+        Bit16,
+        Bit32,
+        Bit64,
+        UBit8,
+        UBit16,
+        UBit32,
+        UBit64,
+        Real32,
+        Real64,
+        ComplexReal32,
+        ComplexReal64,
+    ]
+    .into_iter()
+    .find(|data_type| data_type.name() == type_name)
+    .ok_or_else(|| format!("unknown NumericArray type: {:?}", type_name))?;
+
+    let dims = &dimensions;
+
+    match data_type {
+        Bit8 => build::<i8>(dims, &leaves, integer, type_name),
+        Bit16 => build::<i16>(dims, &leaves, integer, type_name),
+        Bit32 => build::<i32>(dims, &leaves, integer, type_name),
+        Bit64 => build::<i64>(dims, &leaves, integer, type_name),
+        UBit8 => build::<u8>(dims, &leaves, integer, type_name),
+        UBit16 => build::<u16>(dims, &leaves, integer, type_name),
+        UBit32 => build::<u32>(dims, &leaves, integer, type_name),
+        UBit64 => build::<u64>(dims, &leaves, integer, type_name),
+        Real32 => build(
+            dims,
+            &leaves,
+            |leaf| real(leaf).map(|r| r as f32),
+            type_name,
+        ),
+        Real64 => build(dims, &leaves, real, type_name),
+        ComplexReal32 => Err(format!(
+            "{} NumericArray cannot be converted from Expr",
+            type_name
+        )),
+        ComplexReal64 => build(dims, &leaves, complex, type_name),
+    }
+}
+
+/// Group the elements produced by `elements` into a nested list with the specified
+/// dimensions.
+fn nested_list(dimensions: &[usize], elements: &mut std::vec::IntoIter<Expr>) -> Expr {
+    let elements: Vec<Expr> = match dimensions {
+        [] => return list(Vec::new()),
+        [len] => elements.take(*len).collect(),
+        [len, rest @ ..] => (0..*len).map(|_| nested_list(rest, elements)).collect(),
+    };
+
+    list(elements)
+}
+
+/// Collect the leaf elements of the nested list `expr` in row-major order, checking that
+/// `expr` has the specified dimensions.
+fn flatten<'e>(
+    expr: &'e Expr,
+    dimensions: &[usize],
+    leaves: &mut Vec<&'e Expr>,
+) -> Result<(), String> {
+    let (len, rest) = match dimensions.split_first() {
+        Some(split) => split,
+        None => {
+            if normal_elements(expr, "System`List").is_some() {
+                return Err("NumericArray data is not a rectangular array".to_owned());
+            }
+
+            leaves.push(expr);
+            return Ok(());
+        },
+    };
+
+    match normal_elements(expr, "System`List") {
+        Some(elements) if elements.len() == *len => {
+            for elem in elements {
+                flatten(elem, rest, leaves)?;
+            }
+
+            Ok(())
+        },
+        _ => Err("NumericArray data is not a rectangular array".to_owned()),
+    }
+}
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::ops::Range;
+
+use crate::{NumericArray, NumericArrayType};
+
+/// Borrowed view of a rectangular block of the elements of a [`NumericArray`].
+///
+/// A view refers directly to the flat buffer of the array it was created from, and
+/// stores its own dimensions and strides, so creating a view of a sub-block does not
+/// copy any elements. This is useful for windowed algorithms like convolutions and
+/// tiling.
+///
+/// Use [`NumericArray::as_view()`], [`NumericArray::slice()`], or
+/// [`NumericArray::view()`] to construct a `NumericArrayView`.
+///
+/// # Example
+///
+/// Sum each 2x2 tile of a 4x4 matrix:
+///
+/// ```no_run
+/// # use wolfram_library_link::NumericArray;
+/// let data: Vec<i64> = (1..=16).collect();
+/// let matrix = NumericArray::from_array(&[4, 4], &data);
+///
+/// let mut sums = Vec::new();
+///
+/// for row in (0..4).step_by(2) {
+///     for col in (0..4).step_by(2) {
+///         let tile = matrix.view(&[row..row + 2, col..col + 2]);
+///
+///         sums.push(tile.iter().sum::<i64>());
+///     }
+/// }
+///
+/// assert_eq!(sums, vec![14, 22, 46, 54]);
+/// ```
+pub struct NumericArrayView<'a, T> {
+    /// The complete flat buffer of the parent array.
+    data: &'a [T],
+    /// Index into `data` of the first element of this view.
+    offset: usize,
+    dimensions: Vec<usize>,
+    /// Number of elements of `data` between consecutive indices of each axis.
+    strides: Vec<usize>,
+}
+
+impl<T: NumericArrayType> NumericArray<T> {
+    /// Borrow the elements of this array as a [`NumericArrayView`] with the same
+    /// dimensions as this array.
+    pub fn as_view(&self) -> NumericArrayView<'_, T> {
+        NumericArrayView::new(self.as_slice(), self.dimensions())
+    }
+
+    /// Borrow the elements of this array whose index along `axis` is in `range`.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `axis` is greater than or equal to the
+    /// [`rank()`][NumericArray::rank] of this array, or if `range` is out of bounds for
+    /// the dimension of `axis`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::NumericArray;
+    /// let matrix = NumericArray::from_array(&[3, 3], &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    ///
+    /// // The last two columns.
+    /// let columns = matrix.slice(1, 1..3);
+    ///
+    /// assert_eq!(columns.dimensions(), &[3, 2]);
+    /// assert_eq!(columns.to_vec(), vec![2, 3, 5, 6, 8, 9]);
+    /// ```
+    #[track_caller]
+    pub fn slice(&self, axis: usize, range: Range<usize>) -> NumericArrayView<'_, T> {
+        self.as_view().slice(axis, range)
+    }
+
+    /// Borrow the rectangular block of elements of this array whose indices along each
+    /// axis are in the corresponding range in `ranges`.
+    ///
+    /// `ranges` may have fewer elements than the rank of this array, in which case the
+    /// view contains every index along the remaining axes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if `ranges` has more elements than the
+    /// [`rank()`][NumericArray::rank] of this array, or if any range is out of bounds for
+    /// the dimension of the corresponding axis.
+    #[track_caller]
+    pub fn view(&self, ranges: &[Range<usize>]) -> NumericArrayView<'_, T> {
+        self.as_view().view(ranges)
+    }
+}
+
+impl<'a, T> NumericArrayView<'a, T> {
+    /// Construct a view of the contiguous row-major buffer `data` with dimensions
+    /// `dimensions`.
+    fn new(data: &'a [T], dimensions: &[usize]) -> Self {
+        debug_assert_eq!(data.len(), dimensions.iter().product::<usize>());
+
+        let mut strides = vec![1; dimensions.len()];
+
+        for axis in (0..dimensions.len().saturating_sub(1)).rev() {
+            strides[axis] = strides[axis + 1] * dimensions[axis + 1];
+        }
+
+        NumericArrayView {
+            data,
+            offset: 0,
+            dimensions: dimensions.to_vec(),
+            strides,
+        }
+    }
+
+    /// Get the dimensions of this view.
+    pub fn dimensions(&self) -> &[usize] {
+        &self.dimensions
+    }
+
+    /// Get the number of elements of the parent array's flat buffer between consecutive
+    /// indices along each axis of this view.
+    pub fn strides(&self) -> &[usize] {
+        &self.strides
+    }
+
+    /// Get the number of dimensions of this view.
+    pub fn rank(&self) -> usize {
+        self.dimensions.len()
+    }
+
+    /// Get the number of elements in this view.
+    ///
+    /// This is the product of the dimensions of this view.
+    pub fn len(&self) -> usize {
+        self.dimensions.iter().product()
+    }
+
+    /// Returns `true` if the length of any axis of this view is 0.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the element at the position `index`, or `None` if `index` does not have one
+    /// element per axis or is out of bounds.
+    pub fn get(&self, index: &[usize]) -> Option<&'a T> {
+        if index.len() != self.rank() {
+            return None;
+        }
+
+        let mut offset = self.offset;
+
+        for ((&i, &dim), &stride) in index.iter().zip(&self.dimensions).zip(&self.strides)
+        {
+            if i >= dim {
+                return None;
+            }
+
+            offset += i * stride;
+        }
+
+        Some(&self.data[offset])
+    }
+
+    /// Borrow the elements of this view as a contiguous slice, if they are stored
+    /// contiguously in row-major order in the parent array.
+    pub fn as_slice(&self) -> Option<&'a [T]> {
+        // The offset of an empty view may be past the end of the parent array's buffer.
+        if self.is_empty() {
+            return Some(&[]);
+        }
+
+        let mut expected_stride = 1;
+
+        for (&dim, &stride) in self.dimensions.iter().zip(&self.strides).rev() {
+            if dim > 1 && stride != expected_stride {
+                return None;
+            }
+
+            expected_stride *= dim;
+        }
+
+        Some(&self.data[self.offset..self.offset + self.len()])
+    }
+
+    /// Iterate over the elements of this view in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &'a T> + '_ {
+        (0..self.len()).map(move |position| &self.data[self.data_index(position)])
+    }
+
+    /// Copy the elements of this view into a [`Vec`] in row-major order.
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Copy,
+    {
+        match self.as_slice() {
+            Some(slice) => slice.to_vec(),
+            None => self.iter().copied().collect(),
+        }
+    }
+
+    /// Borrow the elements of this view whose index along `axis` is in `range`.
+    ///
+    /// See [`NumericArray::slice()`].
+    #[track_caller]
+    pub fn slice(&self, axis: usize, range: Range<usize>) -> NumericArrayView<'a, T> {
+        assert!(
+            axis < self.rank(),
+            "NumericArrayView::slice(): axis {} is out of bounds for rank {}",
+            axis,
+            self.rank()
+        );
+
+        let dim = self.dimensions[axis];
+
+        assert!(
+            range.start <= range.end && range.end <= dim,
+            "NumericArrayView::slice(): range {:?} is out of bounds for axis {} of length {}",
+            range,
+            axis,
+            dim
+        );
+
+        let mut view = self.clone();
+
+        view.offset += range.start * self.strides[axis];
+        view.dimensions[axis] = range.end - range.start;
+
+        view
+    }
+
+    /// Borrow the rectangular block of elements of this view whose indices along each
+    /// axis are in the corresponding range in `ranges`.
+    ///
+    /// See [`NumericArray::view()`].
+    #[track_caller]
+    pub fn view(&self, ranges: &[Range<usize>]) -> NumericArrayView<'a, T> {
+        assert!(
+            ranges.len() <= self.rank(),
+            "NumericArrayView::view(): {} ranges given for view of rank {}",
+            ranges.len(),
+            self.rank()
+        );
+
+        ranges
+            .iter()
+            .enumerate()
+            .fold(self.clone(), |view, (axis, range)| {
+                view.slice(axis, range.clone())
+            })
+    }
+
+    /// Get the index into the parent array's flat buffer of the element at row-major
+    /// `position` in this view.
+    fn data_index(&self, mut position: usize) -> usize {
+        let mut index = self.offset;
+
+        for (&dim, &stride) in self.dimensions.iter().zip(&self.strides).rev() {
+            index += (position % dim) * stride;
+            position /= dim;
+        }
+
+        index
+    }
+}
+
+impl<T: NumericArrayType + Copy> NumericArrayView<'_, T> {
+    /// Copy the elements of this view into a new [`NumericArray`] with the same
+    /// dimensions.
+    ///
+    /// If this view [is empty][NumericArrayView::is_empty], the new array has no
+    /// elements, and has the same dimensions as this view.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if the new array could not be allocated.
+    pub fn to_numeric_array(&self) -> NumericArray<T> {
+        NumericArray::from_array(&self.dimensions, &self.to_vec())
+    }
+}
+
+impl<T> Clone for NumericArrayView<'_, T> {
+    fn clone(&self) -> Self {
+        NumericArrayView {
+            data: self.data,
+            offset: self.offset,
+            dimensions: self.dimensions.clone(),
+            strides: self.strides.clone(),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NumericArrayView<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NumericArrayView")
+            .field("dimensions", &self.dimensions)
+            .field("strides", &self.strides)
+            .field("elements", &self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,205 @@
+use std::path::Path;
+
+use crate::{
+    convert::normal_elements,
+    expr::{Expr, ExprKind, Symbol},
+    macro_utils::LibraryLinkFunction,
+};
+
+/// A Wolfram Language function that dispatches to one of several native functions
+/// exported by this library, based on the arguments it is called with.
+///
+/// Registered by [`export_overloads!`][crate::export_overloads].
+pub struct Overloads {
+    /// The name the dispatching function is loaded under.
+    pub name: &'static str,
+    /// The path of the Rust module the overloaded function was declared in, as returned
+    /// by [`module_path!()`].
+    pub module: &'static str,
+    /// The names of the exported functions, in the order they are tried.
+    pub variants: &'static [&'static str],
+}
+
+inventory::collect!(Overloads);
+
+impl Overloads {
+    /// Generate code that loads each variant and dispatches to the first one whose
+    /// parameter types match the arguments:
+    ///
+    /// ```wolfram
+    /// With[{
+    ///     overload1 = LibraryFunctionLoad[...],
+    ///     overload2 = LibraryFunctionLoad[...]
+    /// },
+    ///     Function[
+    ///         Which[
+    ///             MatchQ[{##}, {_Integer}], overload1[##],
+    ///             MatchQ[{##}, {_Real}], overload2[##],
+    ///             True, Failure["RustLinkNoMatchingOverload", <| ... |>]
+    ///         ]
+    ///     ]
+    /// ]
+    /// ```
+    pub(crate) fn loading_code(&self, library: &Path) -> Result<Expr, String> {
+        let mut vars = Vec::new();
+        let mut clauses = Vec::new();
+
+        let args = Expr::normal(sys("List"), vec![slot_sequence()]);
+
+        for (index, variant) in self.variants.iter().enumerate() {
+            let func = inventory::iter::<LibraryLinkFunction>
+                .into_iter()
+                .find(|func| func.name() == *variant)
+                .ok_or_else(|| format!("no exported function named '{}'", variant))?;
+
+            let params = match func {
+                LibraryLinkFunction::Native { signature, .. } => signature()?.0,
+                LibraryLinkFunction::Wstp { .. } => {
+                    return Err(format!(
+                        "'{}' is a WSTP function, and cannot be overloaded",
+                        variant
+                    ))
+                },
+            };
+
+            let var = Expr::from(Symbol::new(&format!(
+                "RustLink`Private`overload{}",
+                index + 1
+            )));
+
+            vars.push(Expr::normal(sys("Set"), vec![
+                var.clone(),
+                func.loading_code(library)?,
+            ]));
+
+            let patterns = params.iter().map(parameter_pattern).collect();
+
+            clauses.push(Expr::normal(sys("MatchQ"), vec![
+                args.clone(),
+                Expr::normal(sys("List"), patterns),
+            ]));
+            clauses.push(Expr::normal(var, vec![slot_sequence()]));
+        }
+
+        clauses.push(Expr::from(sys("True")));
+        clauses.push(no_matching_overload_failure(self.name, args));
+
+        Ok(Expr::normal(sys("With"), vec![
+            Expr::normal(sys("List"), vars),
+            Expr::normal(sys("Function"), vec![Expr::normal(sys("Which"), clauses)]),
+        ]))
+    }
+}
+
+/// Get a pattern that matches the values that can be passed to a parameter of type
+/// `param`, as returned by [`FromArg::parameter_type()`][crate::FromArg::parameter_type].
+///
+/// Parameter types that are not recognized match any value.
+fn parameter_pattern(param: &Expr) -> Expr {
+    // {type, "Constant" | "Manual" | "Shared"}
+    if let Some([ty, mode]) = normal_elements(param, "System`List") {
+        if let ExprKind::String(_) = mode.kind() {
+            return parameter_pattern(ty);
+        }
+    }
+
+    if let Some(elements) = normal_elements(param, "System`LibraryDataType") {
+        return match elements {
+            // _NumericArray?(NumericArrayType[#] === "type" &)
+            [head, ty, ..]
+                if is_symbol(head, "System`NumericArray")
+                    && matches!(ty.kind(), ExprKind::String(_)) =>
+            {
+                let array_type =
+                    Expr::normal(sys("NumericArrayType"), vec![Expr::normal(
+                        sys("Slot"),
+                        vec![Expr::from(1i64)],
+                    )]);
+
+                Expr::normal(sys("PatternTest"), vec![
+                    head_pattern(head),
+                    Expr::normal(sys("Function"), vec![Expr::normal(
+                        sys("SameQ"),
+                        vec![array_type, ty.clone()],
+                    )]),
+                ])
+            },
+            [head, ..] => head_pattern(head),
+            [] => blank(None),
+        };
+    }
+
+    match param.kind() {
+        ExprKind::Symbol(symbol) => match symbol.as_str() {
+            "System`Integer" | "System`Real" | "System`Complex" | "System`String" => {
+                blank(Some(symbol.clone()))
+            },
+            _ => blank(None),
+        },
+        ExprKind::String(ty) => match ty.as_str() {
+            "Boolean" => Expr::normal(sys("Alternatives"), vec![
+                Expr::from(sys("True")),
+                Expr::from(sys("False")),
+            ]),
+            "UTF8String" => blank(Some(sys("String"))),
+            "DataStore" => blank(Some(Symbol::new("Developer`DataStore"))),
+            _ => blank(None),
+        },
+        _ => blank(None),
+    }
+}
+
+/// Get a pattern that matches expressions with head `head`, or with any of the heads
+/// in `Alternatives[head1, head2, ...]`.
+fn head_pattern(head: &Expr) -> Expr {
+    if let Some(heads) = normal_elements(head, "System`Alternatives") {
+        return Expr::normal(
+            sys("Alternatives"),
+            heads.iter().map(head_pattern).collect(),
+        );
+    }
+
+    match head.kind() {
+        ExprKind::Symbol(symbol) => blank(Some(symbol.clone())),
+        _ => blank(None),
+    }
+}
+
+/// `Failure["RustLinkNoMatchingOverload", <| ... |>]`
+fn no_matching_overload_failure(name: &str, args: Expr) -> Expr {
+    let rule =
+        |lhs: &str, rhs: Expr| Expr::normal(sys("Rule"), vec![Expr::string(lhs), rhs]);
+
+    Expr::normal(sys("Failure"), vec![
+        Expr::string("RustLinkNoMatchingOverload"),
+        Expr::normal(sys("Association"), vec![
+            rule(
+                "MessageTemplate",
+                Expr::string("No overload of `1` matches the arguments `2`."),
+            ),
+            rule(
+                "MessageParameters",
+                Expr::normal(sys("List"), vec![Expr::string(name), args]),
+            ),
+        ]),
+    ])
+}
+
+fn blank(head: Option<Symbol>) -> Expr {
+    Expr::normal(sys("Blank"), head.into_iter().map(Expr::from).collect())
+}
+
+fn slot_sequence() -> Expr {
+    Expr::normal(sys("SlotSequence"), vec![Expr::from(1i64)])
+}
+
+fn is_symbol(expr: &Expr, name: &str) -> bool {
+    match expr.kind() {
+        ExprKind::Symbol(symbol) => symbol.as_str() == name,
+        _ => false,
+    }
+}
+
+fn sys(name: &str) -> Symbol {
+    Symbol::new(&format!("System`{}", name))
+}
@@ -0,0 +1,177 @@
+//! Data-parallel processing using [Rayon](https://docs.rs/rayon), with support for
+//! Wolfram Language aborts.
+//!
+//! *This module is only available if the `"rayon"` feature of `wolfram-library-link`
+//! is enabled.*
+//!
+//! [`aborted()`][crate::aborted] can only be called from the main Kernel thread, so
+//! code running on a Rayon thread pool cannot check for aborts itself. The functions in
+//! this module run the parallel work on the global Rayon thread pool while the calling
+//! thread acts as a supervisor: it periodically checks whether the user has requested an
+//! abort, and if so, cancels any chunks of work that have not started yet.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{self as wll, parallel, NumericArray};
+//!
+//! wll::export![parallel_sum_of_squares(_)];
+//!
+//! fn parallel_sum_of_squares(array: &NumericArray<f64>) -> f64 {
+//!     let partial_sums: Vec<f64> =
+//!         parallel::par_chunks_abortable(array.as_slice(), 1024, |chunk| {
+//!             chunk.iter().map(|x| x * x).sum()
+//!         })
+//!         .expect("computation was aborted");
+//!
+//!     partial_sums.into_iter().sum()
+//! }
+//! # }
+//! ```
+
+use std::{
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
+
+use rayon::prelude::*;
+
+/// How often the supervising thread checks whether an abort has been requested.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Process `data` in parallel, in chunks of `chunk_size` elements.
+///
+/// `func` is called once for each chunk on the global Rayon thread pool, and the
+/// returned values are collected in chunk order. The last chunk may be shorter than
+/// `chunk_size`.
+///
+/// Returns `None` if the user requested an abort before all chunks were processed.
+/// Chunks that were already being processed when the abort was detected run to
+/// completion, but no new chunks are started.
+///
+/// # Panics
+///
+/// This function will panic if `chunk_size` is 0, or if called from a thread other than
+/// the main Kernel thread. If `func` panics, the panic is propagated to the caller.
+pub fn par_chunks_abortable<T, R, F>(
+    data: &[T],
+    chunk_size: usize,
+    func: F,
+) -> Option<Vec<R>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&[T]) -> R + Sync,
+{
+    assert!(
+        chunk_size != 0,
+        "par_chunks_abortable: chunk size must be non-zero"
+    );
+
+    supervise(|cancelled| {
+        data.par_chunks(chunk_size)
+            .map(|chunk| match cancelled.load(Ordering::Relaxed) {
+                false => Some(func(chunk)),
+                true => None,
+            })
+            .collect()
+    })
+}
+
+/// Process `data` in parallel, in mutable chunks of `chunk_size` elements.
+///
+/// This is the mutable counterpart to [`par_chunks_abortable()`]. If `None` is returned,
+/// only some of the chunks of `data` will have been processed.
+///
+/// # Panics
+///
+/// This function will panic if `chunk_size` is 0, or if called from a thread other than
+/// the main Kernel thread. If `func` panics, the panic is propagated to the caller.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{parallel, NumericArray};
+///
+/// fn normalize(array: &mut NumericArray<f64>, norm: f64) {
+///     let data: &mut [f64] = array.as_slice_mut().expect("array is shared");
+///
+///     parallel::par_chunks_mut_abortable(data, 1024, |chunk| {
+///         for elem in chunk {
+///             *elem /= norm;
+///         }
+///     })
+///     .expect("computation was aborted");
+/// }
+/// ```
+pub fn par_chunks_mut_abortable<T, R, F>(
+    data: &mut [T],
+    chunk_size: usize,
+    func: F,
+) -> Option<Vec<R>>
+where
+    T: Send,
+    R: Send,
+    F: Fn(&mut [T]) -> R + Sync,
+{
+    assert!(
+        chunk_size != 0,
+        "par_chunks_mut_abortable: chunk size must be non-zero"
+    );
+
+    supervise(|cancelled| {
+        data.par_chunks_mut(chunk_size)
+            .map(|chunk| match cancelled.load(Ordering::Relaxed) {
+                false => Some(func(chunk)),
+                true => None,
+            })
+            .collect()
+    })
+}
+
+/// Run `work` on a separate thread, while polling for aborts on the current thread.
+///
+/// If an abort is requested, the flag passed to `work` is set, and `work` is expected
+/// to stop as soon as possible.
+fn supervise<O, W>(work: W) -> Option<O>
+where
+    O: Send,
+    W: FnOnce(&AtomicBool) -> Option<O> + Send,
+{
+    crate::assert_main_thread();
+
+    let cancelled = AtomicBool::new(false);
+
+    let result = thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel::<()>();
+
+        let worker = scope.spawn(|| {
+            // Notify the supervisor on completion, even if `work` panics.
+            let _sender = sender;
+
+            work(&cancelled)
+        });
+
+        // The channel is disconnected when the worker finishes.
+        while let Err(mpsc::RecvTimeoutError::Timeout) =
+            receiver.recv_timeout(POLL_INTERVAL)
+        {
+            if !cancelled.load(Ordering::Relaxed) && crate::aborted() {
+                cancelled.store(true, Ordering::Relaxed);
+            }
+        }
+
+        worker.join()
+    });
+
+    match result {
+        Ok(value) => value,
+        Err(payload) => panic::resume_unwind(payload),
+    }
+}
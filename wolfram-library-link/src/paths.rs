@@ -0,0 +1,151 @@
+use std::{
+    ffi::{CStr, CString},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    expr::{Expr, Symbol},
+    rtl,
+    sys::{mbool, MArgument},
+    TryFromArg,
+};
+
+/// Kind of access to a file that is checked by [`validate_path()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PathAccess {
+    /// The file will be read.
+    Read,
+    /// The file will be created or written to.
+    Write,
+}
+
+/// Check that the Wolfram Kernel allows `path` to be accessed, using the sandbox rules
+/// of the current Kernel session.
+///
+/// When the Kernel is running in a sandbox, for example in the Wolfram Cloud or when
+/// [`$SandboxRestrictions`][ref/$SandboxRestrictions]<sub>WL</sub> have been set, only
+/// some files may be read or written. Libraries that access files should check each
+/// path they were given before accessing it, so that they respect the same
+/// restrictions as the Wolfram Language code that called them.
+///
+/// If the Kernel is not sandboxed, every path is allowed.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+///
+/// *LibraryLink C Function:* [`validatePath`][rtl::validatePath]
+///
+/// [ref/$SandboxRestrictions]: https://reference.wolfram.com/language/ref/$SandboxRestrictions.html
+pub fn validate_path(path: &Path, access: PathAccess) -> Result<(), String> {
+    crate::assert_main_thread();
+
+    let c_path = path
+        .to_str()
+        .and_then(|path| CString::new(path).ok())
+        .ok_or_else(|| format!("path is not a valid string: {}", path.display()))?;
+
+    let access_type: u8 = match access {
+        PathAccess::Read => b'R',
+        PathAccess::Write => b'W',
+    };
+
+    // Note: validatePath() takes a `*mut c_char`, but does not modify the path.
+    let valid: mbool =
+        unsafe { rtl::validatePath(c_path.as_ptr() as *mut _, access_type as _) };
+
+    if !crate::bool_from_mbool(valid) {
+        return Err(format!(
+            "access to path is not allowed by the Kernel sandbox: {}",
+            path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Normalize `path` lexically, removing `.` components and resolving `..` components
+/// against the component that precedes them.
+///
+/// The file system is not accessed, so `path` does not need to exist, and symbolic
+/// links are not resolved.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                },
+                // `..` in the root directory is the root directory.
+                Some(Component::RootDir) => (),
+                // Keep leading `..` components of a relative path.
+                Some(Component::ParentDir | Component::Prefix(_) | Component::CurDir)
+                | None => normalized.push(component),
+            },
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                normalized.push(component)
+            },
+        }
+    }
+
+    // E.g. `a/..`
+    if normalized.as_os_str().is_empty() && !path.as_os_str().is_empty() {
+        normalized.push(Component::CurDir);
+    }
+
+    normalized
+}
+
+/// Convert a `String` argument to a normalized file path that the Kernel allows to be
+/// read.
+///
+/// The path is normalized lexically, removing `.` components and resolving `..`
+/// components, and then checked using [`validate_path()`] with [`PathAccess::Read`].
+/// If the Kernel is sandboxed and does not allow the path to be read, the function is
+/// not called. Use [`validate_path()`] with [`PathAccess::Write`] to check paths that
+/// will be written to.
+///
+/// The Kernel's copy of the string is released after the conversion.
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use std::path::PathBuf;
+///
+/// use wolfram_library_link as wll;
+///
+/// wll::export![file_size(_)];
+///
+/// fn file_size(path: PathBuf) -> i64 {
+///     std::fs::metadata(&path).map(|meta| meta.len() as i64).unwrap_or(-1)
+/// }
+/// # }
+/// ```
+impl TryFromArg<'_> for PathBuf {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<PathBuf, String> {
+        let path: Result<String, String> = {
+            let cstr: &CStr = CStr::from_ptr(*arg.utf8string);
+
+            cstr.to_str()
+                .map(str::to_owned)
+                .map_err(|err| format!("path argument was not valid UTF-8: {}", err))
+        };
+
+        // Now that we own our own copy of the string, disown the Kernel's copy.
+        rtl::UTF8String_disown(*arg.utf8string);
+
+        let path = normalize(Path::new(&path?));
+
+        validate_path(&path, PathAccess::Read)?;
+
+        Ok(path)
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`String"))
+    }
+}
@@ -0,0 +1,341 @@
+use std::{cell::Cell, ffi::CStr};
+
+use ref_cast::RefCast;
+
+use crate::{
+    rtl, sparse_array,
+    sys::{mint, mreal, MArgument},
+    IntoArg, NativeFunction, NumericArray, NumericArrayType,
+};
+
+use crate::expr::Expr;
+
+/// Arguments of a native function that reads its [`MArgument`] values directly.
+///
+/// `RawArgs` is an escape hatch for functions that need to work with the raw
+/// *LibraryLink* arguments, for example because the type of each argument is only known
+/// at runtime, but still want bounds checking and correct handling of ownership. Each
+/// accessor takes the 0-based index of an argument, and returns an error instead of
+/// reading out of bounds.
+///
+/// The accessors are `unsafe`, because `RawArgs` cannot check that the argument at an
+/// index has the type being read: reading an `Integer` argument as a string, for
+/// example, would dereference the integer as a pointer.
+///
+/// Functions with the signature `fn(RawArgs, RawReturn)` can be exported using
+/// [`export!`][crate::export], by specifying the number of parameters as `(_, _)`:
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, RawArgs, RawReturn};
+///
+/// wll::export![string_repeat(_, _)];
+///
+/// fn string_repeat(args: RawArgs, ret: RawReturn) {
+///     // Safety: the argument types match the LibraryFunctionLoad signature below.
+///     unsafe {
+///         let string: &str = args.utf8_str(0).unwrap();
+///         let count: i64 = args.integer(1).unwrap();
+///
+///         ret.set(string.repeat(count as usize));
+///     }
+/// }
+/// # }
+/// ```
+///
+/// ```wolfram
+/// LibraryFunctionLoad["...", "string_repeat", {String, Integer}, String]
+/// ```
+///
+/// The types of the arguments are not known to `wolfram-library-link`, so these
+/// functions cannot be loaded automatically by [`generate_loader!`][crate::generate_loader],
+/// and the caller is responsible for using the accessor that matches the type of each
+/// argument in the [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub>
+/// signature.
+///
+/// # String arguments
+///
+/// String arguments borrowed using [`utf8_str()`][RawArgs::utf8_str] are released back
+/// to the Kernel when the `RawArgs` is dropped, which also happens if the function
+/// panics. String arguments that are never accessed are not released, because
+/// `RawArgs` cannot tell which arguments are strings.
+///
+/// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+pub struct RawArgs<'a> {
+    args: &'a [MArgument],
+    /// Whether the string argument at each index has been borrowed, and so must be
+    /// disowned when this `RawArgs` is dropped.
+    borrowed_strings: Vec<Cell<bool>>,
+}
+
+/// Return value of a native function that reads its [`MArgument`] values directly.
+///
+/// See [`RawArgs`].
+pub struct RawReturn {
+    ret: MArgument,
+}
+
+impl<'a> RawArgs<'a> {
+    /// Get the number of arguments the function was called with.
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns `true` if the function was called with no arguments.
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Get the raw [`MArgument`] values.
+    pub fn as_slice(&self) -> &[MArgument] {
+        self.args
+    }
+
+    /// Get the `Integer` argument at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have type `Integer` in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn integer(&self, index: usize) -> Result<mint, String> {
+        let arg = self.get(index)?;
+
+        Ok(*arg.integer)
+    }
+
+    /// Get the `Real` argument at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have type `Real` in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn real(&self, index: usize) -> Result<mreal, String> {
+        let arg = self.get(index)?;
+
+        Ok(*arg.real)
+    }
+
+    /// Get the `"Boolean"` argument at `index`.
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have type `"Boolean"` in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn boolean(&self, index: usize) -> Result<bool, String> {
+        let arg = self.get(index)?;
+
+        Ok(crate::bool_from_mbool(*arg.boolean))
+    }
+
+    /// Borrow the `String` argument at `index`.
+    ///
+    /// The Kernel's copy of the string is released when this `RawArgs` is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have type `String` in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn utf8_str(&self, index: usize) -> Result<&str, String> {
+        let arg = self.get(index)?;
+
+        let cstr: &CStr = CStr::from_ptr(*arg.utf8string);
+
+        self.borrowed_strings[index].set(true);
+
+        cstr.to_str().map_err(|err| {
+            format!(
+                "argument index {}: string was not valid UTF-8: {}",
+                index, err
+            )
+        })
+    }
+
+    /// Borrow the `NumericArray` argument at `index`, checking that its elements are of
+    /// type `T`.
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have type `LibraryDataType[NumericArray]`, with the
+    /// `"Constant"` or `"Shared"` memory management mode, in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn numeric_array<T: NumericArrayType>(
+        &self,
+        index: usize,
+    ) -> Result<&NumericArray<T>, String> {
+        let arg = self.get(index)?;
+
+        crate::released::acquire(*arg.numeric);
+
+        let array: &NumericArray = NumericArray::ref_cast(&*arg.numeric);
+
+        array.try_kind::<T>().map_err(|()| {
+            format!(
+                "argument index {}: expected NumericArray of type {}, got {}",
+                index,
+                T::TYPE.name(),
+                array.data_type().name()
+            )
+        })
+    }
+
+    /// Copy the packed array argument at `index`, e.g. of type `{Real, 2}`, into a new
+    /// `NumericArray`.
+    ///
+    /// Integer, real, and complex packed arrays are converted to `"Integer64"`,
+    /// `"Real64"`, and `"ComplexReal64"` arrays, respectively. *LibraryLink* provides no
+    /// way for a packed array and a `NumericArray` to share their elements, so the
+    /// elements are always copied.
+    ///
+    /// This can be used to write a single implementation that accepts both packed arrays
+    /// and `NumericArray`s:
+    ///
+    /// ```no_run
+    /// # mod scope {
+    /// use wolfram_library_link::{self as wll, NumericArray, RawArgs, RawReturn};
+    ///
+    /// wll::export![total_packed(_, _)];
+    ///
+    /// fn total_packed(args: RawArgs, ret: RawReturn) {
+    ///     // Safety: the argument and return types match the LibraryFunctionLoad
+    ///     //         signature below.
+    ///     unsafe {
+    ///         let array = args.packed_array(0).unwrap();
+    ///
+    ///         ret.set(total(array.try_kind::<f64>().unwrap()));
+    ///     }
+    /// }
+    ///
+    /// fn total(array: &NumericArray<f64>) -> f64 {
+    ///     array.as_slice().iter().sum()
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// ```wolfram
+    /// LibraryFunctionLoad["...", "total_packed", {{Real, _, "Constant"}}, Real]
+    /// ```
+    ///
+    /// # Safety
+    ///
+    /// The argument at `index` must have a packed array type, e.g. `{Real, 2}`, in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn packed_array(&self, index: usize) -> Result<NumericArray, String> {
+        let arg = self.get(index)?;
+
+        sparse_array::tensor_to_numeric_array(*arg.tensor)
+            .map_err(|err| format!("argument index {}: {}", index, err))
+    }
+
+    fn get(&self, index: usize) -> Result<&MArgument, String> {
+        self.args.get(index).ok_or_else(|| {
+            format!(
+                "argument index {} is out of bounds: function was called with {} {}",
+                index,
+                self.args.len(),
+                match self.args.len() {
+                    1 => "argument",
+                    _ => "arguments",
+                }
+            )
+        })
+    }
+}
+
+impl RawReturn {
+    /// Set the value returned by the function.
+    ///
+    /// # Safety
+    ///
+    /// The type of `value` must match the return type in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function. Writing a value of another type causes the Kernel to misinterpret it,
+    /// e.g. to dereference an integer as a pointer.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn set<T: IntoArg>(self, value: T) {
+        value.into_arg(self.ret)
+    }
+
+    /// Set the value returned by the function to a packed array containing a copy of
+    /// the elements of `array`.
+    ///
+    /// The return type in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function must be a packed array type, e.g. `{Real, 2}`. Only `"Integer64"`,
+    /// `"Real64"`, and `"ComplexReal64"` arrays can be converted to a packed array; use
+    /// [`NumericArray::convert_to()`] to convert arrays of other types first.
+    ///
+    /// See also [`RawArgs::packed_array()`].
+    ///
+    /// # Safety
+    ///
+    /// The return type in the
+    /// [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> signature of the
+    /// function must be a packed array type.
+    ///
+    /// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+    pub unsafe fn set_packed_array(self, array: &NumericArray) -> Result<(), String> {
+        let tensor = sparse_array::numeric_array_to_tensor(array)?;
+
+        *self.ret.tensor = tensor;
+
+        Ok(())
+    }
+
+    /// Get the raw [`MArgument`] that the return value is written to.
+    pub fn as_raw(&self) -> MArgument {
+        self.ret
+    }
+}
+
+impl Drop for RawArgs<'_> {
+    fn drop(&mut self) {
+        // Nothing can borrow from this `RawArgs` anymore, so release the borrowed
+        // strings.
+        for (arg, borrowed) in self.args.iter().zip(self.borrowed_strings.iter()) {
+            if borrowed.get() {
+                unsafe { rtl::UTF8String_disown(*arg.utf8string) }
+            }
+        }
+    }
+}
+
+/// Functions that read their arguments using [`RawArgs`].
+///
+/// See [`RawArgs`] for an example.
+impl<'a: 'b, 'b> NativeFunction<'a> for fn(RawArgs<'b>, RawReturn) {
+    unsafe fn call(&self, args: &'a [MArgument], ret: MArgument) {
+        let raw_args = RawArgs {
+            args,
+            borrowed_strings: args.iter().map(|_| Cell::new(false)).collect(),
+        };
+
+        self(raw_args, RawReturn { ret });
+    }
+
+    fn signature(&self) -> Result<(Vec<Expr>, Expr), String> {
+        Err(
+            "fn(RawArgs, RawReturn) function cannot be loaded automatically: \
+            parameter and return types are unknown."
+                .to_owned(),
+        )
+    }
+}
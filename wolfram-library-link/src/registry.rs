@@ -0,0 +1,256 @@
+//! Registry of Rust values identified by integer handles.
+//!
+//! A [`Registry<T>`] stores values of type `T` and hands out an [`i64`] handle for each
+//! value, which can be returned to the Wolfram Language and passed back into later
+//! calls to refer to the stored value.
+//!
+//! Unlike [managed expressions][crate::managed], values in a registry are not tied to
+//! the lifetime of a Wolfram Language expression, and must be removed explicitly using
+//! [`Registry::remove()`].
+//!
+//! Functions exported using [`export!`][crate::export] can take a [`Handle<T>`]
+//! parameter to resolve a handle to a reference to the stored value.
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use std::sync::Mutex;
+//!
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     registry::{Handle, Registered, Registry},
+//! };
+//!
+//! struct Log {
+//!     lines: Mutex<Vec<String>>,
+//! }
+//!
+//! static LOGS: Registry<Log> = Registry::new();
+//!
+//! impl Registered for Log {
+//!     fn registry() -> &'static Registry<Log> {
+//!         &LOGS
+//!     }
+//! }
+//!
+//! wll::export![
+//!     log_new();
+//!     log_append(_, _);
+//!     log_len(_);
+//!     log_delete(_);
+//! ];
+//!
+//! fn log_new() -> i64 {
+//!     LOGS.insert(Log {
+//!         lines: Mutex::new(Vec::new()),
+//!     })
+//! }
+//!
+//! fn log_append(log: Handle<Log>, line: String) {
+//!     log.lines.lock().unwrap().push(line);
+//! }
+//!
+//! fn log_len(log: Handle<Log>) -> i64 {
+//!     log.lines.lock().unwrap().len() as i64
+//! }
+//!
+//! fn log_delete(handle: i64) -> bool {
+//!     LOGS.remove(handle).is_some()
+//! }
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    fmt,
+    ops::Deref,
+    sync::{Arc, RwLock},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{
+    expr::{Expr, Symbol},
+    sys::MArgument,
+    TryFromArg,
+};
+
+/// Thread-safe storage for values of type `T`, identified by [`i64`] handles.
+///
+/// See the [module documentation](self) for more information.
+pub struct Registry<T> {
+    state: Lazy<RwLock<State<T>>>,
+}
+
+struct State<T> {
+    /// The handle that will be assigned to the next inserted value.
+    next_handle: i64,
+    values: HashMap<i64, Arc<T>>,
+}
+
+/// Type whose values are stored in a static [`Registry`].
+///
+/// Implementing this trait allows [`Handle<T>`] to be used as the parameter type of a
+/// function exported using [`export!`][crate::export].
+pub trait Registered: Send + Sync + Sized + 'static {
+    /// The registry that stores values of this type.
+    fn registry() -> &'static Registry<Self>;
+}
+
+/// Reference to a value stored in a [`Registry`].
+///
+/// `Handle<T>` can be used as the parameter type of a function exported using
+/// [`export!`][crate::export]. The corresponding Wolfram Language argument is the
+/// `Integer` handle returned by [`Registry::insert()`].
+///
+/// Conversion from an argument fails if the handle is stale, i.e. if no value with that
+/// handle is stored in the registry (see [`TryFromArg`]).
+///
+/// The value remains valid for the lifetime of the `Handle`, even if it is removed from
+/// the registry in the meantime.
+pub struct Handle<T> {
+    id: i64,
+    value: Arc<T>,
+}
+
+impl<T> Registry<T> {
+    /// Construct a new, empty registry.
+    pub const fn new() -> Self {
+        Registry {
+            state: Lazy::new(|| {
+                RwLock::new(State {
+                    next_handle: 1,
+                    values: HashMap::new(),
+                })
+            }),
+        }
+    }
+
+    /// Store `value` in this registry, and return the handle that identifies it.
+    ///
+    /// Handles are never reused by the same registry.
+    pub fn insert(&self, value: T) -> i64 {
+        let mut state = self.state.write().unwrap();
+
+        let handle = state.next_handle;
+        state.next_handle += 1;
+
+        state.values.insert(handle, Arc::new(value));
+
+        handle
+    }
+
+    /// Get the value identified by `handle`.
+    ///
+    /// Returns `None` if `handle` is stale.
+    pub fn get(&self, handle: i64) -> Option<Arc<T>> {
+        let state = self.state.read().unwrap();
+
+        state.values.get(&handle).cloned()
+    }
+
+    /// Remove the value identified by `handle` from this registry.
+    ///
+    /// Returns `None` if `handle` is stale. The value is dropped once all outstanding
+    /// [`Handle`]s to it have been dropped.
+    pub fn remove(&self, handle: i64) -> Option<Arc<T>> {
+        let mut state = self.state.write().unwrap();
+
+        state.values.remove(&handle)
+    }
+
+    /// Returns `true` if a value identified by `handle` is stored in this registry.
+    pub fn contains(&self, handle: i64) -> bool {
+        let state = self.state.read().unwrap();
+
+        state.values.contains_key(&handle)
+    }
+
+    /// The number of values stored in this registry.
+    pub fn len(&self) -> usize {
+        self.state.read().unwrap().values.len()
+    }
+
+    /// Returns `true` if no values are stored in this registry.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T: Registered> Handle<T> {
+    /// Get a handle to the value identified by `id` in [`T::registry()`][Registered].
+    ///
+    /// Returns `None` if `id` is stale.
+    pub fn from_id(id: i64) -> Option<Handle<T>> {
+        let value = T::registry().get(id)?;
+
+        Some(Handle { id, value })
+    }
+}
+
+impl<T> Handle<T> {
+    /// Get the integer handle that identifies this value.
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+}
+
+//======================================
+// Trait Impls
+//======================================
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Registry::new()
+    }
+}
+
+impl<T> fmt::Debug for Registry<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Registry")
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl<T> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+impl<T> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Handle {
+            id: self.id,
+            value: Arc::clone(&self.value),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Handle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handle").field("id", &self.id).finish()
+    }
+}
+
+impl<T: Registered> TryFromArg<'_> for Handle<T> {
+    unsafe fn try_from_arg(arg: &MArgument) -> Result<Handle<T>, String> {
+        let id: i64 = *arg.integer;
+
+        Handle::from_id(id).ok_or_else(|| {
+            format!(
+                "stale handle {}: no value of type `{}` is registered with that handle",
+                id,
+                std::any::type_name::<T>()
+            )
+        })
+    }
+
+    fn parameter_type() -> Expr {
+        Expr::symbol(Symbol::new("System`Integer"))
+    }
+}
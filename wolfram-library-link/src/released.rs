@@ -0,0 +1,197 @@
+//! Runtime tracking of containers whose ownership has been released.
+//!
+//! A [`NumericArray`][crate::NumericArray] or [`DataStore`][crate::DataStore] wraps a
+//! raw pointer. Unsafe code that duplicates that pointer, for example by calling
+//! `from_raw()` twice, can cause the same container to be freed or disowned twice, or to
+//! be used after it has been returned to the Kernel. Both are undefined behavior that
+//! typically crash the Kernel some time later, far from the actual mistake.
+//!
+//! In builds with `debug_assertions` enabled, containers are recorded here when their
+//! ownership is released, and any later release or use of the same container panics
+//! with a description of how and where it was first released. In release builds, these
+//! functions do nothing.
+//!
+//! Only the 10,000 most recently released containers are remembered, so that
+//! long-running Kernel sessions don't accumulate records without bound. Mistakes
+//! involving containers released longer ago are not detected.
+
+#[cfg(debug_assertions)]
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Mutex,
+    thread,
+};
+
+#[cfg(debug_assertions)]
+use once_cell::sync::Lazy;
+
+/// The maximum number of released containers remembered by [`release()`]. The oldest
+/// records are discarded first.
+#[cfg(debug_assertions)]
+const MAX_RELEASED: usize = 10_000;
+
+#[cfg(debug_assertions)]
+static RELEASED: Lazy<Mutex<Registry>> = Lazy::new(|| {
+    Mutex::new(Registry {
+        containers: HashMap::new(),
+        order: VecDeque::new(),
+        next_seq: 0,
+    })
+});
+
+#[cfg(debug_assertions)]
+struct Registry {
+    /// Released containers, indexed by container pointer address.
+    containers: HashMap<usize, Released>,
+    /// Address and sequence number of each release, oldest first.
+    ///
+    /// Entries whose container has since been acquired again are left in place, and
+    /// skipped when they are discarded.
+    order: VecDeque<(usize, u64)>,
+    next_seq: u64,
+}
+
+#[cfg(debug_assertions)]
+struct Released {
+    container: &'static str,
+    how: Release,
+    thread: String,
+    /// Distinguishes this release from earlier releases of the same address.
+    seq: u64,
+}
+
+/// How the ownership of a container was released.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Release {
+    /// The owning value was dropped, freeing or disowning the container.
+    Dropped,
+    /// The container was returned to the Kernel as the result of a library function.
+    ReturnedToKernel,
+    /// The container was added to a [`DataStore`][crate::DataStore], which took
+    /// ownership of it.
+    AddedToDataStore,
+}
+
+/// Record that the ownership of the `container` at `raw` has been released.
+///
+/// # Panics
+///
+/// Panics if the container at `raw` has already been released.
+pub(crate) fn release<P>(raw: *mut P, container: &'static str, how: Release) {
+    #[cfg(debug_assertions)]
+    {
+        let mut released = RELEASED.lock().unwrap();
+
+        if let Some(existing) = released.containers.get(&(raw as usize)) {
+            let message = format!(
+                "attempted to release {} at {:p} ({}) on thread '{}', but it was already \
+                released ({}) on thread '{}'. Each reference to a container must be \
+                released exactly once.",
+                container,
+                raw,
+                how.description(),
+                current_thread_name(),
+                existing.how.description(),
+                existing.thread,
+            );
+            // Drop `released` to avoid poisoning RELEASED when we panic.
+            drop(released);
+            panic!("{}", message);
+        }
+
+        released.insert(raw as usize, container, how);
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = (raw, container, how);
+}
+
+/// Record that the container at `raw` is owned or borrowed by this library.
+///
+/// This must be called whenever a container pointer is received from the Kernel or
+/// allocated, because the address of a released container can be reused.
+pub(crate) fn acquire<P>(raw: *mut P) {
+    #[cfg(debug_assertions)]
+    {
+        RELEASED.lock().unwrap().containers.remove(&(raw as usize));
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = raw;
+}
+
+/// Assert that the container at `raw` has not been released.
+///
+/// `access` describes the attempted access, and is used in the panic message.
+pub(crate) fn assert_live<P>(raw: *mut P, access: &str) {
+    #[cfg(debug_assertions)]
+    {
+        let released = RELEASED.lock().unwrap();
+
+        if let Some(existing) = released.containers.get(&(raw as usize)) {
+            let message = format!(
+                "attempted to {} {} at {:p} on thread '{}', but it was already released \
+                ({}) on thread '{}'. A container must not be used after it has been \
+                freed, disowned, or returned to the Kernel.",
+                access,
+                existing.container,
+                raw,
+                current_thread_name(),
+                existing.how.description(),
+                existing.thread,
+            );
+            drop(released);
+            panic!("{}", message);
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = (raw, access);
+}
+
+#[cfg(debug_assertions)]
+impl Registry {
+    /// Record that the `container` at `address` has been released, discarding the
+    /// oldest records if there are more than [`MAX_RELEASED`].
+    fn insert(&mut self, address: usize, container: &'static str, how: Release) {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        self.order.push_back((address, seq));
+        self.containers.insert(address, Released {
+            container,
+            how,
+            thread: current_thread_name(),
+            seq,
+        });
+
+        while self.order.len() > MAX_RELEASED {
+            let (address, seq) = self.order.pop_front().unwrap();
+
+            if self.containers.get(&address).map(|existing| existing.seq) == Some(seq) {
+                self.containers.remove(&address);
+            }
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+impl Release {
+    fn description(&self) -> &'static str {
+        match self {
+            Release::Dropped => "dropped",
+            Release::ReturnedToKernel => "returned to the Kernel",
+            Release::AddedToDataStore => "added to a DataStore",
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+fn current_thread_name() -> String {
+    let current = thread::current();
+
+    match current.name() {
+        Some(name) => name.to_owned(),
+        None => format!("{:?}", current.id()),
+    }
+}
@@ -2,8 +2,17 @@
 //!
 //! Attempting to call these bindings will result in a panic if
 //! [`initialize()`][crate::initialize] has not been called.
+//!
+//! Safe wrappers are provided for some of these functions, including:
+//!
+//! * [`AbortQ`] — [`aborted()`][crate::aborted]
+//! * [`Message`] — [`message()`][crate::message]
+//! * [`evaluateExpression`] — [`evaluate_expression()`][crate::evaluate_expression]
+//! * [`getParallelThreadNumber`] — [`parallel_thread_count()`]
+//! * [`setParallelThreadNumber`] and [`restoreParallelThreadNumber`] —
+//!   [`set_parallel_thread_count()`]
 
-use std::{ffi::c_void, os::raw::c_int};
+use std::{ffi::c_void, marker::PhantomData, os::raw::c_int};
 
 use once_cell::sync::Lazy;
 
@@ -478,3 +487,78 @@ rtl_func![
 
 // pub compileLibraryFunctions: *mut st_WolframCompileLibrary_Functions,
 // pub rawarrayLibraryFunctions: *mut st_WolframRawArrayLibrary_Functions,
+
+//======================================
+// Threads
+//======================================
+
+/// Get the number of threads the Wolfram Runtime uses for parallel computations.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+///
+/// *LibraryLink C Function:* [`getParallelThreadNumber`]
+pub fn parallel_thread_count() -> usize {
+    crate::assert_main_thread();
+
+    let count: c_int = unsafe { getParallelThreadNumber() };
+
+    usize::try_from(count).unwrap_or(0)
+}
+
+/// Set the number of threads the Wolfram Runtime uses for parallel computations, until
+/// the returned guard is dropped.
+///
+/// The previous thread count is restored when the guard is dropped. The Wolfram Runtime
+/// requires that the previous value is restored before the library function that
+/// changed it returns, so the guard cannot be sent to another thread, and should not
+/// outlive the current function call.
+///
+/// If `count` is not a valid thread count, the thread count is not changed.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::rtl;
+///
+/// fn single_threaded_computation() {
+///     let _guard = rtl::set_parallel_thread_count(1);
+///
+///     // ... runtime functions called here will not start additional threads ...
+/// }
+/// ```
+///
+/// *LibraryLink C Functions:* [`setParallelThreadNumber`], [`restoreParallelThreadNumber`]
+pub fn set_parallel_thread_count(count: usize) -> ParallelThreadCountGuard {
+    crate::assert_main_thread();
+
+    let count = c_int::try_from(count).unwrap_or(c_int::MAX);
+
+    let previous: c_int = unsafe { setParallelThreadNumber(count) };
+
+    ParallelThreadCountGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Restores the previous parallel thread count when dropped.
+///
+/// See [`set_parallel_thread_count()`].
+#[must_use = "the previous thread count is restored immediately if the guard is not kept"]
+pub struct ParallelThreadCountGuard {
+    previous: c_int,
+    /// Prevent the guard from being sent to, and dropped on, another thread.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for ParallelThreadCountGuard {
+    fn drop(&mut self) {
+        unsafe { restoreParallelThreadNumber(self.previous) }
+    }
+}
@@ -0,0 +1,143 @@
+//! Check what the Wolfram Kernel allows libraries to do in the current session.
+//!
+//! A Kernel can run in a restricted ("sandboxed") mode, for example in the Wolfram
+//! Cloud, or when [`$SandboxRestrictions`][ref/$SandboxRestrictions]<sub>WL</sub> or
+//! [`Sandbox`][ref/Sandbox]<sub>WL</sub> options are in effect. Wolfram Language code
+//! running in a restricted Kernel cannot access arbitrary files, use the network, or
+//! start other processes.
+//!
+//! Native code loaded into the Kernel is not subject to those restrictions
+//! automatically. Libraries should use the functions in this module to check that an
+//! operation is allowed before performing it, so that they respect the same
+//! restrictions as the Wolfram Language code that calls them, and can degrade
+//! gracefully when an operation is not allowed.
+//!
+//! # Example
+//!
+//! Fall back to a bundled copy of some data when downloading is not allowed:
+//!
+//! ```no_run
+//! # mod scope {
+//! use wolfram_library_link::{
+//!     self as wll,
+//!     sandbox::{self, Capability},
+//! };
+//!
+//! wll::export![load_dataset()];
+//!
+//! fn load_dataset() -> String {
+//!     match sandbox::check(Capability::Network) {
+//!         Ok(()) => download_latest_dataset(),
+//!         Err(_) => bundled_dataset(),
+//!     }
+//! }
+//! # fn download_latest_dataset() -> String { todo!() }
+//! # fn bundled_dataset() -> String { todo!() }
+//! # }
+//! ```
+//!
+//! [ref/$SandboxRestrictions]: https://reference.wolfram.com/language/ref/$SandboxRestrictions.html
+//! [ref/Sandbox]: https://reference.wolfram.com/language/ref/Sandbox.html
+
+use std::path::Path;
+
+use crate::{rtl, PathAccess};
+
+/// An operation that may not be allowed in a restricted Kernel session.
+///
+/// See [`check()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Capability<'p> {
+    /// Read the file at a path.
+    ReadFile(&'p Path),
+    /// Create or write to the file at a path.
+    WriteFile(&'p Path),
+    /// Make network connections.
+    Network,
+    /// Start other processes.
+    Process,
+}
+
+/// Returns `true` if the Kernel is running in a restricted (sandboxed) mode.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+///
+/// *LibraryLink C Function:* [`protectedModeQ`][rtl::protectedModeQ]
+pub fn is_restricted() -> bool {
+    crate::assert_main_thread();
+
+    crate::bool_from_mbool(unsafe { rtl::protectedModeQ() })
+}
+
+/// Check that the Kernel allows `capability` to be used in the current session.
+///
+/// File access is checked using the Kernel's rules for the specific path, using
+/// [`validate_path()`][crate::validate_path]. Network and process access are only
+/// allowed if the Kernel is not [restricted][is_restricted].
+///
+/// Returns an error describing the operation if it is not allowed.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+pub fn check(capability: Capability) -> Result<(), String> {
+    match capability {
+        Capability::ReadFile(path) => crate::validate_path(path, PathAccess::Read),
+        Capability::WriteFile(path) => crate::validate_path(path, PathAccess::Write),
+        Capability::Network => require_unrestricted("network access"),
+        Capability::Process => require_unrestricted("starting processes"),
+    }
+}
+
+/// Returns `true` if the Kernel allows `capability` to be used in the current session.
+///
+/// See [`check()`].
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+pub fn is_allowed(capability: Capability) -> bool {
+    check(capability).is_ok()
+}
+
+/// Run `operation` if the Kernel allows `capability` to be used in the current
+/// session, or return an error without running it.
+///
+/// # Panics
+///
+/// This function will panic if called from a thread other than the main Kernel thread.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::path::Path;
+///
+/// use wolfram_library_link::sandbox::{self, Capability};
+///
+/// fn save_cache(path: &Path, contents: &[u8]) -> Result<(), String> {
+///     sandbox::with_capability(Capability::WriteFile(path), || {
+///         std::fs::write(path, contents).map_err(|err| err.to_string())
+///     })?
+/// }
+/// ```
+pub fn with_capability<F, R>(capability: Capability, operation: F) -> Result<R, String>
+where
+    F: FnOnce() -> R,
+{
+    check(capability)?;
+
+    Ok(operation())
+}
+
+fn require_unrestricted(operation: &str) -> Result<(), String> {
+    if is_restricted() {
+        return Err(format!(
+            "{} is not allowed because the Kernel is running in a restricted mode",
+            operation
+        ));
+    }
+
+    Ok(())
+}
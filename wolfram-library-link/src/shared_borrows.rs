@@ -0,0 +1,119 @@
+//! Runtime tracking of mutable borrows of shared container data.
+//!
+//! A `"Shared"` [`NumericArray`][crate::NumericArray] passed into multiple calls to a
+//! library refers to the same underlying data each time. If the library stores the
+//! array (e.g. for use by a background thread), the compiler cannot prevent two calls
+//! from mutating that data simultaneously.
+//!
+//! In builds with `debug_assertions` enabled, mutable borrows obtained via
+//! [`NumericArray::borrow_shared_mut()`][crate::NumericArray::borrow_shared_mut] are
+//! recorded here, and any overlapping access through the same data pointer panics with
+//! a description of where the conflicting borrow was created. In release builds, these
+//! functions do nothing.
+
+#[cfg(debug_assertions)]
+use std::{collections::HashMap, panic::Location, sync::Mutex, thread};
+
+#[cfg(debug_assertions)]
+use once_cell::sync::Lazy;
+
+/// Active mutable borrows, indexed by data pointer address.
+#[cfg(debug_assertions)]
+static MUTABLE_BORROWS: Lazy<Mutex<HashMap<usize, Borrow>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+#[cfg(debug_assertions)]
+struct Borrow {
+    location: &'static Location<'static>,
+    thread: String,
+}
+
+/// Record a new mutable borrow of the data at `ptr`.
+///
+/// # Panics
+///
+/// Panics if the data at `ptr` is already mutably borrowed.
+#[track_caller]
+pub(crate) fn acquire_mut<T>(ptr: *const T) {
+    #[cfg(debug_assertions)]
+    {
+        let location = Location::caller();
+
+        let mut borrows = MUTABLE_BORROWS.lock().unwrap();
+
+        if let Some(existing) = borrows.get(&(ptr as usize)) {
+            let message = conflict_message("mutably borrow", existing);
+            // Drop `borrows` to avoid poisoning MUTABLE_BORROWS when we panic.
+            drop(borrows);
+            panic!("{}", message);
+        }
+
+        borrows.insert(ptr as usize, Borrow {
+            location,
+            thread: current_thread_name(),
+        });
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = ptr;
+}
+
+/// Release a mutable borrow of the data at `ptr` recorded by [`acquire_mut()`].
+pub(crate) fn release_mut<T>(ptr: *const T) {
+    #[cfg(debug_assertions)]
+    {
+        MUTABLE_BORROWS.lock().unwrap().remove(&(ptr as usize));
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = ptr;
+}
+
+/// Assert that the data at `ptr` is not currently mutably borrowed.
+///
+/// `access` describes the attempted access, and is used in the panic message.
+#[track_caller]
+pub(crate) fn assert_not_mut_borrowed<T>(ptr: *const T, access: &str) {
+    #[cfg(debug_assertions)]
+    {
+        let borrows = MUTABLE_BORROWS.lock().unwrap();
+
+        if let Some(existing) = borrows.get(&(ptr as usize)) {
+            let message = conflict_message(access, existing);
+            drop(borrows);
+            panic!("{}", message);
+        }
+    }
+
+    #[cfg(not(debug_assertions))]
+    let _ = (ptr, access);
+}
+
+#[cfg(debug_assertions)]
+#[track_caller]
+fn conflict_message(access: &str, existing: &Borrow) -> String {
+    let location = Location::caller();
+
+    format!(
+        "attempted to {} shared NumericArray data at {}:{} (thread '{}'), but it is \
+        already mutably borrowed at {}:{} (thread '{}'). Shared containers must not be \
+        accessed while another call is mutating them.",
+        access,
+        location.file(),
+        location.line(),
+        current_thread_name(),
+        existing.location.file(),
+        existing.location.line(),
+        existing.thread,
+    )
+}
+
+#[cfg(debug_assertions)]
+fn current_thread_name() -> String {
+    let current = thread::current();
+
+    match current.name() {
+        Some(name) => name.to_owned(),
+        None => format!("{:?}", current.id()),
+    }
+}
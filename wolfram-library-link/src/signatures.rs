@@ -0,0 +1,356 @@
+//! Validation of exported function signatures against a Wolfram Language spec file.
+
+use std::fmt::Write;
+
+use crate::{
+    convert::normal_elements,
+    exported_functions,
+    expr::{Expr, ExprKind, Symbol},
+};
+
+/// Check that the signatures of the functions exported by this library match `spec`.
+///
+/// Calling a *LibraryLink* function that was loaded using the wrong parameter types
+/// may lead to undefined behavior. Checking the exported functions against a spec that
+/// records the signatures used by the Wolfram Language code that loads them catches
+/// this kind of mismatch early.
+///
+/// `spec` is Wolfram Language source code containing an association from function names
+/// to their [`LibraryFunctionLoad`][ref/LibraryFunctionLoad]<sub>WL</sub> parameter and
+/// return types:
+///
+/// ```wolfram
+/// (* LibraryFunctions.wl *)
+/// <|
+///     "square" -> {{Integer}, Integer},
+///     "total_i64" -> {{{LibraryDataType[NumericArray, "Integer64"], "Constant"}}, Integer},
+///     "time_since_epoch" -> {LinkObject, LinkObject}
+/// |>
+/// ```
+///
+/// An error is returned, describing every mismatch, if:
+///
+/// * a function in `spec` is not exported by this library,
+/// * a function exported by this library is not in `spec`, or
+/// * the signature of an exported function is different from its signature in `spec`.
+///
+/// Functions whose signature cannot be determined, like those with the signature
+/// `fn(&[MArgument], MArgument)`, are not checked.
+///
+/// Use [`check_signatures!`][crate::check_signatures!] to generate a test that calls this
+/// function.
+///
+/// [ref/LibraryFunctionLoad]: https://reference.wolfram.com/language/ref/LibraryFunctionLoad.html
+pub fn check_signatures(spec: &str) -> Result<(), String> {
+    let entries = parse_spec(spec)?;
+
+    let exported = exported_functions();
+
+    let mut errors = String::new();
+
+    for (name, expected) in &entries {
+        let func = match exported.iter().find(|func| func.name() == name) {
+            Some(func) => func,
+            None => {
+                writeln!(errors, "{}: listed in spec, but not exported", name).unwrap();
+                continue;
+            },
+        };
+
+        let (params, ret) = match func.signature() {
+            Ok(signature) => signature,
+            Err(_) => continue,
+        };
+
+        let actual = Expr::normal(Symbol::new("System`List"), vec![params, ret]);
+
+        if actual != *expected {
+            writeln!(
+                errors,
+                "{}: signature {} in spec does not match exported signature {}",
+                name, expected, actual
+            )
+            .unwrap();
+        }
+    }
+
+    for func in &exported {
+        if !entries.iter().any(|(name, _)| name == func.name()) {
+            writeln!(errors, "{}: exported, but not listed in spec", func.name())
+                .unwrap();
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(format!("exported function signature mismatch:\n{}", errors));
+    }
+
+    Ok(())
+}
+
+//======================================
+// Spec parsing
+//======================================
+
+/// Parse `<| "name" -> {params, ret}, ... |>` into a list of names and `{params, ret}`
+/// expressions.
+fn parse_spec(spec: &str) -> Result<Vec<(String, Expr)>, String> {
+    let mut parser = Parser {
+        input: spec,
+        pos: 0,
+    };
+
+    let expr = parser.parse_expr()?;
+
+    parser.skip_whitespace()?;
+
+    if parser.pos != spec.len() {
+        return Err(parser.error("expected end of input"));
+    }
+
+    let rules = normal_elements(&expr, "System`Association")
+        .or_else(|| normal_elements(&expr, "System`List"))
+        .ok_or_else(|| format!("expected spec to be an Association, got {}", expr))?;
+
+    rules
+        .iter()
+        .map(|rule| match normal_elements(rule, "System`Rule") {
+            Some([name, signature]) => match name.kind() {
+                ExprKind::String(name) => Ok((name.clone(), normalize(signature))),
+                _ => Err(format!(
+                    "expected function name to be a String, got {}",
+                    name
+                )),
+            },
+            _ => Err(format!(
+                "expected \"name\" -> {{params, ret}}, got {}",
+                rule
+            )),
+        })
+        .collect()
+}
+
+/// Replace the `"UTF8String"` type with the equivalent `String` type returned by
+/// [`FromArg::parameter_type()`][crate::FromArg::parameter_type].
+fn normalize(expr: &Expr) -> Expr {
+    match expr.kind() {
+        ExprKind::String(string) if string == "UTF8String" => {
+            Expr::from(Symbol::new("System`String"))
+        },
+        ExprKind::Normal(normal) => Expr::normal(
+            normalize(normal.head()),
+            normal.elements().iter().map(normalize).collect(),
+        ),
+        _ => expr.clone(),
+    }
+}
+
+/// Parser for the subset of Wolfram Language syntax used to write type signatures:
+/// symbols, strings, integers, `f[..]`, `{..}`, `<|..|>`, `a | b`, and `a -> b`.
+struct Parser<'i> {
+    input: &'i str,
+    pos: usize,
+}
+
+impl<'i> Parser<'i> {
+    fn parse_expr(&mut self) -> Result<Expr, String> {
+        let lhs = self.parse_alternatives()?;
+
+        if self.eat("->")? {
+            let rhs = self.parse_expr()?;
+
+            return Ok(Expr::normal(Symbol::new("System`Rule"), vec![lhs, rhs]));
+        }
+
+        Ok(lhs)
+    }
+
+    fn parse_alternatives(&mut self) -> Result<Expr, String> {
+        let first = self.parse_primary()?;
+
+        let mut alternatives = vec![first];
+
+        // Note: `|>` closes an association, and is not an alternative.
+        while self.peek_str("|")? && !self.peek_str("|>")? {
+            self.pos += 1;
+            alternatives.push(self.parse_primary()?);
+        }
+
+        Ok(match alternatives.len() {
+            1 => alternatives.pop().unwrap(),
+            _ => Expr::normal(Symbol::new("System`Alternatives"), alternatives),
+        })
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace()?;
+
+        let mut expr = if self.eat("<|")? {
+            let elements = self.parse_sequence("|>")?;
+            Expr::normal(Symbol::new("System`Association"), elements)
+        } else if self.eat("{")? {
+            let elements = self.parse_sequence("}")?;
+            Expr::normal(Symbol::new("System`List"), elements)
+        } else {
+            match self.rest().chars().next() {
+                Some('"') => self.parse_string()?,
+                Some(c) if c.is_ascii_digit() || c == '-' => self.parse_integer()?,
+                Some(c) if c.is_alphabetic() || c == '$' || c == '`' => {
+                    self.parse_symbol()?
+                },
+                _ => return Err(self.error("expected expression")),
+            }
+        };
+
+        while self.eat("[")? {
+            let elements = self.parse_sequence("]")?;
+            expr = Expr::normal(expr, elements);
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse comma-separated expressions up to and including `close`.
+    fn parse_sequence(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut elements = Vec::new();
+
+        if self.eat(close)? {
+            return Ok(elements);
+        }
+
+        loop {
+            elements.push(self.parse_expr()?);
+
+            if self.eat(close)? {
+                return Ok(elements);
+            }
+
+            if !self.eat(",")? {
+                return Err(self.error(&format!("expected ',' or '{}'", close)));
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<Expr, String> {
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices().skip(1);
+
+        while let Some((offset, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += offset + 1;
+                    return Ok(Expr::string(string));
+                },
+                '\\' => match chars.next() {
+                    Some((_, 'n')) => string.push('\n'),
+                    Some((_, 't')) => string.push('\t'),
+                    Some((_, c)) => string.push(c),
+                    None => break,
+                },
+                c => string.push(c),
+            }
+        }
+
+        Err(self.error("unterminated string"))
+    }
+
+    fn parse_integer(&mut self) -> Result<Expr, String> {
+        let rest = self.rest();
+
+        let len = rest
+            .char_indices()
+            .find(|&(index, c)| !(c.is_ascii_digit() || (index == 0 && c == '-')))
+            .map_or(rest.len(), |(index, _)| index);
+
+        let integer: i64 = rest[..len]
+            .parse()
+            .map_err(|_| self.error("invalid integer"))?;
+
+        self.pos += len;
+
+        Ok(Expr::from(integer))
+    }
+
+    fn parse_symbol(&mut self) -> Result<Expr, String> {
+        let rest = self.rest();
+
+        let len = rest
+            .find(|c: char| !(c.is_alphanumeric() || c == '$' || c == '`'))
+            .unwrap_or(rest.len());
+
+        let name = &rest[..len];
+
+        let name = match name.strip_prefix('`') {
+            Some(name) => format!("Global`{}", name),
+            None if name.contains('`') => name.to_owned(),
+            None => format!("System`{}", name),
+        };
+
+        let symbol = Symbol::try_new(&name)
+            .ok_or_else(|| self.error(&format!("invalid symbol: {}", name)))?;
+
+        self.pos += len;
+
+        Ok(Expr::from(symbol))
+    }
+
+    /// Consume `token` if it is next in the input, after any whitespace.
+    fn eat(&mut self, token: &str) -> Result<bool, String> {
+        if self.peek_str(token)? {
+            self.pos += token.len();
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn peek_str(&mut self, token: &str) -> Result<bool, String> {
+        self.skip_whitespace()?;
+
+        Ok(self.rest().starts_with(token))
+    }
+
+    /// Skip whitespace and `(* .. *)` comments, which may be nested.
+    fn skip_whitespace(&mut self) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.pos += rest.len() - trimmed.len();
+
+            if !trimmed.starts_with("(*") {
+                return Ok(());
+            }
+
+            let mut depth = 0;
+            let mut index = 0;
+
+            while depth > 0 || index == 0 {
+                let remaining = &trimmed[index..];
+
+                if remaining.starts_with("(*") {
+                    depth += 1;
+                    index += 2;
+                } else if remaining.starts_with("*)") {
+                    depth -= 1;
+                    index += 2;
+                } else if let Some(c) = remaining.chars().next() {
+                    index += c.len_utf8();
+                } else {
+                    return Err(self.error("unterminated comment"));
+                }
+            }
+
+            self.pos += index;
+        }
+    }
+
+    fn rest(&self) -> &'i str {
+        &self.input[self.pos..]
+    }
+
+    fn error(&self, message: &str) -> String {
+        let line = self.input[..self.pos].matches('\n').count() + 1;
+
+        format!("spec syntax error on line {}: {}", line, message)
+    }
+}
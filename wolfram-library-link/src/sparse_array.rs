@@ -0,0 +1,720 @@
+// Some of the raw CSR accessors are currently only used by the optional `sprs`
+// conversions.
+#![cfg_attr(not(feature = "sprs"), allow(dead_code))]
+
+use std::fmt;
+use std::mem::MaybeUninit;
+
+use static_assertions::assert_eq_size;
+
+use crate::{
+    rtl,
+    sys::{self, mcomplex, mint, mreal},
+    NumericArray, NumericArrayKind,
+};
+
+/// Native Wolfram [`SparseArray`][ref/SparseArray]<sub>WL</sub>.
+///
+/// This type is an ABI-compatible wrapper around [`wolfram_library_link_sys::MSparseArray`].
+///
+/// A `SparseArray` stores its explicit values in compressed sparse row (CSR) form, which
+/// can be accessed using [`row_pointers()`][SparseArray::row_pointers],
+/// [`column_indices()`][SparseArray::column_indices], and
+/// [`explicit_values()`][SparseArray::explicit_values]. Enable the `"sprs"` feature of
+/// `wolfram-library-link` to convert a `SparseArray` to and from a
+/// [`sprs::CsMat`](https://docs.rs/sprs/latest/sprs/type.CsMat.html) without
+/// materializing the dense array.
+///
+/// [ref/SparseArray]: https://reference.wolfram.com/language/ref/SparseArray.html
+#[repr(transparent)]
+#[derive(ref_cast::RefCast)]
+pub struct SparseArray(sys::MSparseArray);
+
+impl SparseArray {
+    /// Construct a `SparseArray` from a raw [`MSparseArray`][sys::MSparseArray].
+    ///
+    /// # Safety
+    ///
+    /// The following conditions must be met for safe usage of this function:
+    ///
+    /// * `raw` must be a fully initialized and valid sparse array object
+    /// * the caller must own the reference to `raw` being transferred, and must not
+    ///   free, disown, or otherwise use that reference after calling this function. The
+    ///   reference is released when the returned `SparseArray` is dropped.
+    pub unsafe fn from_raw(raw: sys::MSparseArray) -> SparseArray {
+        SparseArray(raw)
+    }
+
+    /// Extract the raw [`MSparseArray`][sys::MSparseArray] instance from this
+    /// `SparseArray`.
+    ///
+    /// # Safety
+    ///
+    /// The reference owned by this `SparseArray` is transferred to the caller, which
+    /// becomes responsible for releasing it, either by returning it to the Kernel,
+    /// converting it back into a `SparseArray` using [`SparseArray::from_raw()`], or
+    /// calling [`MSparseArray_disown()`][rtl::MSparseArray_disown] or
+    /// [`MSparseArray_free()`][rtl::MSparseArray_free]. Failing to do so leaks the
+    /// array.
+    pub unsafe fn into_raw(self) -> sys::MSparseArray {
+        let raw = self.as_raw();
+
+        // Don't run Drop on `self`; ownership of this value is being given to the
+        // caller.
+        std::mem::forget(self);
+
+        raw
+    }
+
+    /// Get the raw [`MSparseArray`][sys::MSparseArray] instance of this `SparseArray`,
+    /// without transferring ownership.
+    ///
+    /// # Safety
+    ///
+    /// The returned pointer is only valid while this `SparseArray` is alive, and must not
+    /// be freed or disowned by the caller.
+    #[inline]
+    pub unsafe fn as_raw(&self) -> sys::MSparseArray {
+        let SparseArray(raw) = *self;
+
+        raw
+    }
+
+    /// Returns the number of dimensions in this sparse array.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getRank`][rtl::MSparseArray_getRank].
+    pub fn rank(&self) -> usize {
+        let rank: mint = unsafe { rtl::MSparseArray_getRank(self.as_raw()) };
+
+        usize::try_from(rank).expect("SparseArray rank overflows usize")
+    }
+
+    /// Returns the dimensions of this sparse array.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getDimensions`][rtl::MSparseArray_getDimensions].
+    pub fn dimensions(&self) -> &[usize] {
+        let rank = self.rank();
+
+        let dims: *const mint = unsafe { rtl::MSparseArray_getDimensions(self.as_raw()) };
+
+        assert_eq_size!(mint, usize);
+        let dims: *const usize = dims as *const usize;
+
+        debug_assert!(!dims.is_null());
+
+        unsafe { std::slice::from_raw_parts(dims, rank) }
+    }
+
+    /// Returns the share count of this `SparseArray`.
+    ///
+    /// If this `SparseArray` is not shared, the share count is 0.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_shareCount`][rtl::MSparseArray_shareCount].
+    pub fn share_count(&self) -> usize {
+        let count: mint = unsafe { rtl::MSparseArray_shareCount(self.as_raw()) };
+
+        usize::try_from(count).expect("SparseArray share count mint overflows usize")
+    }
+
+    /// *LibraryLink C Function:* [`MSparseArray_clone`][rtl::MSparseArray_clone].
+    pub fn try_clone(&self) -> Result<SparseArray, sys::errcode_t> {
+        let mut clone: sys::MSparseArray = std::ptr::null_mut();
+
+        let err_code = unsafe { rtl::MSparseArray_clone(self.as_raw(), &mut clone) };
+
+        if err_code != 0 || clone.is_null() {
+            return Err(err_code);
+        }
+
+        Ok(SparseArray(clone))
+    }
+
+    //
+    // Dense conversions
+    //
+
+    /// Construct a dense [`NumericArray`] containing every element of this sparse
+    /// array, including the implicit elements.
+    ///
+    /// Integer, real, and complex sparse arrays are converted to `"Integer64"`,
+    /// `"Real64"`, and `"ComplexReal64"` arrays, respectively.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_toMTensor`][rtl::MSparseArray_toMTensor].
+    pub fn to_dense_numeric_array(&self) -> Result<NumericArray, String> {
+        let mut tensor: sys::MTensor = std::ptr::null_mut();
+
+        let err_code = unsafe { rtl::MSparseArray_toMTensor(self.as_raw(), &mut tensor) };
+
+        if err_code != 0 || tensor.is_null() {
+            return Err(format!(
+                "failed to convert SparseArray to a dense array: error code {}",
+                err_code
+            ));
+        }
+
+        let result = unsafe { tensor_to_numeric_array(tensor) };
+
+        unsafe { rtl::MTensor_free(tensor) };
+
+        result
+    }
+
+    /// Construct a sparse array from the elements of the dense array `array`.
+    ///
+    /// Elements whose absolute value is less than or equal to `tolerance` are replaced
+    /// by zero, as if by [`Chop`][ref/Chop]<sub>WL</sub>, and the implicit value of the
+    /// returned sparse array is zero. Pass a `tolerance` of `0.0` to only omit elements
+    /// that are exactly zero.
+    ///
+    /// Only `"Integer64"`, `"Real64"`, and `"ComplexReal64"` arrays can be converted;
+    /// use [`NumericArray::convert_to()`] to convert arrays of other types first.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_fromMTensor`][rtl::MSparseArray_fromMTensor].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::{NumericArray, SparseArray};
+    /// let dense = NumericArray::from_array(&[2, 2], &[1.0, 1e-12, 0.0, 2.0]);
+    ///
+    /// let sparse = SparseArray::from_dense(&dense, 1e-10).unwrap();
+    /// ```
+    ///
+    /// [ref/Chop]: https://reference.wolfram.com/language/ref/Chop.html
+    pub fn from_dense<T>(
+        array: &NumericArray<T>,
+        tolerance: f64,
+    ) -> Result<Self, String> {
+        let array: &NumericArray = array.as_generic();
+        let dimensions = array.dimensions();
+
+        let (tensor, zero) = match array.kind() {
+            NumericArrayKind::Bit64(array) => {
+                let data = chop(array.as_slice(), tolerance, 0, |x| (x as f64).abs());
+
+                (
+                    new_integer_tensor(dimensions, &data),
+                    new_integer_tensor(&[], &[0]),
+                )
+            },
+            NumericArrayKind::Real64(array) => {
+                let data = chop(array.as_slice(), tolerance, 0.0, f64::abs);
+
+                (
+                    new_real_tensor(dimensions, &data),
+                    new_real_tensor(&[], &[0.0]),
+                )
+            },
+            NumericArrayKind::ComplexReal64(array) => {
+                let zero = mcomplex { ri: [0.0, 0.0] };
+
+                let data = chop(array.as_slice(), tolerance, zero, |z| {
+                    z.ri[0].hypot(z.ri[1])
+                });
+
+                (
+                    new_complex_tensor(dimensions, &data),
+                    new_complex_tensor(&[], &[zero]),
+                )
+            },
+            _ => {
+                return Err(format!(
+                    "NumericArray of type {} cannot be converted to a SparseArray",
+                    array.data_type().name()
+                ))
+            },
+        };
+
+        let mut sparse: sys::MSparseArray = std::ptr::null_mut();
+
+        let err_code =
+            unsafe { rtl::MSparseArray_fromMTensor(tensor, zero, &mut sparse) };
+
+        unsafe {
+            rtl::MTensor_free(tensor);
+            rtl::MTensor_free(zero);
+        }
+
+        if err_code != 0 || sparse.is_null() {
+            return Err(format!(
+                "failed to convert dense array to a SparseArray: error code {}",
+                err_code
+            ));
+        }
+
+        Ok(SparseArray(sparse))
+    }
+
+    //
+    // Compressed sparse row data
+    //
+
+    /// The implicit ("background") value of this sparse array, as a rank 0 tensor.
+    pub(crate) fn implicit_value_raw(&self) -> sys::MTensor {
+        unsafe { *rtl::MSparseArray_getImplicitValue(self.as_raw()) }
+    }
+
+    /// The explicit values of this sparse array, as a rank 1 tensor.
+    pub(crate) fn explicit_values_raw(&self) -> sys::MTensor {
+        unsafe { *rtl::MSparseArray_getExplicitValues(self.as_raw()) }
+    }
+
+    /// Returns the compressed sparse row (CSR) row pointers of this sparse array.
+    ///
+    /// Row pointers are 0-based offsets into the [explicit values][Self::explicit_values].
+    /// The explicit values in row `i` (0-based) are those with offsets in the range
+    /// `row_pointers[i]..row_pointers[i + 1]`. The slice has one more element than the
+    /// number of rows, and the last element is the
+    /// [number of explicit values][Self::explicit_count].
+    ///
+    /// The row pointers are borrowed from the sparse array, and are not copied.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getRowPointers`][rtl::MSparseArray_getRowPointers].
+    pub fn row_pointers(&self) -> &[mint] {
+        unsafe { tensor_integer_data(*rtl::MSparseArray_getRowPointers(self.as_raw())) }
+    }
+
+    /// Returns the compressed sparse row (CSR) column indices of this sparse array.
+    ///
+    /// Column indices are 1-based. For each explicit value, there are `rank - 1`
+    /// consecutive indices, giving the position of the value within its row, so for a
+    /// matrix there is one column index per explicit value.
+    ///
+    /// The column indices are borrowed from the sparse array, and are not copied.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getColumnIndices`][rtl::MSparseArray_getColumnIndices].
+    pub fn column_indices(&self) -> &[mint] {
+        unsafe { tensor_integer_data(*rtl::MSparseArray_getColumnIndices(self.as_raw())) }
+    }
+
+    /// Returns the number of explicit values stored in this sparse array.
+    pub fn explicit_count(&self) -> usize {
+        let count = self.row_pointers().last().copied().unwrap_or(0);
+
+        usize::try_from(count).expect("SparseArray explicit value count overflows usize")
+    }
+
+    /// Copy the explicit values of this sparse array into a new rank 1 [`NumericArray`].
+    ///
+    /// The values are stored in the same order as the
+    /// [column indices][Self::column_indices]. Integer, real, and complex values are
+    /// copied into `"Integer64"`, `"Real64"`, and `"ComplexReal64"` arrays,
+    /// respectively.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getExplicitValues`][rtl::MSparseArray_getExplicitValues].
+    pub fn explicit_values(&self) -> NumericArray {
+        unsafe { tensor_to_numeric_array(self.explicit_values_raw()) }
+            .expect("SparseArray explicit values have an unsupported element type")
+    }
+
+    /// Copy the implicit ("background") value of this sparse array into a new rank 0
+    /// [`NumericArray`].
+    ///
+    /// Use [`NumericArray::as_scalar()`] to get the value.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getImplicitValue`][rtl::MSparseArray_getImplicitValue].
+    pub fn implicit_value(&self) -> NumericArray {
+        unsafe { tensor_to_numeric_array(self.implicit_value_raw()) }
+            .expect("SparseArray implicit value has an unsupported element type")
+    }
+
+    //
+    // Pattern arrays
+    //
+
+    /// Construct a pattern (structural) sparse array with the same explicit positions
+    /// as this sparse array, where every explicit value is replaced by `1` and the
+    /// implicit value is `0`.
+    ///
+    /// This is equivalent to the
+    /// [`"PatternArray"`][ref/SparseArray]<sub>WL</sub> property of a sparse array.
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_getExplicitPositions`][rtl::MSparseArray_getExplicitPositions].
+    ///
+    /// [ref/SparseArray]: https://reference.wolfram.com/language/ref/SparseArray.html
+    pub fn to_pattern(&self) -> Result<SparseArray, String> {
+        let mut positions: sys::MTensor = std::ptr::null_mut();
+
+        let err_code = unsafe {
+            rtl::MSparseArray_getExplicitPositions(self.as_raw(), &mut positions)
+        };
+
+        if err_code != 0 || positions.is_null() {
+            return Err(format!(
+                "failed to get SparseArray explicit positions: error code {}",
+                err_code
+            ));
+        }
+
+        let count = self.explicit_count();
+        let dimensions: Vec<mint> =
+            self.dimensions().iter().map(|&d| d as mint).collect();
+
+        unsafe {
+            SparseArray::from_explicit_positions(
+                positions,
+                new_integer_tensor(&[count], &vec![1; count]),
+                new_integer_tensor(&[dimensions.len()], &dimensions),
+                new_integer_tensor(&[], &[0]),
+            )
+        }
+        .map_err(|err_code| {
+            format!(
+                "failed to construct pattern SparseArray: error code {}",
+                err_code
+            )
+        })
+    }
+
+    /// Construct a pattern (structural) sparse array with the specified dimensions,
+    /// whose explicit values are `1` at each of `positions`, and whose implicit value is
+    /// `0`.
+    ///
+    /// Each position is a list of 1-based indices, one for each dimension.
+    ///
+    /// # Example
+    ///
+    /// Construct the adjacency matrix of a directed graph with edges `1 -> 2` and
+    /// `2 -> 3`:
+    ///
+    /// ```no_run
+    /// # use wolfram_library_link::SparseArray;
+    /// let adjacency = SparseArray::from_pattern(&[3, 3], &[&[1, 2], &[2, 3]]).unwrap();
+    /// ```
+    ///
+    /// *LibraryLink C Function:* [`MSparseArray_fromExplicitPositions`][rtl::MSparseArray_fromExplicitPositions].
+    pub fn from_pattern(
+        dimensions: &[usize],
+        positions: &[&[usize]],
+    ) -> Result<SparseArray, String> {
+        let rank = dimensions.len();
+
+        let mut flat_positions: Vec<mint> = Vec::with_capacity(rank * positions.len());
+
+        for position in positions {
+            if position.len() != rank {
+                return Err(format!(
+                    "expected position of length {}, got {:?}",
+                    rank, position
+                ));
+            }
+
+            let in_bounds = position
+                .iter()
+                .zip(dimensions)
+                .all(|(&index, &dim)| 1 <= index && index <= dim);
+
+            if !in_bounds {
+                return Err(format!(
+                    "position {:?} is out of bounds for dimensions {:?}",
+                    position, dimensions
+                ));
+            }
+
+            flat_positions.extend(position.iter().map(|&index| index as mint));
+        }
+
+        let count = positions.len();
+        let dims: Vec<mint> = dimensions.iter().map(|&d| d as mint).collect();
+
+        unsafe {
+            SparseArray::from_explicit_positions(
+                new_integer_tensor(&[count, rank], &flat_positions),
+                new_integer_tensor(&[count], &vec![1; count]),
+                new_integer_tensor(&[rank], &dims),
+                new_integer_tensor(&[], &[0]),
+            )
+        }
+        .map_err(|err_code| {
+            format!(
+                "failed to construct pattern SparseArray: error code {}",
+                err_code
+            )
+        })
+    }
+
+    /// Construct a sparse array using
+    /// [`MSparseArray_fromExplicitPositions`][rtl::MSparseArray_fromExplicitPositions].
+    ///
+    /// The input tensors are freed before this function returns.
+    pub(crate) unsafe fn from_explicit_positions(
+        positions: sys::MTensor,
+        values: sys::MTensor,
+        dimensions: sys::MTensor,
+        implicit_value: sys::MTensor,
+    ) -> Result<SparseArray, sys::errcode_t> {
+        let mut sparse: sys::MSparseArray = std::ptr::null_mut();
+
+        let err_code = rtl::MSparseArray_fromExplicitPositions(
+            positions,
+            values,
+            dimensions,
+            implicit_value,
+            &mut sparse,
+        );
+
+        for tensor in [positions, values, dimensions, implicit_value] {
+            rtl::MTensor_free(tensor);
+        }
+
+        if err_code != 0 || sparse.is_null() {
+            return Err(err_code);
+        }
+
+        Ok(SparseArray(sparse))
+    }
+}
+
+/// Copy `data`, replacing elements whose absolute value, as computed by `abs`, is less
+/// than or equal to `tolerance` with `zero`.
+fn chop<T: Copy>(data: &[T], tolerance: f64, zero: T, abs: impl Fn(T) -> f64) -> Vec<T> {
+    data.iter()
+        .map(|&x| if abs(x) <= tolerance { zero } else { x })
+        .collect()
+}
+
+//======================================
+// MTensor utilities
+//======================================
+
+/// Get the element type of `tensor`, e.g. [`sys::MType_Integer`].
+pub(crate) unsafe fn tensor_type(tensor: sys::MTensor) -> u32 {
+    rtl::MTensor_getType(tensor) as u32
+}
+
+/// Get the flattened integer data of `tensor`.
+///
+/// # Panics
+///
+/// Panics if `tensor` does not contain integers.
+pub(crate) unsafe fn tensor_integer_data<'a>(tensor: sys::MTensor) -> &'a [mint] {
+    assert_eq!(tensor_type(tensor), sys::MType_Integer);
+
+    tensor_data(tensor, rtl::MTensor_getIntegerData(tensor))
+}
+
+/// Get the flattened real data of `tensor`.
+///
+/// # Panics
+///
+/// Panics if `tensor` does not contain reals.
+pub(crate) unsafe fn tensor_real_data<'a>(tensor: sys::MTensor) -> &'a [mreal] {
+    assert_eq!(tensor_type(tensor), sys::MType_Real);
+
+    tensor_data(tensor, rtl::MTensor_getRealData(tensor))
+}
+
+/// Get the flattened complex data of `tensor`.
+///
+/// # Panics
+///
+/// Panics if `tensor` does not contain complex numbers.
+pub(crate) unsafe fn tensor_complex_data<'a>(tensor: sys::MTensor) -> &'a [mcomplex] {
+    assert_eq!(tensor_type(tensor), sys::MType_Complex);
+
+    tensor_data(tensor, rtl::MTensor_getComplexData(tensor))
+}
+
+/// Get the dimensions of `tensor`.
+pub(crate) unsafe fn tensor_dimensions<'a>(tensor: sys::MTensor) -> &'a [usize] {
+    let rank = usize::try_from(rtl::MTensor_getRank(tensor))
+        .expect("MTensor rank overflows usize");
+
+    // A rank-0 tensor has no dimensions, and its dimensions pointer may be null.
+    if rank == 0 {
+        return &[];
+    }
+
+    assert_eq_size!(mint, usize);
+
+    let dimensions = rtl::MTensor_getDimensions(tensor) as *const usize;
+
+    debug_assert!(!dimensions.is_null());
+
+    std::slice::from_raw_parts(dimensions, rank)
+}
+
+unsafe fn tensor_data<'a, T>(tensor: sys::MTensor, data: *mut T) -> &'a [T] {
+    let len = usize::try_from(rtl::MTensor_getFlattenedLength(tensor))
+        .expect("MTensor length overflows usize");
+
+    if len == 0 {
+        return &[];
+    }
+
+    debug_assert!(!data.is_null());
+
+    std::slice::from_raw_parts(data, len)
+}
+
+/// Allocate a new integer tensor with the specified dimensions and flattened data.
+pub(crate) fn new_integer_tensor(dimensions: &[usize], data: &[mint]) -> sys::MTensor {
+    unsafe {
+        let tensor = new_tensor(sys::MType_Integer, dimensions, data.len());
+
+        let dest = rtl::MTensor_getIntegerData(tensor);
+        std::ptr::copy_nonoverlapping(data.as_ptr(), dest, data.len());
+
+        tensor
+    }
+}
+
+/// Allocate a new real tensor with the specified dimensions and flattened data.
+pub(crate) fn new_real_tensor(dimensions: &[usize], data: &[mreal]) -> sys::MTensor {
+    unsafe {
+        let tensor = new_tensor(sys::MType_Real, dimensions, data.len());
+
+        let dest = rtl::MTensor_getRealData(tensor);
+        std::ptr::copy_nonoverlapping(data.as_ptr(), dest, data.len());
+
+        tensor
+    }
+}
+
+/// Allocate a new complex tensor with the specified dimensions and flattened data.
+pub(crate) fn new_complex_tensor(
+    dimensions: &[usize],
+    data: &[mcomplex],
+) -> sys::MTensor {
+    unsafe {
+        let tensor = new_tensor(sys::MType_Complex, dimensions, data.len());
+
+        let dest = rtl::MTensor_getComplexData(tensor);
+        std::ptr::copy_nonoverlapping(data.as_ptr(), dest, data.len());
+
+        tensor
+    }
+}
+
+unsafe fn new_tensor(type_: u32, dimensions: &[usize], len: usize) -> sys::MTensor {
+    assert_eq!(
+        dimensions.iter().product::<usize>(),
+        len,
+        "MTensor data length does not match dimensions"
+    );
+
+    assert_eq_size!(mint, usize);
+
+    let mut tensor = MaybeUninit::<sys::MTensor>::uninit();
+
+    let err_code = rtl::MTensor_new(
+        type_ as mint,
+        dimensions.len() as mint,
+        dimensions.as_ptr() as *const mint,
+        tensor.as_mut_ptr(),
+    );
+
+    if err_code != 0 {
+        panic!("MTensor_new failed with error code {}", err_code);
+    }
+
+    tensor.assume_init()
+}
+
+//======================================
+// MTensor <-> NumericArray
+//======================================
+
+// Note: The RTL provides no way for an MTensor and an MNumericArray to share the same
+//       buffer, so these conversions always copy the elements.
+
+/// Copy the elements of `tensor` into a new [`NumericArray`].
+///
+/// Integer, real, and complex tensors are converted to `"Integer64"`, `"Real64"`, and
+/// `"ComplexReal64"` arrays, respectively.
+pub(crate) unsafe fn tensor_to_numeric_array(
+    tensor: sys::MTensor,
+) -> Result<NumericArray, String> {
+    let dimensions = tensor_dimensions(tensor);
+
+    let array = match tensor_type(tensor) {
+        sys::MType_Integer => {
+            NumericArray::from_array(dimensions, tensor_integer_data(tensor))
+                .into_generic()
+        },
+        sys::MType_Real => {
+            NumericArray::from_array(dimensions, tensor_real_data(tensor)).into_generic()
+        },
+        sys::MType_Complex => {
+            NumericArray::from_array(dimensions, tensor_complex_data(tensor))
+                .into_generic()
+        },
+        other => {
+            return Err(format!(
+                "MTensor of element type {} cannot be converted to a NumericArray",
+                other
+            ))
+        },
+    };
+
+    Ok(array)
+}
+
+/// Copy the elements of `array` into a new tensor.
+///
+/// Only `"Integer64"`, `"Real64"`, and `"ComplexReal64"` arrays have an equivalent
+/// tensor type. Use [`NumericArray::convert_to()`] to convert arrays of other types
+/// first.
+pub(crate) fn numeric_array_to_tensor(
+    array: &NumericArray,
+) -> Result<sys::MTensor, String> {
+    let dimensions = array.dimensions();
+
+    let tensor = match array.kind() {
+        NumericArrayKind::Bit64(array) => {
+            new_integer_tensor(dimensions, array.as_slice())
+        },
+        NumericArrayKind::Real64(array) => new_real_tensor(dimensions, array.as_slice()),
+        NumericArrayKind::ComplexReal64(array) => {
+            new_complex_tensor(dimensions, array.as_slice())
+        },
+        _ => {
+            return Err(format!(
+                "NumericArray of type {} cannot be converted to a tensor",
+                array.data_type().name()
+            ))
+        },
+    };
+
+    Ok(tensor)
+}
+
+//======================================
+// Trait Impls
+//======================================
+
+impl Clone for SparseArray {
+    fn clone(&self) -> SparseArray {
+        match self.try_clone() {
+            Ok(clone) => clone,
+            Err(err_code) => panic!(
+                "SparseArray::clone: MSparseArray_clone failed with error code {}",
+                err_code
+            ),
+        }
+    }
+}
+
+impl Drop for SparseArray {
+    fn drop(&mut self) {
+        let SparseArray(raw) = *self;
+
+        if self.share_count() > 0 {
+            // This is a "Shared" sparse array, so we should decrement the reference
+            // count.
+            unsafe { rtl::MSparseArray_disown(raw) }
+        } else {
+            // This is a "Manual" sparse array (or one created within Rust), so we should
+            // free its memory directly.
+            unsafe { rtl::MSparseArray_free(raw) }
+        }
+    }
+}
+
+impl fmt::Debug for SparseArray {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SparseArray")
+            .field("dimensions", &self.dimensions())
+            .finish()
+    }
+}
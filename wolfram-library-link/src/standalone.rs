@@ -0,0 +1,69 @@
+//! Using `wolfram-library-link` from a standalone program, without a Wolfram Kernel.
+
+use crate::{catch_panic::call_and_catch_panic, sys};
+
+/// Initialize `wolfram-library-link` in a standalone program, using the Wolfram Runtime
+/// Library.
+///
+/// *This function is only available if the `"standalone-rtl"` feature of
+/// `wolfram-library-link` is enabled.*
+///
+/// Libraries loaded by the Wolfram Kernel are initialized using the callback functions
+/// provided by the Kernel (see [`initialize()`][crate::initialize]). A standalone
+/// executable, e.g. a command-line tool or a test binary, has no Kernel to provide those
+/// functions. Instead, this function initializes the standalone Wolfram Runtime Library
+/// (`WolframRTL`), and uses the callback functions it provides. After this function
+/// returns successfully, values like [`NumericArray`][crate::NumericArray],
+/// [`Image`][crate::Image], and [`DataStore`][crate::DataStore] can be created and used
+/// as usual.
+///
+/// Functions that require a Kernel — e.g. [`evaluate()`][crate::evaluate] — are not
+/// available in a standalone program.
+///
+/// The thread that calls this function is treated as the main thread. Calling this
+/// function again after it has returned successfully has no effect.
+///
+/// # Example
+///
+/// ```no_run
+/// use wolfram_library_link::{self as wll, NumericArray};
+///
+/// fn main() {
+///     wll::initialize_standalone().expect("failed to initialize the Wolfram RTL");
+///
+///     let array = NumericArray::<f64>::from_slice(&[1.0, 2.0, 3.0]);
+///
+///     println!("sum: {}", array.as_slice().iter().sum::<f64>());
+/// }
+/// ```
+pub fn initialize_standalone() -> Result<(), String> {
+    if crate::library_data::is_initialized() {
+        return Ok(());
+    }
+
+    let version = sys::mint::from(sys::WolframLibraryVersion);
+
+    let err_code = unsafe { sys::WolframRTL_initialize(version) };
+
+    if err_code != 0 {
+        return Err(format!(
+            "WolframRTL_initialize() failed with error code {}",
+            err_code
+        ));
+    }
+
+    let data: sys::WolframLibraryData = unsafe { sys::WolframLibraryData_new(version) };
+
+    if data.is_null() {
+        return Err("WolframLibraryData_new() returned NULL".to_owned());
+    }
+
+    // Note: `data` is never freed, because it may already have been stored in the
+    //       global library data, which lives until the process exits.
+    match call_and_catch_panic(|| unsafe { crate::initialize(data) }) {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(())) | Err(_) => Err(
+            "failed to initialize library data provided by the Wolfram RTL".to_owned(),
+        ),
+    }
+}
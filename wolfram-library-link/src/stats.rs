@@ -0,0 +1,239 @@
+use std::{
+    os::raw::c_uint,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    expr::{Expr, Number, Symbol},
+    macro_utils::{ExportOptions, LibraryLinkFunction},
+    sys,
+};
+
+/// Snapshot of the call statistics of an exported library function.
+///
+/// Use [`stats()`] to get the statistics of every function exported by this library.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionStats {
+    /// The name of the function.
+    pub name: String,
+    /// The number of times the function has been called.
+    pub calls: u64,
+    /// The number of calls that returned an error code, including calls that panicked.
+    pub failures: u64,
+    /// The cumulative time spent in calls to the function.
+    pub total_time: Duration,
+    /// The error code returned by the most recent failed call, if any.
+    pub last_error: Option<c_uint>,
+    /// The time spent in individual calls to the function, if it was exported using
+    /// the `#[timing]` attribute of [`export!`][crate::export] and has been called.
+    pub timing: Option<CallTiming>,
+}
+
+/// Wall time of individual calls to a function exported using the `#[timing]`
+/// attribute of [`export!`][crate::export].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CallTiming {
+    /// The time spent in the fastest call.
+    pub min: Duration,
+    /// The time spent in the slowest call.
+    pub max: Duration,
+    /// The time spent in the most recent call.
+    pub last: Duration,
+}
+
+/// Lock-free counters updated by each call to an exported function.
+#[doc(hidden)]
+pub struct CallStats {
+    calls: AtomicU64,
+    failures: AtomicU64,
+    total_nanos: AtomicU64,
+    last_error: AtomicU32,
+    // Only updated for functions exported using `#[timing]`.
+    min_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+    last_nanos: AtomicU64,
+}
+
+/// Get the call statistics of every function exported by this library using
+/// [`export!`][crate::export] or [`export_wstp!`][crate::export_wstp], or registered
+/// using [`register_native()`][crate::register_native].
+///
+/// Statistics are collected for the lifetime of the loaded library.
+///
+/// This information is also available from the Wolfram Language by loading the
+/// `wll_function_stats` WSTP function that is exported automatically by every library
+/// that uses `wolfram-library-link`:
+///
+/// ```wolfram
+/// LibraryFunctionLoad["library", "wll_function_stats", LinkObject, LinkObject][]
+/// ```
+///
+/// which returns a list of associations with the keys `"Name"`, `"Calls"`,
+/// `"Failures"`, `"TotalTime"` and `"LastError"`. The association of a function
+/// exported using `#[timing]` also has a `"Timing"` key, whose value is an association
+/// with the keys `"Min"`, `"Max"` and `"Last"`.
+///
+/// # Example
+///
+/// Find the exported function that the most time has been spent in:
+///
+/// ```no_run
+/// use wolfram_library_link as wll;
+///
+/// let hottest = wll::stats().into_iter().max_by_key(|stats| stats.total_time);
+/// ```
+pub fn stats() -> Vec<FunctionStats> {
+    let exported = inventory::iter::<LibraryLinkFunction>
+        .into_iter()
+        .map(|func| func.stats().snapshot(func.name(), func.is_timed()));
+
+    let dynamic = crate::dynamic::functions()
+        .into_iter()
+        .map(|func| func.stats.snapshot(&func.name, false));
+
+    exported.chain(dynamic).collect()
+}
+
+impl CallStats {
+    #[allow(missing_docs)]
+    pub const fn new() -> Self {
+        CallStats {
+            calls: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+            total_nanos: AtomicU64::new(0),
+            last_error: AtomicU32::new(sys::LIBRARY_NO_ERROR),
+            min_nanos: AtomicU64::new(u64::MAX),
+            max_nanos: AtomicU64::new(0),
+            last_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Call `func`, recording the time it took and the error code it returned.
+    pub fn record<F: FnOnce() -> c_uint>(&self, func: F) -> c_uint {
+        self.record_with(&ExportOptions::new(), func)
+    }
+
+    /// Call `func`, recording the time it took and the error code it returned. If
+    /// `options` enable timing, the time of this individual call is recorded too.
+    pub fn record_with<F: FnOnce() -> c_uint>(
+        &self,
+        options: &ExportOptions,
+        func: F,
+    ) -> c_uint {
+        let start = Instant::now();
+
+        let err_code = func();
+
+        let nanos = u64::try_from(start.elapsed().as_nanos()).unwrap_or(u64::MAX);
+
+        self.calls.fetch_add(1, Ordering::Relaxed);
+        self.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+
+        if options.is_timed() {
+            self.min_nanos.fetch_min(nanos, Ordering::Relaxed);
+            self.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+            self.last_nanos.store(nanos, Ordering::Relaxed);
+        }
+
+        if err_code != sys::LIBRARY_NO_ERROR {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+            self.last_error.store(err_code, Ordering::Relaxed);
+        }
+
+        err_code
+    }
+
+    fn snapshot(&self, name: &str, timed: bool) -> FunctionStats {
+        let last_error = self.last_error.load(Ordering::Relaxed);
+        let calls = self.calls.load(Ordering::Relaxed);
+
+        let nanos =
+            |value: &AtomicU64| Duration::from_nanos(value.load(Ordering::Relaxed));
+
+        FunctionStats {
+            name: name.to_owned(),
+            calls,
+            failures: self.failures.load(Ordering::Relaxed),
+            total_time: Duration::from_nanos(self.total_nanos.load(Ordering::Relaxed)),
+            last_error: if last_error == sys::LIBRARY_NO_ERROR {
+                None
+            } else {
+                Some(last_error)
+            },
+            timing: if timed && calls > 0 {
+                Some(CallTiming {
+                    min: nanos(&self.min_nanos),
+                    max: nanos(&self.max_nanos),
+                    last: nanos(&self.last_nanos),
+                })
+            } else {
+                None
+            },
+        }
+    }
+}
+
+impl Default for CallStats {
+    fn default() -> Self {
+        CallStats::new()
+    }
+}
+
+impl FunctionStats {
+    /// Construct an [`Association`][ref/Association]<sub>WL</sub> describing these
+    /// statistics.
+    ///
+    /// [ref/Association]: https://reference.wolfram.com/language/ref/Association.html
+    pub(crate) fn to_expr(&self) -> Expr {
+        let FunctionStats {
+            ref name,
+            calls,
+            failures,
+            total_time,
+            last_error,
+            timing,
+        } = *self;
+
+        let rule = |key: &str, value: Expr| {
+            Expr::normal(Symbol::new("System`Rule"), vec![Expr::string(key), value])
+        };
+
+        let count = |value: u64| Expr::from(i64::try_from(value).unwrap_or(i64::MAX));
+
+        let seconds = |duration: Duration| {
+            Expr::normal(Symbol::new("System`Quantity"), vec![
+                Expr::number(Number::real(duration.as_secs_f64())),
+                Expr::string("Seconds"),
+            ])
+        };
+
+        let last_error = match last_error {
+            Some(code) => Expr::from(i64::from(code)),
+            None => Expr::normal(Symbol::new("System`Missing"), vec![Expr::string(
+                "NotAvailable",
+            )]),
+        };
+
+        let mut fields = vec![
+            rule("Name", Expr::string(name)),
+            rule("Calls", count(calls)),
+            rule("Failures", count(failures)),
+            rule("TotalTime", seconds(total_time)),
+            rule("LastError", last_error),
+        ];
+
+        if let Some(CallTiming { min, max, last }) = timing {
+            fields.push(rule(
+                "Timing",
+                Expr::normal(Symbol::new("System`Association"), vec![
+                    rule("Min", seconds(min)),
+                    rule("Max", seconds(max)),
+                    rule("Last", seconds(last)),
+                ]),
+            ));
+        }
+
+        Expr::normal(Symbol::new("System`Association"), fields)
+    }
+}
@@ -0,0 +1,209 @@
+//! Generation of Wolfram Language test file skeletons for the functions exported by
+//! this library.
+
+use std::path::PathBuf;
+
+use crate::{
+    convert::normal_elements,
+    exported_functions,
+    expr::{Expr, ExprKind, Number, Symbol},
+    macro_utils::{library_function_load_expr, LoaderOptions},
+    overloads::Overloads,
+    wl_source::to_source,
+};
+
+/// Generate the contents of a `.wlt` file containing one
+/// [`VerificationTest`][ref/VerificationTest]<sub>WL</sub> for each function that the
+/// loader function generated by [`generate_loader!`][crate::generate_loader] would
+/// load from `library`.
+///
+/// Each test loads the function using the same code as the loader function, and calls
+/// it with placeholder arguments of the correct types:
+///
+/// ```wolfram
+/// VerificationTest[
+///     LibraryFunctionLoad["library", "add2", {Integer, Integer}, Integer][0, 0]
+///     ,
+///     Missing["TODO"]
+///     ,
+///     TestID -> "add2"
+/// ]
+/// ```
+///
+/// Arguments of types that no placeholder value is known for are written as
+/// `Missing["TODO"]`.
+///
+/// [ref/VerificationTest]: https://reference.wolfram.com/language/ref/VerificationTest.html
+pub(crate) fn test_stubs(library: PathBuf) -> String {
+    let loader = library_function_load_expr(library.clone(), &LoaderOptions::default());
+
+    let mut functions: Vec<(String, Expr)> =
+        normal_elements(&loader, "System`Association")
+            .unwrap_or(&[])
+            .iter()
+            .filter_map(|rule| match normal_elements(rule, "System`Rule") {
+                // Skip functions that the loader function could not load.
+                Some([_, code]) if normal_elements(code, "System`Failure").is_some() => {
+                    None
+                },
+                Some([name, code]) => match name.kind() {
+                    ExprKind::String(name) => Some((name.clone(), code.clone())),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect();
+
+    functions.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut source = format!(
+        "(* Tests for the functions exported by {}. *)\n\nNeeds[\"MUnit`\"]\n",
+        to_source(&Expr::string(library.display().to_string()))
+    );
+
+    for (name, code) in &functions {
+        let args = parameter_types(name)
+            .unwrap_or_default()
+            .iter()
+            .map(placeholder_argument)
+            .collect();
+
+        let test = Expr::normal(sys("VerificationTest"), vec![
+            Expr::normal(code.clone(), args),
+            missing_todo(),
+            Expr::normal(sys("Rule"), vec![
+                Expr::from(sys("TestID")),
+                Expr::string(name.as_str()),
+            ]),
+        ]);
+
+        source.push('\n');
+        source.push_str(&format_test(&test));
+    }
+
+    source
+}
+
+/// Get the parameter types of the function that the loader function loads under
+/// `name`.
+///
+/// WSTP functions have no fixed parameter types, and are called with no arguments.
+fn parameter_types(name: &str) -> Option<Vec<Expr>> {
+    let exported = exported_functions();
+
+    // Overloaded functions take precedence over exported functions with the same
+    // name. Use the parameter types of the first variant.
+    let name = inventory::iter::<Overloads>
+        .into_iter()
+        .find(|overloads| overloads.name == name)
+        .and_then(|overloads| overloads.variants.first().copied())
+        .unwrap_or(name);
+
+    let (params, _) = exported
+        .iter()
+        .find(|func| func.name() == name)?
+        .signature()
+        .ok()?;
+
+    Some(
+        normal_elements(&params, "System`List")
+            .unwrap_or(&[])
+            .to_vec(),
+    )
+}
+
+/// Get a placeholder value that can be passed to a parameter of type `param`, as
+/// returned by [`FromArg::parameter_type()`][crate::FromArg::parameter_type].
+fn placeholder_argument(param: &Expr) -> Expr {
+    // {type, "Constant" | "Manual" | "Shared"}
+    if let Some([ty, mode]) = normal_elements(param, "System`List") {
+        if let ExprKind::String(_) = mode.kind() {
+            return placeholder_argument(ty);
+        }
+    }
+
+    if let Some(elements) = normal_elements(param, "System`LibraryDataType") {
+        let (head, ty) = match elements {
+            [head] => (head, None),
+            [head, ty, ..] => (head, Some(ty.clone())),
+            [] => return missing_todo(),
+        };
+
+        // Image | Image3D
+        let head = normal_elements(head, "System`Alternatives")
+            .and_then(|heads| heads.first())
+            .unwrap_or(head);
+
+        let head = match head.kind() {
+            ExprKind::Symbol(symbol) => symbol.as_str(),
+            _ => return missing_todo(),
+        };
+
+        let zero = Expr::from(0i64);
+        let list = |elements: Vec<Expr>| Expr::normal(sys("List"), elements);
+
+        return match head {
+            "System`NumericArray" => Expr::normal(sys("NumericArray"), vec![
+                list(vec![zero]),
+                ty.unwrap_or_else(|| Expr::string("Integer64")),
+            ]),
+            "System`SparseArray" => {
+                Expr::normal(sys("SparseArray"), vec![list(vec![zero])])
+            },
+            "System`Image" => Expr::normal(
+                sys("Image"),
+                std::iter::once(list(vec![list(vec![zero])]))
+                    .chain(ty)
+                    .collect(),
+            ),
+            "System`Image3D" => Expr::normal(
+                sys("Image3D"),
+                std::iter::once(list(vec![list(vec![list(vec![zero])])]))
+                    .chain(ty)
+                    .collect(),
+            ),
+            _ => missing_todo(),
+        };
+    }
+
+    match param.kind() {
+        ExprKind::Symbol(symbol) => match symbol.as_str() {
+            "System`Integer" => Expr::from(0i64),
+            "System`Real" => Expr::number(Number::real(0.0)),
+            "System`Complex" => Expr::normal(sys("Complex"), vec![
+                Expr::number(Number::real(0.0)),
+                Expr::number(Number::real(0.0)),
+            ]),
+            "System`String" => Expr::string(""),
+            _ => missing_todo(),
+        },
+        ExprKind::String(ty) => match ty.as_str() {
+            "Boolean" => Expr::from(sys("False")),
+            "UTF8String" => Expr::string(""),
+            "DataStore" => Expr::normal(Symbol::new("Developer`DataStore"), vec![]),
+            _ => missing_todo(),
+        },
+        _ => missing_todo(),
+    }
+}
+
+/// Format a `VerificationTest[input, expected, options]` expression in the layout
+/// conventionally used in `.wlt` files.
+fn format_test(test: &Expr) -> String {
+    let elements = normal_elements(test, "System`VerificationTest").unwrap_or(&[]);
+
+    let parts: Vec<String> = elements
+        .iter()
+        .map(|elem| format!("\t{}\n", to_source(elem)))
+        .collect();
+
+    format!("VerificationTest[\n{}]\n", parts.join("\t,\n"))
+}
+
+fn missing_todo() -> Expr {
+    Expr::normal(sys("Missing"), vec![Expr::string("TODO")])
+}
+
+fn sys(name: &str) -> Symbol {
+    Symbol::new(&format!("System`{}", name))
+}
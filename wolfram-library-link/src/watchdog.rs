@@ -0,0 +1,301 @@
+//! Detect library functions that run for longer than expected.
+//!
+//! A library function that hangs, for example because a native dependency is stuck
+//! waiting on a lock or a network connection, blocks the Wolfram Kernel until it
+//! returns. [`guard()`] starts a watchdog that monitors the running function from a
+//! shared background thread. If the function is still running when its time budget
+//! runs out, the watchdog:
+//!
+//! * marks the [`Watchdog`] as expired, which the function can poll using
+//!   [`Watchdog::is_expired()`] or [`Watchdog::check()`] to stop early,
+//! * records the overrun, which can be retrieved using [`overruns()`],
+//! * raises an asynchronous event, if one was configured using
+//!   [`Watchdog::raise_event()`], and
+//! * cancels a [`CancelToken`], if one was configured using
+//!   [`Watchdog::cancel_token()`].
+//!
+//! The watchdog cannot stop the function itself. Long-running functions should poll
+//! the watchdog regularly, in the same way that they poll
+//! [`aborted()`][crate::aborted].
+//!
+//! # Example
+//!
+//! ```no_run
+//! # mod scope {
+//! use std::time::Duration;
+//!
+//! use wolfram_library_link::{self as wll, watchdog};
+//!
+//! wll::export![sum_of_squares(_)];
+//!
+//! fn sum_of_squares(n: i64) -> i64 {
+//!     let watchdog = watchdog::guard("sum_of_squares", Duration::from_secs(5));
+//!
+//!     let mut sum: i64 = 0;
+//!
+//!     for i in 0..n {
+//!         if i % 1000 == 0 && watchdog.is_expired() {
+//!             panic!("sum_of_squares: time budget exceeded");
+//!         }
+//!
+//!         sum = sum.wrapping_add(i * i);
+//!     }
+//!
+//!     sum
+//! }
+//! # }
+//! ```
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Condvar, Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{sys, AsyncTaskObject, CancelReason, CancelToken, DataStore};
+
+/// The maximum number of overruns retained by [`overruns()`]. Older overruns are
+/// discarded first.
+const MAX_OVERRUNS: usize = 100;
+
+static OVERRUNS: Lazy<Mutex<VecDeque<Overrun>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+
+/// Source of the IDs that distinguish watchdogs with the same deadline.
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+/// The timer shared by all watchdogs. The timer thread is spawned the first time a
+/// watchdog is started.
+static TIMER: Lazy<Timer> = Lazy::new(|| {
+    thread::Builder::new()
+        .name("wll-watchdog".to_owned())
+        .spawn(|| TIMER.run())
+        .expect("failed to spawn watchdog thread");
+
+    Timer {
+        queue: Mutex::new(BTreeMap::new()),
+        changed: Condvar::new(),
+    }
+});
+
+/// Monitors a running library function. Construct a `Watchdog` using [`guard()`].
+///
+/// The watchdog stops monitoring when it is dropped.
+#[must_use = "the watchdog stops monitoring when it is dropped"]
+pub struct Watchdog {
+    shared: Arc<Shared>,
+    /// Key of this watchdog in the queue of the [`Timer`], or `None` if its deadline is
+    /// too far in the future to be represented, in which case it never expires.
+    key: Option<TimerKey>,
+}
+
+/// Deadline and ID of a running watchdog.
+type TimerKey = (Instant, u64);
+
+/// Expires watchdogs whose deadline has passed, on a single background thread.
+struct Timer {
+    /// Running watchdogs, ordered by deadline.
+    queue: Mutex<BTreeMap<TimerKey, Arc<Shared>>>,
+    /// Notified when a watchdog is added to `queue`.
+    changed: Condvar,
+}
+
+struct Shared {
+    name: String,
+    /// The name of the thread that started the watchdog, if it has one.
+    thread: Option<String>,
+    budget: Duration,
+    start: Instant,
+    expired: AtomicBool,
+    /// Asynchronous task ID and name of the event to raise when the budget is exceeded.
+    event: Mutex<Option<(sys::mint, String)>>,
+    /// Token to cancel when the budget is exceeded.
+    token: Mutex<Option<CancelToken>>,
+}
+
+/// A library function that ran for longer than the budget of its [`Watchdog`].
+///
+/// Use [`overruns()`] to get the recent overruns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Overrun {
+    /// The name passed to [`guard()`], usually the name of the library function.
+    pub name: String,
+    /// The time budget that was exceeded.
+    pub budget: Duration,
+    /// The name of the thread the function was running on, if it has one.
+    pub thread: Option<String>,
+}
+
+/// Start a [`Watchdog`] that expires if it is not dropped within `budget`.
+///
+/// `name` identifies the monitored function in the [`Overrun`] recorded if the budget
+/// is exceeded.
+///
+/// A watchdog whose budget is too large to compute a deadline, e.g. [`Duration::MAX`],
+/// never expires.
+///
+/// See the [module documentation](self) for more information.
+///
+/// # Panics
+///
+/// This function will panic if the watchdog thread could not be spawned. The thread is
+/// spawned the first time this function is called, and is shared by all watchdogs.
+pub fn guard(name: &str, budget: Duration) -> Watchdog {
+    let start = Instant::now();
+
+    let shared = Arc::new(Shared {
+        name: name.to_owned(),
+        thread: thread::current().name().map(str::to_owned),
+        budget,
+        start,
+        expired: AtomicBool::new(false),
+        event: Mutex::new(None),
+        token: Mutex::new(None),
+    });
+
+    let key: Option<TimerKey> = start
+        .checked_add(budget)
+        .map(|deadline| (deadline, NEXT_ID.fetch_add(1, Ordering::Relaxed)));
+
+    if let Some(key) = key {
+        TIMER.queue.lock().unwrap().insert(key, Arc::clone(&shared));
+        TIMER.changed.notify_one();
+    }
+
+    Watchdog { shared, key }
+}
+
+/// Get the most recent library function overruns detected by a [`Watchdog`], oldest
+/// first.
+///
+/// At most the 100 most recent overruns are retained.
+pub fn overruns() -> Vec<Overrun> {
+    OVERRUNS.lock().unwrap().iter().cloned().collect()
+}
+
+impl Watchdog {
+    /// Returns `true` if the function has exceeded the time budget of this watchdog.
+    ///
+    /// This is a cheap atomic load, and can be called frequently.
+    pub fn is_expired(&self) -> bool {
+        self.shared.expired.load(Ordering::Acquire)
+    }
+
+    /// Returns an error if the function has exceeded the time budget of this watchdog.
+    pub fn check(&self) -> Result<(), String> {
+        if self.is_expired() {
+            return Err(format!("time budget of {:?} exceeded", self.shared.budget));
+        }
+
+        Ok(())
+    }
+
+    /// Get the time budget of this watchdog.
+    pub fn budget(&self) -> Duration {
+        self.shared.budget
+    }
+
+    /// Get the time elapsed since this watchdog was started.
+    pub fn elapsed(&self) -> Duration {
+        self.shared.start.elapsed()
+    }
+
+    /// Raise the asynchronous event `name` on `task` if the function exceeds the time
+    /// budget of this watchdog.
+    ///
+    /// The event data is a [`DataStore`] containing the budget, in seconds, as a real
+    /// number. If the budget has already been exceeded, the event is not raised.
+    ///
+    /// See [`AsyncTaskObject::raise_async_event()`].
+    pub fn raise_event(self, task: &AsyncTaskObject, name: &str) -> Self {
+        *self.shared.event.lock().unwrap() = Some((task.id(), name.to_owned()));
+        self
+    }
+
+    /// Cancel `token`, with [`CancelReason::Watchdog`], if the function exceeds the time
+    /// budget of this watchdog.
+    ///
+    /// If the budget has already been exceeded, `token` is not cancelled.
+    pub fn cancel_token(self, token: &CancelToken) -> Self {
+        *self.shared.token.lock().unwrap() = Some(token.clone());
+        self
+    }
+}
+
+impl Drop for Watchdog {
+    fn drop(&mut self) {
+        if let Some(key) = self.key {
+            let _: Option<Arc<Shared>> = TIMER.queue.lock().unwrap().remove(&key);
+        }
+    }
+}
+
+impl Timer {
+    /// Body of the watchdog thread.
+    fn run(&self) {
+        let mut queue = self.queue.lock().unwrap();
+
+        loop {
+            let deadline = match queue.keys().next() {
+                Some(&(deadline, _)) => deadline,
+                None => {
+                    queue = self.changed.wait(queue).unwrap();
+                    continue;
+                },
+            };
+
+            let now = Instant::now();
+
+            if deadline > now {
+                queue = self.changed.wait_timeout(queue, deadline - now).unwrap().0;
+                continue;
+            }
+
+            let (_, shared) = queue.pop_first().unwrap();
+
+            // Don't block other watchdogs from being started or dropped while the
+            // actions of this one run.
+            drop(queue);
+            shared.expire();
+            queue = self.queue.lock().unwrap();
+        }
+    }
+}
+
+impl Shared {
+    /// Called on the watchdog thread when the budget is exceeded.
+    fn expire(&self) {
+        self.expired.store(true, Ordering::Release);
+
+        {
+            let mut overruns = OVERRUNS.lock().unwrap();
+
+            if overruns.len() == MAX_OVERRUNS {
+                overruns.pop_front();
+            }
+
+            overruns.push_back(Overrun {
+                name: self.name.clone(),
+                budget: self.budget,
+                thread: self.thread.clone(),
+            });
+        }
+
+        if let Some(token) = self.token.lock().unwrap().take() {
+            token.cancel_with(CancelReason::Watchdog);
+        }
+
+        if let Some((task_id, name)) = self.event.lock().unwrap().take() {
+            let mut data = DataStore::new();
+            data.add_f64(self.budget.as_secs_f64());
+
+            AsyncTaskObject::from_id(task_id).raise_async_event(&name, data);
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use std::{
+    convert::TryFrom,
+    fmt,
+    os::raw::{c_int, c_ushort},
+};
+
+use crate::wstp::{self, Link};
+
+/// Byte order mark, which WSTP includes at the start of strings read using
+/// `WSGetUTF16String()`.
+const BYTE_ORDER_MARK: u16 = 0xFEFF;
+
+/// Wolfram Language string stored as UTF-16 code units.
+///
+/// Wolfram Language strings are sequences of 16-bit characters, and can contain
+/// unpaired surrogate characters (e.g. `FromCharacterCode[16^^D800]`), which cannot be
+/// represented by a Rust [`String`]. `WideString` stores the characters of a string
+/// exactly, so that they can be inspected, or passed on to platform APIs which use
+/// UTF-16, such as those on Windows, without loss.
+///
+/// A `WideString` can be:
+///
+/// * used as a parameter or return type of functions exported using
+///   [`export!`][crate::export]. The corresponding Wolfram *LibraryLink* type is
+///   `String`. Unpaired surrogates are exchanged using their generalized UTF-8
+///   encoding.
+/// * read from and written to a [`Link`] using [`WideString::get()`] and
+///   [`WideString::put()`], in functions exported using
+///   [`export_wstp!`][crate::export_wstp].
+///
+/// # Example
+///
+/// ```no_run
+/// # mod scope {
+/// use wolfram_library_link::{self as wll, WideString};
+///
+/// wll::export![utf16_length(_)];
+///
+/// fn utf16_length(string: WideString) -> i64 {
+///     string.len() as i64
+/// }
+/// # }
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash, Default)]
+pub struct WideString(Vec<u16>);
+
+impl WideString {
+    /// Construct a `WideString` from UTF-16 code units.
+    ///
+    /// `units` may contain unpaired surrogates.
+    pub fn from_utf16(units: Vec<u16>) -> WideString {
+        WideString(units)
+    }
+
+    /// The UTF-16 code units of this string.
+    pub fn as_utf16(&self) -> &[u16] {
+        &self.0
+    }
+
+    /// Convert this string into its UTF-16 code units.
+    pub fn into_utf16(self) -> Vec<u16> {
+        self.0
+    }
+
+    /// Returns the number of UTF-16 code units in this string.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if this string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if this string contains a surrogate character that is not part of
+    /// a valid surrogate pair, and so cannot be converted into a [`String`] without loss.
+    pub fn has_unpaired_surrogates(&self) -> bool {
+        char::decode_utf16(self.0.iter().copied()).any(|result| result.is_err())
+    }
+
+    /// Convert this string into a [`String`], replacing any unpaired surrogates with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][char::REPLACEMENT_CHARACTER].
+    ///
+    /// Use [`String::try_from()`] to detect unpaired surrogates instead.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf16_lossy(&self.0)
+    }
+
+    /// Convert this string into an [`OsString`][std::ffi::OsString].
+    ///
+    /// This conversion is lossless, including for strings containing unpaired
+    /// surrogates.
+    ///
+    /// *This function is only available on Windows.*
+    #[cfg(windows)]
+    pub fn to_os_string(&self) -> std::ffi::OsString {
+        use std::os::windows::ffi::OsStringExt;
+
+        std::ffi::OsString::from_wide(&self.0)
+    }
+
+    //
+    // WSTP
+    //
+
+    /// Read a string from `link`.
+    ///
+    /// *WSTP C Function:* `WSGetUTF16String()`
+    pub fn get(link: &mut Link) -> Result<WideString, String> {
+        let raw_link = unsafe { link.raw_link() };
+
+        let mut data: *const c_ushort = std::ptr::null();
+        let mut len: c_int = 0;
+        let mut chars: c_int = 0;
+
+        if unsafe {
+            wstp::sys::WSGetUTF16String(raw_link, &mut data, &mut len, &mut chars)
+        } == 0
+        {
+            return Err(link_error(link, "expected String"));
+        }
+
+        let mut units: &[u16] = match usize::try_from(len) {
+            Ok(len) if len > 0 && !data.is_null() => unsafe {
+                std::slice::from_raw_parts(data, len)
+            },
+            _ => &[],
+        };
+
+        if units.first() == Some(&BYTE_ORDER_MARK) {
+            units = &units[1..];
+        }
+
+        let string = WideString(units.to_vec());
+
+        unsafe { wstp::sys::WSReleaseUTF16String(raw_link, data, len) };
+
+        Ok(string)
+    }
+
+    /// Write this string to `link`.
+    ///
+    /// *WSTP C Function:* `WSPutUTF16String()`
+    pub fn put(&self, link: &mut Link) -> Result<(), String> {
+        let len = c_int::try_from(self.0.len())
+            .map_err(|_| format!("string is too long: {}", self.0.len()))?;
+
+        let raw_link = unsafe { link.raw_link() };
+
+        if unsafe { wstp::sys::WSPutUTF16String(raw_link, self.0.as_ptr(), len) } == 0 {
+            return Err(link_error(link, "failed to put String"));
+        }
+
+        Ok(())
+    }
+
+    //
+    // Generalized UTF-8
+    //
+
+    /// Decode a string from generalized UTF-8, in which unpaired surrogates are encoded
+    /// like any other code point.
+    ///
+    /// Invalid byte sequences are replaced with
+    /// [`U+FFFD REPLACEMENT CHARACTER`][char::REPLACEMENT_CHARACTER].
+    pub(crate) fn from_generalized_utf8(bytes: &[u8]) -> WideString {
+        let mut units: Vec<u16> = Vec::with_capacity(bytes.len());
+
+        let mut index = 0;
+
+        while index < bytes.len() {
+            let lead = bytes[index];
+
+            let (initial, len) = match lead {
+                0x00..=0x7F => (u32::from(lead), 1),
+                0xC0..=0xDF => (u32::from(lead & 0x1F), 2),
+                0xE0..=0xEF => (u32::from(lead & 0x0F), 3),
+                0xF0..=0xF7 => (u32::from(lead & 0x07), 4),
+                _ => {
+                    units.push(char::REPLACEMENT_CHARACTER as u16);
+                    index += 1;
+                    continue;
+                },
+            };
+
+            let code_point = bytes
+                .get(index + 1..index + len)
+                .filter(|rest| rest.iter().all(|byte| byte & 0xC0 == 0x80))
+                .map(|rest| {
+                    rest.iter()
+                        .fold(initial, |acc, byte| (acc << 6) | u32::from(byte & 0x3F))
+                })
+                .filter(|code_point| *code_point <= 0x10FFFF);
+
+            match code_point {
+                Some(code_point) if code_point >= 0x10000 => {
+                    let offset = code_point - 0x10000;
+                    units.push(0xD800 | (offset >> 10) as u16);
+                    units.push(0xDC00 | (offset & 0x3FF) as u16);
+                    index += len;
+                },
+                Some(code_point) => {
+                    units.push(code_point as u16);
+                    index += len;
+                },
+                None => {
+                    units.push(char::REPLACEMENT_CHARACTER as u16);
+                    index += 1;
+                },
+            }
+        }
+
+        WideString(units)
+    }
+
+    /// Encode this string as generalized UTF-8. Unpaired surrogates are encoded as
+    /// three byte sequences.
+    pub(crate) fn to_generalized_utf8(&self) -> Vec<u8> {
+        let mut bytes: Vec<u8> = Vec::with_capacity(self.0.len());
+
+        for result in char::decode_utf16(self.0.iter().copied()) {
+            match result {
+                Ok(char) => {
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(char.encode_utf8(&mut buffer).as_bytes());
+                },
+                Err(err) => {
+                    let unit = err.unpaired_surrogate();
+                    bytes.extend([
+                        0xE0 | (unit >> 12) as u8,
+                        0x80 | ((unit >> 6) & 0x3F) as u8,
+                        0x80 | (unit & 0x3F) as u8,
+                    ]);
+                },
+            }
+        }
+
+        bytes
+    }
+}
+
+fn link_error(link: &Link, context: &str) -> String {
+    match link.error_message() {
+        Some(message) => format!("{}: {}", context, message),
+        None => context.to_owned(),
+    }
+}
+
+//======================================
+// Trait Impls
+//======================================
+
+impl From<&str> for WideString {
+    fn from(string: &str) -> WideString {
+        WideString(string.encode_utf16().collect())
+    }
+}
+
+impl From<String> for WideString {
+    fn from(string: String) -> WideString {
+        WideString::from(string.as_str())
+    }
+}
+
+impl TryFrom<&WideString> for String {
+    type Error = String;
+
+    /// Fails if `string` contains unpaired surrogates.
+    fn try_from(string: &WideString) -> Result<String, String> {
+        String::from_utf16(&string.0).map_err(|err| err.to_string())
+    }
+}
+
+#[cfg(windows)]
+impl From<&std::ffi::OsStr> for WideString {
+    fn from(string: &std::ffi::OsStr) -> WideString {
+        use std::os::windows::ffi::OsStrExt;
+
+        WideString(string.encode_wide().collect())
+    }
+}
+
+impl fmt::Debug for WideString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "WideString({:?})", self.to_string_lossy())
+    }
+}
+
+impl fmt::Display for WideString {
+    /// Unpaired surrogates are displayed as
+    /// [`U+FFFD REPLACEMENT CHARACTER`][char::REPLACEMENT_CHARACTER].
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_string_lossy())
+    }
+}
@@ -0,0 +1,95 @@
+//! Formatting of expressions as Wolfram Language source code, used to generate `.wl`
+//! and `.wlt` files.
+
+use std::fmt::Write;
+
+use crate::expr::{Expr, ExprKind, Symbol};
+
+/// Format `expr` as Wolfram Language source code.
+///
+/// Symbols in the ``System` `` context are written without their context, and `List`,
+/// `Association`, `Rule`, `Alternatives`, `Slot[1]`, and `SlotSequence[1]` expressions
+/// are written using their operator forms.
+pub(crate) fn to_source(expr: &Expr) -> String {
+    let mut source = String::new();
+    write_source(&mut source, expr);
+    source
+}
+
+fn write_source(out: &mut String, expr: &Expr) {
+    match expr.kind() {
+        ExprKind::Integer(int) => write!(out, "{}", int).unwrap(),
+        ExprKind::Real(real) => {
+            let real: f64 = **real;
+
+            if real.is_finite() && real.fract() == 0.0 {
+                write!(out, "{:.0}.", real).unwrap()
+            } else {
+                write!(out, "{}", real).unwrap()
+            }
+        },
+        ExprKind::String(string) => write_string(out, string),
+        ExprKind::Symbol(symbol) => {
+            let name = symbol.as_str();
+            out.push_str(name.strip_prefix("System`").unwrap_or(name));
+        },
+        ExprKind::Normal(normal) => {
+            let elements = normal.elements();
+
+            let operator = |name: &str| normal.has_head(&Symbol::new(name));
+
+            if operator("System`List") {
+                out.push('{');
+                write_sequence(out, elements, ", ");
+                out.push('}');
+            } else if operator("System`Association") {
+                out.push_str("<|");
+                write_sequence(out, elements, ", ");
+                out.push_str("|>");
+            } else if operator("System`Rule") && elements.len() == 2 {
+                write_sequence(out, elements, " -> ");
+            } else if operator("System`Alternatives") && !elements.is_empty() {
+                write_sequence(out, elements, " | ");
+            } else if operator("System`Slot") && is_one(elements) {
+                out.push('#');
+            } else if operator("System`SlotSequence") && is_one(elements) {
+                out.push_str("##");
+            } else {
+                write_source(out, normal.head());
+                out.push('[');
+                write_sequence(out, elements, ", ");
+                out.push(']');
+            }
+        },
+    }
+}
+
+fn write_sequence(out: &mut String, elements: &[Expr], separator: &str) {
+    for (index, elem) in elements.iter().enumerate() {
+        if index > 0 {
+            out.push_str(separator);
+        }
+
+        write_source(out, elem);
+    }
+}
+
+fn is_one(elements: &[Expr]) -> bool {
+    matches!(elements, [elem] if matches!(elem.kind(), ExprKind::Integer(1)))
+}
+
+fn write_string(out: &mut String, string: &str) {
+    out.push('"');
+
+    for char in string.chars() {
+        match char {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            char => out.push(char),
+        }
+    }
+
+    out.push('"');
+}
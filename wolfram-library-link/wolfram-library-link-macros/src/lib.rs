@@ -1,7 +1,7 @@
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{spanned::Spanned, Error, Item};
 
 //======================================
@@ -91,3 +91,184 @@ fn init_(attr: TokenStream2, item: TokenStream) -> Result<TokenStream2, Error> {
 
     Ok(output)
 }
+
+//======================================
+// #[derive(ManagedExpression)]
+//======================================
+
+#[proc_macro_derive(ManagedExpression, attributes(managed_expression))]
+pub fn derive_managed_expression(input: TokenStream) -> TokenStream {
+    match derive_managed_expression_(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.into_compile_error().into(),
+    }
+}
+
+fn derive_managed_expression_(input: TokenStream) -> Result<TokenStream2, Error> {
+    let input: syn::DeriveInput = syn::parse(input)?;
+
+    // No generics
+    if let Some(lt) = input.generics.lt_token {
+        return Err(Error::new(
+            lt.span(),
+            "managed expression types cannot be generic",
+        ));
+    }
+
+    //-------------------------------------------------------------------
+    // Parse the optional `#[managed_expression(name = "...")]` attribute.
+    //-------------------------------------------------------------------
+
+    let ident: syn::Ident = input.ident;
+
+    let mut name: Option<syn::LitStr> = None;
+
+    for attr in &input.attrs {
+        if !attr.path.is_ident("managed_expression") {
+            continue;
+        }
+
+        let list = match attr.parse_meta()? {
+            syn::Meta::List(list) => list,
+            meta => {
+                return Err(Error::new(
+                    meta.span(),
+                    "expected `#[managed_expression(name = \"...\")]`",
+                ))
+            },
+        };
+
+        for nested in list.nested {
+            match nested {
+                syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                    ref path,
+                    lit: syn::Lit::Str(ref lit),
+                    ..
+                })) if path.is_ident("name") => {
+                    if name.is_some() {
+                        return Err(Error::new(path.span(), "duplicate `name` argument"));
+                    }
+
+                    name = Some(lit.clone());
+                },
+                other => {
+                    return Err(Error::new(
+                        other.span(),
+                        "unexpected argument, expected `name = \"...\"`",
+                    ))
+                },
+            }
+        }
+    }
+
+    let name = name.unwrap_or_else(|| syn::LitStr::new(&ident.to_string(), ident.span()));
+
+    //---------------------------------------------------------------
+    // Create the ManagedExpression impl and register the manager type.
+    //---------------------------------------------------------------
+
+    let snake_name = to_snake_case(&ident.to_string());
+
+    let exports_mod = format_ident!("__{}_managed_expression", snake_name);
+    let new_fn = format_ident!("{}_new", snake_name);
+    let delete_fn = format_ident!("{}_delete", snake_name);
+
+    let output = quote! {
+        impl ::wolfram_library_link::managed::ManagedExpression for #ident {
+            const NAME: &'static str = #name;
+
+            fn instances() -> &'static ::wolfram_library_link::managed::Instances<Self> {
+                static INSTANCES: ::wolfram_library_link::managed::Instances<#ident> =
+                    ::wolfram_library_link::managed::Instances::new();
+
+                &INSTANCES
+            }
+        }
+
+        ::wolfram_library_link::inventory::submit! {
+            ::wolfram_library_link::managed::DerivedManager {
+                register: ::wolfram_library_link::managed::register::<#ident>,
+            }
+        }
+
+        // Export the constructor and destructor library functions.
+        #[doc(hidden)]
+        mod #exports_mod {
+            use ::wolfram_library_link::managed::Handle;
+
+            fn #new_fn(instance: Handle<super::#ident>) {
+                ::wolfram_library_link::managed::derived_new(instance)
+            }
+
+            fn #delete_fn(instance: Handle<super::#ident>) {
+                ::wolfram_library_link::managed::derived_delete(instance)
+            }
+
+            ::wolfram_library_link::export![#new_fn(_); #delete_fn(_);];
+        }
+    };
+
+    Ok(output)
+}
+
+/// Convert a `CamelCase` type name to `snake_case`.
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (index, c) in name.char_indices() {
+        if c.is_uppercase() {
+            if index != 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+
+    snake
+}
+
+//======================================
+// __export_symbol!()
+//======================================
+
+/// Name of the environment variable that sets a prefix for the symbol names of
+/// functions exported by `export!` and `export_wstp!`.
+const EXPORT_PREFIX_ENV_VAR: &str = "WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX";
+
+/// Expands to a string literal containing the name of the function identifier given as
+/// input, prefixed with the value of the `WOLFRAM_LIBRARY_LINK_EXPORT_PREFIX`
+/// environment variable at compile time, if it is set.
+#[doc(hidden)]
+#[proc_macro]
+pub fn __export_symbol(input: TokenStream) -> TokenStream {
+    let exported: syn::Ident = match syn::parse(input) {
+        Ok(exported) => exported,
+        Err(err) => return err.into_compile_error().into(),
+    };
+
+    let prefix = std::env::var(EXPORT_PREFIX_ENV_VAR).unwrap_or_default();
+
+    let is_valid = prefix
+        .chars()
+        .all(|char| char.is_ascii_alphanumeric() || char == '_')
+        && !prefix.starts_with(|char: char| char.is_ascii_digit());
+
+    if !is_valid {
+        return Error::new(
+            exported.span(),
+            format!(
+                "invalid {} value: {:?}: expected ASCII letters, digits, and \
+                underscores, not starting with a digit",
+                EXPORT_PREFIX_ENV_VAR, prefix
+            ),
+        )
+        .into_compile_error()
+        .into();
+    }
+
+    let symbol = syn::LitStr::new(&format!("{}{}", prefix, exported), exported.span());
+
+    quote!(#symbol).into()
+}